@@ -24,12 +24,15 @@ mod ui_central;
 mod ui_info_panels;
 mod ui_inputs;
 mod ui_side_panels;
+mod ui_timeline;
 mod ui_top_bar;
 mod ui_preferences;
 mod ui;
 mod undo;
 mod viewport;
 mod viewport_tools;
+#[cfg(not(target_arch = "wasm32"))]
+mod wedges;
 mod wrangle_help;
 
 pub(crate) use logging::ConsoleBuffer;
@@ -57,6 +60,8 @@ pub(crate) struct LobedoApp {
     eval_dirty: bool,
     last_param_change: Option<Instant>,
     eval_job: Option<eval::EvalJob>,
+    #[cfg(not(target_arch = "wasm32"))]
+    wedge_job: Option<wedges::WedgeJob>,
     node_graph: node_graph::NodeGraphState,
     last_display_state: DisplayState,
     last_node_graph_rect: Option<egui::Rect>,
@@ -79,6 +84,8 @@ pub(crate) struct LobedoApp {
     fit_nodes_on_load: bool,
     last_window_title: String,
     last_url_revision: usize,
+    playing: bool,
+    last_frame_step: Option<Instant>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -109,6 +116,8 @@ impl LobedoApp {
             eval_dirty: false,
             last_param_change: None,
             eval_job: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            wedge_job: None,
             node_graph: node_graph::NodeGraphState::default(),
             last_display_state: DisplayState::Ok,
             last_node_graph_rect: None,
@@ -131,6 +140,8 @@ impl LobedoApp {
             fit_nodes_on_load: false,
             last_window_title: String::new(),
             last_url_revision: lobedo_core::url_revision(),
+            playing: false,
+            last_frame_step: None,
         }
     }
 
@@ -17,8 +17,8 @@ use web_time::Instant;
 use lobedo_core::{
     build_skirt_preview_mesh, evaluate_geometry_graph_with_progress,
     scene_mesh_from_mesh, scene_snapshot_from_geometry, BuiltinNodeKind, Geometry,
-    GeometryEvalState, Mesh, NodeId, ProgressSink, SceneDrawable, SceneSnapshot, ShadingMode,
-    SplatShadingMode,
+    CancelToken, DiskCookCache, GeometryEvalState, Mesh, NodeId, ProgressSink, SceneDrawable,
+    SceneSnapshot, ShadingMode, SplatShadingMode,
 };
 use render::{
     RenderMaterial, RenderMesh, RenderScene, RenderTexture, SelectionShape, ViewportDebug,
@@ -29,6 +29,9 @@ use super::{viewport_tools::input_node_for, DisplayState, LobedoApp};
 
 pub(crate) struct EvalJob {
     receiver: Receiver<EvalResult>,
+    revision: u64,
+    display_node: NodeId,
+    cancel: CancelToken,
 }
 
 struct EvalResult {
@@ -48,6 +51,7 @@ impl LobedoApp {
         if self.eval_job.is_some() {
             return false;
         }
+        self.eval_state_snapshot.set_frame(self.current_frame());
         let all_nodes: HashSet<_> = self.project.graph.nodes().map(|node| node.id).collect();
         let Some(display_node) = self.project.graph.display_node() else {
             return self.node_graph.set_dirty_nodes(all_nodes);
@@ -79,6 +83,47 @@ impl LobedoApp {
         self.cook_info_node(node_id);
     }
 
+    /// Cooks `node_id` and locks it to the result. Returns whether the graph changed.
+    pub(super) fn lock_node_output(&mut self, node_id: NodeId) -> bool {
+        if self.eval_job.is_some() {
+            tracing::warn!("Lock failed: wait for the current cook to finish");
+            return false;
+        }
+        self.eval_state.eval.set_frame(self.current_frame());
+        match lobedo_core::lock_node_output(&mut self.project.graph, node_id, &mut self.eval_state)
+        {
+            Ok(()) => {
+                tracing::info!("node output locked");
+                self.mark_eval_dirty();
+                true
+            }
+            Err(err) => {
+                tracing::warn!("Lock failed: {}", err);
+                false
+            }
+        }
+    }
+
+    fn current_frame(&self) -> f32 {
+        self.project.settings.timeline.current_frame as f32
+    }
+
+    fn sync_disk_cache(&mut self) {
+        let settings = &self.project.settings.disk_cache;
+        let dir = settings.resolve_dir(self.project_path.as_deref());
+        let current = self
+            .eval_state
+            .disk_cache()
+            .map(|cache| (cache.dir().to_path_buf(), cache.min_cook_ms()));
+        let min_cook_ms = settings.min_cook_ms.max(0.0);
+        if current == dir.clone().map(|dir| (dir, min_cook_ms)) {
+            return;
+        }
+        self.eval_state.set_disk_cache(
+            dir.map(|dir| DiskCookCache::new(dir).with_min_cook_ms(min_cook_ms)),
+        );
+    }
+
     fn cook_pending_info_nodes(&mut self) {
         if self.eval_job.is_some() || self.pending_info_nodes.is_empty() {
             return;
@@ -93,6 +138,7 @@ impl LobedoApp {
         if self.eval_state.geometry_for_node(node_id).is_some() {
             return;
         }
+        self.eval_state.eval.set_frame(self.current_frame());
         if let Err(err) =
             lobedo_core::evaluate_geometry_graph(&self.project.graph, node_id, &mut self.eval_state)
         {
@@ -109,6 +155,7 @@ impl LobedoApp {
         if self.refresh_dirty_nodes() {
             ctx.request_repaint();
         }
+        self.cancel_stale_eval_job();
         if self.poll_eval_job(ctx) {
             return;
         }
@@ -169,6 +216,10 @@ impl LobedoApp {
         let template_nodes = graph.template_nodes();
         let selected_node = self.node_graph.selected_node_id();
         let progress = Some(self.node_graph.progress_sink());
+        self.eval_state
+            .set_cache_budget(self.project.settings.cook_cache_budget_bytes());
+        self.sync_disk_cache();
+        self.eval_state.eval.set_frame(self.current_frame());
         self.eval_state_snapshot = self.eval_state.eval.clone();
         let eval_state = std::mem::take(&mut self.eval_state);
         let result = run_eval_job(
@@ -179,10 +230,25 @@ impl LobedoApp {
             selected_node,
             eval_state,
             progress,
+            None,
         );
         self.apply_eval_result(result);
     }
 
+    fn cancel_stale_eval_job(&mut self) {
+        let Some(job) = &self.eval_job else {
+            return;
+        };
+        if job.cancel.is_cancelled() {
+            return;
+        }
+        if self.project.graph.revision() != job.revision
+            || self.project.graph.display_node() != Some(job.display_node)
+        {
+            job.cancel.cancel();
+        }
+    }
+
     fn poll_eval_job(&mut self, ctx: &egui::Context) -> bool {
         let Some(job) = &self.eval_job else {
             return false;
@@ -228,8 +294,14 @@ impl LobedoApp {
         let template_nodes = graph.template_nodes();
         let selected_node = self.node_graph.selected_node_id();
         let progress = Some(self.node_graph.progress_sink());
+        self.eval_state
+            .set_cache_budget(self.project.settings.cook_cache_budget_bytes());
+        self.sync_disk_cache();
+        self.eval_state.eval.set_frame(self.current_frame());
         self.eval_state_snapshot = self.eval_state.eval.clone();
         let eval_state = std::mem::take(&mut self.eval_state);
+        let cancel = CancelToken::new();
+        let job_cancel = cancel.clone();
 
         let (sender, receiver) = std::sync::mpsc::channel();
         thread::spawn(move || {
@@ -241,10 +313,16 @@ impl LobedoApp {
                 selected_node,
                 eval_state,
                 progress,
+                Some(job_cancel),
             );
             let _ = sender.send(result);
         });
-        self.eval_job = Some(EvalJob { receiver });
+        self.eval_job = Some(EvalJob {
+            receiver,
+            revision,
+            display_node,
+            cancel,
+        });
         ctx.request_repaint();
     }
 
@@ -317,6 +395,7 @@ impl LobedoApp {
                 self.node_graph.selected_node_id(),
                 &mut self.eval_state,
                 None,
+                None,
             );
             let merged_template = merge_optional_meshes(self.last_template_mesh.clone(), preview);
             scene.template_mesh = merged_template.as_ref().map(render_mesh_from_mesh);
@@ -387,6 +466,7 @@ impl LobedoApp {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_eval_job(
     graph: lobedo_core::Graph,
     display_node: NodeId,
@@ -395,14 +475,20 @@ fn run_eval_job(
     selected_node: Option<NodeId>,
     mut eval_state: GeometryEvalState,
     progress: Option<ProgressSink>,
+    cancel: Option<CancelToken>,
 ) -> EvalResult {
     let start = Instant::now();
     let mut error_nodes = HashSet::new();
     let mut error_messages = HashMap::new();
     let mut report = lobedo_core::EvalReport::default();
     let mut output: Option<Geometry> = None;
-    match evaluate_geometry_graph_with_progress(&graph, display_node, &mut eval_state, progress.clone())
-    {
+    match evaluate_geometry_graph_with_progress(
+        &graph,
+        display_node,
+        &mut eval_state,
+        progress.clone(),
+        cancel.clone(),
+    ) {
         Ok(result) => {
             report = result.report;
             merge_error_state(&report, &mut error_nodes, &mut error_messages);
@@ -431,12 +517,14 @@ fn run_eval_job(
                 &mut error_nodes,
                 &mut error_messages,
                 progress.clone(),
+                cancel.clone(),
             );
             let preview = splat_merge_preview_mesh(
                 &graph,
                 selected_node,
                 &mut eval_state,
                 progress.clone(),
+                cancel,
             );
             let merged_template = merge_optional_meshes(template_mesh.clone(), preview);
             let selection_shape = selection_shape_for_node(&graph, selected_node);
@@ -640,6 +728,7 @@ fn load_texture_bytes(path: &str) -> Option<Vec<u8>> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn collect_template_meshes(
     graph: &lobedo_core::Graph,
     display_node: lobedo_core::NodeId,
@@ -648,13 +737,20 @@ fn collect_template_meshes(
     error_nodes: &mut HashSet<lobedo_core::NodeId>,
     error_messages: &mut HashMap<lobedo_core::NodeId, String>,
     progress: Option<ProgressSink>,
+    cancel: Option<CancelToken>,
 ) -> Option<Mesh> {
     let mut meshes = Vec::new();
     for node_id in template_nodes {
         if *node_id == display_node {
             continue;
         }
-        match evaluate_geometry_graph_with_progress(graph, *node_id, state, progress.clone()) {
+        match evaluate_geometry_graph_with_progress(
+            graph,
+            *node_id,
+            state,
+            progress.clone(),
+            cancel.clone(),
+        ) {
             Ok(result) => {
                 merge_error_state(&result.report, error_nodes, error_messages);
                 if result.report.output_valid {
@@ -682,6 +778,7 @@ fn splat_merge_preview_mesh(
     selected_node: Option<NodeId>,
     state: &mut lobedo_core::GeometryEvalState,
     progress: Option<ProgressSink>,
+    cancel: Option<CancelToken>,
 ) -> Option<Mesh> {
     let node_id = selected_node?;
     let node = graph.node(node_id)?;
@@ -698,11 +795,13 @@ fn splat_merge_preview_mesh(
     }
     let input_a = input_node_for(graph, node_id, 0)?;
     let input_b = input_node_for(graph, node_id, 1)?;
-    let geo_a = evaluate_geometry_graph_with_progress(graph, input_a, state, progress.clone())
+    let geo_a =
+        evaluate_geometry_graph_with_progress(graph, input_a, state, progress.clone(), cancel.clone())
+            .ok()?
+            .output?;
+    let geo_b = evaluate_geometry_graph_with_progress(graph, input_b, state, progress, cancel)
         .ok()?
         .output?;
-    let geo_b =
-        evaluate_geometry_graph_with_progress(graph, input_b, state, progress).ok()?.output?;
     let splats_a = geo_a.merged_splats()?;
     let splats_b = geo_b.merged_splats()?;
     build_skirt_preview_mesh(&node.params, &splats_a, &splats_b)
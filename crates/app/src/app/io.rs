@@ -4,10 +4,12 @@ use std::path::Path;
 #[cfg(not(target_arch = "wasm32"))]
 use rfd::FileDialog;
 
-use lobedo_core::Project;
+use lobedo_core::{NodeId, Project};
 #[cfg(not(target_arch = "wasm32"))]
 use lobedo_core::{
-    evaluate_geometry_graph, save_splat_ply_with_format, write_gltf, write_obj, SplatSaveFormat,
+    evaluate_geometry_graph, save_splats_with_format, write_eval_profile, write_gltf, write_obj,
+    write_ply_mesh, write_stl, PlyMeshFormat, ProfileFormat, SplatSaveFormat, StlFormat,
+    SubnetAsset,
 };
 
 use super::LobedoApp;
@@ -137,14 +139,35 @@ impl LobedoApp {
                 }
             }
             WriteRequestKind::Gltf => {
+                if let Err(err) = write_gltf(path, &geometry) {
+                    tracing::warn!("GLTF write failed: {}", err);
+                } else {
+                    tracing::info!("GLTF written to {}", path);
+                }
+            }
+            WriteRequestKind::Ply => {
                 let Some(mesh) = geometry.merged_mesh() else {
                     tracing::warn!("Write failed: no mesh output");
                     return;
                 };
-                if let Err(err) = write_gltf(path, &mesh) {
-                    tracing::warn!("GLTF write failed: {}", err);
+                let format = PlyMeshFormat::from_index(node.params.get_int("format", 0));
+                if let Err(err) = write_ply_mesh(path, &mesh, format) {
+                    tracing::warn!("PLY write failed: {}", err);
                 } else {
-                    tracing::info!("GLTF written to {}", path);
+                    tracing::info!("PLY written to {}", path);
+                }
+            }
+            WriteRequestKind::Stl => {
+                let Some(mesh) = geometry.merged_mesh() else {
+                    tracing::warn!("Write failed: no mesh output");
+                    return;
+                };
+                let format = StlFormat::from_index(node.params.get_int("format", 0));
+                let facet_normals = node.params.get_bool("facet_normals", true);
+                if let Err(err) = write_stl(path, &mesh, format, facet_normals) {
+                    tracing::warn!("STL write failed: {}", err);
+                } else {
+                    tracing::info!("STL written to {}", path);
                 }
             }
             WriteRequestKind::Splat => {
@@ -152,14 +175,11 @@ impl LobedoApp {
                     tracing::warn!("Write failed: no splat output");
                     return;
                 };
-                let format = match node.params.get_int("format", 0) {
-                    1 => SplatSaveFormat::Ascii,
-                    _ => SplatSaveFormat::BinaryLittle,
-                };
-                if let Err(err) = save_splat_ply_with_format(path, &splats, format) {
-                    tracing::warn!("PLY write failed: {}", err);
+                let format = SplatSaveFormat::from_index(node.params.get_int("format", 0));
+                if let Err(err) = save_splats_with_format(path, &splats, format) {
+                    tracing::warn!("Splat write failed: {}", err);
                 } else {
-                    tracing::info!("PLY written to {}", path);
+                    tracing::info!("Splats written to {}", path);
                 }
             }
         }
@@ -202,6 +222,81 @@ impl LobedoApp {
         }
     }
 
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn export_asset_dialog(&mut self, _node_id: NodeId) {
+        tracing::warn!("Asset export is not available in web builds.");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn export_asset_dialog(&mut self, node_id: NodeId) {
+        let asset = match SubnetAsset::from_node(&self.project.graph, node_id) {
+            Ok(asset) => asset,
+            Err(err) => {
+                tracing::warn!("Asset export failed: {}", err);
+                return;
+            }
+        };
+        if let Some(path) = FileDialog::new()
+            .add_filter("Lobedo Asset", &["json"])
+            .set_file_name(format!("{}.json", asset.name))
+            .save_file()
+        {
+            match asset.save(&path) {
+                Ok(()) => tracing::info!("asset written to {}", path.display()),
+                Err(err) => tracing::warn!("Asset export failed: {}", err),
+            }
+        }
+    }
+
+    /// Writes the last cook's report as a Chrome trace or a JSON summary,
+    /// depending on the chosen file name.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn export_profile_dialog(&mut self) {
+        let Some(report) = self.last_eval_report.as_ref() else {
+            return;
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter("Cook Profile", &["json"])
+            .set_file_name("cook.trace.json")
+            .save_file()
+        else {
+            return;
+        };
+        match write_eval_profile(
+            &self.project.graph,
+            report,
+            &path,
+            ProfileFormat::for_path(&path),
+        ) {
+            Ok(()) => tracing::info!("cook profile written to {}", path.display()),
+            Err(err) => tracing::warn!("Profile export failed: {}", err),
+        }
+    }
+
+    /// Adds an asset file to the project, replacing an asset with the same name.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn import_asset_dialog(&mut self) {
+        let Some(path) = FileDialog::new()
+            .add_filter("Lobedo Asset", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+        let asset = match SubnetAsset::load(&path) {
+            Ok(asset) => asset,
+            Err(err) => {
+                tracing::error!("failed to import asset: {}", err);
+                return;
+            }
+        };
+        tracing::info!("asset {} imported", asset.name);
+        let assets = &mut self.project.assets;
+        match assets.iter_mut().find(|existing| existing.name == asset.name) {
+            Some(existing) => *existing = asset,
+            None => assets.push(asset),
+        }
+    }
+
     fn load_project_from_bytes(
         &mut self,
         data: &[u8],
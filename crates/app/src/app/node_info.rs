@@ -2,7 +2,9 @@ use std::collections::BTreeMap;
 
 use eframe::egui;
 
-use lobedo_core::{AttributeDomain, AttributeInfo, AttributeType, BuiltinNodeKind, Geometry};
+use lobedo_core::{
+    AttributeDomain, AttributeInfo, AttributeType, BuiltinNodeKind, Geometry, ParamValue,
+};
 
 use crate::app::LobedoApp;
 
@@ -12,6 +14,27 @@ pub(super) struct NodeInfoPanel {
     pub(super) open: bool,
 }
 
+fn show_values_info(ui: &mut egui::Ui, values: &[(String, ParamValue)]) {
+    ui.heading("Values");
+    egui::Grid::new("node_info_values")
+        .num_columns(2)
+        .spacing([12.0, 6.0])
+        .show(ui, |ui| {
+            for (name, value) in values {
+                ui.label(name);
+                ui.label(match value {
+                    ParamValue::Float(v) => format!("{:.4}", v),
+                    ParamValue::Int(v) => v.to_string(),
+                    ParamValue::Bool(v) => v.to_string(),
+                    ParamValue::Vec2(v) => format!("({:.4}, {:.4})", v[0], v[1]),
+                    ParamValue::Vec3(v) => format!("({:.4}, {:.4}, {:.4})", v[0], v[1], v[2]),
+                    ParamValue::String(v) => v.clone(),
+                });
+                ui.end_row();
+            }
+        });
+}
+
 impl LobedoApp {
     pub(super) fn show_node_info_panel(
         &mut self,
@@ -46,7 +69,13 @@ impl LobedoApp {
                 ui.add_space(8.0);
                 ui.separator();
             }
-            if let Some(geometry) = self.eval_state.geometry_for_node(node_id) {
+            let values = self.output_values(node);
+            if !values.is_empty() {
+                show_values_info(ui, &values);
+            } else if let Some(geometry) = self
+                .eval_state
+                .displayed_geometry(&self.project.graph, node_id)
+            {
                 self.show_geometry_info(ui, geometry, node);
             } else if error_message.is_none() {
                 ui.label("No geometry available for this node.");
@@ -59,6 +88,19 @@ impl LobedoApp {
         }
     }
 
+    /// Cooked values of a value node, by output name.
+    fn output_values(&self, node: &lobedo_core::Node) -> Vec<(String, ParamValue)> {
+        node.outputs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, pin_id)| {
+                let value = self.eval_state.value_for_output(node.id, index)?;
+                let pin = self.project.graph.pin(*pin_id)?;
+                Some((pin.name.clone(), value.clone()))
+            })
+            .collect()
+    }
+
     fn show_geometry_info(
         &self,
         ui: &mut egui::Ui,
@@ -375,7 +375,10 @@ impl LobedoApp {
                 style.visuals.widgets.active.bg_stroke.width = stroke_width;
                 style.spacing.item_spacing = egui::vec2(10.0, 6.0);
                 let selected = self.node_graph.selected_node_id();
-                let geometry = selected.and_then(|id| self.eval_state.geometry_for_node(id));
+                let geometry = selected.and_then(|id| {
+                    self.eval_state
+                        .displayed_geometry(&self.project.graph, id)
+                });
                 let mesh = geometry.and_then(|geo| geo.merged_mesh());
                 let splats = geometry.and_then(|geo| geo.merged_splats());
                 show_spreadsheet(
@@ -606,9 +609,17 @@ impl LobedoApp {
                 ui,
                 &mut self.project.graph,
                 &mut self.project.settings,
+                &self.project.assets,
                 &mut self.eval_dirty,
                 &self.eval_state_snapshot,
             );
+            if let Some(node_id) = self.node_graph.take_asset_export_request() {
+                self.export_asset_dialog(node_id);
+            }
+            let locked = self
+                .node_graph
+                .take_lock_request()
+                .is_some_and(|node_id| self.lock_node_output(node_id));
             if self.eval_dirty || was_dirty {
                 let dirty_changed = self.refresh_dirty_nodes();
                 if dirty_changed {
@@ -625,7 +636,7 @@ impl LobedoApp {
                 }
             }
             let layout_moved = self.node_graph.take_layout_changed();
-            if (self.node_graph.take_changed() || layout_moved) && !*undo_pushed {
+            if (self.node_graph.take_changed() || layout_moved || locked) && !*undo_pushed {
                 self.queue_undo_snapshot(snapshot, pointer_down);
                 *undo_pushed = true;
             }
@@ -772,7 +783,7 @@ fn mesh_corner_uvs(mesh: &lobedo_core::Mesh) -> Option<Vec<[f32; 2]>> {
     if point_uvs.is_none() {
         if let Some(uvs) = mesh.uvs.as_ref() {
             if uvs.len() == mesh.positions.len() {
-                point_uvs = Some(uvs.clone());
+                point_uvs = Some(uvs.to_vec());
             }
         }
     }
@@ -193,7 +193,71 @@ impl LobedoApp {
                                 self.last_param_change = None;
                                 self.evaluate_graph();
                             }
+                            ui.horizontal(|ui| {
+                                ui.label("Cook cache budget (MB, 0 = unlimited)");
+                                ui.add(
+                                    egui::DragValue::new(
+                                        &mut self.project.settings.cook_cache_budget_mb,
+                                    )
+                                    .speed(16.0)
+                                    .update_while_editing(false)
+                                    .range(0..=65536),
+                                );
+                            });
+                            let stats = &self.eval_state_snapshot.stats;
+                            ui.label(format!(
+                                "Cache: {:.1} MB  Evictions: {}  Recooks: {}",
+                                stats.cache_bytes as f64 / (1024.0 * 1024.0),
+                                stats.evictions,
+                                stats.recooks
+                            ));
+                            ui.checkbox(
+                                &mut self.project.settings.disk_cache.enabled,
+                                "Disk cook cache",
+                            );
+                            if self.project.settings.disk_cache.enabled {
+                                ui.horizontal(|ui| {
+                                    ui.label("Cache dir");
+                                    ui.text_edit_singleline(
+                                        &mut self.project.settings.disk_cache.dir,
+                                    );
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Min cook time (ms)");
+                                    ui.add(
+                                        egui::DragValue::new(
+                                            &mut self.project.settings.disk_cache.min_cook_ms,
+                                        )
+                                        .speed(5.0)
+                                        .update_while_editing(false)
+                                        .range(0.0..=60000.0),
+                                    );
+                                });
+                                ui.label(format!(
+                                    "Disk hits: {}  Writes: {}",
+                                    stats.disk_hits, stats.disk_writes
+                                ));
+                                if let Some(cache) = self.eval_state.disk_cache() {
+                                    if ui.button("Clear disk cache").clicked() {
+                                        match cache.clear() {
+                                            Ok(freed) => tracing::info!(
+                                                "cleared disk cook cache ({:.1} MB)",
+                                                freed as f64 / (1024.0 * 1024.0)
+                                            ),
+                                            Err(err) => {
+                                                tracing::warn!("failed to clear disk cook cache: {}", err)
+                                            }
+                                        }
+                                    }
+                                } else if self.project_path.is_none()
+                                    && self.project.settings.disk_cache.dir.trim().is_empty()
+                                {
+                                    ui.label("Save the project or set a directory to use the disk cache");
+                                }
+                            }
 
+                            #[cfg(not(target_arch = "wasm32"))]
+                            let mut export_profile = false;
                             if let Some(report) = &self.last_eval_report {
                                 let computed = report.computed.len();
                                 ui.label(format!(
@@ -206,6 +270,10 @@ impl LobedoApp {
                                 if !report.output_valid {
                                     ui.colored_label(egui::Color32::RED, "Output invalid");
                                 }
+                                #[cfg(not(target_arch = "wasm32"))]
+                                {
+                                    export_profile = ui.button("Export profile...").clicked();
+                                }
                                 let mut nodes: Vec<_> = report.node_reports.values().collect();
                                 nodes.sort_by(|a, b| {
                                     b.duration_ms
@@ -236,6 +304,10 @@ impl LobedoApp {
                                     }
                                 }
                             }
+                            #[cfg(not(target_arch = "wasm32"))]
+                            if export_profile {
+                                self.export_profile_dialog();
+                            }
 
                             egui::ComboBox::from_label("Log level")
                                 .selected_text(format!("{:?}", self.log_level))
@@ -261,6 +333,13 @@ impl LobedoApp {
                         });
                 }
 
+                #[cfg(not(target_arch = "wasm32"))]
+                if self.project.settings.panels.show_debug {
+                    egui::CollapsingHeader::new("Wedges")
+                        .default_open(!self.project.wedges.is_empty())
+                        .show(ui, |ui| self.show_wedges(ui));
+                }
+
                 if self.project.settings.panels.show_console {
                     egui::CollapsingHeader::new("Console")
                         .default_open(true)
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use eframe::egui;
+
+use super::LobedoApp;
+
+impl LobedoApp {
+    pub(super) fn show_timeline(&mut self, ctx: &egui::Context) {
+        self.advance_playback(ctx);
+        let mut frame = self.project.settings.timeline.current_frame;
+        egui::TopBottomPanel::bottom("timeline").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let timeline = &mut self.project.settings.timeline;
+                let (start, end) = (
+                    timeline.frame_start,
+                    timeline.frame_end.max(timeline.frame_start),
+                );
+                if ui.button("|<").on_hover_text("First frame").clicked() {
+                    frame = start;
+                }
+                if ui.button("<").on_hover_text("Previous frame").clicked() {
+                    frame = (frame - 1).max(start);
+                }
+                let play_label = if self.playing { "Pause" } else { "Play" };
+                if ui.button(play_label).clicked() {
+                    self.playing = !self.playing;
+                    self.last_frame_step = None;
+                }
+                if ui.button(">").on_hover_text("Next frame").clicked() {
+                    frame = (frame + 1).min(end);
+                }
+                if ui.button(">|").on_hover_text("Last frame").clicked() {
+                    frame = end;
+                }
+                ui.add(egui::DragValue::new(&mut frame).prefix("Frame ").speed(0.2));
+                ui.separator();
+
+                let settings_width = 250.0;
+                let slider_width = (ui.available_width() - settings_width).max(80.0);
+                ui.spacing_mut().slider_width = slider_width;
+                ui.add(egui::Slider::new(&mut frame, start..=end).show_value(false));
+                ui.separator();
+
+                ui.add(
+                    egui::DragValue::new(&mut timeline.frame_start)
+                        .prefix("Start ")
+                        .speed(0.2)
+                        .update_while_editing(false),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut timeline.frame_end)
+                        .prefix("End ")
+                        .speed(0.2)
+                        .update_while_editing(false)
+                        .range(timeline.frame_start..=i32::MAX),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut timeline.fps)
+                        .suffix(" fps")
+                        .speed(0.1)
+                        .update_while_editing(false)
+                        .range(1.0..=240.0),
+                );
+            });
+        });
+        self.set_current_frame(frame);
+        self.node_graph
+            .set_current_frame(self.project.settings.timeline.current_frame as f32);
+    }
+
+    /// Steps playback at the project frame rate, but only once the previous frame
+    /// has cooked so every frame is shown.
+    fn advance_playback(&mut self, ctx: &egui::Context) {
+        if !self.playing {
+            return;
+        }
+        ctx.request_repaint();
+        if self.eval_dirty || self.eval_job.is_some() {
+            return;
+        }
+        let timeline = &self.project.settings.timeline;
+        let interval = Duration::from_secs_f32(1.0 / timeline.fps.max(1.0));
+        if self
+            .last_frame_step
+            .is_some_and(|last| last.elapsed() < interval)
+        {
+            return;
+        }
+        self.last_frame_step = Some(Instant::now());
+        self.set_current_frame(timeline.next_frame());
+    }
+
+    pub(super) fn set_current_frame(&mut self, frame: i32) {
+        let timeline = &mut self.project.settings.timeline;
+        if timeline.current_frame == frame {
+            return;
+        }
+        timeline.current_frame = frame;
+        // Frame changes skip the parameter debounce so scrubbing stays live.
+        self.eval_dirty = true;
+    }
+}
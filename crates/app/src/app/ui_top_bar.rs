@@ -36,6 +36,12 @@ impl LobedoApp {
                             self.save_project_dialog();
                             ui.close();
                         }
+
+                        ui.separator();
+                        if ui.button("Import Asset...").clicked() {
+                            self.import_asset_dialog();
+                            ui.close();
+                        }
                     }
 
                     #[cfg(target_arch = "wasm32")]
@@ -43,6 +49,7 @@ impl LobedoApp {
                         ui.add_enabled(false, egui::Button::new("Open..."));
                         ui.add_enabled(false, egui::Button::new("Save"));
                         ui.add_enabled(false, egui::Button::new("Save As..."));
+                        ui.add_enabled(false, egui::Button::new("Import Asset..."));
                         ui.label("File I/O is not available in web builds.");
                     }
 
@@ -0,0 +1,170 @@
+use std::thread;
+use std::time::Duration;
+
+use eframe::egui;
+use lobedo_core::{run_wedge, CancelToken, Wedge, WedgeParam, WedgeValues};
+
+use super::LobedoApp;
+
+pub(crate) struct WedgeJob {
+    name: String,
+    handle: thread::JoinHandle<()>,
+    cancel: CancelToken,
+}
+
+impl LobedoApp {
+    /// Lists the project's wedges with their settings and a Run button. Runs
+    /// happen on a background thread against a copy of the project, so the
+    /// graph stays editable meanwhile.
+    pub(super) fn show_wedges(&mut self, ui: &mut egui::Ui) {
+        if self
+            .wedge_job
+            .as_ref()
+            .is_some_and(|job| job.handle.is_finished())
+        {
+            self.wedge_job = None;
+        }
+        if let Some(job) = &self.wedge_job {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(format!("Running {}", job.name));
+                if ui.button("Cancel").clicked() {
+                    job.cancel.cancel();
+                }
+            });
+            ui.ctx().request_repaint_after(Duration::from_millis(100));
+        }
+
+        let mut run = None;
+        let mut remove = None;
+        let running = self.wedge_job.is_some();
+        for (index, wedge) in self.project.wedges.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut wedge.name);
+                    if ui.add_enabled(!running, egui::Button::new("Run")).clicked() {
+                        run = Some(index);
+                    }
+                    if ui.small_button("x").clicked() {
+                        remove = Some(index);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Node");
+                    ui.text_edit_singleline(&mut wedge.node);
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Output");
+                    ui.text_edit_singleline(&mut wedge.output_path);
+                });
+                show_wedge_params(ui, &mut wedge.params);
+            });
+        }
+        if let Some(index) = remove {
+            self.project.wedges.remove(index);
+        }
+        if ui.button("Add wedge").clicked() {
+            let node = self
+                .last_selected_node
+                .and_then(|node_id| self.project.graph.node(node_id))
+                .map(|node| node.name.clone())
+                .unwrap_or_default();
+            self.project.wedges.push(Wedge {
+                name: format!("wedge{}", self.project.wedges.len() + 1),
+                node,
+                output_path: "wedges/out_${wedge}.ply".to_string(),
+                ..Default::default()
+            });
+        }
+        if let Some(index) = run {
+            self.start_wedge(index);
+        }
+    }
+
+    fn start_wedge(&mut self, index: usize) {
+        let Some(wedge) = self.project.wedges.get(index).cloned() else {
+            return;
+        };
+        let project = self.project.clone();
+        let base_dir = self
+            .project_path
+            .as_deref()
+            .and_then(|path| path.parent())
+            .map(|dir| dir.to_path_buf());
+        let cancel = CancelToken::new();
+        let job_cancel = cancel.clone();
+        let name = wedge.name.clone();
+        let handle = thread::spawn(move || {
+            match run_wedge(&project, &wedge, base_dir.as_deref(), Some(job_cancel)) {
+                Ok(manifest) => {
+                    for run in &manifest.runs {
+                        if let Some(err) = &run.error {
+                            tracing::warn!(
+                                "wedge {} run {} failed: {}",
+                                wedge.name,
+                                run.index,
+                                err
+                            );
+                        }
+                    }
+                    tracing::info!(
+                        "wedge {}: {} runs ({} failed) in {:.2} ms",
+                        wedge.name,
+                        manifest.runs.len(),
+                        manifest.failed_runs(),
+                        manifest.total_ms
+                    );
+                }
+                Err(err) => tracing::warn!("Wedge failed: {}", err),
+            }
+        });
+        self.wedge_job = Some(WedgeJob {
+            name,
+            handle,
+            cancel,
+        });
+    }
+}
+
+fn show_wedge_params(ui: &mut egui::Ui, params: &mut Vec<WedgeParam>) {
+    let mut remove = None;
+    for (index, param) in params.iter_mut().enumerate() {
+        ui.push_id(("param", index), |ui| {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut param.node).desired_width(70.0));
+                ui.label(".");
+                ui.add(egui::TextEdit::singleline(&mut param.param).desired_width(70.0));
+                if ui.small_button("x").clicked() {
+                    remove = Some(index);
+                }
+            });
+            ui.horizontal(|ui| match &mut param.values {
+                WedgeValues::Range { start, end, steps } => {
+                    ui.add(egui::DragValue::new(start).speed(0.01));
+                    ui.label("to");
+                    ui.add(egui::DragValue::new(end).speed(0.01));
+                    ui.label("steps");
+                    ui.add(egui::DragValue::new(steps).range(1..=1000));
+                }
+                WedgeValues::List { values } => {
+                    ui.label(format!("{} values", values.len()));
+                }
+            });
+        });
+    }
+    if let Some(index) = remove {
+        params.remove(index);
+    }
+    if ui.small_button("Add parameter").clicked() {
+        params.push(WedgeParam {
+            node: String::new(),
+            param: String::new(),
+            values: WedgeValues::Range {
+                start: 0.0,
+                end: 1.0,
+                steps: 5,
+            },
+        });
+    }
+}
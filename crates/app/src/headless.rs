@@ -1,7 +1,13 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::process;
 
-use lobedo_core::Project;
+use lobedo_core::{
+    builtin_kind_from_name, default_params, evaluate_geometry_graph, param_specs, run_wedge,
+    write_eval_profile, write_geometry_with, BuiltinNodeKind, DiskCookCache, EvalError, EvalReport,
+    ExportKind, Geometry, GeometryEvalState, Graph, NodeId, ParamKind, ParamOption, ParamSpec,
+    ParamValue, ProfileFormat, Project, Severity,
+};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -23,6 +29,8 @@ struct PlanNode {
     inputs: Vec<PlanPin>,
     #[serde(default)]
     outputs: Vec<PlanPin>,
+    #[serde(default)]
+    params: BTreeMap<String, ParamValue>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -45,8 +53,23 @@ struct PlanEndpoint {
 
 struct HeadlessArgs {
     plan_path: Option<PathBuf>,
+    project_path: Option<PathBuf>,
     save_path: Option<PathBuf>,
+    cook: Option<String>,
+    output_path: Option<PathBuf>,
+    overrides: Vec<ParamOverride>,
     print: bool,
+    disk_cache: Option<PathBuf>,
+    frame: Option<i32>,
+    lint: bool,
+    profile_path: Option<PathBuf>,
+    wedge: Option<String>,
+}
+
+struct ParamOverride {
+    node: String,
+    key: String,
+    value: String,
 }
 
 pub fn maybe_run_headless(args: &[String]) -> Result<bool, String> {
@@ -58,13 +81,24 @@ pub fn maybe_run_headless(args: &[String]) -> Result<bool, String> {
     }
 
     let parsed = parse_headless_args(args)?;
-    let plan = if let Some(path) = parsed.plan_path {
-        load_headless_plan(&path)?
+    let (mut project, plan_output) = if let Some(path) = parsed.project_path.as_ref() {
+        (load_project_json(path)?, None)
     } else {
-        default_headless_plan()
+        let plan = if let Some(path) = parsed.plan_path.as_ref() {
+            load_headless_plan(path)?
+        } else {
+            default_headless_plan()
+        };
+        (build_project_from_plan(&plan)?, plan.output_node)
     };
+    apply_param_overrides(&mut project.graph, &parsed.overrides)?;
+    if let Some(frame) = parsed.frame {
+        project.settings.timeline.current_frame = frame;
+    }
 
-    let project = build_project_from_plan(&plan)?;
+    if parsed.lint {
+        lint_graph(&project.graph)?;
+    }
 
     if let Some(path) = parsed.save_path {
         save_project_json(&project, &path)?;
@@ -76,18 +110,49 @@ pub fn maybe_run_headless(args: &[String]) -> Result<bool, String> {
         println!("{json}");
     }
 
-    if let Some(output) = plan.output_node {
+    if let Some(target) = parsed.cook.as_deref() {
+        let disk_cache_dir = parsed.disk_cache.clone().or_else(|| {
+            project
+                .settings
+                .disk_cache
+                .resolve_dir(parsed.project_path.as_deref())
+        });
+        let disk_cache = disk_cache_dir.map(|dir| {
+            DiskCookCache::new(dir).with_min_cook_ms(project.settings.disk_cache.min_cook_ms)
+        });
+        cook_node(
+            &project,
+            target,
+            parsed.output_path.as_deref(),
+            parsed.profile_path.as_deref(),
+            disk_cache,
+        )?;
+    } else if let Some(output) = plan_output {
         validate_topo_sort(&project, &output)?;
     }
 
+    if let Some(name) = parsed.wedge.as_deref() {
+        let base_dir = parsed.project_path.as_deref().and_then(Path::parent);
+        run_headless_wedge(&project, name, base_dir)?;
+    }
+
     tracing::info!("headless: completed");
     Ok(true)
 }
 
 fn parse_headless_args(args: &[String]) -> Result<HeadlessArgs, String> {
     let mut plan_path = None;
+    let mut project_path = None;
     let mut save_path = None;
+    let mut cook = None;
+    let mut output_path = None;
+    let mut overrides = Vec::new();
     let mut print = false;
+    let mut disk_cache = None;
+    let mut frame = None;
+    let mut lint = false;
+    let mut profile_path = None;
+    let mut wedge = None;
     let mut iter = args.iter().peekable();
 
     while let Some(arg) = iter.next() {
@@ -99,15 +164,70 @@ fn parse_headless_args(args: &[String]) -> Result<HeadlessArgs, String> {
                     .ok_or_else(|| "--plan requires a path".to_string())?;
                 plan_path = Some(PathBuf::from(value));
             }
+            "--project" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--project requires a path".to_string())?;
+                project_path = Some(PathBuf::from(value));
+            }
             "--save" => {
                 let value = iter
                     .next()
                     .ok_or_else(|| "--save requires a path".to_string())?;
                 save_path = Some(PathBuf::from(value));
             }
+            "--cook" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--cook requires a node name".to_string())?;
+                cook = Some(value.clone());
+            }
+            "--output" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--output requires a path".to_string())?;
+                output_path = Some(PathBuf::from(value));
+            }
+            "--set" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--set requires node.param=value".to_string())?;
+                overrides.push(parse_param_override(value)?);
+            }
             "--print" => {
                 print = true;
             }
+            "--disk-cache" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--disk-cache requires a directory".to_string())?;
+                disk_cache = Some(PathBuf::from(value));
+            }
+            "--frame" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--frame requires a frame number".to_string())?;
+                frame = Some(
+                    value
+                        .parse::<i32>()
+                        .map_err(|_| format!("invalid --frame value '{}'", value))?,
+                );
+            }
+            "--lint" => {
+                lint = true;
+            }
+            "--profile" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--profile requires a path".to_string())?;
+                profile_path = Some(PathBuf::from(value));
+            }
+            "--wedge" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--wedge requires a wedge name".to_string())?;
+                wedge = Some(value.clone());
+            }
             "--help" | "-h" => {
                 print_headless_help();
                 process::exit(0);
@@ -116,16 +236,35 @@ fn parse_headless_args(args: &[String]) -> Result<HeadlessArgs, String> {
         }
     }
 
+    if plan_path.is_some() && project_path.is_some() {
+        return Err("--plan and --project cannot be used together".to_string());
+    }
+    if output_path.is_some() && cook.is_none() {
+        return Err("--output requires --cook".to_string());
+    }
+    if profile_path.is_some() && cook.is_none() {
+        return Err("--profile requires --cook".to_string());
+    }
+
     Ok(HeadlessArgs {
         plan_path,
+        project_path,
         save_path,
+        cook,
+        output_path,
+        overrides,
         print,
+        disk_cache,
+        frame,
+        lint,
+        profile_path,
+        wedge,
     })
 }
 
 fn print_headless_help() {
     println!(
-        "Headless mode options:\n  --headless | -headless\n  --plan <path>\n  --save <path>\n  --print"
+        "Headless mode options:\n  --headless | -headless\n  --plan <path>\n  --project <path>\n  --save <path>\n  --print\n  --cook <node>\n  --output <path.obj|.gltf|.glb|.stl|.ply|.splat|.ksplat|.spz>\n  --set <node.param=value> (repeatable)\n  --disk-cache <dir>\n  --frame <n>\n  --lint (fails on validation errors)\n  --profile <path.json|.trace.json>\n  --wedge <name> (runs a wedge saved in the project)"
     );
 }
 
@@ -145,6 +284,7 @@ fn default_headless_plan() -> HeadlessPlan {
                     name: "mesh".to_string(),
                     pin_type: lobedo_core::PinType::Geometry,
                 }],
+                params: BTreeMap::new(),
             },
             PlanNode {
                 name: "Output".to_string(),
@@ -154,6 +294,7 @@ fn default_headless_plan() -> HeadlessPlan {
                     pin_type: lobedo_core::PinType::Geometry,
                 }],
                 outputs: Vec::new(),
+                params: BTreeMap::new(),
             },
         ],
         links: vec![PlanLink {
@@ -194,7 +335,9 @@ fn build_project_from_plan(plan: &HeadlessPlan) -> Result<Project, String> {
                     pin_type: pin.pin_type,
                 })
                 .collect(),
+            variadic_inputs: false,
         });
+        apply_plan_params(&mut project.graph, node_id, node)?;
         name_to_id.insert(node.name.clone(), node_id);
     }
 
@@ -230,6 +373,163 @@ fn build_project_from_plan(plan: &HeadlessPlan) -> Result<Project, String> {
     Ok(project)
 }
 
+fn apply_plan_params(
+    graph: &mut Graph,
+    node_id: NodeId,
+    plan_node: &PlanNode,
+) -> Result<(), String> {
+    // Plan nodes without params stay as declared, builtin name or not.
+    if plan_node.params.is_empty() {
+        return Ok(());
+    }
+    let Some(kind) = builtin_kind_from_name(&plan_node.name) else {
+        return Err(format!(
+            "node {} is not a builtin node kind; params are not supported",
+            plan_node.name
+        ));
+    };
+    graph.set_node_kind_id(node_id, kind.id());
+    for (key, value) in default_params(kind).values {
+        graph
+            .set_param(node_id, key, value)
+            .map_err(|err| format!("param error: {:?}", err))?;
+    }
+    for (key, value) in &plan_node.params {
+        let value = validate_param_value(kind, key, value.clone())
+            .map_err(|err| format!("node {}: {}", plan_node.name, err))?;
+        graph
+            .set_param(node_id, key.clone(), value)
+            .map_err(|err| format!("param error: {:?}", err))?;
+    }
+    Ok(())
+}
+
+fn param_spec_for(kind: BuiltinNodeKind, key: &str) -> Result<ParamSpec, String> {
+    param_specs(kind)
+        .into_iter()
+        .find(|spec| spec.key == key)
+        .ok_or_else(|| format!("unknown param {} for {}", key, kind.name()))
+}
+
+/// Checks `value` against the param's kind, converting ints for float params,
+/// and against the listed values of int menus.
+fn validate_param_value(
+    kind: BuiltinNodeKind,
+    key: &str,
+    value: ParamValue,
+) -> Result<ParamValue, String> {
+    let spec = param_spec_for(kind, key)?;
+    let value = match (spec.kind, value) {
+        (ParamKind::Float, ParamValue::Int(v)) => ParamValue::Float(v as f32),
+        (ParamKind::Float, value @ ParamValue::Float(_))
+        | (ParamKind::Int, value @ ParamValue::Int(_))
+        | (ParamKind::Bool, value @ ParamValue::Bool(_))
+        | (ParamKind::Vec2, value @ ParamValue::Vec2(_))
+        | (ParamKind::Vec3, value @ ParamValue::Vec3(_))
+        | (ParamKind::String, value @ ParamValue::String(_)) => value,
+        (expected, value) => {
+            return Err(format!(
+                "param {} expects {:?}, got {:?}",
+                key, expected, value
+            ))
+        }
+    };
+    let allowed: Vec<i32> = spec
+        .options
+        .iter()
+        .filter_map(|option| match option {
+            ParamOption::Int { value, .. } => Some(*value),
+            ParamOption::String { .. } => None,
+        })
+        .collect();
+    if let ParamValue::Int(v) = value {
+        if !allowed.is_empty() && !allowed.contains(&v) {
+            return Err(format!(
+                "param {} expects one of {:?}, got {}",
+                key, allowed, v
+            ));
+        }
+    }
+    Ok(value)
+}
+
+fn parse_param_override(text: &str) -> Result<ParamOverride, String> {
+    let (target, value) = text
+        .split_once('=')
+        .ok_or_else(|| format!("invalid --set {} (expected node.param=value)", text))?;
+    let (node, key) = target
+        .rsplit_once('.')
+        .ok_or_else(|| format!("invalid --set {} (expected node.param=value)", text))?;
+    if node.is_empty() || key.is_empty() {
+        return Err(format!("invalid --set {} (expected node.param=value)", text));
+    }
+    Ok(ParamOverride {
+        node: node.to_string(),
+        key: key.to_string(),
+        value: value.to_string(),
+    })
+}
+
+fn parse_param_text(kind: ParamKind, text: &str) -> Result<ParamValue, String> {
+    let floats = |count: usize| -> Result<Vec<f32>, String> {
+        let values: Vec<f32> = text
+            .split(',')
+            .map(|part| part.trim().parse::<f32>())
+            .collect::<Result<_, _>>()
+            .map_err(|err| format!("invalid number in {}: {}", text, err))?;
+        if values.len() != count {
+            return Err(format!("expected {} comma-separated values, got {}", count, text));
+        }
+        Ok(values)
+    };
+    match kind {
+        ParamKind::Float => text
+            .trim()
+            .parse::<f32>()
+            .map(ParamValue::Float)
+            .map_err(|err| format!("invalid float {}: {}", text, err)),
+        ParamKind::Int => text
+            .trim()
+            .parse::<i32>()
+            .map(ParamValue::Int)
+            .map_err(|err| format!("invalid int {}: {}", text, err)),
+        ParamKind::Bool => match text.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "on" | "yes" => Ok(ParamValue::Bool(true)),
+            "0" | "false" | "off" | "no" => Ok(ParamValue::Bool(false)),
+            _ => Err(format!("invalid bool {}", text)),
+        },
+        ParamKind::Vec2 => {
+            let v = floats(2)?;
+            Ok(ParamValue::Vec2([v[0], v[1]]))
+        }
+        ParamKind::Vec3 => {
+            let v = floats(3)?;
+            Ok(ParamValue::Vec3([v[0], v[1], v[2]]))
+        }
+        ParamKind::String => Ok(ParamValue::String(text.to_string())),
+    }
+}
+
+fn apply_param_overrides(graph: &mut Graph, overrides: &[ParamOverride]) -> Result<(), String> {
+    for entry in overrides {
+        let node_id = find_node_by_name(graph, &entry.node)?;
+        let kind = graph
+            .node(node_id)
+            .and_then(|node| node.builtin_kind())
+            .ok_or_else(|| format!("node {} has no builtin kind", entry.node))?;
+        let spec = param_spec_for(kind, &entry.key)
+            .map_err(|err| format!("node {}: {}", entry.node, err))?;
+        let value = parse_param_text(spec.kind, &entry.value)
+            .and_then(|value| validate_param_value(kind, &entry.key, value))
+            .map_err(|err| format!("--set {}.{}: {}", entry.node, entry.key, err))?;
+        graph
+            .set_param(node_id, entry.key.clone(), value)
+            .map_err(|err| format!("param error: {:?}", err))?;
+        tracing::info!("headless: set {}.{} = {}", entry.node, entry.key, entry.value);
+    }
+    Ok(())
+}
+
 fn find_pin_id(
     graph: &lobedo_core::Graph,
     node_id: lobedo_core::NodeId,
@@ -237,12 +537,12 @@ fn find_pin_id(
     kind: lobedo_core::PinKind,
 ) -> Option<lobedo_core::PinId> {
     let node = graph.node(node_id)?;
-    let pins = match kind {
-        lobedo_core::PinKind::Input => &node.inputs,
-        lobedo_core::PinKind::Output => &node.outputs,
+    let pins: Vec<lobedo_core::PinId> = match kind {
+        lobedo_core::PinKind::Input => node.input_pins().copied().collect(),
+        lobedo_core::PinKind::Output => node.outputs.clone(),
     };
 
-    pins.iter().copied().find(|pin_id| {
+    pins.into_iter().find(|pin_id| {
         graph
             .pin(*pin_id)
             .map(|pin| pin.name == pin_name)
@@ -250,6 +550,13 @@ fn find_pin_id(
     })
 }
 
+fn load_project_json(path: &Path) -> Result<Project, String> {
+    let data = std::fs::read(path).map_err(|err| err.to_string())?;
+    let mut project: Project = serde_json::from_slice(&data).map_err(|err| err.to_string())?;
+    project.migrate_to_latest();
+    Ok(project)
+}
+
 fn save_project_json(project: &Project, path: &Path) -> Result<(), String> {
     let data = serde_json::to_vec_pretty(project).map_err(|err| err.to_string())?;
     std::fs::write(path, data).map_err(|err| err.to_string())
@@ -271,6 +578,310 @@ fn validate_topo_sort(project: &Project, output_node_name: &str) -> Result<(), S
     Ok(())
 }
 
+fn lint_graph(graph: &Graph) -> Result<(), String> {
+    let diagnostics = graph.validate();
+    let mut errors = 0;
+    for diagnostic in &diagnostics {
+        let severity = match diagnostic.severity {
+            Severity::Error => {
+                errors += 1;
+                "error"
+            }
+            Severity::Warning => "warning",
+        };
+        let mut location = node_label(graph, diagnostic.node);
+        if let Some(param) = diagnostic.param.as_deref() {
+            location = format!("{} [{}]", location, param);
+        }
+        println!("{}: {}: {}", severity, location, diagnostic.message);
+        if let Some(suggestion) = diagnostic.suggestion.as_deref() {
+            println!("  help: {}", suggestion);
+        }
+    }
+    println!(
+        "lint: {} errors, {} warnings",
+        errors,
+        diagnostics.len() - errors
+    );
+    if errors > 0 {
+        return Err(format!("lint failed with {} errors", errors));
+    }
+    Ok(())
+}
+
+fn run_headless_wedge(
+    project: &Project,
+    name: &str,
+    base_dir: Option<&Path>,
+) -> Result<(), String> {
+    let wedge = project
+        .wedges
+        .iter()
+        .find(|wedge| wedge.name == name)
+        .ok_or_else(|| format!("wedge {} not found", name))?;
+    let manifest = run_wedge(project, wedge, base_dir, None)?;
+    for run in &manifest.runs {
+        let values: Vec<String> = run
+            .values
+            .iter()
+            .map(|(key, value)| format!("{}={:?}", key, value))
+            .collect();
+        match run.error.as_deref() {
+            None => println!(
+                "wedge {}: {} -> {} ({:.2} ms)",
+                run.index,
+                values.join(" "),
+                run.output,
+                run.duration_ms
+            ),
+            Some(err) => println!("wedge {}: {} failed: {}", run.index, values.join(" "), err),
+        }
+    }
+    println!(
+        "wedge {}: {} runs in {:.2} ms",
+        manifest.wedge,
+        manifest.runs.len(),
+        manifest.total_ms
+    );
+    let failed = manifest.failed_runs();
+    if failed > 0 {
+        return Err(format!("wedge {} had {} failed runs", name, failed));
+    }
+    Ok(())
+}
+
+fn find_node_by_name(graph: &Graph, name: &str) -> Result<NodeId, String> {
+    let matches: Vec<NodeId> = graph
+        .nodes()
+        .filter(|node| node.name == name)
+        .map(|node| node.id)
+        .collect();
+    match matches.as_slice() {
+        [] => Err(format!("node {} not found", name)),
+        [node_id] => Ok(*node_id),
+        _ => Err(format!(
+            "node name {} is ambiguous ({} nodes share it)",
+            name,
+            matches.len()
+        )),
+    }
+}
+
+fn cook_node(
+    project: &Project,
+    target: &str,
+    output_path: Option<&Path>,
+    profile_path: Option<&Path>,
+    disk_cache: Option<DiskCookCache>,
+) -> Result<(), String> {
+    let graph = &project.graph;
+    let node_id = find_node_by_name(graph, target)?;
+    let mut state = GeometryEvalState::new();
+    state.set_disk_cache(disk_cache);
+    state
+        .eval
+        .set_frame(project.settings.timeline.current_frame as f32);
+    let result = evaluate_geometry_graph(graph, node_id, &mut state)
+        .map_err(|err| format!("cook failed: {:?}", err))?;
+    print_eval_report(graph, &result.report);
+    if let Some(path) = profile_path {
+        write_eval_profile(graph, &result.report, path, ProfileFormat::for_path(path))?;
+        tracing::info!("headless: wrote cook profile to {:?}", path);
+    }
+    if !result.report.output_valid {
+        return Err(format!("cook of {} failed", target));
+    }
+    let geometry = result
+        .output
+        .ok_or_else(|| format!("node {} produced no geometry", target))?;
+
+    if let Some(path) = output_path {
+        export_geometry(&geometry, path, ExportKind::for_path(path)?)?;
+    } else if let Some((path, kind)) = node_export_target(graph, node_id) {
+        export_geometry(&geometry, Path::new(&path), kind)?;
+    }
+    Ok(())
+}
+
+fn print_eval_report(graph: &Graph, report: &EvalReport) {
+    let total_ms: f32 = report
+        .node_reports
+        .values()
+        .map(|entry| entry.duration_ms)
+        .sum();
+    println!(
+        "cook: {} nodes, {} computed, {} cache hits, {:.2} ms",
+        report.ordered.len(),
+        report.computed.len(),
+        report.cache_hits,
+        total_ms
+    );
+    for node_id in &report.ordered {
+        let Some(entry) = report.node_reports.get(node_id) else {
+            continue;
+        };
+        let status = if entry.error.is_some() {
+            "error"
+        } else if entry.cache_hit {
+            "cached"
+        } else if report.disk_hits.contains(node_id) {
+            "disk"
+        } else {
+            "cooked"
+        };
+        println!(
+            "  {:<28} {:>10.2} ms  {}",
+            node_label(graph, *node_id),
+            entry.duration_ms,
+            status
+        );
+    }
+    for err in &report.errors {
+        match err {
+            EvalError::Node { node, message } => {
+                println!("  error: {}: {}", node_label(graph, *node), message);
+            }
+            EvalError::Upstream { node, upstream } => {
+                let upstream: Vec<String> = upstream
+                    .iter()
+                    .map(|id| node_label(graph, *id))
+                    .collect();
+                println!(
+                    "  error: {}: upstream failed ({})",
+                    node_label(graph, *node),
+                    upstream.join(", ")
+                );
+            }
+        }
+    }
+}
+
+fn node_label(graph: &Graph, node_id: NodeId) -> String {
+    match graph.node(node_id) {
+        Some(node) => format!("{} {:?}", node.name, node_id),
+        None => format!("{:?}", node_id),
+    }
+}
+
+fn node_export_target(graph: &Graph, node_id: NodeId) -> Option<(String, ExportKind)> {
+    let node = graph.node(node_id)?;
+    let kind = ExportKind::for_node(node)?;
+    let path = node.params.get_string("path", "");
+    if path.trim().is_empty() {
+        return None;
+    }
+    Some((path.to_string(), kind))
+}
+
+fn export_geometry(geometry: &Geometry, path: &Path, kind: ExportKind) -> Result<(), String> {
+    write_geometry_with(geometry, path, kind)?;
+    tracing::info!("headless: wrote {}", path.display());
+    Ok(())
+}
+
 fn default_category() -> String {
     "Default".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_param_text_by_kind() {
+        assert_eq!(
+            parse_param_text(ParamKind::Vec3, "1, 2,3").unwrap(),
+            ParamValue::Vec3([1.0, 2.0, 3.0])
+        );
+        assert_eq!(
+            parse_param_text(ParamKind::Vec2, "0.5,-1").unwrap(),
+            ParamValue::Vec2([0.5, -1.0])
+        );
+        assert!(parse_param_text(ParamKind::Vec3, "1,2").is_err());
+        assert!(parse_param_text(ParamKind::Vec2, "1,2,3").is_err());
+        assert!(parse_param_text(ParamKind::Vec3, "1,x,3").is_err());
+        assert_eq!(
+            parse_param_text(ParamKind::Int, " 4 ").unwrap(),
+            ParamValue::Int(4)
+        );
+        assert!(parse_param_text(ParamKind::Int, "4.5").is_err());
+        for text in ["1", "true", "On", "YES"] {
+            assert_eq!(
+                parse_param_text(ParamKind::Bool, text).unwrap(),
+                ParamValue::Bool(true)
+            );
+        }
+        for text in ["0", "false", "off", "No"] {
+            assert_eq!(
+                parse_param_text(ParamKind::Bool, text).unwrap(),
+                ParamValue::Bool(false)
+            );
+        }
+        assert!(parse_param_text(ParamKind::Bool, "maybe").is_err());
+    }
+
+    #[test]
+    fn splits_overrides_at_the_last_dot() {
+        let entry = parse_param_override("Box.v2.size=1,2,3").unwrap();
+        assert_eq!(entry.node, "Box.v2");
+        assert_eq!(entry.key, "size");
+        assert_eq!(entry.value, "1,2,3");
+        let entry = parse_param_override("File.path=a=b.obj").unwrap();
+        assert_eq!(entry.key, "path");
+        assert_eq!(entry.value, "a=b.obj");
+        for text in ["Box.size", "size=1", ".size=1", "Box.=1"] {
+            assert!(parse_param_override(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn validates_param_values_against_specs() {
+        let kind = BuiltinNodeKind::StlOutput;
+        assert_eq!(
+            validate_param_value(kind, "format", ParamValue::Int(1)).unwrap(),
+            ParamValue::Int(1)
+        );
+        assert!(validate_param_value(kind, "format", ParamValue::Int(2)).is_err());
+        assert!(validate_param_value(kind, "format", ParamValue::Int(-1)).is_err());
+        assert!(validate_param_value(kind, "facet_normals", ParamValue::Int(1)).is_err());
+        assert!(validate_param_value(kind, "missing", ParamValue::Int(1)).is_err());
+        assert!(
+            validate_param_value(BuiltinNodeKind::Box, "size", ParamValue::Vec2([1.0, 1.0]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn plan_params_only_apply_when_given() {
+        let mut graph = Graph::default();
+        let node_id = graph.add_node(lobedo_core::NodeDefinition {
+            name: "Box".to_string(),
+            category: default_category(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            variadic_inputs: false,
+        });
+        let mut plan_node = PlanNode {
+            name: "Box".to_string(),
+            category: default_category(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            params: BTreeMap::new(),
+        };
+        apply_plan_params(&mut graph, node_id, &plan_node).unwrap();
+        let node = graph.node(node_id).unwrap();
+        assert!(node.kind_id.is_empty());
+        assert!(node.params.values.is_empty());
+
+        plan_node
+            .params
+            .insert("size".to_string(), ParamValue::Vec3([2.0, 2.0, 2.0]));
+        apply_plan_params(&mut graph, node_id, &plan_node).unwrap();
+        let node = graph.node(node_id).unwrap();
+        assert_eq!(node.kind_id, BuiltinNodeKind::Box.id());
+        assert_eq!(
+            node.params.values.get("size"),
+            Some(&ParamValue::Vec3([2.0, 2.0, 2.0]))
+        );
+    }
+}
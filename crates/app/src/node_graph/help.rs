@@ -4,11 +4,10 @@ use egui::{
     Color32, Context, FontId, Frame, Id, Margin, Order, Pos2, Rect, RichText,
     ScrollArea, Vec2,
 };
-use lobedo_core::{builtin_kind_from_id, builtin_kind_from_name, help_summary, node_help_page};
+use lobedo_core::node_help_page;
 
 pub fn node_help(help_key: &str) -> Option<&'static str> {
-    let kind = builtin_kind_from_id(help_key).or_else(|| builtin_kind_from_name(help_key))?;
-    help_summary(kind)
+    node_help_page(help_key)?.description.first().copied()
 }
 
 pub fn param_help(_node_name: &str, param: &str) -> Option<Cow<'static, str>> {
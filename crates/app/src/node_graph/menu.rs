@@ -1,17 +1,25 @@
 use egui::Ui;
 
-use lobedo_core::{menu_group, node_definition, node_specs, BuiltinNodeKind};
+use lobedo_core::{node_types, BuiltinNodeKind};
 
 use super::utils::submenu_menu_button;
 
 #[derive(Clone)]
 pub(super) struct MenuItem {
-    pub(super) kind: BuiltinNodeKind,
+    pub(super) kind_id: String,
     pub(super) name: String,
     pub(super) category: String,
     pub(super) submenu: Option<&'static str>,
 }
 
+#[derive(Clone)]
+pub(super) enum MenuPick {
+    /// Built-in or plugin node kind id.
+    Node(String),
+    /// Index into the project's subnet assets.
+    Asset(usize),
+}
+
 pub(super) struct MenuLayout {
     pub(super) items: Vec<MenuItem>,
     pub(super) submenus: Vec<MenuSubgroup>,
@@ -22,16 +30,28 @@ pub(super) struct MenuSubgroup {
     pub(super) items: Vec<MenuItem>,
 }
 
-pub(super) fn builtin_menu_items() -> Vec<MenuItem> {
-    node_specs()
-        .iter()
-        .map(|spec| {
-            let definition = node_definition(spec.kind);
+/// Built-in and registered plugin nodes.
+pub(super) fn node_menu_items() -> Vec<MenuItem> {
+    node_types()
+        .into_iter()
+        // Subnets are created by collapsing nodes or from assets.
+        .filter(|node_type| {
+            !matches!(
+                node_type.builtin_kind(),
+                Some(
+                    BuiltinNodeKind::Subnet
+                        | BuiltinNodeKind::SubnetInput
+                        | BuiltinNodeKind::SubnetOutput
+                )
+            )
+        })
+        .map(|node_type| {
+            let definition = node_type.definition();
             MenuItem {
-                kind: spec.kind,
+                kind_id: node_type.kind_id().to_string(),
                 name: definition.name,
                 category: definition.category,
-                submenu: menu_group(spec.kind),
+                submenu: node_type.menu_group(),
             }
         })
         .collect()
@@ -62,13 +82,13 @@ pub(super) fn menu_layout(items: &[MenuItem]) -> MenuLayout {
     layout
 }
 
-pub(super) fn render_menu_layout(ui: &mut Ui, layout: MenuLayout) -> Option<BuiltinNodeKind> {
+pub(super) fn render_menu_layout(ui: &mut Ui, layout: MenuLayout) -> Option<String> {
     for submenu in layout.submenus {
         let mut picked = None;
         submenu_menu_button(ui, submenu.name, |ui| {
             for item in &submenu.items {
                 if ui.button(item.name.as_str()).clicked() {
-                    picked = Some(item.kind);
+                    picked = Some(item.kind_id.clone());
                     ui.close();
                 }
             }
@@ -80,7 +100,7 @@ pub(super) fn render_menu_layout(ui: &mut Ui, layout: MenuLayout) -> Option<Buil
 
     for item in layout.items {
         if ui.button(item.name.as_str()).clicked() {
-            return Some(item.kind);
+            return Some(item.kind_id);
         }
     }
 
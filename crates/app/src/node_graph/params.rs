@@ -12,8 +12,9 @@ use std::sync::{Mutex, OnceLock};
 use wasm_bindgen_futures::spawn_local;
 
 use lobedo_core::{
-    parse_color_gradient, BuiltinNodeKind, ColorGradient, ParamKind, ParamOption, ParamRange,
-    ParamSpec, ParamValue, ParamWidget, ParamPathKind,
+    check_expression, parse_color_gradient, BuiltinNodeKind, Channel, ColorGradient, Interpolation,
+    Keyframe, ParamKind, ParamOption, ParamRange, ParamSpec, ParamValue, ParamWidget,
+    ParamPathKind,
 };
 
 use super::help::{param_help, show_help_tooltip};
@@ -446,6 +447,133 @@ pub(super) fn edit_param_with_spec(
     }
 }
 
+/// Row editing the expression that drives a parameter. Returns the new expression,
+/// `Some(None)` when it was removed, or `None` when nothing changed.
+pub(super) fn edit_expression_row(
+    ui: &mut Ui,
+    node_name: &str,
+    node_kind: Option<BuiltinNodeKind>,
+    spec: &ParamSpec,
+    expression: &str,
+) -> Option<Option<String>> {
+    let label = if spec.label.is_empty() {
+        display_label(node_name, node_kind, spec.key)
+    } else {
+        spec.label.to_string()
+    };
+    let mut text = expression.to_string();
+    let mut result = None;
+    param_row_with_label(ui, spec.key, &format!("{} =", label), spec.help, |ui| {
+        let height = ui.spacing().interact_size.y;
+        let mut edit = egui::TextEdit::singleline(&mut text).code_editor();
+        let error = check_expression(expression).err();
+        if error.is_some() {
+            edit = edit.text_color(ui.visuals().error_fg_color);
+        }
+        let response = ui.add_sized([(ui.available_width() - 32.0).max(120.0), height], edit);
+        if let Some(error) = error {
+            response.clone().on_hover_text(error);
+        }
+        if response.changed() {
+            result = Some(Some(text.clone()));
+        }
+        if ui
+            .add_sized([28.0, height], egui::Button::new("x"))
+            .on_hover_text("Remove expression")
+            .clicked()
+        {
+            result = Some(None);
+        }
+        result.is_some()
+    });
+    result
+}
+
+/// Row under a keyframed parameter: toggles the key on `frame`, picks the
+/// interpolation of the segment the frame falls in and clears the animation.
+/// Returns the new channel, `Some(None)` when cleared, or `None` when unchanged.
+pub(super) fn edit_keyframe_row(
+    ui: &mut Ui,
+    key: &str,
+    channel: &Channel,
+    frame: f32,
+) -> Option<Option<Channel>> {
+    let mut result = None;
+    let on_key = channel.key_at(frame).is_some();
+    let label = format!("{} keys", channel.keys.len());
+    param_row_with_label(ui, key, &label, None, |ui| {
+        let height = ui.spacing().interact_size.y;
+        let (text, hover) = if on_key {
+            ("Remove key", format!("Remove the key on frame {}", frame))
+        } else {
+            ("Set key", format!("Key the current value on frame {}", frame))
+        };
+        if ui
+            .add_sized([96.0, height], egui::Button::new(text))
+            .on_hover_text(hover)
+            .clicked()
+        {
+            let mut next = channel.clone();
+            if on_key {
+                next.remove_key(frame);
+            } else if let Some(value) = channel.evaluate(frame) {
+                next.set_key(Keyframe {
+                    frame,
+                    value,
+                    interpolation: segment_interpolation(channel, frame),
+                });
+            }
+            result = Some(Some(next));
+        }
+        let segment = channel
+            .keys
+            .iter()
+            .rposition(|key| key.frame <= frame)
+            .unwrap_or(0);
+        let mut interpolation = segment_interpolation(channel, frame);
+        egui::ComboBox::from_id_salt((key, "interpolation"))
+            .selected_text(match interpolation {
+                Interpolation::Linear => "Linear",
+                Interpolation::Bezier => "Bezier",
+            })
+            .width((ui.available_width() - 32.0).max(80.0))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut interpolation, Interpolation::Linear, "Linear");
+                ui.selectable_value(&mut interpolation, Interpolation::Bezier, "Bezier");
+            })
+            .response
+            .on_hover_text("Interpolation from the key at or before the current frame");
+        if interpolation != segment_interpolation(channel, frame) {
+            let mut next = channel.clone();
+            if let Some(key) = next.keys.get_mut(segment) {
+                key.interpolation = interpolation;
+            }
+            result = Some(Some(next));
+        }
+        if ui
+            .add_sized([28.0, height], egui::Button::new("x"))
+            .on_hover_text("Remove all keys")
+            .clicked()
+        {
+            result = Some(None);
+        }
+        result.is_some()
+    });
+    result
+}
+
+/// Interpolation of the key at or before `frame`, used for keys added there.
+pub(super) fn segment_interpolation(channel: &Channel, frame: f32) -> Interpolation {
+    channel
+        .keys
+        .iter()
+        .rev()
+        .find(|key| key.frame <= frame)
+        .or(channel.keys.first())
+        .map(|key| key.interpolation)
+        .unwrap_or_default()
+}
+
 pub(super) fn edit_group_row(
     ui: &mut Ui,
     node_name: &str,
@@ -803,6 +931,8 @@ enum PathPickerKind {
     ReadMesh,
     WriteObj,
     WriteGltf,
+    WritePly,
+    WriteStl,
     ReadSplat,
     WriteSplat,
     ReadTexture,
@@ -813,6 +943,8 @@ fn path_picker_kind_from_spec(spec: &ParamSpec) -> Option<PathPickerKind> {
         ParamPathKind::ReadMesh => PathPickerKind::ReadMesh,
         ParamPathKind::WriteObj => PathPickerKind::WriteObj,
         ParamPathKind::WriteGltf => PathPickerKind::WriteGltf,
+        ParamPathKind::WritePly => PathPickerKind::WritePly,
+        ParamPathKind::WriteStl => PathPickerKind::WriteStl,
         ParamPathKind::ReadSplat => PathPickerKind::ReadSplat,
         ParamPathKind::WriteSplat => PathPickerKind::WriteSplat,
         ParamPathKind::ReadTexture => PathPickerKind::ReadTexture,
@@ -861,7 +993,11 @@ fn open_path_picker_button(
     {
         if matches!(
             kind,
-            PathPickerKind::WriteObj | PathPickerKind::WriteGltf | PathPickerKind::WriteSplat
+            PathPickerKind::WriteObj
+                | PathPickerKind::WriteGltf
+                | PathPickerKind::WritePly
+                | PathPickerKind::WriteStl
+                | PathPickerKind::WriteSplat
         ) {
             ui.add_enabled(false, egui::Button::new("..."))
                 .on_hover_text("Save dialogs are not available in web builds yet");
@@ -876,10 +1012,14 @@ fn open_path_picker_button(
             let kind_copy = kind;
             spawn_local(async move {
                 let (label, extensions) = match kind_copy {
-                    PathPickerKind::ReadMesh => ("Mesh", &["obj", "gltf", "glb"][..]),
+                    PathPickerKind::ReadMesh => ("Mesh", &["obj", "gltf", "glb", "ply", "stl"][..]),
                     PathPickerKind::WriteObj => ("OBJ", &["obj"][..]),
                     PathPickerKind::WriteGltf => ("glTF", &["glb", "gltf"][..]),
-                    PathPickerKind::ReadSplat | PathPickerKind::WriteSplat => ("PLY", &["ply"][..]),
+                    PathPickerKind::WritePly => ("PLY", &["ply"][..]),
+                    PathPickerKind::WriteStl => ("STL", &["stl"][..]),
+                    PathPickerKind::ReadSplat | PathPickerKind::WriteSplat => {
+                        ("Splats", &["ply", "splat", "ksplat", "spz"][..])
+                    }
                     PathPickerKind::ReadTexture => ("Image", &["png", "jpg", "jpeg"][..]),
                 };
                 let dialog = AsyncFileDialog::new().add_filter(label, extensions);
@@ -912,11 +1052,13 @@ fn open_path_picker_button(
 #[cfg(not(target_arch = "wasm32"))]
 fn open_path_picker(kind: PathPickerKind, current: &str) -> Option<String> {
         let (label, extensions, is_save, default_name) = match kind {
-        PathPickerKind::ReadMesh => ("Mesh", &["obj", "gltf", "glb"][..], false, "model.obj"),
+        PathPickerKind::ReadMesh => ("Mesh", &["obj", "gltf", "glb", "ply", "stl"][..], false, "model.obj"),
         PathPickerKind::WriteObj => ("OBJ", &["obj"][..], true, "output.obj"),
         PathPickerKind::WriteGltf => ("glTF", &["glb", "gltf"][..], true, "output.glb"),
-        PathPickerKind::ReadSplat => ("PLY", &["ply"][..], false, "splats.ply"),
-        PathPickerKind::WriteSplat => ("PLY", &["ply"][..], true, "output.ply"),
+        PathPickerKind::WritePly => ("PLY", &["ply"][..], true, "output.ply"),
+        PathPickerKind::WriteStl => ("STL", &["stl"][..], true, "output.stl"),
+        PathPickerKind::ReadSplat => ("Splats", &["ply", "splat", "ksplat", "spz"][..], false, "splats.ply"),
+        PathPickerKind::WriteSplat => ("Splats", &["ply", "splat", "ksplat", "spz"][..], true, "output.ply"),
         PathPickerKind::ReadTexture => ("Image", &["png", "jpg", "jpeg"][..], false, "texture.png"),
         };
     let mut dialog = FileDialog::new().add_filter(label, extensions);
@@ -11,7 +11,10 @@ use egui::{vec2, Color32, Frame, LayerId, Pos2, Rect, Sense, Stroke, Ui, UiBuild
 use egui_snarl::ui::{BackgroundPattern, SnarlStyle};
 use egui_snarl::{InPinId, OutPinId, Snarl};
 
-use lobedo_core::{Graph, GraphNote, NodeId, ProgressEvent, ProgressSink, ProjectSettings};
+use lobedo_core::{
+    Diagnostic, Graph, GraphNote, NodeId, PinId, ProgressEvent, ProgressSink, ProjectSettings,
+    SubnetAsset,
+};
 
 use super::viewer::NodeGraphViewer;
 
@@ -52,16 +55,25 @@ pub struct NodeGraphState {
     pub(super) output_pin_positions: Rc<RefCell<HashMap<OutPinId, Pos2>>>,
     pub(super) error_nodes: HashSet<NodeId>,
     pub(super) error_messages: HashMap<NodeId, String>,
+    /// `Graph::validate` diagnostics by node, with the graph revision they were
+    /// computed for.
+    pub(super) lint: HashMap<NodeId, Vec<Diagnostic>>,
+    pub(super) lint_revision: Option<u64>,
     pub(super) dirty_nodes: HashSet<NodeId>,
     pub(super) node_menu_request: Option<NodeMenuRequest>,
     pub(super) node_menu_open: bool,
     pub(super) node_menu_screen_pos: Pos2,
     pub(super) node_menu_node: Option<NodeId>,
     pub(super) pending_write_request: Option<WriteRequest>,
+    pub(super) asset_export_request: Option<NodeId>,
+    /// Node to lock; locking needs the app's cooked outputs.
+    pub(super) lock_request: Option<NodeId>,
     pub(super) progress_state: Arc<Mutex<NodeProgressState>>,
     pub(super) selected_note: Option<u64>,
     pub(super) last_changed: bool,
     pub(super) layout_changed: bool,
+    /// Frame keyframed parameters are shown and keyed at.
+    pub(super) current_frame: f32,
 }
 
 #[derive(Clone, Copy)]
@@ -80,6 +92,8 @@ pub struct NodeInfoRequest {
 pub enum WriteRequestKind {
     Obj,
     Gltf,
+    Ply,
+    Stl,
     Splat,
 }
 
@@ -122,16 +136,21 @@ impl Default for NodeGraphState {
             output_pin_positions: Rc::new(RefCell::new(HashMap::new())),
             error_nodes: HashSet::new(),
             error_messages: HashMap::new(),
+            lint: HashMap::new(),
+            lint_revision: None,
             dirty_nodes: HashSet::new(),
             node_menu_request: None,
             node_menu_open: false,
             node_menu_screen_pos: Pos2::new(0.0, 0.0),
             node_menu_node: None,
             pending_write_request: None,
+            asset_export_request: None,
+            lock_request: None,
             progress_state: Arc::new(Mutex::new(NodeProgressState::default())),
             selected_note: None,
             last_changed: false,
             layout_changed: false,
+            current_frame: 1.0,
         }
     }
 }
@@ -196,11 +215,20 @@ impl NodeGraphState {
         self.pending_write_request.take()
     }
 
+    pub fn take_asset_export_request(&mut self) -> Option<NodeId> {
+        self.asset_export_request.take()
+    }
+
+    pub fn take_lock_request(&mut self) -> Option<NodeId> {
+        self.lock_request.take()
+    }
+
     pub fn show(
         &mut self,
         ui: &mut Ui,
         graph: &mut Graph,
         settings: &mut ProjectSettings,
+        assets: &[SubnetAsset],
         eval_dirty: &mut bool,
         eval_state: &lobedo_core::EvalState,
     ) {
@@ -209,6 +237,7 @@ impl NodeGraphState {
             self.sync_wires(graph);
             self.needs_wire_sync = false;
         }
+        self.update_lint(graph);
 
         self.prev_node_ui_rects = std::mem::take(&mut self.node_ui_rects);
         self.node_ui_rects.clear();
@@ -249,6 +278,7 @@ impl NodeGraphState {
             wrangle_help_request: &mut self.wrangle_help_request,
             error_nodes: &self.error_nodes,
             error_messages: &self.error_messages,
+            lint: &self.lint,
             dim_nodes: &dim_nodes,
             skip_header_click,
             changed: false,
@@ -287,7 +317,7 @@ impl NodeGraphState {
             self.needs_wire_sync = true;
         }
 
-        if self.add_menu_open && self.show_add_menu(ui, graph, settings) {
+        if self.add_menu_open && self.show_add_menu(ui, graph, settings, assets) {
             self.last_changed = true;
             *eval_dirty = true;
             self.needs_wire_sync = true;
@@ -311,6 +341,10 @@ impl NodeGraphState {
             *eval_dirty = true;
             self.needs_wire_sync = true;
         }
+        if self.needs_wire_sync {
+            // Variadic inputs left free by this frame's edits go away.
+            graph.compact_variadic_inputs();
+        }
     }
 
     pub fn take_changed(&mut self) -> bool {
@@ -566,11 +600,26 @@ impl NodeGraphState {
         changed
     }
 
+    fn update_lint(&mut self, graph: &Graph) {
+        if self.lint_revision == Some(graph.revision()) {
+            return;
+        }
+        self.lint_revision = Some(graph.revision());
+        self.lint.clear();
+        for diagnostic in graph.validate() {
+            self.lint.entry(diagnostic.node).or_default().push(diagnostic);
+        }
+    }
+
     pub fn set_error_state(&mut self, nodes: HashSet<NodeId>, messages: HashMap<NodeId, String>) {
         self.error_nodes = nodes;
         self.error_messages = messages;
     }
 
+    pub fn set_current_frame(&mut self, frame: f32) {
+        self.current_frame = frame;
+    }
+
     pub fn set_dirty_nodes(&mut self, nodes: HashSet<NodeId>) -> bool {
         if self.dirty_nodes == nodes {
             return false;
@@ -611,7 +660,7 @@ impl NodeGraphState {
         let Some(node) = graph.node(node_id) else {
             return false;
         };
-        let input_pins = node.inputs.clone();
+        let input_pins: Vec<PinId> = node.input_pins().copied().collect();
         let output_pins = node.outputs.clone();
         let mut upstream_outputs = HashSet::new();
         let mut downstream_inputs = Vec::new();
@@ -641,6 +690,7 @@ impl NodeGraphState {
         if self.selected_node.as_ref() == Some(&node_id) {
             self.selected_node = None;
         }
+        graph.compact_variadic_inputs();
         self.needs_wire_sync = true;
         true
     }
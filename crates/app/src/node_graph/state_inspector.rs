@@ -2,11 +2,17 @@ use egui::Ui;
 use std::collections::HashSet;
 
 use lobedo_core::{
-    param_specs_for_kind_id, param_specs_for_name, BuiltinNodeKind, Graph, NodeParams, ParamWidget,
+    is_animatable, node_definition, param_specs_for_kind_id, param_specs_for_name,
+    promote_param, promoted_param_specs, unpromote_param, BuiltinNodeKind, Channel, Graph,
+    Keyframe, Node, NodeId, NodeParams, ParamSpec, ParamValue, ParamWidget, PinType,
+    PromotedParam, Subnet,
 };
 
 use super::help::{node_help, show_help_page_window, show_help_tooltip};
-use super::params::{edit_group_row, edit_param, edit_param_with_spec};
+use super::params::{
+    edit_expression_row, edit_group_row, edit_keyframe_row, edit_param, edit_param_with_spec,
+    segment_interpolation,
+};
 use super::state::{NodeGraphState, WriteRequest, WriteRequestKind};
 
 impl NodeGraphState {
@@ -34,6 +40,9 @@ impl NodeGraphState {
         let node_kind = node.builtin_kind();
         let node_category = node.category.clone();
         let param_values = node.params.values.clone();
+        let expressions = node.expressions.clone();
+        let channels = node.channels.clone();
+        let input_sources = node.variadic_inputs.then(|| connected_input_sources(graph, node));
         let visible_params = NodeParams {
             values: param_values.clone(),
         };
@@ -100,6 +109,13 @@ impl NodeGraphState {
         } else {
             param_specs_for_name(&node_name)
         };
+        let promoted = promoted_params(node);
+        let promotable = node.subnet.as_deref().map(promotable_params);
+        let candidates = expression_candidates(node, &param_specs, &promoted, &visible_params);
+        let key_candidates = keyframe_candidates(node, &candidates);
+        let linked = linked_param_sources(graph, node);
+        let input_candidates = param_input_candidates(graph, node, &param_specs, &visible_params);
+        let removable_inputs = removable_param_inputs(graph, node);
         let mut spec_keys = HashSet::new();
         let should_skip = |key: &str| -> bool {
             if matches!(node_kind, Some(BuiltinNodeKind::Group | BuiltinNodeKind::Delete))
@@ -158,6 +174,30 @@ impl NodeGraphState {
                 if !spec.is_visible(&visible_params) {
                     continue;
                 }
+                if let Some(source) = linked.iter().find(|(key, _)| key == spec.key) {
+                    ui.label(format!("{}: from {}", spec.label, source.1))
+                        .on_hover_text("Set by the linked value input");
+                    rendered_any = true;
+                    continue;
+                }
+                if let Some(expression) = expressions.get(spec.key) {
+                    if let Some(next) =
+                        edit_expression_row(ui, &node_name, node_kind, spec, expression)
+                    {
+                        if graph.set_param_expression(node_id, spec.key, next).is_ok() {
+                            changed = true;
+                        }
+                    }
+                    rendered_any = true;
+                    continue;
+                }
+                if let Some(channel) = channels.get(spec.key) {
+                    changed |= self.edit_animated_param(ui, graph, node_id, spec.key, channel, |ui, value| {
+                        edit_param_with_spec(ui, &node_name, node_kind, spec, value)
+                    });
+                    rendered_any = true;
+                    continue;
+                }
                 let (next_value, did_change) =
                     edit_param_with_spec(ui, &node_name, node_kind, spec, value);
                 if did_change
@@ -174,6 +214,98 @@ impl NodeGraphState {
             }
         }
 
+        for (promoted, spec, inner_kind) in &promoted {
+            let Some(value) = param_values.get(&promoted.key).cloned() else {
+                continue;
+            };
+            spec_keys.insert(promoted.key.clone());
+            if let Some(expression) = expressions.get(&promoted.key) {
+                let next = ui
+                    .push_id(&promoted.key, |ui| {
+                        edit_expression_row(ui, &node_name, *inner_kind, spec, expression)
+                    })
+                    .inner;
+                if let Some(next) = next {
+                    if graph
+                        .set_param_expression(node_id, promoted.key.clone(), next)
+                        .is_ok()
+                    {
+                        changed = true;
+                    }
+                }
+                rendered_any = true;
+                continue;
+            }
+            if let Some(channel) = channels.get(&promoted.key) {
+                changed |= ui
+                    .push_id(&promoted.key, |ui| {
+                        self.edit_animated_param(ui, graph, node_id, &promoted.key, channel, |ui, value| {
+                            edit_param_with_spec(ui, &node_name, *inner_kind, spec, value)
+                        })
+                    })
+                    .inner;
+                rendered_any = true;
+                continue;
+            }
+            let (next_value, did_change) = ui
+                .push_id(&promoted.key, |ui| {
+                    edit_param_with_spec(ui, &node_name, *inner_kind, spec, value)
+                })
+                .inner;
+            if did_change
+                && graph
+                    .set_param(node_id, promoted.key.clone(), next_value)
+                    .is_ok()
+            {
+                changed = true;
+            }
+            rendered_any = true;
+        }
+        if let Some(promotable) = &promotable {
+            if rendered_any {
+                ui.separator();
+            }
+            if self.show_promote_controls(ui, graph, node_id, promotable, &promoted) {
+                changed = true;
+            }
+            rendered_any = true;
+        }
+
+        if !candidates.is_empty() {
+            if rendered_any {
+                ui.separator();
+            }
+            if self.show_expression_controls(ui, graph, node_id, &candidates) {
+                changed = true;
+            }
+            if !key_candidates.is_empty() && self.show_keyframe_controls(ui, graph, node_id, &key_candidates) {
+                changed = true;
+            }
+            rendered_any = true;
+        }
+
+        if !input_candidates.is_empty() || !removable_inputs.is_empty() {
+            if rendered_any {
+                ui.separator();
+            }
+            if show_param_input_controls(ui, graph, node_id, &input_candidates, &removable_inputs) {
+                self.needs_wire_sync = true;
+                changed = true;
+            }
+            rendered_any = true;
+        }
+
+        if let Some(sources) = input_sources.filter(|sources| sources.len() > 1) {
+            if rendered_any {
+                ui.separator();
+            }
+            if show_input_order(ui, graph, node_id, &sources) {
+                self.needs_wire_sync = true;
+                changed = true;
+            }
+            rendered_any = true;
+        }
+
         let mut param_keys: Vec<String> = param_values
             .keys()
             .filter(|key| !spec_keys.contains(*key))
@@ -210,25 +342,24 @@ impl NodeGraphState {
 
         if matches!(
             node_kind,
-            Some(BuiltinNodeKind::ObjOutput | BuiltinNodeKind::GltfOutput | BuiltinNodeKind::WriteSplats)
+            Some(
+                BuiltinNodeKind::ObjOutput
+                    | BuiltinNodeKind::GltfOutput
+                    | BuiltinNodeKind::PlyOutput
+                    | BuiltinNodeKind::StlOutput
+                    | BuiltinNodeKind::WriteSplats
+            )
         ) {
             ui.separator();
-            let label = if node_kind == Some(BuiltinNodeKind::ObjOutput) {
-                "Write OBJ"
-            } else if node_kind == Some(BuiltinNodeKind::GltfOutput) {
-                "Write GLTF"
-            } else {
-                "Write PLY"
+            let (label, kind) = match node_kind {
+                Some(BuiltinNodeKind::ObjOutput) => ("Write OBJ", WriteRequestKind::Obj),
+                Some(BuiltinNodeKind::GltfOutput) => ("Write GLTF", WriteRequestKind::Gltf),
+                Some(BuiltinNodeKind::PlyOutput) => ("Write PLY", WriteRequestKind::Ply),
+                Some(BuiltinNodeKind::StlOutput) => ("Write STL", WriteRequestKind::Stl),
+                _ => ("Write Splats", WriteRequestKind::Splat),
             };
             let can_write = !cfg!(target_arch = "wasm32");
             if ui.add_enabled(can_write, egui::Button::new(label)).clicked() {
-                let kind = if node_kind == Some(BuiltinNodeKind::ObjOutput) {
-                    WriteRequestKind::Obj
-                } else if node_kind == Some(BuiltinNodeKind::GltfOutput) {
-                    WriteRequestKind::Gltf
-                } else {
-                    WriteRequestKind::Splat
-                };
                 self.pending_write_request = Some(WriteRequest { node_id, kind });
             }
             if !can_write {
@@ -239,6 +370,159 @@ impl NodeGraphState {
         changed
     }
 
+    /// Combo boxes promoting inner parameters of a subnet and removing promoted ones.
+    fn show_promote_controls(
+        &mut self,
+        ui: &mut Ui,
+        graph: &mut Graph,
+        node_id: NodeId,
+        promotable: &[(NodeId, String, String)],
+        promoted: &[PromotedSpec],
+    ) -> bool {
+        let mut promote = None;
+        egui::ComboBox::from_id_salt((node_id, "promote_param"))
+            .selected_text("Promote parameter...")
+            .width(ui.available_width())
+            .show_ui(ui, |ui| {
+                for (inner_id, inner_name, key) in promotable {
+                    let label = format!("{} / {}", inner_name, key);
+                    if ui.selectable_label(false, label).clicked() {
+                        promote = Some((*inner_id, key.clone()));
+                    }
+                }
+            });
+        let mut unpromote = None;
+        if !promoted.is_empty() {
+            egui::ComboBox::from_id_salt((node_id, "unpromote_param"))
+                .selected_text("Unpromote parameter...")
+                .width(ui.available_width())
+                .show_ui(ui, |ui| {
+                    for (promoted, _, _) in promoted {
+                        if ui.selectable_label(false, promoted.key.as_str()).clicked() {
+                            unpromote = Some(promoted.key.clone());
+                        }
+                    }
+                });
+        }
+
+        if let Some((inner_id, key)) = promote {
+            if let Err(err) = promote_param(graph, node_id, inner_id, &key) {
+                tracing::warn!("Promote failed: {}", err);
+                return false;
+            }
+            return true;
+        }
+        if let Some(key) = unpromote {
+            if let Err(err) = unpromote_param(graph, node_id, &key) {
+                tracing::warn!("Unpromote failed: {}", err);
+                return false;
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Combo box adding an expression to one of the node's parameters, seeded with
+    /// the parameter's current value.
+    fn show_expression_controls(
+        &mut self,
+        ui: &mut Ui,
+        graph: &mut Graph,
+        node_id: NodeId,
+        candidates: &[(String, ParamValue)],
+    ) -> bool {
+        let path_name = graph.node_path_name(node_id).unwrap_or_default();
+        let mut picked = None;
+        egui::ComboBox::from_id_salt((node_id, "add_expression"))
+            .selected_text("Add expression...")
+            .width(ui.available_width())
+            .show_ui(ui, |ui| {
+                for (key, value) in candidates {
+                    if ui.selectable_label(false, key.as_str()).clicked() {
+                        picked = Some((key.clone(), literal_expression(value)));
+                    }
+                }
+            })
+            .response
+            .on_hover_text(format!(
+                "Other nodes reference this one as ch(\"../{}/<parameter>\")",
+                path_name
+            ));
+        let Some((key, expression)) = picked else {
+            return false;
+        };
+        graph
+            .set_param_expression(node_id, key, Some(expression))
+            .is_ok()
+    }
+
+    /// Parameter driven by keyframes: the widget shows the value on the current
+    /// frame and edits key it there, with the key controls on a row below.
+    fn edit_animated_param(
+        &self,
+        ui: &mut Ui,
+        graph: &mut Graph,
+        node_id: NodeId,
+        key: &str,
+        channel: &Channel,
+        edit: impl FnOnce(&mut Ui, ParamValue) -> (ParamValue, bool),
+    ) -> bool {
+        let frame = self.current_frame;
+        let mut changed = false;
+        if let Some(value) = channel.evaluate(frame) {
+            let (next_value, did_change) = edit(ui, value);
+            if did_change {
+                let keyframe = Keyframe {
+                    frame,
+                    value: next_value,
+                    interpolation: segment_interpolation(channel, frame),
+                };
+                changed |= graph.set_keyframe(node_id, key, keyframe).is_ok();
+            }
+        }
+        let next = ui
+            .push_id((key, "keys"), |ui| edit_keyframe_row(ui, key, channel, frame))
+            .inner;
+        if let Some(next) = next {
+            changed |= graph.set_param_channel(node_id, key, next).is_ok();
+        }
+        changed
+    }
+
+    /// Combo box keying one of the node's float or vector parameters on the
+    /// current frame.
+    fn show_keyframe_controls(
+        &mut self,
+        ui: &mut Ui,
+        graph: &mut Graph,
+        node_id: NodeId,
+        candidates: &[(String, ParamValue)],
+    ) -> bool {
+        let frame = self.current_frame;
+        let mut picked = None;
+        egui::ComboBox::from_id_salt((node_id, "add_keyframe"))
+            .selected_text("Set keyframe...")
+            .width(ui.available_width())
+            .show_ui(ui, |ui| {
+                for (key, value) in candidates {
+                    if ui.selectable_label(false, key.as_str()).clicked() {
+                        picked = Some((key.clone(), value.clone()));
+                    }
+                }
+            })
+            .response
+            .on_hover_text(format!("Animate a parameter, starting with a key on frame {}", frame));
+        let Some((key, value)) = picked else {
+            return false;
+        };
+        let keyframe = Keyframe {
+            frame,
+            value,
+            interpolation: Default::default(),
+        };
+        graph.set_keyframe(node_id, key, keyframe).is_ok()
+    }
+
     pub fn inspector_desired_height(&self, graph: &Graph) -> f32 {
         let row_height = 36.0;
         let separator_height = 8.0;
@@ -264,6 +548,8 @@ impl NodeGraphState {
 
         let mut heights = Vec::new();
         let mut spec_keys = HashSet::new();
+        let promoted = promoted_params(node);
+        let linked = linked_param_sources(graph, node);
         let should_skip = |key: &str| -> bool {
             if matches!(node_kind, Some(BuiltinNodeKind::Group | BuiltinNodeKind::Delete))
                 && key == "selection"
@@ -306,13 +592,77 @@ impl NodeGraphState {
                 if !spec.is_visible(&visible_params) {
                     continue;
                 }
+                if node.expressions.contains_key(spec.key)
+                    || linked.iter().any(|(key, _)| key == spec.key)
+                {
+                    heights.push(row_height);
+                    continue;
+                }
                 heights.push(row_height_for_spec(spec));
+                if node.channels.contains_key(spec.key) {
+                    heights.push(row_height);
+                }
             }
             if param_values.len() > spec_keys.len() {
                 heights.push(separator_height);
             }
         }
 
+        for (promoted, spec, _) in &promoted {
+            if param_values.contains_key(&promoted.key) {
+                spec_keys.insert(promoted.key.clone());
+                if node.expressions.contains_key(&promoted.key) {
+                    heights.push(row_height);
+                } else {
+                    heights.push(row_height_for_spec(spec));
+                    if node.channels.contains_key(&promoted.key) {
+                        heights.push(row_height);
+                    }
+                }
+            }
+        }
+        if node.subnet.is_some() {
+            if !heights.is_empty() {
+                heights.push(separator_height);
+            }
+            heights.push(row_height);
+            if !promoted.is_empty() {
+                heights.push(row_height);
+            }
+        }
+        let candidates = expression_candidates(node, &param_specs, &promoted, &visible_params);
+        if !candidates.is_empty() {
+            if !heights.is_empty() {
+                heights.push(separator_height);
+            }
+            heights.push(row_height);
+            if !keyframe_candidates(node, &candidates).is_empty() {
+                heights.push(row_height);
+            }
+        }
+        let input_candidates = param_input_candidates(graph, node, &param_specs, &visible_params);
+        let removable_inputs = removable_param_inputs(graph, node);
+        if !input_candidates.is_empty() || !removable_inputs.is_empty() {
+            if !heights.is_empty() {
+                heights.push(separator_height);
+            }
+            if !input_candidates.is_empty() {
+                heights.push(row_height);
+            }
+            if !removable_inputs.is_empty() {
+                heights.push(row_height);
+            }
+        }
+        if node.variadic_inputs {
+            let inputs = connected_input_sources(graph, node).len();
+            if inputs > 1 {
+                if !heights.is_empty() {
+                    heights.push(separator_height);
+                }
+                heights.extend(std::iter::repeat_n(row_height, inputs));
+            }
+        }
+
         let mut param_keys: Vec<String> = param_values
             .keys()
             .filter(|key| !spec_keys.contains(*key))
@@ -340,6 +690,8 @@ impl NodeGraphState {
             Some(
                 BuiltinNodeKind::ObjOutput
                     | BuiltinNodeKind::GltfOutput
+                    | BuiltinNodeKind::PlyOutput
+                    | BuiltinNodeKind::StlOutput
                     | BuiltinNodeKind::WriteSplats
             )
         ) {
@@ -359,3 +711,230 @@ impl NodeGraphState {
     }
 }
 
+/// Rows listing the connected inputs of a variadic node with buttons moving them
+/// up and down.
+fn show_input_order(ui: &mut Ui, graph: &mut Graph, node_id: NodeId, sources: &[String]) -> bool {
+    let mut moved = None;
+    for (idx, source) in sources.iter().enumerate() {
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(idx > 0, egui::Button::new("^"))
+                .on_hover_text("Move input up")
+                .clicked()
+            {
+                moved = Some((idx, idx - 1));
+            }
+            if ui
+                .add_enabled(idx + 1 < sources.len(), egui::Button::new("v"))
+                .on_hover_text("Move input down")
+                .clicked()
+            {
+                moved = Some((idx, idx + 1));
+            }
+            ui.label(format!("in{}: {}", idx, source));
+        });
+    }
+    let Some((from, to)) = moved else {
+        return false;
+    };
+    graph.move_input(node_id, from, to).unwrap_or(false)
+}
+
+/// Names of the nodes feeding the connected inputs, in input order.
+fn connected_input_sources(graph: &Graph, node: &Node) -> Vec<String> {
+    graph
+        .input_sources(node.id)
+        .into_iter()
+        .flatten()
+        .filter_map(|(source, _)| graph.node(source).map(|source| source.name.clone()))
+        .collect()
+}
+
+/// Combo boxes adding a value input to a parameter and removing added ones.
+fn show_param_input_controls(
+    ui: &mut Ui,
+    graph: &mut Graph,
+    node_id: NodeId,
+    candidates: &[(String, PinType)],
+    removable: &[String],
+) -> bool {
+    let mut added = None;
+    if !candidates.is_empty() {
+        egui::ComboBox::from_id_salt((node_id, "add_param_input"))
+            .selected_text("Add input...")
+            .width(ui.available_width())
+            .show_ui(ui, |ui| {
+                for (key, pin_type) in candidates {
+                    if ui.selectable_label(false, key.as_str()).clicked() {
+                        added = Some((key.clone(), *pin_type));
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Expose a parameter as an input pin so a value node can drive it");
+    }
+    let mut removed = None;
+    if !removable.is_empty() {
+        egui::ComboBox::from_id_salt((node_id, "remove_param_input"))
+            .selected_text("Remove input...")
+            .width(ui.available_width())
+            .show_ui(ui, |ui| {
+                for key in removable {
+                    if ui.selectable_label(false, key.as_str()).clicked() {
+                        removed = Some(key.clone());
+                    }
+                }
+            });
+    }
+    if let Some((key, pin_type)) = added {
+        if let Err(err) = graph.add_param_input(node_id, &key, pin_type) {
+            tracing::warn!("Add input failed: {:?}", err);
+            return false;
+        }
+        return true;
+    }
+    removed.is_some_and(|key| graph.remove_param_input(node_id, &key))
+}
+
+/// Parameters set by a linked value input, with the name of the node feeding them.
+fn linked_param_sources(graph: &Graph, node: &Node) -> Vec<(String, String)> {
+    graph
+        .param_input_sources(node.id)
+        .into_iter()
+        .filter_map(|(key, source)| {
+            let (source, _) = source?;
+            Some((key, graph.node(source)?.name.clone()))
+        })
+        .collect()
+}
+
+/// Visible parameters holding a value that can be linked and have no input yet.
+/// Enum parameters are left out.
+fn param_input_candidates(
+    graph: &Graph,
+    node: &Node,
+    param_specs: &[ParamSpec],
+    visible_params: &NodeParams,
+) -> Vec<(String, PinType)> {
+    param_specs
+        .iter()
+        .filter(|spec| spec.is_visible(visible_params) && spec.options.is_empty())
+        .filter(|spec| graph.param_input(node.id, spec.key).is_none())
+        .filter_map(|spec| {
+            let pin_type = PinType::for_value(node.params.values.get(spec.key)?)?;
+            Some((spec.key.to_string(), pin_type))
+        })
+        .collect()
+}
+
+/// Value inputs added from the inspector; the ones a node is defined with stay.
+fn removable_param_inputs(graph: &Graph, node: &Node) -> Vec<String> {
+    let defined: Vec<String> = node
+        .builtin_kind()
+        .map(|kind| {
+            node_definition(kind)
+                .inputs
+                .into_iter()
+                .map(|pin| pin.name)
+                .collect()
+        })
+        .unwrap_or_default();
+    graph
+        .param_input_sources(node.id)
+        .into_iter()
+        .map(|(key, _)| key)
+        .filter(|key| !defined.contains(key))
+        .collect()
+}
+
+type PromotedSpec = (PromotedParam, ParamSpec, Option<BuiltinNodeKind>);
+
+/// Promoted parameters of a subnet node with the kind of the inner node they drive.
+fn promoted_params(node: &Node) -> Vec<PromotedSpec> {
+    let Some(subnet) = node.subnet.as_deref() else {
+        return Vec::new();
+    };
+    promoted_param_specs(node)
+        .into_iter()
+        .map(|(promoted, spec)| {
+            let kind = subnet
+                .graph
+                .node(promoted.node)
+                .and_then(|inner| inner.builtin_kind());
+            (promoted, spec, kind)
+        })
+        .collect()
+}
+
+/// Inner parameters of a subnet that are not promoted yet.
+fn promotable_params(subnet: &Subnet) -> Vec<(NodeId, String, String)> {
+    let mut params = Vec::new();
+    for inner in subnet.graph.nodes() {
+        if matches!(
+            inner.builtin_kind(),
+            Some(BuiltinNodeKind::SubnetInput | BuiltinNodeKind::SubnetOutput)
+        ) {
+            continue;
+        }
+        for key in inner.params.values.keys() {
+            let promoted = subnet
+                .promoted
+                .iter()
+                .any(|promoted| promoted.node == inner.id && &promoted.param == key);
+            if !promoted {
+                params.push((inner.id, inner.name.clone(), key.clone()));
+            }
+        }
+    }
+    params
+}
+
+/// Visible parameters that can take an expression but have none yet.
+fn expression_candidates(
+    node: &Node,
+    param_specs: &[ParamSpec],
+    promoted: &[PromotedSpec],
+    visible_params: &NodeParams,
+) -> Vec<(String, ParamValue)> {
+    let specs = param_specs
+        .iter()
+        .chain(promoted.iter().map(|(_, spec, _)| spec));
+    let keys = param_specs
+        .iter()
+        .map(|spec| spec.key.to_string())
+        .chain(promoted.iter().map(|(promoted, _, _)| promoted.key.clone()));
+    specs
+        .zip(keys)
+        .filter(|(spec, key)| {
+            spec.is_visible(visible_params)
+                && !matches!(spec.widget, ParamWidget::Code | ParamWidget::Gradient)
+                && !node.expressions.contains_key(key)
+        })
+        .filter_map(|(_, key)| {
+            let value = node.params.values.get(&key)?.clone();
+            Some((key, value))
+        })
+        .collect()
+}
+
+/// Expression candidates that can be keyframed and have no keys yet.
+fn keyframe_candidates(node: &Node, candidates: &[(String, ParamValue)]) -> Vec<(String, ParamValue)> {
+    candidates
+        .iter()
+        .filter(|(key, value)| is_animatable(value) && !node.channels.contains_key(key))
+        .cloned()
+        .collect()
+}
+
+/// Expression source reproducing a literal parameter value.
+fn literal_expression(value: &ParamValue) -> String {
+    match value {
+        ParamValue::Float(v) => v.to_string(),
+        ParamValue::Int(v) => v.to_string(),
+        ParamValue::Bool(v) => if *v { "1" } else { "0" }.to_string(),
+        ParamValue::Vec2(v) => format!("vec2({}, {})", v[0], v[1]),
+        ParamValue::Vec3(v) => format!("vec3({}, {}, {})", v[0], v[1], v[2]),
+        ParamValue::String(v) if v.contains('"') => format!("'{}'", v),
+        ParamValue::String(v) => format!("\"{}\"", v),
+    }
+}
@@ -166,7 +166,7 @@ impl NodeGraphState {
         let rect = self.node_ui_rects.get(&pin.node)?;
         let core_id = *self.snarl_to_core.get(&pin.node)?;
         let node = graph.node(core_id)?;
-        let count = node.inputs.len().max(1);
+        let count = node.input_pins().count().max(1);
         let t = (pin.input as f32 + 1.0) / (count as f32 + 1.0);
         Some(Pos2::new(rect.left(), rect.top() + rect.height() * t))
     }
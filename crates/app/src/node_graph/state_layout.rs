@@ -60,11 +60,17 @@ impl NodeGraphState {
                 continue;
             }
 
-            let pos = self.next_pos;
+            let pos = match node.position {
+                Some(pos) => Pos2::new(pos[0], pos[1]),
+                None => {
+                    let pos = self.next_pos;
+                    self.advance_pos();
+                    pos
+                }
+            };
             let snarl_id = self.snarl.insert_node(pos, SnarlNode { core_id: node.id });
             self.core_to_snarl.insert(node.id, snarl_id);
             self.snarl_to_core.insert(snarl_id, node.id);
-            self.advance_pos();
             self.needs_wire_sync = true;
         }
 
@@ -126,7 +132,7 @@ impl NodeGraphState {
         let from_node = graph.node(from_pin.node)?;
         let to_node = graph.node(to_pin.node)?;
         let from_index = from_node.outputs.iter().position(|id| *id == from)?;
-        let to_index = to_node.inputs.iter().position(|id| *id == to)?;
+        let to_index = to_node.input_pins().position(|id| *id == to)?;
 
         let snarl_from = *self.core_to_snarl.get(&from_pin.node)?;
         let snarl_to = *self.core_to_snarl.get(&to_pin.node)?;
@@ -1,11 +1,14 @@
 use egui::{Frame, Pos2, Ui};
 
 use egui::{Color32, TextStyle};
-use lobedo_core::{BuiltinNodeKind, Graph, NodeId, ProjectSettings};
+use lobedo_core::{
+    collapse_to_subnet, expand_subnet, BuiltinNodeKind, Graph, NodeId, ProjectSettings,
+    SubnetAsset,
+};
 
-use super::menu::{builtin_menu_items, menu_layout, render_menu_layout};
+use super::menu::{menu_layout, node_menu_items, render_menu_layout, MenuPick};
 use super::state::{NodeGraphState, NodeInfoRequest};
-use super::utils::{add_builtin_node, add_builtin_node_checked};
+use super::utils::{add_node_checked, add_node_of_kind, submenu_menu_button};
 
 impl NodeGraphState {
     pub(super) fn show_node_menu(&mut self, ui: &mut Ui, graph: &mut Graph) -> bool {
@@ -52,6 +55,57 @@ impl NodeGraphState {
                     }
                     close_menu = true;
                 }
+                let locked = node_id
+                    .and_then(|node_id| graph.node(node_id))
+                    .is_some_and(|node| node.locked.is_some());
+                if locked {
+                    if ui.button("Unlock output").clicked() {
+                        if let Some(node_id) = node_id {
+                            if graph.unlock_node(node_id).is_ok() {
+                                changed = true;
+                            }
+                        }
+                        close_menu = true;
+                    }
+                } else if ui.button("Lock output").clicked() {
+                    self.lock_request = node_id;
+                    close_menu = true;
+                }
+                ui.separator();
+                if ui.button("Collapse into subnet").clicked() {
+                    if let Some(node_id) = node_id {
+                        if self.collapse_into_subnet(ui, graph, node_id) {
+                            changed = true;
+                        }
+                    }
+                    close_menu = true;
+                }
+                let is_subnet = node_id
+                    .and_then(|node_id| graph.node(node_id))
+                    .is_some_and(|node| node.subnet.is_some());
+                if is_subnet {
+                    if ui.button("Expand subnet").clicked() {
+                        if let Some(node_id) = node_id {
+                            match expand_subnet(graph, node_id) {
+                                Ok(added) => {
+                                    self.selected_node = added.first().copied();
+                                    self.ensure_nodes(graph);
+                                    changed = true;
+                                }
+                                Err(err) => tracing::warn!("Expand subnet failed: {}", err),
+                            }
+                        }
+                        close_menu = true;
+                    }
+                    let can_export = !cfg!(target_arch = "wasm32");
+                    if ui
+                        .add_enabled(can_export, egui::Button::new("Export asset..."))
+                        .clicked()
+                    {
+                        self.asset_export_request = node_id;
+                        close_menu = true;
+                    }
+                }
             });
 
         if let Some(inner) = response {
@@ -77,6 +131,31 @@ impl NodeGraphState {
         changed
     }
 
+    /// Collapses the rectangle-selected nodes, or just `node_id` when it is not
+    /// part of the selection.
+    fn collapse_into_subnet(&mut self, ui: &Ui, graph: &mut Graph, node_id: NodeId) -> bool {
+        let snarl_id = ui.make_persistent_id("node_graph");
+        let mut nodes: Vec<NodeId> = egui_snarl::ui::get_selected_nodes(snarl_id, ui.ctx())
+            .into_iter()
+            .filter_map(|snarl_node| self.snarl_to_core.get(&snarl_node).copied())
+            .collect();
+        if !nodes.contains(&node_id) {
+            nodes = vec![node_id];
+        }
+        self.sync_graph_positions(graph);
+        match collapse_to_subnet(graph, &nodes) {
+            Ok(subnet_id) => {
+                self.selected_node = Some(subnet_id);
+                self.ensure_nodes(graph);
+                true
+            }
+            Err(err) => {
+                tracing::warn!("Collapse into subnet failed: {}", err);
+                false
+            }
+        }
+    }
+
     pub fn open_add_menu(&mut self, pos: Pos2) {
         self.add_menu_open = true;
         self.add_menu_screen_pos = pos;
@@ -91,39 +170,39 @@ impl NodeGraphState {
 
     pub fn add_demo_graph(&mut self, graph: &mut Graph) {
         let origin = self.next_pos;
-        let box_id = add_builtin_node(
+        let box_id = add_node_of_kind(
             graph,
             &mut self.snarl,
             &mut self.core_to_snarl,
             &mut self.snarl_to_core,
-            BuiltinNodeKind::Box,
+            BuiltinNodeKind::Box.id(),
             origin,
         );
-        let transform_id = add_builtin_node(
+        let transform_id = add_node_of_kind(
             graph,
             &mut self.snarl,
             &mut self.core_to_snarl,
             &mut self.snarl_to_core,
-            BuiltinNodeKind::Transform,
+            BuiltinNodeKind::Transform.id(),
             Pos2::new(origin.x + 240.0, origin.y),
         );
-        let output_id = add_builtin_node_checked(
+        let output_id = add_node_checked(
             graph,
             &mut self.snarl,
             &mut self.core_to_snarl,
             &mut self.snarl_to_core,
-            BuiltinNodeKind::Output,
+            BuiltinNodeKind::Output.id(),
             Pos2::new(origin.x + 480.0, origin.y),
         );
 
-        let box_out = graph
-            .node(box_id)
+        let box_out = box_id
+            .and_then(|id| graph.node(id))
             .and_then(|node| node.outputs.first().copied());
-        let transform_in = graph
-            .node(transform_id)
+        let transform_in = transform_id
+            .and_then(|id| graph.node(id))
             .and_then(|node| node.inputs.first().copied());
-        let transform_out = graph
-            .node(transform_id)
+        let transform_out = transform_id
+            .and_then(|id| graph.node(id))
             .and_then(|node| node.outputs.first().copied());
         let output_in = output_id
             .and_then(|id| graph.node(id))
@@ -144,6 +223,7 @@ impl NodeGraphState {
         ui: &mut Ui,
         graph: &mut Graph,
         settings: &mut ProjectSettings,
+        assets: &[SubnetAsset],
     ) -> bool {
         let mut close_menu = ui.input(|i| i.key_pressed(egui::Key::Escape));
         let mut menu_rect = None;
@@ -181,24 +261,27 @@ impl NodeGraphState {
                     ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter));
                 }
 
-        let items = builtin_menu_items();
+        let items = node_menu_items();
         let filter = self.add_menu_filter.to_lowercase();
+        let mut picked = None;
         if filter.is_empty() {
-            let layout = menu_layout(&items);
-            if let Some(kind) = render_menu_layout(ui, layout) {
-                if let Some(core_id) = self.try_add_node(graph, kind, self.add_menu_graph_pos) {
-                    changed = true;
-                    if let Some(pending) = self.pending_wire.take() {
-                        if self.connect_pending_wire(graph, core_id, pending) {
-                            changed = true;
+            if !assets.is_empty() {
+                submenu_menu_button(ui, "Assets", |ui| {
+                    for (idx, asset) in assets.iter().enumerate() {
+                        if ui.button(asset.name.as_str()).clicked() {
+                            picked = Some(MenuPick::Asset(idx));
+                            ui.close();
                         }
                     }
-                }
-                close_menu = true;
+                });
+            }
+            if picked.is_none() {
+                let layout = menu_layout(&items);
+                picked = render_menu_layout(ui, layout).map(MenuPick::Node);
             }
         } else {
                     let mut matched = false;
-                    let mut first_match: Option<BuiltinNodeKind> = None;
+                    let mut first_match: Option<MenuPick> = None;
                     for item in items {
                         let submenu = item.submenu.unwrap_or("");
                         if !item.name.to_lowercase().contains(&filter)
@@ -209,7 +292,7 @@ impl NodeGraphState {
                         }
                         matched = true;
                         if first_match.is_none() {
-                            first_match = Some(item.kind);
+                            first_match = Some(MenuPick::Node(item.kind_id.clone()));
                         }
                         let label = if submenu.is_empty() {
                             item.name.to_string()
@@ -217,37 +300,48 @@ impl NodeGraphState {
                             format!("{} / {}", submenu, item.name)
                         };
                         if ui.button(label).clicked() {
-                            if let Some(core_id) =
-                                self.try_add_node(graph, item.kind, self.add_menu_graph_pos)
-                            {
-                                changed = true;
-                                if let Some(pending) = self.pending_wire.take() {
-                                    if self.connect_pending_wire(graph, core_id, pending) {
-                                        changed = true;
-                                    }
-                                }
-                            }
-                            close_menu = true;
+                            picked = Some(MenuPick::Node(item.kind_id.clone()));
+                        }
+                    }
+                    for (idx, asset) in assets.iter().enumerate() {
+                        if !asset.name.to_lowercase().contains(&filter)
+                            && !"assets".contains(&filter)
+                        {
+                            continue;
+                        }
+                        matched = true;
+                        if first_match.is_none() {
+                            first_match = Some(MenuPick::Asset(idx));
+                        }
+                        if ui.button(format!("Assets / {}", asset.name)).clicked() {
+                            picked = Some(MenuPick::Asset(idx));
                         }
                     }
                     if !matched {
                         ui.label("No matches.");
-                    } else if activate_first {
-                        if let Some(kind) = first_match {
-                            if let Some(core_id) =
-                                self.try_add_node(graph, kind, self.add_menu_graph_pos)
-                            {
-                                changed = true;
-                                if let Some(pending) = self.pending_wire.take() {
-                                    if self.connect_pending_wire(graph, core_id, pending) {
-                                        changed = true;
-                                    }
-                                }
-                            }
-                            close_menu = true;
-                        }
+                    } else if activate_first && picked.is_none() {
+                        picked = first_match;
+                    }
+                }
+        if let Some(pick) = picked {
+            let core_id = match pick {
+                MenuPick::Node(kind_id) => {
+                    self.try_add_node(graph, &kind_id, self.add_menu_graph_pos)
+                }
+                MenuPick::Asset(idx) => assets
+                    .get(idx)
+                    .map(|asset| self.add_asset_node(graph, asset, self.add_menu_graph_pos)),
+            };
+            if let Some(core_id) = core_id {
+                changed = true;
+                if let Some(pending) = self.pending_wire.take() {
+                    if self.connect_pending_wire(graph, core_id, pending) {
+                        changed = true;
                     }
                 }
+            }
+            close_menu = true;
+        }
             });
 
         if let Some(inner) = response {
@@ -273,18 +367,30 @@ impl NodeGraphState {
         changed
     }
 
+    fn add_asset_node(&mut self, graph: &mut Graph, asset: &SubnetAsset, pos: Pos2) -> NodeId {
+        let was_empty = graph.nodes().next().is_none();
+        let core_id = asset.instantiate(graph);
+        let _ = graph.set_node_position(core_id, [pos.x, pos.y]);
+        if was_empty {
+            let _ = graph.set_display_node(Some(core_id));
+        }
+        self.ensure_nodes(graph);
+        self.needs_wire_sync = true;
+        core_id
+    }
+
     fn try_add_node(
         &mut self,
         graph: &mut Graph,
-        kind: BuiltinNodeKind,
+        kind_id: &str,
         pos: Pos2,
     ) -> Option<NodeId> {
-        let core_id = add_builtin_node_checked(
+        let core_id = add_node_checked(
             graph,
             &mut self.snarl,
             &mut self.core_to_snarl,
             &mut self.snarl_to_core,
-            kind,
+            kind_id,
             pos,
         )?;
         self.needs_wire_sync = true;
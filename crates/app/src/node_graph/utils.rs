@@ -3,9 +3,7 @@ use std::collections::HashMap;
 use egui::{Color32, Pos2, RichText, Ui};
 use egui_snarl::Snarl;
 
-use lobedo_core::{
-    default_params, node_definition, BuiltinNodeKind, Graph, NodeId, PinId, PinType,
-};
+use lobedo_core::{add_registered_node, BuiltinNodeKind, Graph, NodeId, PinId, PinType};
 
 use super::state::SnarlNode;
 
@@ -22,39 +20,35 @@ pub(super) fn pin_color(pin_type: PinType) -> Color32 {
     }
 }
 
-pub(super) fn add_builtin_node(
+/// Adds a built-in or plugin node with its default parameters.
+pub(super) fn add_node_of_kind(
     graph: &mut Graph,
     snarl: &mut Snarl<SnarlNode>,
     core_to_snarl: &mut HashMap<NodeId, egui_snarl::NodeId>,
     snarl_to_core: &mut HashMap<egui_snarl::NodeId, NodeId>,
-    kind: BuiltinNodeKind,
+    kind_id: &str,
     pos: Pos2,
-) -> NodeId {
+) -> Option<NodeId> {
     let was_empty = graph.nodes().next().is_none();
-    let core_id = graph.add_node(node_definition(kind));
-    let _ = graph.set_node_kind_id(core_id, kind.id());
-    let params = default_params(kind);
-    for (key, value) in params.values {
-        let _ = graph.set_param(core_id, key, value);
-    }
+    let core_id = add_registered_node(graph, kind_id)?;
     if was_empty {
         let _ = graph.set_display_node(Some(core_id));
     }
     let snarl_id = snarl.insert_node(pos, SnarlNode { core_id });
     core_to_snarl.insert(core_id, snarl_id);
     snarl_to_core.insert(snarl_id, core_id);
-    core_id
+    Some(core_id)
 }
 
-pub(super) fn add_builtin_node_checked(
+pub(super) fn add_node_checked(
     graph: &mut Graph,
     snarl: &mut Snarl<SnarlNode>,
     core_to_snarl: &mut HashMap<NodeId, egui_snarl::NodeId>,
     snarl_to_core: &mut HashMap<egui_snarl::NodeId, NodeId>,
-    kind: BuiltinNodeKind,
+    kind_id: &str,
     pos: Pos2,
 ) -> Option<NodeId> {
-    if kind == BuiltinNodeKind::Output
+    if kind_id == BuiltinNodeKind::Output.id()
         && graph
             .nodes()
             .any(|node| node.builtin_kind() == Some(BuiltinNodeKind::Output))
@@ -62,14 +56,14 @@ pub(super) fn add_builtin_node_checked(
         tracing::warn!("Only one Output node is supported right now.");
         return None;
     }
-    Some(add_builtin_node(
+    add_node_of_kind(
         graph,
         snarl,
         core_to_snarl,
         snarl_to_core,
-        kind,
+        kind_id,
         pos,
-    ))
+    )
 }
 
 pub(super) fn core_input_pin(
@@ -77,7 +71,7 @@ pub(super) fn core_input_pin(
     node_id: NodeId,
     input_index: usize,
 ) -> Option<PinId> {
-    graph.node(node_id)?.inputs.get(input_index).copied()
+    graph.node(node_id)?.input_pins().nth(input_index).copied()
 }
 
 pub(super) fn core_output_pin(
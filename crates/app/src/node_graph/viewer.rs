@@ -6,7 +6,7 @@ use egui::{vec2, Align2, Color32, FontId, Pos2, Rect, TextStyle, Ui};
 use egui_snarl::ui::{AnyPins, PinInfo, SnarlPin, SnarlViewer};
 use egui_snarl::{InPinId, OutPinId, Snarl};
 
-use lobedo_core::{BuiltinNodeKind, Graph, NodeId, PinId};
+use lobedo_core::{BuiltinNodeKind, Diagnostic, Graph, NodeId, PinId, Severity};
 
 use super::state::{
     GraphTransformState, HeaderButtonRects, NodeProgressView, PendingWire, SnarlNode,
@@ -33,6 +33,7 @@ pub(super) struct NodeGraphViewer<'a> {
     pub(super) wrangle_help_request: &'a mut Option<Pos2>,
     pub(super) error_nodes: &'a HashSet<NodeId>,
     pub(super) error_messages: &'a HashMap<NodeId, String>,
+    pub(super) lint: &'a HashMap<NodeId, Vec<Diagnostic>>,
     pub(super) dim_nodes: &'a HashSet<NodeId>,
     pub(super) skip_header_click: bool,
     pub(super) changed: bool,
@@ -375,7 +376,7 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
     fn inputs(&mut self, node: &SnarlNode) -> usize {
         self.graph
             .node(node.core_id)
-            .map(|node| node.inputs.len())
+            .map(|node| node.input_pins().count())
             .unwrap_or(0)
     }
 
@@ -418,9 +419,26 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
     ) -> impl egui_snarl::ui::SnarlPin + 'static {
         if let Some(core_pin) = self.core_pin_for_output(snarl, pin.id) {
             if let Some(pin_data) = self.graph.pin(core_pin) {
-                ui.label(&pin_data.name);
+                let pin_type = pin_data.pin_type;
+                let node = self.graph.node(pin_data.node);
+                let multi_output = node.is_some_and(|node| node.outputs.len() > 1);
+                if multi_output {
+                    // Clicking an output label displays that output.
+                    let displayed = node.is_some_and(|node| {
+                        node.display
+                            && self.graph.output_index(core_pin) == Some(node.display_output)
+                    });
+                    let name = pin_data.name.clone();
+                    if ui.selectable_label(displayed, name).clicked()
+                        && self.graph.set_display_output(core_pin).is_ok()
+                    {
+                        self.changed = true;
+                    }
+                } else {
+                    ui.label(&pin_data.name);
+                }
                 return RecordedPin {
-                    pin: PinInfo::circle().with_fill(pin_color(pin_data.pin_type)),
+                    pin: PinInfo::circle().with_fill(pin_color(pin_type)),
                     record: PinRecord::Out(pin.id, Rc::clone(&self.output_pin_positions)),
                     graph_to_screen: self.graph_transform.to_global,
                 };
@@ -496,6 +514,14 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
                 .rect_stroke(ui_rect, 6.0, stroke, egui::StrokeKind::Inside);
         }
 
+        if self
+            .graph
+            .node(core_id)
+            .is_some_and(|node| node.locked.is_some())
+        {
+            show_lock_badge(ui, ui_rect, node);
+        }
+
         if self.error_nodes.contains(&core_id) {
             let stroke = egui::Stroke::new(3.0, egui::Color32::from_rgb(220, 60, 60));
             ui.painter()
@@ -522,6 +548,10 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
             }
         }
 
+        if let Some(diagnostics) = self.lint.get(&core_id) {
+            show_lint_badge(ui, ui_rect, node, diagnostics);
+        }
+
         if let Some(message) = self.error_messages.get(&core_id) {
             let font_id = ui
                 .style()
@@ -746,3 +776,78 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
         self.changed = true;
     }
 }
+
+/// Outline and badge marking a node whose output is locked.
+fn show_lock_badge(ui: &mut Ui, ui_rect: Rect, node: egui_snarl::NodeId) {
+    let color = Color32::from_rgb(60, 170, 160);
+    ui.painter().rect_stroke(
+        ui_rect,
+        6.0,
+        egui::Stroke::new(2.0, color),
+        egui::StrokeKind::Outside,
+    );
+    let badge_center = egui::pos2(ui_rect.left() + 8.0, ui_rect.bottom() - 8.0);
+    let badge_rect = Rect::from_center_size(badge_center, vec2(12.0, 12.0));
+    ui.painter().circle_filled(badge_center, 5.0, color);
+    ui.painter().text(
+        badge_center,
+        Align2::CENTER_CENTER,
+        "L",
+        FontId::proportional(9.0),
+        Color32::WHITE,
+    );
+    ui.interact(
+        badge_rect,
+        ui.make_persistent_id(("node-lock", node)),
+        egui::Sense::hover(),
+    )
+    .on_hover_text("Locked: the stored output is used and upstream nodes are not cooked");
+}
+
+/// Badge at the top-left corner listing what `Graph::validate` found on the node.
+fn show_lint_badge(
+    ui: &mut Ui,
+    ui_rect: Rect,
+    node: egui_snarl::NodeId,
+    diagnostics: &[Diagnostic],
+) {
+    let color = if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+        Color32::from_rgb(220, 60, 60)
+    } else {
+        Color32::from_rgb(230, 160, 40)
+    };
+    let badge_center = egui::pos2(ui_rect.left() + 8.0, ui_rect.top() + 8.0);
+    let badge_rect = Rect::from_center_size(badge_center, vec2(12.0, 12.0));
+    ui.painter().circle_filled(badge_center, 5.0, color);
+    ui.painter().text(
+        badge_center,
+        Align2::CENTER_CENTER,
+        "?",
+        FontId::proportional(10.0),
+        Color32::WHITE,
+    );
+    let lines: Vec<String> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let severity = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            match &diagnostic.suggestion {
+                Some(suggestion) => {
+                    format!("{}: {}\n  {}", severity, diagnostic.message, suggestion)
+                }
+                None => format!("{}: {}", severity, diagnostic.message),
+            }
+        })
+        .collect();
+    ui.interact(
+        badge_rect,
+        ui.make_persistent_id(("node-lint", node)),
+        egui::Sense::hover(),
+    )
+    .on_hover_text(lines.join("\n"));
+}
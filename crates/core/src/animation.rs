@@ -0,0 +1,266 @@
+use serde::{Deserialize, Serialize};
+
+use crate::graph::ParamValue;
+
+/// Keys closer than this share a frame.
+const FRAME_EPSILON: f32 = 1.0e-4;
+
+/// How a key eases into the next one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    /// Smooth curve with automatic tangents, flat at the first and last key.
+    Bezier,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    pub frame: f32,
+    pub value: ParamValue,
+    /// Interpolation of the segment that starts at this key.
+    #[serde(default)]
+    pub interpolation: Interpolation,
+}
+
+/// Keyframes of one animated parameter, sorted by frame.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Channel {
+    pub keys: Vec<Keyframe>,
+}
+
+/// Only float and vector parameters can be keyframed.
+pub fn is_animatable(value: &ParamValue) -> bool {
+    matches!(
+        value,
+        ParamValue::Float(_) | ParamValue::Vec2(_) | ParamValue::Vec3(_)
+    )
+}
+
+impl Channel {
+    /// Inserts `key`, replacing any key on the same frame. Returns whether the
+    /// channel changed.
+    pub fn set_key(&mut self, key: Keyframe) -> bool {
+        match self
+            .keys
+            .iter()
+            .position(|existing| (existing.frame - key.frame).abs() < FRAME_EPSILON)
+        {
+            Some(idx) if self.keys[idx] == key => false,
+            Some(idx) => {
+                self.keys[idx] = key;
+                true
+            }
+            None => {
+                let idx = self
+                    .keys
+                    .partition_point(|existing| existing.frame < key.frame);
+                self.keys.insert(idx, key);
+                true
+            }
+        }
+    }
+
+    pub fn remove_key(&mut self, frame: f32) -> bool {
+        let before = self.keys.len();
+        self.keys
+            .retain(|key| (key.frame - frame).abs() >= FRAME_EPSILON);
+        self.keys.len() != before
+    }
+
+    pub fn key_at(&self, frame: f32) -> Option<&Keyframe> {
+        self.keys
+            .iter()
+            .find(|key| (key.frame - frame).abs() < FRAME_EPSILON)
+    }
+
+    /// Whether the value can change over time.
+    pub fn is_animated(&self) -> bool {
+        self.keys
+            .windows(2)
+            .any(|pair| pair[0].value != pair[1].value)
+    }
+
+    /// Value at `frame`, held constant before the first and after the last key.
+    pub fn evaluate(&self, frame: f32) -> Option<ParamValue> {
+        let first = self.keys.first()?;
+        let last = self.keys.last()?;
+        if frame <= first.frame {
+            return Some(first.value.clone());
+        }
+        if frame >= last.frame {
+            return Some(last.value.clone());
+        }
+        let idx = self.keys.partition_point(|key| key.frame <= frame) - 1;
+        let (from, to) = (&self.keys[idx], &self.keys[idx + 1]);
+        let (Some(a), Some(b)) = (components(&from.value), components(&to.value)) else {
+            return Some(from.value.clone());
+        };
+        if a.len() != b.len() {
+            return Some(from.value.clone());
+        }
+        let span = to.frame - from.frame;
+        let t = (frame - from.frame) / span;
+        let values: Vec<f32> = match from.interpolation {
+            Interpolation::Linear => a.iter().zip(&b).map(|(a, b)| a + (b - a) * t).collect(),
+            Interpolation::Bezier => {
+                let start_slope = self.slope(idx);
+                let end_slope = self.slope(idx + 1);
+                let (t2, t3) = (t * t, t * t * t);
+                let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+                let h10 = t3 - 2.0 * t2 + t;
+                let h01 = -2.0 * t3 + 3.0 * t2;
+                let h11 = t3 - t2;
+                (0..a.len())
+                    .map(|c| {
+                        let m0 = start_slope.get(c).copied().unwrap_or(0.0) * span;
+                        let m1 = end_slope.get(c).copied().unwrap_or(0.0) * span;
+                        h00 * a[c] + h10 * m0 + h01 * b[c] + h11 * m1
+                    })
+                    .collect()
+            }
+        };
+        Some(with_components(&from.value, &values))
+    }
+
+    /// Per-frame slope at key `idx`, from its neighbours; flat at the ends.
+    fn slope(&self, idx: usize) -> Vec<f32> {
+        if idx == 0 || idx + 1 >= self.keys.len() {
+            return Vec::new();
+        }
+        let (prev, next) = (&self.keys[idx - 1], &self.keys[idx + 1]);
+        let (Some(a), Some(b)) = (components(&prev.value), components(&next.value)) else {
+            return Vec::new();
+        };
+        let span = next.frame - prev.frame;
+        a.iter().zip(&b).map(|(a, b)| (b - a) / span).collect()
+    }
+}
+
+fn components(value: &ParamValue) -> Option<Vec<f32>> {
+    match value {
+        ParamValue::Float(v) => Some(vec![*v]),
+        ParamValue::Vec2(v) => Some(v.to_vec()),
+        ParamValue::Vec3(v) => Some(v.to_vec()),
+        _ => None,
+    }
+}
+
+fn with_components(template: &ParamValue, values: &[f32]) -> ParamValue {
+    match template {
+        ParamValue::Float(_) => ParamValue::Float(values[0]),
+        ParamValue::Vec2(_) => ParamValue::Vec2([values[0], values[1]]),
+        ParamValue::Vec3(_) => ParamValue::Vec3([values[0], values[1], values[2]]),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Channel, Interpolation, Keyframe};
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::{Graph, NodeId, ParamValue};
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    fn key(frame: f32, value: f32, interpolation: Interpolation) -> Keyframe {
+        Keyframe {
+            frame,
+            value: ParamValue::Float(value),
+            interpolation,
+        }
+    }
+
+    fn value_at(channel: &Channel, frame: f32) -> f32 {
+        match channel.evaluate(frame) {
+            Some(ParamValue::Float(value)) => value,
+            other => panic!("unexpected value {:?}", other),
+        }
+    }
+
+    #[test]
+    fn interpolates_between_keys_and_holds_outside() {
+        let mut channel = Channel::default();
+        assert!(channel.set_key(key(10.0, 2.0, Interpolation::Linear)));
+        assert!(channel.set_key(key(1.0, 0.0, Interpolation::Linear)));
+        assert!(!channel.set_key(key(1.0, 0.0, Interpolation::Linear)));
+        assert_eq!(channel.keys[0].frame, 1.0);
+        assert_eq!(value_at(&channel, -5.0), 0.0);
+        assert!((value_at(&channel, 5.5) - 1.0).abs() < 1.0e-5);
+        assert_eq!(value_at(&channel, 20.0), 2.0);
+
+        channel.keys[0].interpolation = Interpolation::Bezier;
+        // Flat tangents at both ends ease in and out around the same midpoint.
+        assert!((value_at(&channel, 5.5) - 1.0).abs() < 1.0e-5);
+        assert!(value_at(&channel, 2.0) < 2.0 / 9.0);
+
+        assert!(channel.remove_key(10.0));
+        assert!(!channel.is_animated());
+    }
+
+    fn chain(graph: &mut Graph, kinds: &[BuiltinNodeKind]) -> Vec<NodeId> {
+        let nodes: Vec<NodeId> = kinds
+            .iter()
+            .map(|kind| graph.add_node(node_definition(*kind)))
+            .collect();
+        for pair in nodes.windows(2) {
+            let from = graph.node(pair[0]).unwrap().outputs[0];
+            let to = graph.node(pair[1]).unwrap().inputs[0];
+            graph.add_link(from, to).unwrap();
+        }
+        nodes
+    }
+
+    #[test]
+    fn frame_changes_recook_only_time_dependent_nodes() {
+        let mut graph = Graph::default();
+        let nodes = chain(
+            &mut graph,
+            &[
+                BuiltinNodeKind::Box,
+                BuiltinNodeKind::Transform,
+                BuiltinNodeKind::Transform,
+                BuiltinNodeKind::Transform,
+            ],
+        );
+        let (source, still, keyed, driven) = (nodes[0], nodes[1], nodes[2], nodes[3]);
+        for (frame, x) in [(1.0, 0.0), (11.0, 10.0)] {
+            let keyframe = Keyframe {
+                frame,
+                value: ParamValue::Vec3([x, 0.0, 0.0]),
+                interpolation: Interpolation::Linear,
+            };
+            graph.set_keyframe(keyed, "translate", keyframe).unwrap();
+        }
+        graph
+            .set_param_expression(driven, "scale", Some("vec3(1 + $F * 0)".to_string()))
+            .unwrap();
+
+        let mut state = GeometryEvalState::new();
+        state.eval.set_frame(6.0);
+        evaluate_geometry_graph(&graph, driven, &mut state).unwrap();
+        let min_x = |state: &GeometryEvalState| {
+            state
+                .geometry_for_node(keyed)
+                .unwrap()
+                .merged_mesh()
+                .unwrap()
+                .bounds()
+                .unwrap()
+                .min[0]
+        };
+        assert!((min_x(&state) - 4.5).abs() < 1.0e-5);
+
+        state.eval.set_frame(7.0);
+        let result = evaluate_geometry_graph(&graph, driven, &mut state).unwrap();
+        assert!((min_x(&state) - 5.5).abs() < 1.0e-5);
+        assert!(result.report.computed.contains(&keyed));
+        assert!(result.report.computed.contains(&driven));
+        assert!(!result.report.computed.contains(&source));
+        assert!(!result.report.computed.contains(&still));
+
+        // Same frame again: nothing to do.
+        let result = evaluate_geometry_graph(&graph, driven, &mut state).unwrap();
+        assert!(result.report.computed.is_empty());
+    }
+}
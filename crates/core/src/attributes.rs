@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+
+use crate::buffer::{ByteCounter, SharedVec};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AttributeDomain {
     Point,
@@ -27,7 +31,7 @@ pub enum AttributeType {
     String,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StringTableAttribute {
     pub values: Vec<String>,
     pub indices: Vec<u32>,
@@ -52,13 +56,13 @@ impl StringTableAttribute {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AttributeStorage {
-    Float(Vec<f32>),
-    Int(Vec<i32>),
-    Vec2(Vec<[f32; 2]>),
-    Vec3(Vec<[f32; 3]>),
-    Vec4(Vec<[f32; 4]>),
+    Float(SharedVec<f32>),
+    Int(SharedVec<i32>),
+    Vec2(SharedVec<[f32; 2]>),
+    Vec3(SharedVec<[f32; 3]>),
+    Vec4(SharedVec<[f32; 4]>),
     StringTable(StringTableAttribute),
 }
 
@@ -89,6 +93,20 @@ impl AttributeStorage {
         }
     }
 
+    pub fn count_bytes(&self, counter: &mut ByteCounter) {
+        match self {
+            AttributeStorage::Float(values) => counter.add(values),
+            AttributeStorage::Int(values) => counter.add(values),
+            AttributeStorage::Vec2(values) => counter.add(values),
+            AttributeStorage::Vec3(values) => counter.add(values),
+            AttributeStorage::Vec4(values) => counter.add(values),
+            AttributeStorage::StringTable(values) => counter.add_bytes(
+                values.indices.len() * std::mem::size_of::<u32>()
+                    + values.values.iter().map(String::len).sum::<usize>(),
+            ),
+        }
+    }
+
     pub fn as_ref(&self) -> AttributeRef<'_> {
         match self {
             AttributeStorage::Float(values) => AttributeRef::Float(values.as_slice()),
@@ -161,7 +179,7 @@ impl<'a> AttributeRef<'a> {
     }
 }
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct MeshAttributes {
     point: HashMap<String, AttributeStorage>,
     vertex: HashMap<String, AttributeStorage>,
@@ -195,4 +213,13 @@ impl MeshAttributes {
     pub fn remove(&mut self, domain: AttributeDomain, name: &str) -> Option<AttributeStorage> {
         self.map_mut(domain).remove(name)
     }
+
+    pub fn count_bytes(&self, counter: &mut ByteCounter) {
+        for storage in AttributeDomain::ALL
+            .iter()
+            .flat_map(|domain| self.map(*domain).values())
+        {
+            storage.count_bytes(counter);
+        }
+    }
 }
@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A `Vec` behind an `Arc`. Cloning only bumps a reference count; the first
+/// mutable access to a shared buffer copies it, so nodes that pass geometry
+/// through (or only touch a few channels) keep sharing the rest with their inputs.
+pub struct SharedVec<T>(Arc<Vec<T>>);
+
+impl<T> SharedVec<T> {
+    pub fn new() -> Self {
+        Self(Arc::new(Vec::new()))
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.0) > 1
+    }
+
+    pub fn byte_len(&self) -> usize {
+        self.0.len() * std::mem::size_of::<T>()
+    }
+}
+
+/// Sums buffer sizes across geometries, counting each shared buffer once.
+#[derive(Debug, Default)]
+pub struct ByteCounter {
+    seen: HashSet<usize>,
+    total: usize,
+}
+
+impl ByteCounter {
+    pub fn add<T>(&mut self, values: &SharedVec<T>) {
+        if self.seen.insert(Arc::as_ptr(&values.0) as usize) {
+            self.total += values.byte_len();
+        }
+    }
+
+    /// Bytes of a buffer that is never shared.
+    pub fn add_bytes(&mut self, bytes: usize) {
+        self.total += bytes;
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+impl<T: Clone> SharedVec<T> {
+    pub fn into_vec(self) -> Vec<T> {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+impl<T> Default for SharedVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for SharedVec<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Deref for SharedVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T: Clone> DerefMut for SharedVec<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.as_slice().fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for SharedVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.0 == other.0
+    }
+}
+
+impl<T: PartialEq> PartialEq<Vec<T>> for SharedVec<T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl<T: PartialEq> PartialEq<[T]> for SharedVec<T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.0.as_slice() == other
+    }
+}
+
+impl<T> From<Vec<T>> for SharedVec<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self(Arc::new(values))
+    }
+}
+
+impl<T: Clone> From<&[T]> for SharedVec<T> {
+    fn from(values: &[T]) -> Self {
+        Self(Arc::new(values.to_vec()))
+    }
+}
+
+impl<T: Clone> From<SharedVec<T>> for Vec<T> {
+    fn from(values: SharedVec<T>) -> Self {
+        values.into_vec()
+    }
+}
+
+impl<T> FromIterator<T> for SharedVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(Arc::new(iter.into_iter().collect()))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SharedVec<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a mut SharedVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().iter_mut()
+    }
+}
+
+impl<T: Clone> IntoIterator for SharedVec<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
+    }
+}
+
+impl<T: Serialize> Serialize for SharedVec<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.as_slice().serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for SharedVec<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ByteCounter, SharedVec};
+
+    #[test]
+    fn clones_share_until_written() {
+        let original: SharedVec<f32> = vec![1.0, 2.0, 3.0].into();
+        let mut copy = original.clone();
+        assert!(copy.ptr_eq(&original));
+        assert!(original.is_shared());
+
+        copy[1] = 5.0;
+        assert!(!copy.ptr_eq(&original));
+        assert_eq!(original.as_slice(), &[1.0, 2.0, 3.0]);
+        assert_eq!(copy.as_slice(), &[1.0, 5.0, 3.0]);
+        assert!(!original.is_shared());
+    }
+
+    #[test]
+    fn unique_buffers_are_not_copied() {
+        let mut values: SharedVec<u32> = vec![1, 2].into();
+        let before = values.as_ptr();
+        values[0] = 7;
+        assert_eq!(values.as_ptr(), before);
+        assert_eq!(values.into_vec(), vec![7, 2]);
+    }
+
+    #[test]
+    fn counter_counts_shared_buffers_once() {
+        let values: SharedVec<u32> = vec![1, 2, 3].into();
+        let mut counter = ByteCounter::default();
+        counter.add(&values);
+        counter.add(&values.clone());
+        assert_eq!(counter.total(), 12);
+        let mut copy = values.clone();
+        copy[0] = 4;
+        counter.add(&copy);
+        assert_eq!(counter.total(), 24);
+    }
+}
@@ -0,0 +1,492 @@
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::assets::is_url;
+use crate::expression::{expression_dependencies, is_time_dependent};
+use crate::geometry::Geometry;
+use crate::graph::{Graph, NodeId};
+use crate::node_registry::node_type_for;
+use crate::param_spec::ParamPathKind;
+
+const ENTRY_MAGIC: &[u8; 4] = b"LBCC";
+const ENTRY_VERSION: u32 = 2;
+const ENTRY_EXTENSION: &str = "lbcc";
+
+/// Cooked node outputs stored on disk, keyed by [`node_cache_keys`]. Unlike the
+/// in-memory signatures, the keys only depend on graph content, so entries survive
+/// reopening a project.
+#[derive(Debug)]
+pub struct DiskCookCache {
+    dir: PathBuf,
+    min_cook_ms: f32,
+    hits: AtomicU64,
+    writes: AtomicU64,
+}
+
+impl DiskCookCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            min_cook_ms: 0.0,
+            hits: AtomicU64::new(0),
+            writes: AtomicU64::new(0),
+        }
+    }
+
+    /// Outputs that cooked faster than this are not written back.
+    pub fn with_min_cook_ms(mut self, min_cook_ms: f32) -> Self {
+        self.min_cook_ms = min_cook_ms.max(0.0);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn min_cook_ms(&self) -> f32 {
+        self.min_cook_ms
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn writes(&self) -> u64 {
+        self.writes.load(Ordering::Relaxed)
+    }
+
+    pub fn contains(&self, key: u64) -> bool {
+        self.entry_path(key).is_file()
+    }
+
+    /// Loads the geometry of every output of a cached node.
+    pub fn load(&self, key: u64) -> Option<Vec<Geometry>> {
+        let bytes = fs::read(self.entry_path(key)).ok()?;
+        let geometries = match decode_entry(&bytes) {
+            Ok(geometries) => geometries,
+            Err(err) => {
+                tracing::warn!("disk cook cache: dropping entry {:016x}: {}", key, err);
+                let _ = fs::remove_file(self.entry_path(key));
+                return None;
+            }
+        };
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(geometries)
+    }
+
+    pub fn store(&self, key: u64, geometries: &[Geometry]) -> Result<(), String> {
+        fs::create_dir_all(&self.dir).map_err(|err| err.to_string())?;
+        let payload = bincode::serialize(geometries).map_err(|err| err.to_string())?;
+        let path = self.entry_path(key);
+        // Write to a temporary file first so a concurrent reader never sees a
+        // partially written entry.
+        let tmp_path = path.with_extension(format!("{}.{}", ENTRY_EXTENSION, std::process::id()));
+        let mut file = fs::File::create(&tmp_path).map_err(|err| err.to_string())?;
+        file.write_all(ENTRY_MAGIC)
+            .and_then(|_| file.write_all(&ENTRY_VERSION.to_le_bytes()))
+            .and_then(|_| file.write_all(&payload))
+            .map_err(|err| err.to_string())?;
+        drop(file);
+        fs::rename(&tmp_path, &path).map_err(|err| err.to_string())?;
+        self.writes.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Removes every entry and returns the number of bytes freed.
+    pub fn clear(&self) -> Result<u64, String> {
+        let mut freed = 0;
+        for (path, len) in self.entries() {
+            fs::remove_file(&path).map_err(|err| err.to_string())?;
+            freed += len;
+        }
+        Ok(freed)
+    }
+
+    pub fn size_on_disk(&self) -> u64 {
+        self.entries().iter().map(|(_, len)| len).sum()
+    }
+
+    fn entries(&self) -> Vec<(PathBuf, u64)> {
+        let Ok(read_dir) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        read_dir
+            .flatten()
+            .filter(|entry| {
+                entry.path().extension().and_then(|ext| ext.to_str()) == Some(ENTRY_EXTENSION)
+            })
+            .map(|entry| {
+                let len = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+                (entry.path(), len)
+            })
+            .collect()
+    }
+
+    fn entry_path(&self, key: u64) -> PathBuf {
+        self.dir.join(format!("{:016x}.{}", key, ENTRY_EXTENSION))
+    }
+}
+
+fn decode_entry(bytes: &[u8]) -> Result<Vec<Geometry>, String> {
+    if bytes.len() < 8 || &bytes[0..4] != ENTRY_MAGIC {
+        return Err("not a cook cache entry".to_string());
+    }
+    let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    if version != ENTRY_VERSION {
+        return Err(format!("unsupported entry version {}", version));
+    }
+    bincode::deserialize(&bytes[8..]).map_err(|err| err.to_string())
+}
+
+/// Content keys for `output` and everything upstream of it. A key hashes the node
+/// kind, parameters and expressions (with the parameters they reference), bypass
+/// flag and the metadata of files it reads, plus the keys of its inputs. Keyframes
+/// count too, and time-dependent nodes also hash `frame`. Nodes that write files
+/// are left out so their side effects still run, and nodes reading in-memory
+/// assets (whose ids are per session) are left out together with everything
+/// downstream of them.
+pub fn node_cache_keys(graph: &Graph, output: NodeId, frame: f32) -> HashMap<NodeId, u64> {
+    let mut memo = HashMap::new();
+    content_key(graph, output, frame, &mut memo);
+    let mut keys = HashMap::new();
+    for (node_id, key) in memo {
+        let Some(key) = key else {
+            continue;
+        };
+        if writes_files(graph, node_id) {
+            continue;
+        }
+        // Which outputs are read changes what some nodes cook, but not what
+        // downstream nodes see, so it stays out of their keys.
+        let key = match graph.output_demand(node_id) {
+            Some(demand) => {
+                let mut hasher = StableHasher::new();
+                key.hash(&mut hasher);
+                demand.hash(&mut hasher);
+                hasher.finish()
+            }
+            None => key,
+        };
+        keys.insert(node_id, key);
+    }
+    keys
+}
+
+fn content_key(
+    graph: &Graph,
+    node_id: NodeId,
+    frame: f32,
+    memo: &mut HashMap<NodeId, Option<u64>>,
+) -> Option<u64> {
+    if let Some(key) = memo.get(&node_id) {
+        return *key;
+    }
+    // Guards against cycles; a valid graph never revisits a node in progress.
+    memo.insert(node_id, None);
+
+    let key = (|| {
+        let node = graph.node(node_id)?;
+        let mut hasher = StableHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        ENTRY_VERSION.hash(&mut hasher);
+        // A locked node's output depends on nothing but the stored geometry.
+        if let Some(locked) = &node.locked {
+            locked.content_hash().hash(&mut hasher);
+            return Some(hasher.finish());
+        }
+        node.kind_id.hash(&mut hasher);
+        node.name.hash(&mut hasher);
+        node.bypass.hash(&mut hasher);
+        serde_json::to_string(&node.params)
+            .ok()?
+            .hash(&mut hasher);
+        hash_read_paths(graph, node_id, &mut hasher)?;
+        if !node.expressions.is_empty() {
+            node.expressions.hash(&mut hasher);
+            for dependency in expression_dependencies(graph, node_id) {
+                let other = graph.node(dependency)?;
+                serde_json::to_string(&other.params).ok()?.hash(&mut hasher);
+                other.expressions.hash(&mut hasher);
+                if !other.channels.is_empty() {
+                    serde_json::to_string(&other.channels).ok()?.hash(&mut hasher);
+                }
+            }
+        }
+        if !node.channels.is_empty() {
+            serde_json::to_string(&node.channels).ok()?.hash(&mut hasher);
+        }
+        if is_time_dependent(graph, node) {
+            frame.to_bits().hash(&mut hasher);
+        }
+        if let Some(subnet) = &node.subnet {
+            serde_json::to_string(subnet).ok()?.hash(&mut hasher);
+            for inner in subnet.graph.nodes() {
+                hash_read_paths(&subnet.graph, inner.id, &mut hasher)?;
+            }
+        }
+
+        for pin_id in node.input_pins() {
+            let Some(link) = graph.input_link(*pin_id) else {
+                0u64.hash(&mut hasher);
+                continue;
+            };
+            let from_pin = graph.pin(link.from)?;
+            let upstream = graph.node(from_pin.node)?;
+            let output_index = upstream.outputs.iter().position(|pin| *pin == link.from)?;
+            content_key(graph, from_pin.node, frame, memo)?.hash(&mut hasher);
+            output_index.hash(&mut hasher);
+        }
+        Some(hasher.finish())
+    })();
+    memo.insert(node_id, key);
+    key
+}
+
+fn hash_read_paths(graph: &Graph, node_id: NodeId, hasher: &mut StableHasher) -> Option<()> {
+    let node = graph.node(node_id)?;
+    let Some(node_type) = node_type_for(node) else {
+        return Some(());
+    };
+    for spec in node_type.param_specs() {
+        if !matches!(
+            spec.path_kind,
+            Some(ParamPathKind::ReadMesh | ParamPathKind::ReadSplat | ParamPathKind::ReadTexture)
+        ) {
+            continue;
+        }
+        let path = node.params.get_string(spec.key, "");
+        if path.starts_with("mem://") {
+            return None;
+        }
+        if path.is_empty() || is_url(path) {
+            continue;
+        }
+        match fs::metadata(path) {
+            Ok(meta) => {
+                meta.len().hash(hasher);
+                meta.modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|since| since.as_nanos())
+                    .hash(hasher);
+            }
+            Err(_) => u64::MAX.hash(hasher),
+        }
+    }
+    Some(())
+}
+
+fn writes_files(graph: &Graph, node_id: NodeId) -> bool {
+    let Some(node) = graph.node(node_id) else {
+        return false;
+    };
+    if let Some(subnet) = &node.subnet {
+        return subnet
+            .graph
+            .nodes()
+            .any(|inner| writes_files(&subnet.graph, inner.id));
+    }
+    let Some(node_type) = node_type_for(node) else {
+        return false;
+    };
+    node_type.param_specs().iter().any(|spec| {
+        matches!(
+            spec.path_kind,
+            Some(
+                ParamPathKind::WriteObj
+                    | ParamPathKind::WriteGltf
+                    | ParamPathKind::WritePly
+                    | ParamPathKind::WriteStl
+                    | ParamPathKind::WriteSplat
+            )
+        )
+    })
+}
+
+/// FNV-1a, which unlike `DefaultHasher` is guaranteed not to change between
+/// toolchains.
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::{NodeDefinition, NodeParams, ParamValue};
+    use crate::mesh::make_box;
+    use crate::node_registry::{add_registered_node, register_node_type, NodeType};
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+    use crate::param_spec::ParamSpec;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "lobedo_cook_cache_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn round_trips_geometry() {
+        let dir = temp_dir("round_trip");
+        let cache = DiskCookCache::new(&dir);
+        let mut mesh = make_box([1.0, 2.0, 3.0]);
+        mesh.groups
+            .map_mut(crate::attributes::AttributeDomain::Primitive)
+            .insert("top".to_string(), vec![true; mesh.face_counts.len()]);
+        let geometry = Geometry::with_mesh(mesh);
+
+        assert!(cache.load(7).is_none());
+        cache.store(7, &[geometry.clone(), Geometry::default()]).unwrap();
+        let loaded = cache.load(7).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].meshes[0].positions, geometry.meshes[0].positions);
+        assert_eq!(loaded[0].meshes[0].groups, geometry.meshes[0].groups);
+        assert!(loaded[1].is_empty());
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.writes(), 1);
+
+        assert!(cache.clear().unwrap() > 0);
+        assert!(cache.load(7).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn keys_follow_upstream_content() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        let from = graph.node(source).unwrap().outputs[0];
+        let to = graph.node(transform).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+
+        let first = node_cache_keys(&graph, transform, 1.0);
+        assert_eq!(first.len(), 2);
+        assert_eq!(node_cache_keys(&graph, transform, 1.0), first);
+
+        graph
+            .set_param(source, "size", ParamValue::Vec3([2.0, 2.0, 2.0]))
+            .unwrap();
+        let second = node_cache_keys(&graph, transform, 1.0);
+        assert_ne!(first[&source], second[&source]);
+        assert_ne!(first[&transform], second[&transform]);
+    }
+
+    struct Writer;
+
+    impl NodeType for Writer {
+        fn kind_id(&self) -> &str {
+            "cook_cache_test:writer"
+        }
+
+        fn definition(&self) -> NodeDefinition {
+            NodeDefinition {
+                name: "Writer".to_string(),
+                ..node_definition(BuiltinNodeKind::ObjOutput)
+            }
+        }
+
+        fn param_specs(&self) -> Vec<ParamSpec> {
+            vec![ParamSpec::path("path", "Path", ParamPathKind::WriteObj)]
+        }
+
+        fn compute_geometry(
+            &self,
+            _params: &NodeParams,
+            inputs: &[Geometry],
+        ) -> Result<Vec<Geometry>, String> {
+            Ok(inputs.to_vec())
+        }
+    }
+
+    #[test]
+    fn plugin_writers_are_not_cached() {
+        register_node_type(Writer).unwrap();
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let writer = add_registered_node(&mut graph, "cook_cache_test:writer").unwrap();
+        let from = graph.node(source).unwrap().outputs[0];
+        let to = graph.node(writer).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+
+        let keys = node_cache_keys(&graph, writer, 1.0);
+        assert!(keys.contains_key(&source));
+        assert!(!keys.contains_key(&writer));
+    }
+    #[test]
+    fn disk_hits_skip_upstream_cooks() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let middle = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        let last = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        for (from, to) in [(source, middle), (middle, last)] {
+            let from = graph.node(from).unwrap().outputs[0];
+            let to = graph.node(to).unwrap().inputs[0];
+            graph.add_link(from, to).unwrap();
+        }
+        let dir = temp_dir("skip_upstream");
+        let with_cache = || {
+            let mut state = GeometryEvalState::new();
+            state.set_disk_cache(Some(DiskCookCache::new(&dir)));
+            state
+        };
+
+        let mut first = with_cache();
+        let report = evaluate_geometry_graph(&graph, last, &mut first)
+            .unwrap()
+            .report;
+        assert_eq!(report.computed, vec![source, middle, last]);
+        // Storing the entries does not dirty the nodes that wrote them.
+        let report = evaluate_geometry_graph(&graph, last, &mut first)
+            .unwrap()
+            .report;
+        assert!(report.computed.is_empty());
+        assert_eq!(report.ordered, vec![source, middle, last]);
+
+        // A fresh state, as after reopening, loads the output without its inputs.
+        let mut reopened = with_cache();
+        let result = evaluate_geometry_graph(&graph, last, &mut reopened).unwrap();
+        assert_eq!(result.report.ordered, vec![last]);
+        assert_eq!(result.report.disk_hits, vec![last]);
+        let expected = first.geometry_for_node(last).unwrap();
+        assert_eq!(result.output.as_ref(), Some(expected));
+        let report = evaluate_geometry_graph(&graph, last, &mut reopened)
+            .unwrap()
+            .report;
+        assert!(report.computed.is_empty());
+
+        graph
+            .set_param(last, "translate", ParamValue::Vec3([0.0, 1.0, 0.0]))
+            .unwrap();
+        let report = evaluate_geometry_graph(&graph, last, &mut reopened)
+            .unwrap()
+            .report;
+        assert_eq!(report.computed, vec![middle, last]);
+        assert_eq!(report.disk_hits, vec![middle]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -6,19 +6,36 @@ use std::time::Instant;
 #[cfg(target_arch = "wasm32")]
 use web_time::Instant;
 
-use crate::graph::{Graph, GraphError, NodeId, NodeParams};
-use crate::progress::{set_progress_context, ProgressEvent, ProgressSink};
+use crate::expression::effective_param_version;
+use crate::graph::{Graph, GraphError, Node, NodeId, NodeParams};
+use crate::parallel;
+use crate::progress::{
+    set_progress_context, take_phases, CancelToken, PhaseReport, ProgressEvent, ProgressSink,
+    CANCELLED_MESSAGE,
+};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct EvalCacheStats {
     pub hits: u64,
     pub misses: u64,
+    pub cache_bytes: u64,
+    pub evictions: u64,
+    pub recooks: u64,
+    pub disk_hits: u64,
+    pub disk_writes: u64,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct EvalState {
     nodes: BTreeMap<NodeId, NodeEvalState>,
     pub stats: EvalCacheStats,
+    /// Frame that keyframes and `$F` are evaluated at.
+    frame: f32,
+    /// Nodes that cook only one of their inputs (Switch), by selected input index.
+    selected_inputs: BTreeMap<NodeId, usize>,
+    /// Nodes that load from the disk cook cache, by content key. Like locked
+    /// nodes, their inputs are not cooked and the key is their signature.
+    disk_nodes: BTreeMap<NodeId, u64>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -28,6 +45,7 @@ struct NodeEvalState {
     last_upstream_signature: u64,
     initialized: bool,
     output_version: u64,
+    evicted: bool,
 }
 
 #[derive(Debug, Default)]
@@ -40,15 +58,28 @@ pub struct EvalReport {
     pub dirty: Vec<DirtyNodeReport>,
     pub errors: Vec<EvalError>,
     pub output_valid: bool,
+    pub cancelled: bool,
+    /// Computed nodes whose output was loaded from the disk cook cache.
+    pub disk_hits: Vec<NodeId>,
 }
 
 #[derive(Debug, Clone)]
 pub struct EvalNodeReport {
     pub node: NodeId,
+    /// Offset from the start of the evaluation.
+    pub start_ms: f32,
     pub duration_ms: f32,
     pub cache_hit: bool,
     pub output_version: u64,
     pub error: Option<EvalError>,
+    /// Rayon worker that cooked the node; `None` when it cooked on the
+    /// evaluating thread or was not cooked.
+    pub thread: Option<usize>,
+    pub phases: Vec<PhaseReport>,
+    /// Approximate size of the linked inputs and of the outputs, filled in by
+    /// geometry evaluation.
+    pub input_bytes: usize,
+    pub output_bytes: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -76,10 +107,73 @@ impl EvalState {
         Self::default()
     }
 
+    /// Moves evaluation to `frame`; only time-dependent nodes become dirty.
+    pub fn set_frame(&mut self, frame: f32) {
+        self.frame = frame;
+    }
+
+    pub fn frame(&self) -> f32 {
+        self.frame
+    }
+
+    /// Restricts `node_id` to cooking input `index`; the other inputs' upstream
+    /// nodes are neither evaluated nor reported.
+    pub(crate) fn select_input(&mut self, node_id: NodeId, index: usize) {
+        self.selected_inputs.insert(node_id, index);
+    }
+
+    pub fn selected_input(&self, node_id: NodeId) -> Option<usize> {
+        self.selected_inputs.get(&node_id).copied()
+    }
+
+    pub(crate) fn retain_selected_inputs(&mut self, keep: impl Fn(NodeId) -> bool) {
+        self.selected_inputs.retain(|node_id, _| keep(*node_id));
+    }
+
+    pub(crate) fn disk_nodes(&self) -> &BTreeMap<NodeId, u64> {
+        &self.disk_nodes
+    }
+
+    pub(crate) fn set_disk_key(&mut self, node_id: NodeId, key: Option<u64>) {
+        match key {
+            Some(key) => self.disk_nodes.insert(node_id, key),
+            None => self.disk_nodes.remove(&node_id),
+        };
+    }
+
+    pub(crate) fn retain_disk_nodes(&mut self, keep: impl Fn(NodeId) -> bool) {
+        self.disk_nodes.retain(|node_id, _| keep(*node_id));
+    }
+
+    /// Whether `node_id` last cooked from disk cook cache entry `key`.
+    pub(crate) fn cooked_from_disk(&self, node_id: NodeId, key: u64) -> bool {
+        self.nodes.get(&node_id).is_some_and(|state| {
+            state.initialized
+                && !state.evicted
+                && state.last_signature == hash_signature(key, &[], &[])
+        })
+    }
+
     pub fn node_output_version(&self, node_id: NodeId) -> Option<u64> {
         self.nodes.get(&node_id).map(|state| state.output_version)
     }
 
+    /// Records that the owner dropped this node's cached output. The node stays clean
+    /// (downstream signatures are untouched) but is recooked whenever this
+    /// evaluation or a dirty downstream node needs its output again.
+    pub fn mark_evicted(&mut self, node_id: NodeId) {
+        if let Some(node_state) = self.nodes.get_mut(&node_id) {
+            if node_state.initialized && !node_state.evicted {
+                node_state.evicted = true;
+                self.stats.evictions += 1;
+            }
+        }
+    }
+
+    pub fn is_evicted(&self, node_id: NodeId) -> bool {
+        self.nodes.get(&node_id).is_some_and(|state| state.evicted)
+    }
+
     fn node_state_mut(&mut self, node_id: NodeId) -> &mut NodeEvalState {
         self.nodes.entry(node_id).or_default()
     }
@@ -100,166 +194,347 @@ pub fn evaluate_from_with<F>(
     compute: F,
 ) -> Result<EvalReport, GraphError>
 where
-    F: FnMut(NodeId, &NodeParams) -> Result<(), String>,
+    F: Fn(NodeId, &NodeParams) -> Result<(), String> + Sync,
 {
-    evaluate_from_with_progress(graph, output, state, None, compute)
+    evaluate_from_with_progress(graph, output, state, None, None, compute)
+}
+
+struct ScheduledNode<'a> {
+    node: NodeId,
+    params: &'a NodeParams,
+    param_version: u64,
+    signature: u64,
+    upstream_signature: u64,
+    recook: bool,
+    report: EvalNodeReport,
 }
 
+/// Evaluates `output` and its upstream nodes in waves: every node whose upstream
+/// has settled is checked against the cache, and the remaining dirty nodes of a
+/// wave are computed concurrently. Report lists keep topological order.
+///
+/// Nodes that fail with [`CANCELLED_MESSAGE`] keep their previous cache state;
+/// once `cancel` fires, later waves are skipped and the report comes back with
+/// `cancelled` set.
 pub fn evaluate_from_with_progress<F>(
     graph: &Graph,
     output: NodeId,
     state: &mut EvalState,
     progress: Option<ProgressSink>,
-    mut compute: F,
+    cancel: Option<CancelToken>,
+    compute: F,
 ) -> Result<EvalReport, GraphError>
 where
-    F: FnMut(NodeId, &NodeParams) -> Result<(), String>,
+    F: Fn(NodeId, &NodeParams) -> Result<(), String> + Sync,
 {
-    let ordered = graph.topo_sort_from(output)?;
+    let ordered = active_order(graph, output, state)?;
+    let order_index: HashMap<NodeId, usize> = ordered
+        .iter()
+        .enumerate()
+        .map(|(idx, node_id)| (*node_id, idx))
+        .collect();
     let mut report = EvalReport {
         ordered,
         output_valid: true,
         ..Default::default()
     };
-    let mut failed_nodes = Vec::<NodeId>::new();
-
-    for node_id in &report.ordered {
-        let node = graph
-            .node(*node_id)
-            .ok_or(GraphError::MissingNode(*node_id))?;
-        let mut upstream = graph.upstream_nodes(*node_id);
-        upstream.sort();
-
-        let mut upstream_versions = Vec::with_capacity(upstream.len());
-        for upstream_id in &upstream {
-            let upstream_state = state.node_state_mut(*upstream_id);
-            upstream_versions.push((*upstream_id, upstream_state.output_version));
-        }
+    let eval_start = Instant::now();
+    let recook = evicted_nodes_to_recook(graph, &report.ordered, output, state);
+    let mut failed_nodes = HashSet::<NodeId>::new();
+    let mut settled = HashSet::<NodeId>::new();
+    let mut pending = report.ordered.clone();
 
-        let upstream_signature = hash_upstream(&upstream_versions);
-        let signature = hash_signature(node.param_version, &upstream_versions);
-        let (last_signature, output_version) = {
-            let node_state = state.node_state_mut(*node_id);
-            (node_state.last_signature, node_state.output_version)
-        };
-        let mut node_report = EvalNodeReport {
-            node: *node_id,
-            duration_ms: 0.0,
-            cache_hit: false,
-            output_version,
-            error: None,
-        };
+    let is_cancelled = || cancel.as_ref().is_some_and(CancelToken::is_cancelled);
 
-        let upstream_failed: Vec<NodeId> = upstream
-            .iter()
-            .copied()
-            .filter(|id| failed_nodes.contains(id))
-            .collect();
-        if !upstream_failed.is_empty() {
-            let error = EvalError::Upstream {
-                node: *node_id,
-                upstream: upstream_failed,
-            };
-            node_report.error = Some(error.clone());
-            report.errors.push(error);
+    while !pending.is_empty() {
+        if is_cancelled() {
+            report.cancelled = true;
             report.output_valid = false;
-            failed_nodes.push(*node_id);
-            report.node_reports.insert(*node_id, node_report);
-            continue;
+            break;
         }
+        let mut batch = Vec::new();
+        let mut waiting = Vec::new();
 
-        let dirty_reason = {
-            let node_state = state.node_state_mut(*node_id);
-            if !node_state.initialized {
-                Some(DirtyReason::NewNode)
-            } else if last_signature == signature {
-                None
-            } else {
-                let param_changed = node.param_version != node_state.last_param_version;
-                let upstream_changed = upstream_signature != node_state.last_upstream_signature;
-                match (param_changed, upstream_changed) {
-                    (true, true) => Some(DirtyReason::ParamAndUpstreamChanged),
-                    (true, false) => Some(DirtyReason::ParamChanged),
-                    (false, true) => Some(DirtyReason::UpstreamChanged),
-                    (false, false) => None,
-                }
+        for node_id in pending {
+            let node = graph
+                .node(node_id)
+                .ok_or(GraphError::MissingNode(node_id))?;
+            let mut upstream = active_upstream(graph, node_id, state);
+            upstream.sort();
+            if !upstream.iter().all(|id| settled.contains(id)) {
+                waiting.push(node_id);
+                continue;
             }
-        };
-        if let Some(reason) = dirty_reason {
-            report.dirty.push(DirtyNodeReport {
-                node: *node_id,
-                reason,
-            });
-        }
 
-        if last_signature == signature {
-            report.cache_hits += 1;
-            state.stats.hits += 1;
-            node_report.cache_hit = true;
-            node_report.output_version = output_version;
-            let node_state = state.node_state_mut(*node_id);
-            node_state.last_param_version = node.param_version;
-            node_state.last_upstream_signature = upstream_signature;
-            node_state.initialized = true;
-            report.node_reports.insert(*node_id, node_report);
-            continue;
-        }
+            let mut upstream_versions = Vec::with_capacity(upstream.len());
+            for upstream_id in &upstream {
+                let upstream_state = state.node_state_mut(*upstream_id);
+                upstream_versions.push((*upstream_id, upstream_state.output_version));
+            }
 
-        let start = Instant::now();
-        if let Some(sink) = progress.as_ref() {
-            (sink)(ProgressEvent::Start { node: *node_id });
-        }
-        let _guard = set_progress_context(*node_id, progress.clone());
-        let compute_result = compute(*node_id, &node.params);
-        if let Some(sink) = progress.as_ref() {
-            (sink)(ProgressEvent::Finish { node: *node_id });
-        }
-        node_report.duration_ms = start.elapsed().as_secs_f32() * 1000.0;
+            let wiring = active_wiring(graph, node_id, state);
+            let param_version = param_version(graph, node, state);
+            let upstream_signature = hash_upstream(&wiring, &upstream_versions);
+            let signature = hash_signature(param_version, &wiring, &upstream_versions);
+            let (last_signature, output_version) = {
+                let node_state = state.node_state_mut(node_id);
+                (node_state.last_signature, node_state.output_version)
+            };
+            let mut node_report = EvalNodeReport {
+                node: node_id,
+                start_ms: eval_start.elapsed().as_secs_f32() * 1000.0,
+                duration_ms: 0.0,
+                cache_hit: false,
+                output_version,
+                error: None,
+                thread: None,
+                phases: Vec::new(),
+                input_bytes: 0,
+                output_bytes: 0,
+            };
 
-        match compute_result {
-            Ok(()) => {
-                let node_state = state.node_state_mut(*node_id);
-                node_state.last_signature = signature;
-                node_state.last_param_version = node.param_version;
-                node_state.last_upstream_signature = upstream_signature;
-                node_state.initialized = true;
-                node_state.output_version = node_state.output_version.wrapping_add(1);
-                node_report.output_version = node_state.output_version;
-                report.cache_misses += 1;
-                state.stats.misses += 1;
-                report.computed.push(*node_id);
-            }
-            Err(message) => {
-                let error = EvalError::Node {
-                    node: *node_id,
-                    message,
+            let upstream_failed: Vec<NodeId> = upstream
+                .iter()
+                .copied()
+                .filter(|id| failed_nodes.contains(id))
+                .collect();
+            if !upstream_failed.is_empty() {
+                let error = EvalError::Upstream {
+                    node: node_id,
+                    upstream: upstream_failed,
                 };
                 node_report.error = Some(error.clone());
                 report.errors.push(error);
                 report.output_valid = false;
-                failed_nodes.push(*node_id);
+                failed_nodes.insert(node_id);
+                settled.insert(node_id);
+                report.node_reports.insert(node_id, node_report);
+                continue;
+            }
+
+            let dirty_reason = {
+                let node_state = state.node_state_mut(node_id);
+                if !node_state.initialized {
+                    Some(DirtyReason::NewNode)
+                } else if last_signature == signature {
+                    None
+                } else {
+                    let param_changed = param_version != node_state.last_param_version;
+                    let upstream_changed =
+                        upstream_signature != node_state.last_upstream_signature;
+                    match (param_changed, upstream_changed) {
+                        (true, true) => Some(DirtyReason::ParamAndUpstreamChanged),
+                        (true, false) => Some(DirtyReason::ParamChanged),
+                        (false, true) => Some(DirtyReason::UpstreamChanged),
+                        (false, false) => None,
+                    }
+                }
+            };
+            if let Some(reason) = dirty_reason {
+                report.dirty.push(DirtyNodeReport {
+                    node: node_id,
+                    reason,
+                });
+            }
+
+            let recook_node = last_signature == signature && recook.contains(&node_id);
+            if last_signature == signature && !recook_node {
+                report.cache_hits += 1;
+                state.stats.hits += 1;
+                node_report.cache_hit = true;
+                node_report.output_version = output_version;
+                let node_state = state.node_state_mut(node_id);
+                node_state.last_param_version = param_version;
+                node_state.last_upstream_signature = upstream_signature;
+                node_state.initialized = true;
+                settled.insert(node_id);
+                report.node_reports.insert(node_id, node_report);
+                continue;
             }
+
+            batch.push(ScheduledNode {
+                node: node_id,
+                params: &node.params,
+                param_version,
+                signature,
+                upstream_signature,
+                recook: recook_node,
+                report: node_report,
+            });
         }
 
-        report.node_reports.insert(*node_id, node_report);
+        let results = parallel::map_tasks(&batch, |scheduled| {
+            compute_scheduled(
+                scheduled,
+                eval_start,
+                progress.as_ref(),
+                cancel.as_ref(),
+                &compute,
+            )
+        });
+        for (scheduled, (compute_result, mut node_report)) in batch.into_iter().zip(results) {
+            let node_id = scheduled.node;
+            match compute_result {
+                Ok(()) => {
+                    let node_state = state.node_state_mut(node_id);
+                    node_state.last_signature = scheduled.signature;
+                    node_state.last_param_version = scheduled.param_version;
+                    node_state.last_upstream_signature = scheduled.upstream_signature;
+                    node_state.initialized = true;
+                    node_state.evicted = false;
+                    // A recook reproduces the evicted output, so downstream stays cached.
+                    if !scheduled.recook {
+                        node_state.output_version = node_state.output_version.wrapping_add(1);
+                    }
+                    node_report.output_version = node_state.output_version;
+                    report.cache_misses += 1;
+                    state.stats.misses += 1;
+                    if scheduled.recook {
+                        state.stats.recooks += 1;
+                    }
+                    report.computed.push(node_id);
+                }
+                // Only the cancellation error counts; other failures in the wave
+                // are still reported.
+                Err(message) if message == CANCELLED_MESSAGE => {
+                    report.cancelled = true;
+                    report.output_valid = false;
+                    continue;
+                }
+                Err(message) => {
+                    let error = EvalError::Node {
+                        node: node_id,
+                        message,
+                    };
+                    node_report.error = Some(error.clone());
+                    report.errors.push(error);
+                    report.output_valid = false;
+                    failed_nodes.insert(node_id);
+                }
+            }
+            settled.insert(node_id);
+            report.node_reports.insert(node_id, node_report);
+        }
+
+        pending = waiting;
     }
 
+    let position = |node_id: &NodeId| order_index.get(node_id).copied().unwrap_or(usize::MAX);
+    report.computed.sort_by_key(position);
+    report.dirty.sort_by_key(|entry| position(&entry.node));
+    report.errors.sort_by_key(|error| match error {
+        EvalError::Node { node, .. } | EvalError::Upstream { node, .. } => position(node),
+    });
+
     Ok(report)
 }
 
+fn compute_scheduled<F>(
+    scheduled: &ScheduledNode<'_>,
+    eval_start: Instant,
+    progress: Option<&ProgressSink>,
+    cancel: Option<&CancelToken>,
+    compute: &F,
+) -> (Result<(), String>, EvalNodeReport)
+where
+    F: Fn(NodeId, &NodeParams) -> Result<(), String> + Sync,
+{
+    let node_id = scheduled.node;
+    let mut report = scheduled.report.clone();
+    let start = Instant::now();
+    report.start_ms = start.duration_since(eval_start).as_secs_f32() * 1000.0;
+    report.thread = parallel::worker_index();
+    if let Some(sink) = progress {
+        (sink)(ProgressEvent::Start { node: node_id });
+    }
+    let result = {
+        let _guard = set_progress_context(node_id, progress.cloned(), cancel.cloned());
+        let result = compute(node_id, scheduled.params);
+        report.phases = take_phases();
+        result
+    };
+    if let Some(sink) = progress {
+        (sink)(ProgressEvent::Finish { node: node_id });
+    }
+    report.duration_ms = start.elapsed().as_secs_f32() * 1000.0;
+    (result, report)
+}
+
+/// Evicted nodes only need their output back when they are the evaluated output or
+/// feed a node that will compute, so walk the predicted dirty set backwards.
+fn evicted_nodes_to_recook(
+    graph: &Graph,
+    ordered: &[NodeId],
+    output: NodeId,
+    state: &EvalState,
+) -> HashSet<NodeId> {
+    let mut recook = HashSet::new();
+    if !state.nodes.values().any(|node_state| node_state.evicted) {
+        return recook;
+    }
+
+    let mut dirty = HashSet::new();
+    for node_id in ordered {
+        let Some(node) = graph.node(*node_id) else {
+            continue;
+        };
+        let mut upstream = active_upstream(graph, *node_id, state);
+        upstream.sort();
+        let is_dirty = match state.nodes.get(node_id) {
+            Some(node_state) if node_state.initialized => {
+                let upstream_versions: Vec<(NodeId, u64)> = upstream
+                    .iter()
+                    .map(|upstream_id| {
+                        let output_version = state
+                            .nodes
+                            .get(upstream_id)
+                            .map(|state| state.output_version)
+                            .unwrap_or_default();
+                        (*upstream_id, output_version)
+                    })
+                    .collect();
+                let wiring = active_wiring(graph, *node_id, state);
+                upstream.iter().any(|id| dirty.contains(id))
+                    || hash_signature(param_version(graph, node, state), &wiring, &upstream_versions)
+                        != node_state.last_signature
+            }
+            _ => true,
+        };
+        if is_dirty {
+            dirty.insert(*node_id);
+        }
+    }
+
+    let mut needed = HashSet::from([output]);
+    for node_id in ordered.iter().rev() {
+        let computes = if dirty.contains(node_id) {
+            true
+        } else if needed.contains(node_id) && state.is_evicted(*node_id) {
+            recook.insert(*node_id);
+            true
+        } else {
+            false
+        };
+        if computes {
+            needed.extend(active_upstream(graph, *node_id, state));
+        }
+    }
+    recook
+}
+
 pub fn collect_dirty_nodes(
     graph: &Graph,
     output: NodeId,
     state: &EvalState,
 ) -> Result<Vec<DirtyNodeReport>, GraphError> {
-    let ordered = graph.topo_sort_from(output)?;
+    let ordered = active_order(graph, output, state)?;
     let mut dirty = Vec::new();
 
     for node_id in ordered {
         let node = graph
             .node(node_id)
             .ok_or(GraphError::MissingNode(node_id))?;
-        let mut upstream = graph.upstream_nodes(node_id);
+        let mut upstream = active_upstream(graph, node_id, state);
         upstream.sort();
 
         let upstream_versions: Vec<(NodeId, u64)> = upstream
@@ -274,8 +549,9 @@ pub fn collect_dirty_nodes(
             })
             .collect();
 
-        let upstream_signature = hash_upstream(&upstream_versions);
-        let signature = hash_signature(node.param_version, &upstream_versions);
+        let wiring = active_wiring(graph, node_id, state);
+        let upstream_signature = hash_upstream(&wiring, &upstream_versions);
+        let signature = hash_signature(param_version(graph, node, state), &wiring, &upstream_versions);
 
         let node_state = state.nodes.get(&node_id);
         let (initialized, last_signature, last_param_version, last_upstream_signature) =
@@ -295,7 +571,7 @@ pub fn collect_dirty_nodes(
         } else if last_signature == signature {
             None
         } else {
-            let param_changed = node.param_version != last_param_version;
+            let param_changed = param_version(graph, node, state) != last_param_version;
             let upstream_changed = upstream_signature != last_upstream_signature;
             match (param_changed, upstream_changed) {
                 (true, true) => Some(DirtyReason::ParamAndUpstreamChanged),
@@ -319,13 +595,13 @@ pub fn collect_dirty_nodes_full(
 ) -> Result<Vec<DirtyNodeReport>, GraphError> {
     let ordered = graph.topo_sort_all()?;
     let mut dirty = Vec::new();
-    let mut dirty_set = std::collections::HashSet::new();
+    let mut dirty_set = HashSet::new();
 
     for node_id in ordered {
         let node = graph
             .node(node_id)
             .ok_or(GraphError::MissingNode(node_id))?;
-        let mut upstream = graph.upstream_nodes(node_id);
+        let mut upstream = active_upstream(graph, node_id, state);
         upstream.sort();
 
         let upstream_versions: Vec<(NodeId, u64)> = upstream
@@ -339,7 +615,8 @@ pub fn collect_dirty_nodes_full(
                 (*upstream_id, output_version)
             })
             .collect();
-        let upstream_signature = hash_upstream(&upstream_versions);
+        let wiring = active_wiring(graph, node_id, state);
+        let upstream_signature = hash_upstream(&wiring, &upstream_versions);
 
         let node_state = state.nodes.get(&node_id);
         let (initialized, last_param_version, last_upstream_signature) =
@@ -355,7 +632,7 @@ pub fn collect_dirty_nodes_full(
 
         let upstream_dirty = upstream.iter().any(|id| dirty_set.contains(id));
         let upstream_changed = upstream_dirty || upstream_signature != last_upstream_signature;
-        let param_changed = node.param_version != last_param_version;
+        let param_changed = param_version(graph, node, state) != last_param_version;
         let reason = if !initialized {
             Some(DirtyReason::NewNode)
         } else if param_changed && upstream_changed {
@@ -384,8 +661,8 @@ pub fn node_dirty(
     graph: &Graph,
     state: &EvalState,
     node_id: NodeId,
-    cache: &mut std::collections::HashMap<NodeId, bool>,
-    visiting: &mut std::collections::HashSet<NodeId>,
+    cache: &mut HashMap<NodeId, bool>,
+    visiting: &mut HashSet<NodeId>,
 ) -> bool {
     if let Some(value) = cache.get(&node_id) {
         return *value;
@@ -405,10 +682,10 @@ pub fn node_dirty(
             let last_upstream_signature = node_state
                 .map(|state| state.last_upstream_signature)
                 .unwrap_or(0);
-            if node.param_version != last_param_version {
+            if param_version(graph, node, state) != last_param_version {
                 true
             } else {
-                let mut upstream = graph.upstream_nodes(node_id);
+                let mut upstream = active_upstream(graph, node_id, state);
                 upstream.sort();
                 let mut upstream_dirty = false;
                 for upstream_id in &upstream {
@@ -431,7 +708,8 @@ pub fn node_dirty(
                             (*upstream_id, output_version)
                         })
                         .collect();
-                    let upstream_signature = hash_upstream(&upstream_versions);
+                    let wiring = active_wiring(graph, node_id, state);
+                    let upstream_signature = hash_upstream(&wiring, &upstream_versions);
                     upstream_signature != last_upstream_signature
                 }
             }
@@ -444,15 +722,129 @@ pub fn node_dirty(
     dirty
 }
 
-fn hash_signature(param_version: u64, upstream_versions: &[(NodeId, u64)]) -> u64 {
+/// Nodes `output` cooks from, in topological order, skipping inputs a node did
+/// not select and the inputs of locked and disk cache nodes.
+fn active_order(
+    graph: &Graph,
+    output: NodeId,
+    state: &EvalState,
+) -> Result<Vec<NodeId>, GraphError> {
+    let ordered = graph.topo_sort_from(output)?;
+    if state.selected_inputs.is_empty()
+        && state.disk_nodes.is_empty()
+        && !graph.nodes().any(|node| node.locked.is_some())
+    {
+        return Ok(ordered);
+    }
+    let mut active = HashSet::new();
+    let mut stack = vec![output];
+    while let Some(node_id) = stack.pop() {
+        if active.insert(node_id) {
+            stack.extend(active_upstream(graph, node_id, state));
+        }
+    }
+    Ok(ordered
+        .into_iter()
+        .filter(|node_id| active.contains(node_id))
+        .collect())
+}
+
+pub(crate) fn active_upstream(graph: &Graph, node_id: NodeId, state: &EvalState) -> Vec<NodeId> {
+    if is_locked(graph, node_id) || state.disk_nodes.contains_key(&node_id) {
+        return Vec::new();
+    }
+    match state.selected_inputs.get(&node_id) {
+        Some(index) => graph
+            .input_sources(node_id)
+            .get(*index)
+            .copied()
+            .flatten()
+            .into_iter()
+            .chain(
+                graph
+                    .param_input_sources(node_id)
+                    .into_iter()
+                    .filter_map(|(_, source)| source),
+            )
+            .map(|(upstream, _)| upstream)
+            .collect(),
+        None => graph.upstream_nodes(node_id),
+    }
+}
+
+/// Input wiring with the inputs a node did not select left out, so rewiring them
+/// does not dirty it.
+fn active_wiring(
+    graph: &Graph,
+    node_id: NodeId,
+    state: &EvalState,
+) -> Vec<Option<(NodeId, usize)>> {
+    if is_locked(graph, node_id) || state.disk_nodes.contains_key(&node_id) {
+        return Vec::new();
+    }
+    let mut wiring = graph.input_sources(node_id);
+    if let Some(selected) = state.selected_inputs.get(&node_id) {
+        for (index, source) in wiring.iter_mut().enumerate() {
+            if index != *selected {
+                *source = None;
+            }
+        }
+    }
+    wiring.extend(
+        graph
+            .param_input_sources(node_id)
+            .into_iter()
+            .map(|(_, source)| source),
+    );
+    wiring
+}
+
+fn is_locked(graph: &Graph, node_id: NodeId) -> bool {
+    graph
+        .node(node_id)
+        .is_some_and(|node| node.locked.is_some())
+}
+
+fn param_version(graph: &Graph, node: &Node, state: &EvalState) -> u64 {
+    if let Some(locked) = &node.locked {
+        return locked.version;
+    }
+    if let Some(key) = state.disk_nodes.get(&node.id) {
+        return *key;
+    }
+    let mut version = effective_param_version(graph, node, state.frame);
+    if let Some(demand) = graph.output_demand(node.id) {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        version.hash(&mut hasher);
+        demand.hash(&mut hasher);
+        version = hasher.finish();
+    }
+    match state.selected_inputs.get(&node.id) {
+        Some(selected) => {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            version.hash(&mut hasher);
+            selected.hash(&mut hasher);
+            hasher.finish()
+        }
+        None => version,
+    }
+}
+
+fn hash_signature(
+    param_version: u64,
+    wiring: &[Option<(NodeId, usize)>],
+    upstream_versions: &[(NodeId, u64)],
+) -> u64 {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     param_version.hash(&mut hasher);
+    wiring.hash(&mut hasher);
     upstream_versions.hash(&mut hasher);
     hasher.finish()
 }
 
-fn hash_upstream(upstream_versions: &[(NodeId, u64)]) -> u64 {
+fn hash_upstream(wiring: &[Option<(NodeId, usize)>], upstream_versions: &[(NodeId, u64)]) -> u64 {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    wiring.hash(&mut hasher);
     upstream_versions.hash(&mut hasher);
     hasher.finish()
 }
@@ -475,6 +867,7 @@ mod tests {
             outputs: (0..outputs)
                 .map(|i| make_pin(&format!("out{}", i)))
                 .collect(),
+            variadic_inputs: false,
         }
     }
 
@@ -567,4 +960,181 @@ mod tests {
             .iter()
             .any(|err| matches!(err, EvalError::Upstream { node, .. } if *node == c)));
     }
+
+    #[test]
+    fn independent_branches_keep_topo_order() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(node_def("A", 0, 1));
+        let b = graph.add_node(node_def("B", 0, 1));
+        let x = graph.add_node(node_def("X", 1, 1));
+        let y = graph.add_node(node_def("Y", 1, 1));
+        let merge = graph.add_node(node_def("Merge", 2, 0));
+        connect(&mut graph, a, x);
+        connect(&mut graph, b, y);
+        connect(&mut graph, x, merge);
+        let y_out = graph.node(y).unwrap().outputs[0];
+        let merge_in = graph.node(merge).unwrap().inputs[1];
+        graph.add_link(y_out, merge_in).unwrap();
+
+        let mut state = EvalState::new();
+        let first = evaluate_from(&graph, merge, &mut state).unwrap();
+        assert_eq!(first.computed, first.ordered);
+        assert_eq!(first.cache_misses, 5);
+        let dirty: Vec<NodeId> = first.dirty.iter().map(|entry| entry.node).collect();
+        assert_eq!(dirty, first.ordered);
+
+        graph.set_param(b, "size", ParamValue::Float(2.0)).unwrap();
+        let report = evaluate_from_with(&graph, merge, &mut state, |node_id, _params| {
+            if node_id == y {
+                Err("boom".to_string())
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap();
+        assert_eq!(report.computed, vec![b]);
+        assert_eq!(report.cache_hits, 2);
+        assert!(report.node_reports[&x].cache_hit);
+        assert_eq!(report.errors.len(), 2);
+        assert!(matches!(report.errors[0], EvalError::Node { node, .. } if node == y));
+        assert!(matches!(report.errors[1], EvalError::Upstream { node, .. } if node == merge));
+        assert_eq!(state.stats.hits, 2);
+        assert_eq!(state.stats.misses, 6);
+    }
+
+    #[test]
+    fn cancelled_nodes_stay_dirty() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(node_def("A", 0, 1));
+        let b = graph.add_node(node_def("B", 1, 1));
+        let c = graph.add_node(node_def("C", 1, 0));
+        connect(&mut graph, a, b);
+        connect(&mut graph, b, c);
+
+        let mut state = EvalState::new();
+        evaluate_from(&graph, c, &mut state).unwrap();
+        graph.set_param(b, "twist", ParamValue::Float(1.0)).unwrap();
+
+        let cancel = CancelToken::new();
+        let report = evaluate_from_with_progress(
+            &graph,
+            c,
+            &mut state,
+            None,
+            Some(cancel.clone()),
+            |node_id, _params| {
+                if node_id == b {
+                    cancel.cancel();
+                    Err(crate::progress::CANCELLED_MESSAGE.to_string())
+                } else {
+                    Ok(())
+                }
+            },
+        )
+        .unwrap();
+        assert!(report.cancelled);
+        assert!(!report.output_valid);
+        assert!(report.errors.is_empty());
+        assert!(report.computed.is_empty());
+        assert!(!report.node_reports.contains_key(&c));
+
+        let report = evaluate_from(&graph, c, &mut state).unwrap();
+        assert!(!report.cancelled);
+        assert_eq!(report.computed, vec![b, c]);
+        assert!(report.node_reports[&a].cache_hit);
+    }
+
+    #[test]
+    fn failures_next_to_a_cancel_are_reported() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(node_def("A", 0, 1));
+        let b = graph.add_node(node_def("B", 1, 1));
+        let d = graph.add_node(node_def("D", 1, 1));
+        let merge = graph.add_node(node_def("Merge", 2, 0));
+        connect(&mut graph, a, b);
+        connect(&mut graph, a, d);
+        let d_out = graph.node(d).unwrap().outputs[0];
+        let merge_in = graph.node(merge).unwrap().inputs[1];
+        graph.add_link(d_out, merge_in).unwrap();
+        connect(&mut graph, b, merge);
+
+        let cancel = CancelToken::new();
+        let report = evaluate_from_with_progress(
+            &graph,
+            merge,
+            &mut EvalState::new(),
+            None,
+            Some(cancel.clone()),
+            |node_id, _params| {
+                if node_id == b {
+                    cancel.cancel();
+                    Err(crate::progress::CANCELLED_MESSAGE.to_string())
+                } else if node_id == d {
+                    Err("boom".to_string())
+                } else {
+                    Ok(())
+                }
+            },
+        )
+        .unwrap();
+        assert!(report.cancelled);
+        assert_eq!(report.errors.len(), 1);
+        assert!(matches!(
+            &report.errors[0],
+            EvalError::Node { node, message } if *node == d && message == "boom"
+        ));
+        assert!(!report.node_reports.contains_key(&b));
+    }
+
+    #[test]
+    fn evicted_nodes_recook_only_when_needed() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(node_def("A", 0, 1));
+        let b = graph.add_node(node_def("B", 1, 1));
+        let c = graph.add_node(node_def("C", 1, 0));
+        connect(&mut graph, a, b);
+        connect(&mut graph, b, c);
+
+        let mut state = EvalState::new();
+        evaluate_from(&graph, c, &mut state).unwrap();
+        state.mark_evicted(a);
+        state.mark_evicted(b);
+        let b_version = state.node_output_version(b);
+
+        let report = evaluate_from(&graph, c, &mut state).unwrap();
+        assert!(report.computed.is_empty());
+        assert_eq!(report.cache_hits, 3);
+
+        graph.set_param(c, "twist", ParamValue::Float(1.0)).unwrap();
+        let report = evaluate_from(&graph, c, &mut state).unwrap();
+        assert_eq!(report.computed, vec![a, b, c]);
+        assert!(!state.is_evicted(b));
+        assert_eq!(state.node_output_version(b), b_version);
+        assert_eq!(state.stats.evictions, 2);
+        assert_eq!(state.stats.recooks, 2);
+
+        let report = evaluate_from(&graph, c, &mut state).unwrap();
+        assert_eq!(report.cache_hits, 3);
+    }
+
+    #[test]
+    fn reports_nested_phases() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(node_def("A", 0, 1));
+
+        let mut state = EvalState::new();
+        let report = evaluate_from_with(&graph, a, &mut state, |_node_id, _params| {
+            let _outer = crate::progress::progress_phase("build");
+            let _inner = crate::progress::progress_phase("sort");
+            Ok(())
+        })
+        .unwrap();
+        let phases = &report.node_reports[&a].phases;
+        let names: Vec<(&str, usize)> = phases
+            .iter()
+            .map(|phase| (phase.name.as_str(), phase.depth))
+            .collect();
+        assert_eq!(names, vec![("build", 0), ("sort", 1)]);
+        assert!(phases[0].duration_ms >= phases[1].duration_ms);
+    }
 }
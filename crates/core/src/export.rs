@@ -0,0 +1,129 @@
+use std::path::Path;
+
+use crate::geometry::Geometry;
+use crate::gltf_io::write_gltf;
+use crate::graph::Node;
+use crate::mesh_ply::{write_ply_mesh, PlyMeshFormat};
+use crate::mesh_stl::{write_stl, StlFormat};
+use crate::nodes::obj_output::write_obj;
+use crate::nodes_builtin::BuiltinNodeKind;
+use crate::splat::{save_splats_with_format, SplatSaveFormat};
+
+/// A file format geometry can be written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+    Obj,
+    Gltf,
+    Ply(PlyMeshFormat),
+    Stl {
+        format: StlFormat,
+        facet_normals: bool,
+    },
+    Splat(SplatSaveFormat),
+}
+
+impl ExportKind {
+    /// Picks the format from the extension of `path`: meshes to `.obj`,
+    /// `.gltf`, `.glb` or `.stl`, splats to binary `.ply`, `.compressed.ply`,
+    /// `.splat`, `.ksplat` or `.spz`.
+    pub fn for_path(path: &Path) -> Result<Self, String> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        match extension.as_str() {
+            "obj" => Ok(Self::Obj),
+            "gltf" | "glb" => Ok(Self::Gltf),
+            "stl" => Ok(Self::Stl {
+                format: StlFormat::Binary,
+                facet_normals: true,
+            }),
+            _ => SplatSaveFormat::for_path(path)
+                .map(Self::Splat)
+                .ok_or_else(|| {
+                    format!(
+                        "unsupported output extension for {} \
+                         (expected .obj, .gltf, .glb, .stl, .ply, .splat, .ksplat or .spz)",
+                        path.display()
+                    )
+                }),
+        }
+    }
+
+    /// The format an output node writes with its current parameters, or `None`
+    /// for nodes that don't write files.
+    pub fn for_node(node: &Node) -> Option<Self> {
+        let format = node.params.get_int("format", 0);
+        Some(match node.builtin_kind()? {
+            BuiltinNodeKind::ObjOutput => Self::Obj,
+            BuiltinNodeKind::GltfOutput => Self::Gltf,
+            BuiltinNodeKind::PlyOutput => Self::Ply(PlyMeshFormat::from_index(format)),
+            BuiltinNodeKind::StlOutput => Self::Stl {
+                format: StlFormat::from_index(format),
+                facet_normals: node.params.get_bool("facet_normals", true),
+            },
+            BuiltinNodeKind::WriteSplats => Self::Splat(SplatSaveFormat::from_index(format)),
+            _ => return None,
+        })
+    }
+}
+
+/// Writes `geometry` in the format picked by the extension of `path`; see
+/// [`ExportKind::for_path`].
+pub fn write_geometry(geometry: &Geometry, path: &Path) -> Result<(), String> {
+    write_geometry_with(geometry, path, ExportKind::for_path(path)?)
+}
+
+/// Writes `geometry` as `kind`. A binary `.ply` splat export without splats is
+/// written as a mesh.
+pub fn write_geometry_with(
+    geometry: &Geometry,
+    path: &Path,
+    kind: ExportKind,
+) -> Result<(), String> {
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| format!("invalid output path {}", path.display()))?;
+    match kind {
+        ExportKind::Obj => {
+            let mesh = geometry
+                .merged_mesh()
+                .ok_or_else(|| "OBJ export requires mesh output".to_string())?;
+            write_obj(path_str, &mesh).map_err(|err| format!("OBJ write failed: {err}"))
+        }
+        ExportKind::Gltf => {
+            write_gltf(path_str, geometry).map_err(|err| format!("GLTF write failed: {err}"))
+        }
+        ExportKind::Ply(format) => {
+            let mesh = geometry
+                .merged_mesh()
+                .ok_or_else(|| "PLY export requires mesh output".to_string())?;
+            write_ply_mesh(path_str, &mesh, format)
+                .map_err(|err| format!("PLY write failed: {err}"))
+        }
+        ExportKind::Stl {
+            format,
+            facet_normals,
+        } => {
+            let mesh = geometry
+                .merged_mesh()
+                .ok_or_else(|| "STL export requires mesh output".to_string())?;
+            write_stl(path_str, &mesh, format, facet_normals)
+                .map_err(|err| format!("STL write failed: {err}"))
+        }
+        ExportKind::Splat(SplatSaveFormat::BinaryLittle) if geometry.splats.is_empty() => {
+            if geometry.meshes.is_empty() {
+                return Err("PLY export requires mesh or splat output".to_string());
+            }
+            write_geometry_with(geometry, path, ExportKind::Ply(PlyMeshFormat::BinaryLittle))
+        }
+        ExportKind::Splat(format) => {
+            let splats = geometry
+                .merged_splats()
+                .ok_or_else(|| "splat export requires splat output".to_string())?;
+            save_splats_with_format(path_str, &splats, format)
+                .map_err(|err| format!("splat write failed: {err}"))
+        }
+    }
+}
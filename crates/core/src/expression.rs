@@ -0,0 +1,966 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
+
+use crate::animation::Channel;
+use crate::attributes::{AttributeDomain, AttributeRef};
+use crate::geometry::Geometry;
+use crate::graph::{Graph, Node, NodeId, NodeParams, ParamValue};
+use crate::nodes_builtin::default_params;
+
+/// The current frame, as in `sin($F * 0.1)`.
+const FRAME_VARIABLE: &str = "$F";
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExprValue {
+    Number(f32),
+    Vector(Vec<f32>),
+    String(String),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(f32),
+    String(String),
+    Ident(String),
+    Neg(Box<Expr>),
+    Binary {
+        op: BinaryOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Call {
+        name: String,
+        args: Vec<Expr>,
+    },
+    Index {
+        expr: Box<Expr>,
+        index: Box<Expr>,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f32),
+    String(String),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+/// Checks that `source` parses, without evaluating it.
+pub fn check_expression(source: &str) -> Result<(), String> {
+    parse(source).map(|_| ())
+}
+
+/// Parameters of `node_id` with keyframed parameters evaluated at `frame` and
+/// every expression evaluated against the node's inputs. Borrowed unchanged when
+/// the node has neither.
+pub(crate) fn resolve_params<'a>(
+    graph: &Graph,
+    node_id: NodeId,
+    params: &'a NodeParams,
+    inputs: &[Option<Geometry>],
+    frame: f32,
+) -> Result<Cow<'a, NodeParams>, String> {
+    let Some(node) = graph.node(node_id) else {
+        return Ok(Cow::Borrowed(params));
+    };
+    if node.expressions.is_empty() && node.channels.is_empty() {
+        return Ok(Cow::Borrowed(params));
+    }
+    let mut resolved = params.clone();
+    for (key, channel) in &node.channels {
+        if let Some(value) = channel.evaluate(frame) {
+            resolved.values.insert(key.clone(), value);
+        }
+    }
+    let mut evaluator = Evaluator {
+        graph,
+        frame,
+        stack: Vec::new(),
+    };
+    for (key, source) in &node.expressions {
+        evaluator.stack.push((node_id, key.clone()));
+        let value = evaluator
+            .evaluate_source(source, node_id, inputs)
+            .and_then(|value| coerce(value, literal_value(node, key).as_ref()))
+            .map_err(|err| format!("expression for '{}': {}", key, err))?;
+        evaluator.stack.pop();
+        resolved.values.insert(key.clone(), value);
+    }
+    Ok(Cow::Owned(resolved))
+}
+
+/// Parameter version of `node` folded with the versions of the nodes its
+/// expressions reference, so editing a referenced parameter dirties the node.
+/// Time-dependent nodes also fold in `frame`.
+pub(crate) fn effective_param_version(graph: &Graph, node: &Node, frame: f32) -> u64 {
+    let time_dependent = is_time_dependent(graph, node);
+    if node.expressions.is_empty() && !time_dependent {
+        return node.param_version;
+    }
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    node.param_version.hash(&mut hasher);
+    for dependency in expression_dependencies(graph, node.id) {
+        if let Some(other) = graph.node(dependency) {
+            dependency.hash(&mut hasher);
+            other.param_version.hash(&mut hasher);
+        }
+    }
+    if time_dependent {
+        frame.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Whether the node's parameters change with the frame: it has animated keys, an
+/// expression reads `$F`, it references such a parameter, or it is a subnet with a
+/// time-dependent node inside.
+pub(crate) fn is_time_dependent(graph: &Graph, node: &Node) -> bool {
+    if reads_frame(node) {
+        return true;
+    }
+    if !node.expressions.is_empty()
+        && expression_dependencies(graph, node.id)
+            .into_iter()
+            .any(|dependency| graph.node(dependency).is_some_and(reads_frame))
+    {
+        return true;
+    }
+    node.subnet.as_ref().is_some_and(|subnet| {
+        subnet
+            .graph
+            .nodes()
+            .any(|inner| is_time_dependent(&subnet.graph, inner))
+    })
+}
+
+fn reads_frame(node: &Node) -> bool {
+    node.channels.values().any(Channel::is_animated)
+        || node
+            .expressions
+            .values()
+            .any(|source| parse(source).is_ok_and(|expr| uses_frame(&expr)))
+}
+
+fn uses_frame(expr: &Expr) -> bool {
+    match expr {
+        Expr::Ident(name) => name == FRAME_VARIABLE,
+        Expr::Call { args, .. } => args.iter().any(uses_frame),
+        Expr::Neg(expr) => uses_frame(expr),
+        Expr::Binary { left, right, .. } => uses_frame(left) || uses_frame(right),
+        Expr::Index { expr, index } => uses_frame(expr) || uses_frame(index),
+        Expr::Number(_) | Expr::String(_) => false,
+    }
+}
+
+/// Other nodes whose parameters the expressions of `node_id` read through `ch()`,
+/// following their own expressions in turn.
+pub(crate) fn expression_dependencies(graph: &Graph, node_id: NodeId) -> BTreeSet<NodeId> {
+    let mut found = BTreeSet::new();
+    let mut pending = vec![node_id];
+    let mut visited = BTreeSet::new();
+    while let Some(current) = pending.pop() {
+        if !visited.insert(current) {
+            continue;
+        }
+        let Some(node) = graph.node(current) else {
+            continue;
+        };
+        for source in node.expressions.values() {
+            let Ok(expr) = parse(source) else {
+                continue;
+            };
+            let mut paths = Vec::new();
+            collect_channel_paths(&expr, &mut paths);
+            for path in paths {
+                if let Ok((target, _)) = resolve_channel(graph, current, &path) {
+                    if target != node_id {
+                        found.insert(target);
+                    }
+                    pending.push(target);
+                }
+            }
+        }
+    }
+    found
+}
+
+fn collect_channel_paths(expr: &Expr, paths: &mut Vec<String>) {
+    match expr {
+        Expr::Call { name, args } => {
+            if name == "ch" {
+                if let Some(Expr::String(path)) = args.first() {
+                    paths.push(path.clone());
+                }
+            }
+            for arg in args {
+                collect_channel_paths(arg, paths);
+            }
+        }
+        Expr::Neg(expr) => collect_channel_paths(expr, paths),
+        Expr::Binary { left, right, .. } => {
+            collect_channel_paths(left, paths);
+            collect_channel_paths(right, paths);
+        }
+        Expr::Index { expr, index } => {
+            collect_channel_paths(expr, paths);
+            collect_channel_paths(index, paths);
+        }
+        Expr::Number(_) | Expr::String(_) | Expr::Ident(_) => {}
+    }
+}
+
+/// Resolves `size`, `./size`, `box_3/size` or `../box_3/size` to a node and key.
+/// Other relative paths, such as into a parent network, are errors.
+fn resolve_channel<'p>(
+    graph: &Graph,
+    node_id: NodeId,
+    path: &'p str,
+) -> Result<(NodeId, &'p str), String> {
+    let path = path.trim();
+    let mut segments: Vec<&str> = path.split('/').collect();
+    let key = segments.pop().unwrap_or_default();
+    if key.is_empty() {
+        return Err(format!("channel path '{}' has no parameter", path));
+    }
+    let name = match segments.as_slice() {
+        [] | ["."] => return Ok((node_id, key)),
+        [name] | ["..", name] if !matches!(*name, "" | "." | "..") => *name,
+        _ => return Err(format!("unsupported channel path '{}'", path)),
+    };
+    let target = graph
+        .find_node_by_path_name(name)
+        .ok_or_else(|| format!("no node named '{}'", name))?;
+    Ok((target, key))
+}
+
+struct Evaluator<'a> {
+    graph: &'a Graph,
+    frame: f32,
+    /// Parameters being evaluated, to report reference cycles.
+    stack: Vec<(NodeId, String)>,
+}
+
+impl Evaluator<'_> {
+    fn evaluate_source(
+        &mut self,
+        source: &str,
+        node_id: NodeId,
+        inputs: &[Option<Geometry>],
+    ) -> Result<ExprValue, String> {
+        let expr = parse(source)?;
+        self.evaluate(&expr, node_id, inputs)
+    }
+
+    fn evaluate(
+        &mut self,
+        expr: &Expr,
+        node_id: NodeId,
+        inputs: &[Option<Geometry>],
+    ) -> Result<ExprValue, String> {
+        match expr {
+            Expr::Number(value) => Ok(ExprValue::Number(*value)),
+            Expr::String(value) => Ok(ExprValue::String(value.clone())),
+            Expr::Ident(name) => match name.as_str() {
+                "pi" => Ok(ExprValue::Number(std::f32::consts::PI)),
+                FRAME_VARIABLE => Ok(ExprValue::Number(self.frame)),
+                _ => Err(format!("unknown name '{}'", name)),
+            },
+            Expr::Neg(expr) => {
+                let value = self.evaluate(expr, node_id, inputs)?;
+                map_components(value, |v| -v, "-")
+            }
+            Expr::Binary { op, left, right } => {
+                let left = self.evaluate(left, node_id, inputs)?;
+                let right = self.evaluate(right, node_id, inputs)?;
+                binary(*op, left, right)
+            }
+            Expr::Index { expr, index } => {
+                let value = self.evaluate(expr, node_id, inputs)?;
+                let index = as_index(&self.evaluate(index, node_id, inputs)?)?;
+                match value {
+                    ExprValue::Vector(values) => values
+                        .get(index)
+                        .copied()
+                        .map(ExprValue::Number)
+                        .ok_or_else(|| format!("index {} out of range", index)),
+                    _ => Err("only vectors can be indexed".to_string()),
+                }
+            }
+            Expr::Call { name, args } => {
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    values.push(self.evaluate(arg, node_id, inputs)?);
+                }
+                self.call(name, values, node_id, inputs)
+            }
+        }
+    }
+
+    fn call(
+        &mut self,
+        name: &str,
+        args: Vec<ExprValue>,
+        node_id: NodeId,
+        inputs: &[Option<Geometry>],
+    ) -> Result<ExprValue, String> {
+        match (name, args.as_slice()) {
+            ("ch", [ExprValue::String(path)]) => self.channel(path, node_id, inputs),
+            ("detail", [ExprValue::String(attr)]) => detail(inputs, 0, attr),
+            ("detail", [input, ExprValue::String(attr)]) => detail(inputs, as_index(input)?, attr),
+            ("bbox_min" | "bbox_max" | "bbox_size" | "bbox_center", []) => bbox(name, inputs, 0),
+            ("bbox_min" | "bbox_max" | "bbox_size" | "bbox_center", [input]) => {
+                bbox(name, inputs, as_index(input)?)
+            }
+            ("abs", [value]) => map_components(value.clone(), f32::abs, name),
+            ("floor", [value]) => map_components(value.clone(), f32::floor, name),
+            ("ceil", [value]) => map_components(value.clone(), f32::ceil, name),
+            ("round", [value]) => map_components(value.clone(), f32::round, name),
+            ("sqrt", [value]) => map_components(value.clone(), f32::sqrt, name),
+            ("sin", [value]) => map_components(value.clone(), f32::sin, name),
+            ("cos", [value]) => map_components(value.clone(), f32::cos, name),
+            ("tan", [value]) => map_components(value.clone(), f32::tan, name),
+            ("min", [ExprValue::Number(a), ExprValue::Number(b)]) => Ok(ExprValue::Number(a.min(*b))),
+            ("max", [ExprValue::Number(a), ExprValue::Number(b)]) => Ok(ExprValue::Number(a.max(*b))),
+            ("pow", [ExprValue::Number(a), ExprValue::Number(b)]) => Ok(ExprValue::Number(a.powf(*b))),
+            ("clamp", [value, ExprValue::Number(lo), ExprValue::Number(hi)]) => {
+                let (lo, hi) = (*lo, *hi);
+                map_components(value.clone(), |v| v.clamp(lo, hi.max(lo)), name)
+            }
+            ("length", [ExprValue::Vector(values)]) => Ok(ExprValue::Number(
+                values.iter().map(|v| v * v).sum::<f32>().sqrt(),
+            )),
+            ("vec2" | "vec3", _) => {
+                let len = if name == "vec2" { 2 } else { 3 };
+                let mut components = Vec::with_capacity(len);
+                for arg in &args {
+                    match arg {
+                        ExprValue::Number(value) => components.push(*value),
+                        _ => return Err(format!("{}() takes numbers", name)),
+                    }
+                }
+                match components.len() {
+                    1 => Ok(ExprValue::Vector(vec![components[0]; len])),
+                    n if n == len => Ok(ExprValue::Vector(components)),
+                    _ => Err(format!("{}() takes 1 or {} numbers", name, len)),
+                }
+            }
+            (
+                "ch" | "detail" | "bbox_min" | "bbox_max" | "bbox_size" | "bbox_center" | "abs"
+                | "floor" | "ceil" | "round" | "sqrt" | "sin" | "cos" | "tan" | "min" | "max"
+                | "pow" | "clamp" | "length",
+                _,
+            ) => Err(format!("wrong arguments for {}()", name)),
+            _ => Err(format!("unknown function '{}'", name)),
+        }
+    }
+
+    fn channel(
+        &mut self,
+        path: &str,
+        node_id: NodeId,
+        inputs: &[Option<Geometry>],
+    ) -> Result<ExprValue, String> {
+        let (target, key) = resolve_channel(self.graph, node_id, path)?;
+        let node = self
+            .graph
+            .node(target)
+            .ok_or_else(|| format!("no node for '{}'", path))?;
+        if let Some(source) = node.expressions.get(key) {
+            let entry = (target, key.to_string());
+            if self.stack.contains(&entry) {
+                return Err(format!("reference cycle through '{}'", path));
+            }
+            // Another node's inputs are not available here, so only expressions
+            // on this node can read geometry.
+            let inputs = if target == node_id { inputs } else { &[] };
+            self.stack.push(entry);
+            let value = self.evaluate_source(source, target, inputs);
+            self.stack.pop();
+            return value;
+        }
+        if let Some(value) = node.channels.get(key).and_then(|channel| channel.evaluate(self.frame)) {
+            return Ok(param_to_value(&value));
+        }
+        match literal_value(node, key) {
+            Some(value) => Ok(param_to_value(&value)),
+            None => Err(format!("no parameter '{}'", path)),
+        }
+    }
+}
+
+/// The literal value of a parameter, falling back to the node kind's default for
+/// parameters that were never set.
+fn literal_value(node: &Node, key: &str) -> Option<ParamValue> {
+    if let Some(value) = node.params.values.get(key) {
+        return Some(value.clone());
+    }
+    let kind = node.builtin_kind()?;
+    default_params(kind).values.remove(key)
+}
+
+/// Converts a value arriving on a value input to the type of parameter `key`.
+pub(crate) fn coerce_param(node: &Node, key: &str, value: &ParamValue) -> Result<ParamValue, String> {
+    coerce(param_to_value(value), literal_value(node, key).as_ref())
+        .map_err(|err| format!("input '{}': {}", key, err))
+}
+
+/// First value of detail attribute `name`, as a parameter value.
+pub(crate) fn detail_param(geometry: &Geometry, name: &str) -> Result<ParamValue, String> {
+    detail_of(geometry, name)
+        .and_then(|value| coerce(value, None))
+        .map_err(|err| format!("input {}", err))
+}
+
+/// Bounding box measure `name` (`bbox_min`, `bbox_max`, `bbox_size` or
+/// `bbox_center`) of `geometry`.
+pub(crate) fn bbox_param(name: &str, geometry: &Geometry) -> Result<ParamValue, String> {
+    bbox_of(name, geometry)
+        .and_then(|value| coerce(value, None))
+        .map_err(|err| format!("input {}", err))
+}
+
+fn param_to_value(value: &ParamValue) -> ExprValue {
+    match value {
+        ParamValue::Float(value) => ExprValue::Number(*value),
+        ParamValue::Int(value) => ExprValue::Number(*value as f32),
+        ParamValue::Bool(value) => ExprValue::Number(if *value { 1.0 } else { 0.0 }),
+        ParamValue::Vec2(value) => ExprValue::Vector(value.to_vec()),
+        ParamValue::Vec3(value) => ExprValue::Vector(value.to_vec()),
+        ParamValue::String(value) => ExprValue::String(value.clone()),
+    }
+}
+
+/// Converts an expression result to the type of the parameter's literal value.
+fn coerce(value: ExprValue, literal: Option<&ParamValue>) -> Result<ParamValue, String> {
+    let vector = |len: usize, value: &ExprValue| -> Result<Vec<f32>, String> {
+        match value {
+            ExprValue::Number(v) => Ok(vec![*v; len]),
+            ExprValue::Vector(values) if values.len() == len => Ok(values.clone()),
+            ExprValue::Vector(values) => Err(format!(
+                "expected {} components, got {}",
+                len,
+                values.len()
+            )),
+            ExprValue::String(_) => Err("expected a number, got a string".to_string()),
+        }
+    };
+    let number = |value: &ExprValue| -> Result<f32, String> {
+        match value {
+            ExprValue::Number(v) => Ok(*v),
+            ExprValue::Vector(values) if values.len() == 1 => Ok(values[0]),
+            ExprValue::Vector(_) => Err("expected a number, got a vector".to_string()),
+            ExprValue::String(_) => Err("expected a number, got a string".to_string()),
+        }
+    };
+    match literal {
+        Some(ParamValue::Float(_)) => Ok(ParamValue::Float(number(&value)?)),
+        Some(ParamValue::Int(_)) => Ok(ParamValue::Int(number(&value)?.round() as i32)),
+        Some(ParamValue::Bool(_)) => Ok(ParamValue::Bool(number(&value)? != 0.0)),
+        Some(ParamValue::Vec2(_)) => {
+            let v = vector(2, &value)?;
+            Ok(ParamValue::Vec2([v[0], v[1]]))
+        }
+        Some(ParamValue::Vec3(_)) => {
+            let v = vector(3, &value)?;
+            Ok(ParamValue::Vec3([v[0], v[1], v[2]]))
+        }
+        Some(ParamValue::String(_)) => Ok(ParamValue::String(display_value(&value))),
+        None => Ok(match value {
+            ExprValue::Number(v) => ParamValue::Float(v),
+            ExprValue::Vector(v) if v.len() == 2 => ParamValue::Vec2([v[0], v[1]]),
+            ExprValue::Vector(v) if v.len() == 3 => ParamValue::Vec3([v[0], v[1], v[2]]),
+            ExprValue::Vector(v) => return Err(format!("unsupported {}-component vector", v.len())),
+            ExprValue::String(v) => ParamValue::String(v),
+        }),
+    }
+}
+
+fn display_value(value: &ExprValue) -> String {
+    match value {
+        ExprValue::Number(v) => v.to_string(),
+        ExprValue::Vector(values) => values
+            .iter()
+            .map(f32::to_string)
+            .collect::<Vec<_>>()
+            .join(" "),
+        ExprValue::String(v) => v.clone(),
+    }
+}
+
+fn as_index(value: &ExprValue) -> Result<usize, String> {
+    match value {
+        ExprValue::Number(v) if *v >= 0.0 => Ok(*v as usize),
+        _ => Err("expected a non-negative index".to_string()),
+    }
+}
+
+fn map_components(
+    value: ExprValue,
+    op: impl Fn(f32) -> f32,
+    name: &str,
+) -> Result<ExprValue, String> {
+    match value {
+        ExprValue::Number(v) => Ok(ExprValue::Number(op(v))),
+        ExprValue::Vector(values) => Ok(ExprValue::Vector(values.into_iter().map(op).collect())),
+        ExprValue::String(_) => Err(format!("{} does not apply to strings", name)),
+    }
+}
+
+fn binary(op: BinaryOp, left: ExprValue, right: ExprValue) -> Result<ExprValue, String> {
+    let apply = |a: f32, b: f32| match op {
+        BinaryOp::Add => a + b,
+        BinaryOp::Sub => a - b,
+        BinaryOp::Mul => a * b,
+        BinaryOp::Div => a / b,
+        BinaryOp::Rem => a % b,
+    };
+    match (left, right) {
+        (ExprValue::Number(a), ExprValue::Number(b)) => Ok(ExprValue::Number(apply(a, b))),
+        (ExprValue::Vector(a), ExprValue::Number(b)) => {
+            Ok(ExprValue::Vector(a.into_iter().map(|a| apply(a, b)).collect()))
+        }
+        (ExprValue::Number(a), ExprValue::Vector(b)) => {
+            Ok(ExprValue::Vector(b.into_iter().map(|b| apply(a, b)).collect()))
+        }
+        (ExprValue::Vector(a), ExprValue::Vector(b)) if a.len() == b.len() => Ok(
+            ExprValue::Vector(a.into_iter().zip(b).map(|(a, b)| apply(a, b)).collect()),
+        ),
+        (ExprValue::Vector(_), ExprValue::Vector(_)) => {
+            Err("vectors have different lengths".to_string())
+        }
+        (left @ ExprValue::String(_), right) | (left, right @ ExprValue::String(_))
+            if matches!(op, BinaryOp::Add) =>
+        {
+            Ok(ExprValue::String(format!(
+                "{}{}",
+                display_value(&left),
+                display_value(&right)
+            )))
+        }
+        _ => Err("strings only support +".to_string()),
+    }
+}
+
+fn input_geometry(inputs: &[Option<Geometry>], index: usize) -> Result<&Geometry, String> {
+    inputs
+        .get(index)
+        .and_then(Option::as_ref)
+        .ok_or_else(|| format!("input {} is not connected", index))
+}
+
+fn detail(inputs: &[Option<Geometry>], index: usize, name: &str) -> Result<ExprValue, String> {
+    let geometry = input_geometry(inputs, index)?;
+    detail_of(geometry, name).map_err(|err| format!("input {} {}", index, err))
+}
+
+fn detail_of(geometry: &Geometry, name: &str) -> Result<ExprValue, String> {
+    let attr = geometry
+        .meshes
+        .iter()
+        .find_map(|mesh| mesh.attribute(AttributeDomain::Detail, name))
+        .or_else(|| {
+            geometry
+                .splats
+                .iter()
+                .find_map(|splats| splats.attribute(AttributeDomain::Detail, name))
+        })
+        .ok_or_else(|| format!("has no detail attribute '{}'", name))?;
+    let value = match attr {
+        AttributeRef::Float(values) => values.first().map(|v| ExprValue::Number(*v)),
+        AttributeRef::Int(values) => values.first().map(|v| ExprValue::Number(*v as f32)),
+        AttributeRef::Vec2(values) => values.first().map(|v| ExprValue::Vector(v.to_vec())),
+        AttributeRef::Vec3(values) => values.first().map(|v| ExprValue::Vector(v.to_vec())),
+        AttributeRef::Vec4(values) => values.first().map(|v| ExprValue::Vector(v.to_vec())),
+        AttributeRef::StringTable(values) => {
+            values.value(0).map(|v| ExprValue::String(v.to_string()))
+        }
+    };
+    value.ok_or_else(|| format!("detail attribute '{}' is empty", name))
+}
+
+fn bbox(name: &str, inputs: &[Option<Geometry>], index: usize) -> Result<ExprValue, String> {
+    let geometry = input_geometry(inputs, index)?;
+    bbox_of(name, geometry).map_err(|err| format!("input {} {}", index, err))
+}
+
+fn bbox_of(name: &str, geometry: &Geometry) -> Result<ExprValue, String> {
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+    let positions = geometry
+        .meshes
+        .iter()
+        .flat_map(|mesh| mesh.positions.iter())
+        .chain(geometry.splats.iter().flat_map(|splats| splats.positions.iter()));
+    let mut any = false;
+    for p in positions {
+        any = true;
+        for axis in 0..3 {
+            min[axis] = min[axis].min(p[axis]);
+            max[axis] = max[axis].max(p[axis]);
+        }
+    }
+    if !any {
+        return Err("has no points".to_string());
+    }
+    let value: Vec<f32> = match name {
+        "bbox_min" => min.to_vec(),
+        "bbox_max" => max.to_vec(),
+        "bbox_size" => (0..3).map(|axis| max[axis] - min[axis]).collect(),
+        _ => (0..3).map(|axis| (max[axis] + min[axis]) * 0.5).collect(),
+    };
+    Ok(ExprValue::Vector(value))
+}
+
+fn parse(source: &str) -> Result<Expr, String> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_expr()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("unexpected {:?}", token));
+    }
+    Ok(expr)
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0usize;
+    while i < chars.len() {
+        let c = chars[i];
+        let single = match c {
+            '+' => Some(Token::Plus),
+            '-' => Some(Token::Minus),
+            '*' => Some(Token::Star),
+            '/' => Some(Token::Slash),
+            '%' => Some(Token::Percent),
+            '(' => Some(Token::LParen),
+            ')' => Some(Token::RParen),
+            '[' => Some(Token::LBracket),
+            ']' => Some(Token::RBracket),
+            ',' => Some(Token::Comma),
+            _ => None,
+        };
+        if let Some(token) = single {
+            tokens.push(token);
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let start = i + 1;
+            let end = chars[start..]
+                .iter()
+                .position(|ch| *ch == c)
+                .map(|offset| start + offset)
+                .ok_or_else(|| "unterminated string".to_string())?;
+            tokens.push(Token::String(chars[start..end].iter().collect()));
+            i = end + 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = text
+                .parse::<f32>()
+                .map_err(|_| format!("invalid number '{}'", text))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_ascii_alphabetic() || c == '_' || c == '$' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            return Err(format!("unexpected character '{}'", c));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("expected {:?}, found {:?}", expected, token)),
+            None => Err(format!("expected {:?}", expected)),
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_term()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => BinaryOp::Add,
+                Some(Token::Minus) => BinaryOp::Sub,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.parse_term()?;
+            left = Expr::Binary {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => BinaryOp::Mul,
+                Some(Token::Slash) => BinaryOp::Div,
+                Some(Token::Percent) => BinaryOp::Rem,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            left = Expr::Binary {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some(Token::Minus) => {
+                self.pos += 1;
+                Ok(Expr::Neg(Box::new(self.parse_unary()?)))
+            }
+            Some(Token::Plus) => {
+                self.pos += 1;
+                self.parse_unary()
+            }
+            _ => self.parse_postfix(),
+        }
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_primary()?;
+        while self.peek() == Some(&Token::LBracket) {
+            self.pos += 1;
+            let index = self.parse_expr()?;
+            self.expect(Token::RBracket)?;
+            expr = Expr::Index {
+                expr: Box::new(expr),
+                index: Box::new(index),
+            };
+        }
+        Ok(expr)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Expr::Number(value)),
+            Some(Token::String(value)) => Ok(Expr::String(value)),
+            Some(Token::LParen) => {
+                let expr = self.parse_expr()?;
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::Ident(name)) => {
+                if self.peek() != Some(&Token::LParen) {
+                    return Ok(Expr::Ident(name));
+                }
+                self.pos += 1;
+                let mut args = Vec::new();
+                if self.peek() == Some(&Token::RParen) {
+                    self.pos += 1;
+                    return Ok(Expr::Call { name, args });
+                }
+                loop {
+                    args.push(self.parse_expr()?);
+                    match self.next() {
+                        Some(Token::Comma) => continue,
+                        Some(Token::RParen) => break,
+                        Some(token) => return Err(format!("unexpected {:?}", token)),
+                        None => return Err("missing ')'".to_string()),
+                    }
+                }
+                Ok(Expr::Call { name, args })
+            }
+            Some(token) => Err(format!("unexpected {:?}", token)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::{DirtyReason, EvalError};
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::{Graph, NodeDefinition, NodeId, ParamValue};
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    use super::{check_expression, resolve_channel};
+
+    fn box_and_transform() -> (Graph, NodeId, NodeId) {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        let from = graph.node(source).unwrap().outputs[0];
+        let to = graph.node(transform).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+        (graph, source, transform)
+    }
+
+    fn min_corner(state: &GeometryEvalState, node: NodeId) -> [f32; 3] {
+        state
+            .geometry_for_node(node)
+            .unwrap()
+            .merged_mesh()
+            .unwrap()
+            .bounds()
+            .unwrap()
+            .min
+    }
+
+    #[test]
+    fn parses_arithmetic_and_calls() {
+        assert!(check_expression("-(1 + 2) * 3 % 2 / ch('size')[0]").is_ok());
+        assert!(check_expression("bbox_size(0)[1] + detail(0, \"iteration\")").is_ok());
+        assert!(check_expression("1 +").is_err());
+        assert!(check_expression("ch(\"size\"").is_err());
+    }
+
+    #[test]
+    fn channel_references_follow_and_dirty_the_referenced_node() {
+        let (mut graph, source, transform) = box_and_transform();
+        assert_eq!(graph.node_path_name(source).as_deref(), Some("box_1"));
+        graph
+            .set_param_expression(
+                transform,
+                "translate",
+                Some("ch(\"../box_1/size\") * 0.5".to_string()),
+            )
+            .unwrap();
+
+        let mut state = GeometryEvalState::new();
+        evaluate_geometry_graph(&graph, transform, &mut state).unwrap();
+        assert_eq!(min_corner(&state, transform), [0.0, 0.0, 0.0]);
+
+        graph
+            .set_param(source, "size", ParamValue::Vec3([2.0, 2.0, 2.0]))
+            .unwrap();
+        let result = evaluate_geometry_graph(&graph, transform, &mut state).unwrap();
+        assert!(result
+            .report
+            .dirty
+            .iter()
+            .any(|dirty| dirty.node == transform
+                && dirty.reason == DirtyReason::ParamAndUpstreamChanged));
+        assert_eq!(min_corner(&state, transform), [0.0, 0.0, 0.0]);
+
+    }
+
+    #[test]
+    fn channel_paths_name_exactly_one_node() {
+        let mut graph = Graph::default();
+        let ids: Vec<NodeId> = (0..12)
+            .map(|idx| {
+                let name = if idx == 1 { "Box1" } else { "Box" };
+                graph.add_node(NodeDefinition {
+                    name: name.to_string(),
+                    ..node_definition(BuiltinNodeKind::Box)
+                })
+            })
+            .collect();
+        assert_eq!(graph.node_path_name(ids[1]).as_deref(), Some("box1_2"));
+        assert_eq!(graph.node_path_name(ids[11]).as_deref(), Some("box_12"));
+
+        let resolve = |path| resolve_channel(&graph, ids[0], path);
+        assert_eq!(resolve("../box_12/size"), Ok((ids[11], "size")));
+        assert_eq!(resolve("box1_2/size"), Ok((ids[1], "size")));
+        assert_eq!(resolve("./size"), Ok((ids[0], "size")));
+        assert!(resolve("box12/size").is_err());
+        assert!(resolve("../size").is_err());
+        assert!(resolve("../../box_12/size").is_err());
+        assert!(resolve("box_12/").is_err());
+    }
+
+    #[test]
+    fn reference_cycles_are_errors() {
+        let (mut graph, _, transform) = box_and_transform();
+        graph
+            .set_param_expression(transform, "translate", Some("ch('scale')".to_string()))
+            .unwrap();
+        graph
+            .set_param_expression(transform, "scale", Some("ch('translate')".to_string()))
+            .unwrap();
+        let result =
+            evaluate_geometry_graph(&graph, transform, &mut GeometryEvalState::new()).unwrap();
+        assert!(result.report.errors.iter().any(|err| matches!(
+            err,
+            EvalError::Node { message, .. } if message.contains("reference cycle")
+        )));
+    }
+
+    #[test]
+    fn bbox_functions_read_the_input_geometry() {
+        let (mut graph, _, transform) = box_and_transform();
+        graph
+            .set_param_expression(
+                transform,
+                "translate",
+                Some("vec3(0, -bbox_min()[1], 0)".to_string()),
+            )
+            .unwrap();
+        let mut state = GeometryEvalState::new();
+        evaluate_geometry_graph(&graph, transform, &mut state).unwrap();
+        assert_eq!(min_corner(&state, transform), [-0.5, 0.0, -0.5]);
+    }
+
+    #[test]
+    fn expression_errors_become_node_errors() {
+        let (mut graph, _, transform) = box_and_transform();
+        graph
+            .set_param_expression(transform, "translate", Some("nope(1)".to_string()))
+            .unwrap();
+        let result =
+            evaluate_geometry_graph(&graph, transform, &mut GeometryEvalState::new()).unwrap();
+        assert!(!result.report.output_valid);
+        assert!(result.report.errors.iter().any(|err| matches!(
+            err,
+            EvalError::Node { node, message }
+                if *node == transform && message.contains("expression for 'translate'")
+        )));
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::geometry::Geometry;
+use crate::geometry_eval::{
+    eval_failure_message, evaluate_geometry_graph_with_inputs, GeometryEvalState,
+};
+use crate::graph::{Graph, NodeId};
+use crate::nodes::for_each::{
+    iteration_count, loop_pieces, mode, tag_iteration, PieceValue, MODE_COUNT,
+};
+use crate::nodes_builtin::BuiltinNodeKind;
+use crate::progress::{current_cancel_token, CANCELLED_MESSAGE};
+
+/// The network a For-Each End loops over. Since the begin node and the whole body
+/// are upstream of the end node, editing any of them dirties the end node like any
+/// other upstream change.
+pub(crate) struct ForEachLoop {
+    begin: NodeId,
+    /// Node and output index wired into the end node.
+    body_end: (NodeId, usize),
+    /// Nodes upstream of the body that cook once, outside the loop.
+    outer: Vec<NodeId>,
+}
+
+impl ForEachLoop {
+    /// Finds the For-Each Begin matching `end`, skipping over nested loops.
+    pub(crate) fn find(graph: &Graph, end: NodeId) -> Result<Self, String> {
+        let body_end = graph
+            .input_sources(end)
+            .first()
+            .copied()
+            .flatten()
+            .ok_or_else(|| "For-Each End has no input".to_string())?;
+
+        let mut begin = None;
+        let mut visited = HashSet::new();
+        let mut stack = vec![(body_end.0, 0usize)];
+        while let Some((node_id, depth)) = stack.pop() {
+            if !visited.insert((node_id, depth)) {
+                continue;
+            }
+            let mut depth = depth;
+            match graph.node(node_id).and_then(|node| node.builtin_kind()) {
+                Some(BuiltinNodeKind::ForEachBegin) if depth == 0 => {
+                    begin = Some(node_id);
+                    break;
+                }
+                Some(BuiltinNodeKind::ForEachBegin) => depth -= 1,
+                Some(BuiltinNodeKind::ForEachEnd) => depth += 1,
+                _ => {}
+            }
+            for upstream in graph.upstream_nodes(node_id) {
+                stack.push((upstream, depth));
+            }
+        }
+        let begin =
+            begin.ok_or_else(|| "For-Each End has no matching For-Each Begin".to_string())?;
+
+        let ordered = graph
+            .topo_sort_from(body_end.0)
+            .map_err(|err| format!("{:?}", err))?;
+        let mut body = BTreeSet::new();
+        for node_id in &ordered {
+            let in_body = graph
+                .upstream_nodes(*node_id)
+                .iter()
+                .any(|upstream| *upstream == begin || body.contains(upstream));
+            if in_body && *node_id != begin {
+                body.insert(*node_id);
+            }
+        }
+        let outer = ordered
+            .into_iter()
+            .filter(|node_id| *node_id != begin && !body.contains(node_id))
+            .collect();
+        Ok(Self {
+            begin,
+            body_end,
+            outer,
+        })
+    }
+
+    /// Cooked outputs of the nodes the loop reads from outside its body.
+    pub(crate) fn outer_outputs(
+        &self,
+        outputs: &BTreeMap<NodeId, Vec<Geometry>>,
+    ) -> BTreeMap<NodeId, Vec<Geometry>> {
+        self.outer
+            .iter()
+            .filter_map(|node_id| Some((*node_id, outputs.get(node_id)?.clone())))
+            .collect()
+    }
+
+    /// Cooks the body once per iteration, with the begin node's output replaced by
+    /// the iteration's geometry. Piece results are merged; in count mode each
+    /// result feeds the next iteration and the last one is returned.
+    pub(crate) fn cook(
+        &self,
+        graph: &Graph,
+        outer_outputs: BTreeMap<NodeId, Vec<Geometry>>,
+        frame: f32,
+    ) -> Result<Geometry, String> {
+        let params = &graph
+            .node(self.begin)
+            .ok_or_else(|| "missing For-Each Begin".to_string())?
+            .params;
+        let begin_input = graph
+            .input_sources(self.begin)
+            .first()
+            .copied()
+            .flatten()
+            .and_then(|(node_id, index)| outer_outputs.get(&node_id)?.get(index).cloned())
+            .unwrap_or_default();
+
+        let cancel = current_cancel_token();
+        let mut injected = outer_outputs;
+        let mut cook_iteration = |geometry: Geometry| -> Result<Geometry, String> {
+            if cancel.as_ref().is_some_and(|cancel| cancel.is_cancelled()) {
+                return Err(CANCELLED_MESSAGE.to_string());
+            }
+            injected.insert(self.begin, vec![geometry]);
+            let mut state = GeometryEvalState::new();
+            state.eval.set_frame(frame);
+            let result = evaluate_geometry_graph_with_inputs(
+                graph,
+                self.body_end.0,
+                &mut state,
+                cancel.clone(),
+                &injected,
+            )
+            .map_err(|err| format!("{:?}", err))?;
+            if result.report.cancelled {
+                return Err(CANCELLED_MESSAGE.to_string());
+            }
+            if !result.report.output_valid {
+                return Err(eval_failure_message(graph, &result.report));
+            }
+            Ok(state
+                .geometry_for_output(self.body_end.0, self.body_end.1)
+                .cloned()
+                .unwrap_or_default())
+        };
+
+        if mode(params) == MODE_COUNT {
+            let count = iteration_count(params);
+            let mut geometry = begin_input;
+            for iteration in 0..count {
+                tag_iteration(
+                    &mut geometry,
+                    iteration,
+                    count,
+                    &PieceValue::Int(iteration as i32),
+                )?;
+                geometry = cook_iteration(geometry)?;
+            }
+            return Ok(geometry);
+        }
+
+        let mut merged = Geometry::default();
+        for piece in loop_pieces(params, &begin_input)? {
+            merged.append(cook_iteration(piece)?);
+        }
+        Ok(merged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attributes::{AttributeDomain, AttributeStorage, StringTableAttribute};
+    use crate::geometry::Geometry;
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::{Graph, NodeId, ParamValue};
+    use crate::mesh::Mesh;
+    use crate::nodes::for_each::MODE_COUNT;
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    fn link(graph: &mut Graph, from: NodeId, to: NodeId) {
+        let from = graph.node(from).unwrap().outputs[0];
+        let to = graph.node(to).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+    }
+
+    fn detail_int(geometry: &Geometry, name: &str) -> i32 {
+        match geometry.meshes[0].attributes.get(AttributeDomain::Detail, name) {
+            Some(AttributeStorage::Int(values)) => values[0],
+            other => panic!("unexpected {} attribute {:?}", name, other),
+        }
+    }
+
+    #[test]
+    fn loops_over_pieces_and_merges_results() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let begin = graph.add_node(node_definition(BuiltinNodeKind::ForEachBegin));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        let end = graph.add_node(node_definition(BuiltinNodeKind::ForEachEnd));
+        link(&mut graph, source, begin);
+        link(&mut graph, begin, transform);
+        link(&mut graph, transform, end);
+
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&graph, end, &mut state).unwrap();
+        assert!(result.output.is_none(), "missing piece attribute is an error");
+
+        graph
+            .set_param(begin, "mode", ParamValue::Int(1))
+            .unwrap();
+        let result = evaluate_geometry_graph(&graph, end, &mut state).unwrap();
+        let merged = result.output.unwrap();
+        let mesh = merged.merged_mesh().unwrap();
+        assert_eq!(mesh.face_counts.len(), 6);
+        assert_eq!(mesh.positions.len(), 24);
+        let begin_out = state.geometry_for_node(begin).unwrap();
+        assert_eq!(detail_int(begin_out, "iteration"), 0);
+        assert_eq!(detail_int(begin_out, "numiterations"), 6);
+
+        // Editing the body recooks the loop through the end node.
+        graph
+            .set_param(transform, "translate", ParamValue::Vec3([0.0, 1.0, 0.0]))
+            .unwrap();
+        let result = evaluate_geometry_graph(&graph, end, &mut state).unwrap();
+        assert!(result.report.computed.contains(&end));
+        assert!(!result.report.computed.contains(&source));
+        let mesh = result.output.unwrap().merged_mesh().unwrap();
+        let min_y = mesh.positions.iter().map(|p| p[1]).fold(f32::MAX, f32::min);
+        assert!((min_y - 0.5).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn splits_by_string_attribute() {
+        let mut mesh = Mesh::with_positions_faces(
+            vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [2.0, 0.0, 0.0],
+            ],
+            vec![0, 1, 2, 3, 1, 4, 2],
+            vec![4, 3],
+        );
+        mesh.set_attribute(
+            AttributeDomain::Primitive,
+            "name",
+            AttributeStorage::StringTable(StringTableAttribute::new(
+                vec!["a".to_string(), "b".to_string()],
+                vec![1, 0],
+            )),
+        )
+        .unwrap();
+        let pieces = crate::nodes::for_each::loop_pieces(
+            &crate::nodes::for_each::begin_default_params(),
+            &Geometry::with_mesh(mesh),
+        )
+        .unwrap();
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].meshes[0].face_counts.to_vec(), vec![4]);
+        assert_eq!(pieces[0].meshes[0].positions.len(), 4);
+        assert_eq!(pieces[1].meshes[0].face_counts.to_vec(), vec![3]);
+        assert_eq!(pieces[1].meshes[0].indices.to_vec(), vec![0, 2, 1]);
+        match pieces[1].meshes[0].attributes.get(AttributeDomain::Detail, "value") {
+            Some(AttributeStorage::StringTable(values)) => assert_eq!(values.value(0), Some("a")),
+            other => panic!("unexpected value attribute {:?}", other),
+        }
+    }
+
+    #[test]
+    fn count_mode_feeds_each_result_back() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let begin = graph.add_node(node_definition(BuiltinNodeKind::ForEachBegin));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        let end = graph.add_node(node_definition(BuiltinNodeKind::ForEachEnd));
+        link(&mut graph, source, begin);
+        link(&mut graph, begin, transform);
+        link(&mut graph, transform, end);
+        graph
+            .set_param(begin, "mode", ParamValue::Int(MODE_COUNT))
+            .unwrap();
+        graph
+            .set_param(begin, "iterations", ParamValue::Int(3))
+            .unwrap();
+        graph
+            .set_param(transform, "translate", ParamValue::Vec3([1.0, 0.0, 0.0]))
+            .unwrap();
+
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&graph, end, &mut state).unwrap();
+        let output = result.output.unwrap();
+        let mesh = output.merged_mesh().unwrap();
+        let min_x = mesh.positions.iter().map(|p| p[0]).fold(f32::MAX, f32::min);
+        assert!((min_x - 2.5).abs() < 1.0e-5);
+        assert_eq!(detail_int(&output, "iteration"), 2);
+    }
+}
@@ -1,13 +1,16 @@
 use std::collections::BTreeSet;
 
+use serde::{Deserialize, Serialize};
+
 use crate::attributes::{AttributeDomain, AttributeStorage, MeshAttributes, StringTableAttribute};
+use crate::buffer::{ByteCounter, SharedVec};
 use crate::material::MaterialLibrary;
 use crate::mesh::Mesh;
 use crate::curve::Curve;
 use crate::splat::SplatGeo;
 use crate::volume::Volume;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Geometry {
     pub meshes: Vec<Mesh>,
     pub splats: Vec<SplatGeo>,
@@ -60,7 +63,7 @@ impl Geometry {
             let _ = mesh.set_attribute(
                 AttributeDomain::Point,
                 "uv",
-                AttributeStorage::Vec2(uvs),
+                AttributeStorage::Vec2(uvs.into()),
             );
         }
         let indices = (0..point_count as u32).collect();
@@ -91,6 +94,29 @@ impl Geometry {
             && self.volumes.is_empty()
     }
 
+    pub fn approx_bytes(&self) -> usize {
+        let mut counter = ByteCounter::default();
+        self.count_bytes(&mut counter);
+        counter.total()
+    }
+
+    /// Adds this geometry's buffers to `counter`, so buffers shared with
+    /// geometry counted before are not counted again.
+    pub fn count_bytes(&self, counter: &mut ByteCounter) {
+        for mesh in &self.meshes {
+            mesh.count_bytes(counter);
+        }
+        for splats in &self.splats {
+            splats.count_bytes(counter);
+        }
+        for curve in &self.curves {
+            counter.add_bytes(curve.indices.len() * std::mem::size_of::<u32>());
+        }
+        for volume in &self.volumes {
+            counter.add_bytes(volume.values.len() * std::mem::size_of::<f32>());
+        }
+    }
+
     pub fn append(&mut self, mut other: Geometry) {
         let mut self_mesh = take_merged_mesh(&mut self.meshes);
         let mut other_mesh = take_merged_mesh(&mut other.meshes);
@@ -248,11 +274,11 @@ fn merge_splat_attributes(splats: &[SplatGeo]) -> MeshAttributes {
                 }
                 _ => {
                     let mut combined = match storage {
-                        AttributeStorage::Float(_) => AttributeStorage::Float(Vec::new()),
-                        AttributeStorage::Int(_) => AttributeStorage::Int(Vec::new()),
-                        AttributeStorage::Vec2(_) => AttributeStorage::Vec2(Vec::new()),
-                        AttributeStorage::Vec3(_) => AttributeStorage::Vec3(Vec::new()),
-                        AttributeStorage::Vec4(_) => AttributeStorage::Vec4(Vec::new()),
+                        AttributeStorage::Float(_) => AttributeStorage::Float(SharedVec::new()),
+                        AttributeStorage::Int(_) => AttributeStorage::Int(SharedVec::new()),
+                        AttributeStorage::Vec2(_) => AttributeStorage::Vec2(SharedVec::new()),
+                        AttributeStorage::Vec3(_) => AttributeStorage::Vec3(SharedVec::new()),
+                        AttributeStorage::Vec4(_) => AttributeStorage::Vec4(SharedVec::new()),
                         AttributeStorage::StringTable(_) => {
                             AttributeStorage::StringTable(StringTableAttribute::new(Vec::new(), Vec::new()))
                         }
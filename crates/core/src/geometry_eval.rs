@@ -1,17 +1,40 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
 
-use crate::eval::{evaluate_from_with_progress, EvalReport, EvalState};
-use crate::geometry::Geometry;
-use crate::graph::{Graph, GraphError, NodeId};
-use crate::nodes_builtin::{
-    builtin_kind_from_id, builtin_kind_from_name, compute_geometry_node, input_policy, InputPolicy,
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use crate::buffer::ByteCounter;
+use crate::cook_cache::{node_cache_keys, DiskCookCache};
+use crate::eval::{
+    active_upstream, evaluate_from_with_progress, node_dirty, EvalError, EvalReport, EvalState,
 };
-use crate::progress::ProgressSink;
+use crate::expression::{coerce_param, resolve_params};
+use crate::for_each::ForEachLoop;
+use crate::geometry::Geometry;
+use crate::graph::{Graph, GraphError, NodeId, ParamValue};
+use crate::node_registry::node_type_for;
+use crate::nodes::switch;
+use crate::nodes_builtin::{BuiltinNodeKind, InputPolicy};
+use crate::progress::{CancelToken, ProgressSink};
+use crate::subnet::{cook_subnet, SubnetEvalState};
 
 #[derive(Debug, Default)]
 pub struct GeometryEvalState {
     pub eval: EvalState,
-    outputs: BTreeMap<NodeId, Geometry>,
+    /// Cooked geometry per node, one entry per output pin.
+    outputs: BTreeMap<NodeId, Vec<Geometry>>,
+    /// Values cooked by value nodes, one entry per output pin. Kept only while the
+    /// node's (empty) geometry outputs are cached.
+    values: BTreeMap<NodeId, Vec<ParamValue>>,
+    last_used: BTreeMap<NodeId, u64>,
+    use_tick: u64,
+    cache_budget: Option<usize>,
+    disk_cache: Option<DiskCookCache>,
+    /// Inner states of subnet nodes.
+    subnets: BTreeMap<NodeId, SubnetEvalState>,
 }
 
 #[derive(Debug)]
@@ -25,8 +48,92 @@ impl GeometryEvalState {
         Self::default()
     }
 
+    /// Geometry on the first output of `node_id`.
     pub fn geometry_for_node(&self, node_id: NodeId) -> Option<&Geometry> {
-        self.outputs.get(&node_id)
+        self.geometry_for_output(node_id, 0)
+    }
+
+    pub fn geometry_for_output(&self, node_id: NodeId, index: usize) -> Option<&Geometry> {
+        self.outputs.get(&node_id)?.get(index)
+    }
+
+    /// Geometry on every output of `node_id`.
+    pub fn outputs_for_node(&self, node_id: NodeId) -> Option<&[Geometry]> {
+        self.outputs.get(&node_id).map(Vec::as_slice)
+    }
+
+    pub fn value_for_output(&self, node_id: NodeId, index: usize) -> Option<&ParamValue> {
+        self.values.get(&node_id)?.get(index)
+    }
+
+    /// Geometry on the output picked by the node's display flag.
+    pub fn displayed_geometry(&self, graph: &Graph, node_id: NodeId) -> Option<&Geometry> {
+        let index = graph.node(node_id).map_or(0, |node| node.display_output);
+        self.geometry_for_output(node_id, index)
+    }
+
+    /// Caps the approximate size of cached outputs; `None` keeps everything.
+    pub fn set_cache_budget(&mut self, budget_bytes: Option<usize>) {
+        self.cache_budget = budget_bytes;
+    }
+
+    pub fn cache_budget(&self) -> Option<usize> {
+        self.cache_budget
+    }
+
+    pub fn set_disk_cache(&mut self, cache: Option<DiskCookCache>) {
+        self.disk_cache = cache;
+    }
+
+    pub fn disk_cache(&self) -> Option<&DiskCookCache> {
+        self.disk_cache.as_ref()
+    }
+
+    /// Approximate size of the cached outputs. Buffers that nodes share with
+    /// their inputs count once.
+    pub fn cached_bytes(&self) -> usize {
+        let mut counter = ByteCounter::default();
+        for geometry in self.outputs.values().flatten() {
+            geometry.count_bytes(&mut counter);
+        }
+        counter.total()
+    }
+
+    fn track_cache_usage(&mut self, graph: &Graph, output: NodeId, report: &EvalReport) {
+        self.use_tick += 1;
+        for node_id in &report.ordered {
+            if self.outputs.contains_key(node_id) {
+                self.last_used.insert(*node_id, self.use_tick);
+            }
+        }
+        let outputs = &self.outputs;
+        self.last_used.retain(|node_id, _| outputs.contains_key(node_id));
+
+        self.enforce_cache_budget(graph, output);
+        self.eval.stats.cache_bytes = self.cached_bytes() as u64;
+    }
+
+    fn enforce_cache_budget(&mut self, graph: &Graph, output: NodeId) {
+        let Some(budget) = self.cache_budget else {
+            return;
+        };
+        let display = graph.display_node();
+        // Evicting a node frees only the buffers no other cached node shares, so
+        // the total is recounted after each eviction.
+        while self.cached_bytes() > budget {
+            let victim = self
+                .last_used
+                .iter()
+                .filter(|(node_id, _)| **node_id != output && Some(**node_id) != display)
+                .min_by_key(|(_, tick)| **tick)
+                .map(|(node_id, _)| *node_id);
+            let Some(node_id) = victim else {
+                break;
+            };
+            self.outputs.remove(&node_id);
+            self.last_used.remove(&node_id);
+            self.eval.mark_evicted(node_id);
+        }
     }
 }
 
@@ -35,7 +142,7 @@ pub fn evaluate_geometry_graph(
     output: NodeId,
     state: &mut GeometryEvalState,
 ) -> Result<GeometryEvalResult, GraphError> {
-    evaluate_geometry_graph_with_progress(graph, output, state, None)
+    evaluate_geometry_graph_with_progress(graph, output, state, None, None)
 }
 
 pub fn evaluate_geometry_graph_with_progress(
@@ -43,46 +150,138 @@ pub fn evaluate_geometry_graph_with_progress(
     output: NodeId,
     state: &mut GeometryEvalState,
     progress: Option<ProgressSink>,
+    cancel: Option<CancelToken>,
 ) -> Result<GeometryEvalResult, GraphError> {
-    let outputs = &mut state.outputs;
+    evaluate_geometry_graph_impl(graph, output, state, progress, cancel, &BTreeMap::new())
+}
+
+/// Evaluates with the outputs of the nodes in `injected` replaced by the given
+/// geometry, as used for subnet inputs and for-each iterations.
+pub(crate) fn evaluate_geometry_graph_with_inputs(
+    graph: &Graph,
+    output: NodeId,
+    state: &mut GeometryEvalState,
+    cancel: Option<CancelToken>,
+    injected: &BTreeMap<NodeId, Vec<Geometry>>,
+) -> Result<GeometryEvalResult, GraphError> {
+    evaluate_geometry_graph_impl(graph, output, state, None, cancel, injected)
+}
+
+/// Message for a nested evaluation that failed, naming the first failing node.
+pub(crate) fn eval_failure_message(graph: &Graph, report: &EvalReport) -> String {
+    report
+        .errors
+        .iter()
+        .find_map(|err| match err {
+            EvalError::Node { node, message } => {
+                let name = graph.node(*node).map_or("?", |node| node.name.as_str());
+                Some(format!("{}: {}", name, message))
+            }
+            EvalError::Upstream { .. } => None,
+        })
+        .unwrap_or_else(|| "inner network failed".to_string())
+}
+
+fn evaluate_geometry_graph_impl(
+    graph: &Graph,
+    output: NodeId,
+    state: &mut GeometryEvalState,
+    progress: Option<ProgressSink>,
+    cancel: Option<CancelToken>,
+    injected: &BTreeMap<NodeId, Vec<Geometry>>,
+) -> Result<GeometryEvalResult, GraphError> {
+    select_switch_inputs(graph, output, state, cancel.as_ref(), injected)?;
+    let selected_inputs: BTreeMap<NodeId, usize> = graph
+        .nodes()
+        .filter_map(|node| Some((node.id, state.eval.selected_input(node.id)?)))
+        .collect();
+    let frame = state.eval.frame();
+    let disk_keys = state
+        .disk_cache
+        .as_ref()
+        .map(|_| node_cache_keys(graph, output, frame))
+        .unwrap_or_default();
+    select_disk_nodes(graph, output, state, &disk_keys);
+    // Independent nodes cook concurrently, so outputs are shared behind a lock for
+    // the duration of the evaluation.
+    let shared_outputs = Mutex::new(std::mem::take(&mut state.outputs));
+    let shared_values = Mutex::new(std::mem::take(&mut state.values));
+    let subnet_states = Mutex::new(std::mem::take(&mut state.subnets));
+    let disk_cache = state.disk_cache.as_ref();
+    let disk_nodes = state.eval.disk_nodes().clone();
+    let disk_hits = Mutex::new(HashSet::new());
     let report = evaluate_from_with_progress(
         graph,
         output,
         &mut state.eval,
         progress,
+        cancel,
         |node_id, params| {
+        if let Some(geometries) = injected.get(&node_id) {
+            shared_outputs
+                .lock()
+                .expect("geometry outputs lock")
+                .insert(node_id, geometries.clone());
+            return Ok(());
+        }
         let node = graph
             .node(node_id)
             .ok_or_else(|| "missing node".to_string())?;
-        let kind = if node.kind_id.is_empty() {
-            builtin_kind_from_name(&node.name)
-        } else {
-            builtin_kind_from_id(&node.kind_id)
-                .or_else(|| builtin_kind_from_name(&node.name))
+        if let Some(locked) = &node.locked {
+            let mut geometries = locked.geometries.clone();
+            geometries.resize_with(node.outputs.len().max(1), Geometry::default);
+            shared_outputs
+                .lock()
+                .expect("geometry outputs lock")
+                .insert(node_id, geometries);
+            return Ok(());
+        }
+        if let Some((cache, key)) = disk_cache.zip(disk_nodes.get(&node_id)) {
+            // Its inputs were not cooked, so a vanished entry can't fall back.
+            let mut geometries = cache
+                .load(*key)
+                .ok_or_else(|| "cook cache entry went missing; cook again".to_string())?;
+            geometries.resize_with(node.outputs.len().max(1), Geometry::default);
+            disk_hits.lock().expect("disk hits lock").insert(node_id);
+            shared_outputs
+                .lock()
+                .expect("geometry outputs lock")
+                .insert(node_id, geometries);
+            return Ok(());
         }
-        .ok_or_else(|| {
+        let node_type = node_type_for(node).ok_or_else(|| {
             if node.kind_id.is_empty() {
                 format!("unknown node type {}", node.name)
             } else {
                 format!("unknown node kind {} ({})", node.kind_id, node.name)
             }
         })?;
+        let kind = node_type.builtin_kind();
 
+        let selected = selected_inputs.get(&node_id).copied();
         let mut input_geometries = Vec::with_capacity(node.inputs.len());
         let mut input_names = Vec::with_capacity(node.inputs.len());
-        for pin_id in &node.inputs {
+        for (idx, pin_id) in node.inputs.iter().enumerate() {
             let pin = graph
                 .pin(*pin_id)
                 .ok_or_else(|| "missing input pin".to_string())?;
             input_names.push(pin.name.clone());
-            let link = graph.input_link(*pin_id);
+            // Inputs that were not selected are not cooked.
+            let link = graph
+                .input_link(*pin_id)
+                .filter(|_| selected.is_none_or(|selected| selected == idx));
             let geometry = if let Some(link) = link {
                 let from_pin = graph
                     .pin(link.from)
                     .ok_or_else(|| "missing upstream pin".to_string())?;
                 let upstream_id = from_pin.node;
+                let output_index = graph
+                    .output_index(link.from)
+                    .ok_or_else(|| "missing upstream pin".to_string())?;
+                let outputs = shared_outputs.lock().expect("geometry outputs lock");
                 let geometry = outputs
                     .get(&upstream_id)
+                    .and_then(|geometries| geometries.get(output_index))
                     .ok_or_else(|| format!("missing upstream output {:?}", upstream_id))?;
                 Some(geometry.clone())
             } else {
@@ -90,18 +289,57 @@ pub fn evaluate_geometry_graph_with_progress(
             };
             input_geometries.push(geometry);
         }
+        let mut param_inputs = Vec::new();
+        for (key, source) in graph.param_input_sources(node_id) {
+            let Some((upstream_id, output_index)) = source else {
+                continue;
+            };
+            let value = shared_values
+                .lock()
+                .expect("values lock")
+                .get(&upstream_id)
+                .and_then(|values| values.get(output_index))
+                .cloned()
+                .ok_or_else(|| format!("input '{}' is not wired to a value output", key))?;
+            let value = coerce_param(node, &key, &value)?;
+            param_inputs.push((key, value));
+        }
 
         if node.bypass {
-            let geometry = input_geometries
+            // The first input passes through the first output; any other outputs
+            // are left empty.
+            let mut geometries = vec![Geometry::default(); node.outputs.len().max(1)];
+            geometries[0] = input_geometries
                 .first()
                 .cloned()
                 .flatten()
                 .unwrap_or_default();
-            outputs.insert(node_id, geometry);
+            shared_outputs
+                .lock()
+                .expect("geometry outputs lock")
+                .insert(node_id, geometries);
             return Ok(());
         }
 
-        let inputs = match input_policy(kind) {
+        let mut params = resolve_params(graph, node_id, params, &input_geometries, frame)?;
+        if !param_inputs.is_empty() {
+            // Linked values win over literals, keys and expressions.
+            params.to_mut().values.extend(param_inputs);
+        }
+        if kind == Some(BuiltinNodeKind::Switch) {
+            let index = selected.unwrap_or_default();
+            let geometry = input_geometries
+                .get(index)
+                .cloned()
+                .flatten()
+                .ok_or_else(|| format!("Switch input {} is not connected", index))?;
+            shared_outputs
+                .lock()
+                .expect("geometry outputs lock")
+                .insert(node_id, vec![geometry]);
+            return Ok(());
+        }
+        let inputs = match node_type.input_policy() {
             InputPolicy::None => Vec::new(),
             InputPolicy::RequireAll => {
                 let mut inputs = Vec::with_capacity(input_geometries.len());
@@ -123,7 +361,7 @@ pub fn evaluate_geometry_graph_with_progress(
                     let suffix = if min == 1 { "" } else { "s" };
                     return Err(format!(
                         "{} requires at least {} input{}",
-                        kind.name(),
+                        node_type.definition().name,
                         min,
                         suffix
                     ));
@@ -132,19 +370,99 @@ pub fn evaluate_geometry_graph_with_progress(
             }
         };
 
-        let geometry = compute_geometry_node(kind, params, &inputs)?;
-        outputs.insert(node_id, geometry);
+        if let Some(values) = node_type.compute_values(&params, &inputs) {
+            shared_values
+                .lock()
+                .expect("values lock")
+                .insert(node_id, values?);
+            shared_outputs
+                .lock()
+                .expect("geometry outputs lock")
+                .insert(node_id, vec![Geometry::default(); node.outputs.len().max(1)]);
+            return Ok(());
+        }
+
+        let disk_entry = disk_cache.zip(disk_keys.get(&node_id).copied());
+        let start = Instant::now();
+        let mut geometries = match kind {
+            Some(BuiltinNodeKind::ForEachEnd) => {
+                let for_each = ForEachLoop::find(graph, node_id)?;
+                // Snapshot what the loop reads so the lock is free while it cooks.
+                let outer_outputs = for_each
+                    .outer_outputs(&shared_outputs.lock().expect("geometry outputs lock"));
+                vec![for_each.cook(graph, outer_outputs, frame)?]
+            }
+            Some(BuiltinNodeKind::Subnet) => {
+                let subnet = node
+                    .subnet
+                    .as_deref()
+                    .ok_or_else(|| "subnet has no inner network".to_string())?;
+                // The lock is not held while cooking so other subnets can run.
+                let mut subnet_state = subnet_states
+                    .lock()
+                    .expect("subnet states lock")
+                    .remove(&node_id)
+                    .unwrap_or_default();
+                let result = cook_subnet(subnet, &params, &inputs, &mut subnet_state, frame);
+                subnet_states
+                    .lock()
+                    .expect("subnet states lock")
+                    .insert(node_id, subnet_state);
+                result?
+            }
+            _ => node_type.compute_demanded_geometry(
+                &params,
+                &inputs,
+                &graph.demanded_outputs(node_id),
+            )?,
+        };
+        geometries.resize_with(node.outputs.len().max(1), Geometry::default);
+        if let Some((cache, key)) = disk_entry {
+            if start.elapsed().as_secs_f32() * 1000.0 >= cache.min_cook_ms() {
+                if let Err(err) = cache.store(key, &geometries) {
+                    tracing::warn!("disk cook cache: failed to store {:?}: {}", node_id, err);
+                }
+            }
+        }
+        shared_outputs
+            .lock()
+            .expect("geometry outputs lock")
+            .insert(node_id, geometries);
         Ok(())
     },
-    )?;
+    );
+    state.outputs = shared_outputs
+        .into_inner()
+        .expect("geometry outputs lock");
+    state.values = shared_values.into_inner().expect("values lock");
+    state.subnets = subnet_states.into_inner().expect("subnet states lock");
+    state
+        .subnets
+        .retain(|node_id, _| graph.node(*node_id).is_some());
+    let mut report = report?;
+    record_geometry_sizes(graph, state, &mut report);
+    let disk_hits = disk_hits.into_inner().expect("disk hits lock");
+    report.disk_hits = report
+        .computed
+        .iter()
+        .copied()
+        .filter(|node_id| disk_hits.contains(node_id))
+        .collect();
+    if let Some(cache) = &state.disk_cache {
+        state.eval.stats.disk_hits = cache.hits();
+        state.eval.stats.disk_writes = cache.writes();
+    }
 
-    if !report.output_valid {
+    // Interrupted nodes never replaced their previous output, which still matches
+    // their cached signature, so a cancelled cook leaves the outputs map as is.
+    if !report.output_valid && !report.cancelled {
+        let outputs = &mut state.outputs;
         for err in &report.errors {
             match err {
-                crate::eval::EvalError::Node { node, .. } => {
+                EvalError::Node { node, .. } => {
                     outputs.remove(node);
                 }
-                crate::eval::EvalError::Upstream { node, upstream } => {
+                EvalError::Upstream { node, upstream } => {
                     outputs.remove(node);
                     for upstream_node in upstream {
                         outputs.remove(upstream_node);
@@ -153,15 +471,281 @@ pub fn evaluate_geometry_graph_with_progress(
             }
         }
         outputs.remove(&output);
+    }
+    state.track_cache_usage(graph, output, &report);
+    let outputs = &state.outputs;
+    state.values.retain(|node_id, _| outputs.contains_key(node_id));
+
+    if !report.output_valid {
         return Ok(GeometryEvalResult {
             report,
             output: None,
         });
     }
 
-    let output_geometry = outputs.get(&output).cloned();
+    let output_geometry = state.displayed_geometry(graph, output).cloned();
     Ok(GeometryEvalResult {
         report,
         output: output_geometry,
     })
 }
+
+fn record_geometry_sizes(graph: &Graph, state: &GeometryEvalState, report: &mut EvalReport) {
+    let output_bytes = |node_id: NodeId, index: Option<usize>| -> usize {
+        let Some(geometries) = state.outputs.get(&node_id) else {
+            return 0;
+        };
+        match index {
+            Some(index) => geometries.get(index).map_or(0, Geometry::approx_bytes),
+            None => geometries.iter().map(Geometry::approx_bytes).sum(),
+        }
+    };
+    for (node_id, entry) in &mut report.node_reports {
+        let selected = state.eval.selected_input(*node_id);
+        entry.output_bytes = output_bytes(*node_id, None);
+        entry.input_bytes = graph
+            .input_sources(*node_id)
+            .into_iter()
+            .enumerate()
+            .filter(|(idx, _)| selected.is_none_or(|selected| selected == *idx))
+            .filter_map(|(_, source)| source)
+            .map(|(upstream, index)| output_bytes(upstream, Some(index)))
+            .sum();
+    }
+}
+
+/// Picks the nodes that load from the disk cook cache instead of cooking their
+/// inputs, walking up from `output`. A node still clean in memory keeps its
+/// inputs, so storing its entry does not dirty it.
+fn select_disk_nodes(
+    graph: &Graph,
+    output: NodeId,
+    state: &mut GeometryEvalState,
+    keys: &HashMap<NodeId, u64>,
+) {
+    let Some(cache) = state.disk_cache.as_ref() else {
+        state.eval.retain_disk_nodes(|_| false);
+        return;
+    };
+    let Ok(ordered) = graph.topo_sort_from(output) else {
+        return;
+    };
+    // Start from the previous choice with fresh keys, so the dirty checks below
+    // see upstream nodes the way the cook will.
+    let previous: Vec<NodeId> = state.eval.disk_nodes().keys().copied().collect();
+    for node_id in previous {
+        state
+            .eval
+            .set_disk_key(node_id, keys.get(&node_id).copied());
+    }
+    let mut dirty = HashMap::new();
+    let mut needed = HashSet::from([output]);
+    let mut selected = HashSet::new();
+    for node_id in ordered.into_iter().rev() {
+        if !needed.contains(&node_id) {
+            continue;
+        }
+        let key = keys.get(&node_id).copied().filter(|_| {
+            graph
+                .node(node_id)
+                .is_some_and(|node| node.locked.is_none())
+        });
+        if let Some(key) = key {
+            let in_memory = state.outputs.contains_key(&node_id);
+            state.eval.set_disk_key(node_id, None);
+            dirty.remove(&node_id);
+            let clean = in_memory
+                && !node_dirty(graph, &state.eval, node_id, &mut dirty, &mut HashSet::new());
+            if !clean
+                && (cache.contains(key) || (in_memory && state.eval.cooked_from_disk(node_id, key)))
+            {
+                state.eval.set_disk_key(node_id, Some(key));
+                selected.insert(node_id);
+                continue;
+            }
+        }
+        needed.extend(active_upstream(graph, node_id, &state.eval));
+    }
+    state
+        .eval
+        .retain_disk_nodes(|node_id| selected.contains(&node_id));
+}
+
+/// Picks the input of every Switch that `output` depends on, so the evaluation
+/// only cooks the selected branches. A Switch reading its index from a detail
+/// attribute cooks its first input beforehand.
+fn select_switch_inputs(
+    graph: &Graph,
+    output: NodeId,
+    state: &mut GeometryEvalState,
+    cancel: Option<&CancelToken>,
+    injected: &BTreeMap<NodeId, Vec<Geometry>>,
+) -> Result<(), GraphError> {
+    let frame = state.eval.frame();
+    let mut visited = HashSet::new();
+    let mut stack = vec![output];
+    while let Some(node_id) = stack.pop() {
+        if !visited.insert(node_id) {
+            continue;
+        }
+        let Some(node) = graph.node(node_id) else {
+            continue;
+        };
+        // Locked nodes do not cook their inputs.
+        if node.locked.is_some() {
+            continue;
+        }
+        if node.builtin_kind() != Some(BuiltinNodeKind::Switch) {
+            stack.extend(graph.upstream_nodes(node_id));
+            continue;
+        }
+        let sources = graph.input_sources(node_id);
+        // A bypassed switch passes its first input through.
+        let index = if node.bypass {
+            0
+        } else {
+            // Errors in the index expression surface when the switch itself cooks.
+            let mut params = resolve_params(graph, node_id, &node.params, &[], frame)
+                .map(|params| params.into_owned())
+                .unwrap_or_else(|_| node.params.clone());
+            // Linked values are cooked up front so they can pick the branch too.
+            for (key, source) in graph.param_input_sources(node_id) {
+                let Some((source, output_index)) = source else {
+                    continue;
+                };
+                evaluate_geometry_graph_impl(graph, source, state, None, cancel.cloned(), injected)?;
+                if let Some(value) = state
+                    .value_for_output(source, output_index)
+                    .and_then(|value| coerce_param(node, &key, value).ok())
+                {
+                    params.values.insert(key, value);
+                }
+            }
+            let mut index = params.get_int("index", 0);
+            if switch::mode(&params) == switch::MODE_DETAIL_ATTRIBUTE {
+                if let Some((source, output_index)) = sources.first().copied().flatten() {
+                    evaluate_geometry_graph_impl(
+                        graph,
+                        source,
+                        state,
+                        None,
+                        cancel.cloned(),
+                        injected,
+                    )?;
+                    if let Some(value) = state
+                        .geometry_for_output(source, output_index)
+                        .and_then(|geometry| switch::detail_index(&params, geometry))
+                    {
+                        index = value;
+                    }
+                }
+            }
+            // The free pin after the connected inputs is never selected.
+            let input_count = sources
+                .iter()
+                .rposition(Option::is_some)
+                .map_or(1, |idx| idx + 1);
+            switch::clamp_index(index, input_count)
+        };
+        state.eval.select_input(node_id, index);
+        if let Some((source, _)) = sources.get(index).copied().flatten() {
+            stack.push(source);
+        }
+    }
+    state.eval.retain_selected_inputs(|node_id| {
+        graph
+            .node(node_id)
+            .is_some_and(|node| node.builtin_kind() == Some(BuiltinNodeKind::Switch))
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attributes::AttributeDomain;
+    use crate::graph::{Graph, ParamValue};
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    use super::{evaluate_geometry_graph, GeometryEvalState};
+
+    #[test]
+    fn downstream_nodes_read_the_linked_output() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let split = graph.add_node(node_definition(BuiltinNodeKind::Split));
+        let downstream = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        let source_out = graph.node(source).unwrap().outputs[0];
+        let split_in = graph.node(split).unwrap().inputs[0];
+        let matching = graph.node(split).unwrap().outputs[0];
+        let nonmatching = graph.node(split).unwrap().outputs[1];
+        let downstream_in = graph.node(downstream).unwrap().inputs[0];
+        graph.add_link(source_out, split_in).unwrap();
+        graph.add_link(matching, downstream_in).unwrap();
+
+        // An empty group sends everything to the matching output.
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&graph, downstream, &mut state).unwrap();
+        assert!(!result.output.unwrap().meshes.is_empty());
+        assert!(state.geometry_for_output(split, 1).unwrap().is_empty());
+
+        graph.remove_link_between(matching, downstream_in);
+        graph.add_link(nonmatching, downstream_in).unwrap();
+        let result = evaluate_geometry_graph(&graph, downstream, &mut state).unwrap();
+        assert!(result.report.computed.contains(&downstream));
+        assert!(!result.report.computed.contains(&split));
+        assert!(result.output.unwrap().meshes.is_empty());
+
+        graph.set_display_output(nonmatching).unwrap();
+        let result = evaluate_geometry_graph(&graph, split, &mut state).unwrap();
+        assert!(result.output.unwrap().is_empty());
+    }
+
+    #[test]
+    fn shared_buffers_count_once_in_the_cache() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let output = graph.add_node(node_definition(BuiltinNodeKind::Output));
+        let from = graph.node(source).unwrap().outputs[0];
+        let to = graph.node(output).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+
+        let mut state = GeometryEvalState::new();
+        evaluate_geometry_graph(&graph, output, &mut state).unwrap();
+        let box_bytes = state.geometry_for_node(source).unwrap().approx_bytes();
+        assert!(box_bytes > 0);
+        assert_eq!(state.cached_bytes(), box_bytes);
+
+        // Both nodes fit in the budget of one box, so nothing is evicted.
+        state.set_cache_budget(Some(box_bytes));
+        let result = evaluate_geometry_graph(&graph, output, &mut state).unwrap();
+        assert!(result.report.computed.is_empty());
+        assert!(state.geometry_for_node(source).is_some());
+    }
+    #[test]
+    fn cooked_outputs_share_unchanged_buffers() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let color = graph.add_node(node_definition(BuiltinNodeKind::Color));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        graph
+            .set_param(transform, "translate", ParamValue::Vec3([1.0, 0.0, 0.0]))
+            .unwrap();
+        for (from, to) in [(source, color), (color, transform)] {
+            let from = graph.node(from).unwrap().outputs[0];
+            let to = graph.node(to).unwrap().inputs[0];
+            graph.add_link(from, to).unwrap();
+        }
+
+        let mut state = GeometryEvalState::new();
+        evaluate_geometry_graph(&graph, transform, &mut state).unwrap();
+        let mesh = |node_id| &state.geometry_for_node(node_id).unwrap().meshes[0];
+        // Color only adds an attribute, so the topology and positions stay shared.
+        assert!(mesh(color).positions.ptr_eq(&mesh(source).positions));
+        assert!(mesh(color).indices.ptr_eq(&mesh(source).indices));
+        assert!(mesh(color).attribute(AttributeDomain::Point, "Cd").is_some());
+        // Moving the points copies the positions and nothing else.
+        assert!(!mesh(transform).positions.ptr_eq(&mesh(color).positions));
+        assert!(mesh(transform).indices.ptr_eq(&mesh(source).indices));
+        assert_ne!(mesh(transform).positions, mesh(source).positions);
+    }
+}
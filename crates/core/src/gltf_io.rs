@@ -2,6 +2,10 @@
 use std::path::Path;
 
 use crate::attributes::{AttributeDomain, AttributeRef, AttributeStorage, StringTableAttribute};
+use crate::buffer::SharedVec;
+use crate::curve::Curve;
+use crate::geometry::Geometry;
+use crate::material::MaterialLibrary;
 use crate::mesh::Mesh;
 
 pub fn load_gltf_mesh(path: &str) -> Result<Mesh, String> {
@@ -120,9 +124,10 @@ fn build_mesh_from_gltf(
 
     let mut mesh = Mesh::with_positions_indices(positions, indices);
     if include_normals && normals.len() == mesh.positions.len() {
-        mesh.normals = Some(normals);
+        mesh.normals = Some(normals.into());
     }
     if include_uvs && uvs.len() == mesh.positions.len() {
+        let uvs: SharedVec<[f32; 2]> = uvs.into();
         mesh.uvs = Some(uvs.clone());
         let _ = mesh.set_attribute(
             AttributeDomain::Point,
@@ -134,7 +139,7 @@ fn build_mesh_from_gltf(
         let _ = mesh.set_attribute(
             AttributeDomain::Point,
             "Cd",
-            AttributeStorage::Vec3(colors),
+            AttributeStorage::Vec3(colors.into()),
         );
     }
     if !material_indices.is_empty() && material_indices.len() == mesh.indices.len() / 3 {
@@ -153,9 +158,12 @@ fn build_mesh_from_gltf(
     Ok(mesh)
 }
 
-pub fn write_gltf(path: &str, mesh: &Mesh) -> Result<(), String> {
-    let export = build_export_mesh(mesh)?;
-    let (json, bin) = build_gltf_payload(&export, Path::new(path))?;
+/// Writes the merged mesh as one primitive per `material` value, with the
+/// geometry's material library as metallic-roughness materials and their base
+/// color textures embedded in the binary buffer. Curves become a separate
+/// line mesh. Extra float point attributes are written as `_name` accessors.
+pub fn write_gltf(path: &str, geometry: &Geometry) -> Result<(), String> {
+    let (json, bin) = build_gltf_payload(geometry, Path::new(path))?;
     let json_bytes = serde_json::to_vec(&json).map_err(|err| err.to_string())?;
     let extension = Path::new(path)
         .extension()
@@ -191,10 +199,24 @@ pub fn write_gltf(path: &str, mesh: &Mesh) -> Result<(), String> {
 
 struct ExportMesh {
     positions: Vec<[f32; 3]>,
-    indices: Vec<u32>,
     normals: Option<Vec<[f32; 3]>>,
     uvs: Option<Vec<[f32; 2]>>,
     colors: Option<Vec<[f32; 3]>>,
+    attributes: Vec<ExportAttribute>,
+    /// Triangle indices grouped by material name, in order of first use.
+    primitives: Vec<(Option<String>, Vec<u32>)>,
+}
+
+struct ExportAttribute {
+    name: String,
+    components: usize,
+    values: Vec<f32>,
+}
+
+struct ExportCurves {
+    positions: Vec<[f32; 3]>,
+    colors: Option<Vec<[f32; 3]>>,
+    indices: Vec<u32>,
 }
 
 fn build_export_mesh(mesh: &Mesh) -> Result<ExportMesh, String> {
@@ -202,6 +224,7 @@ fn build_export_mesh(mesh: &Mesh) -> Result<ExportMesh, String> {
     let vertex_uvs = vertex_uvs(mesh);
     let point_colors = point_colors(mesh);
     let vertex_colors = vertex_colors(mesh);
+    let point_attributes = point_attributes(mesh);
     let corner_normals = mesh
         .corner_normals
         .as_ref()
@@ -213,27 +236,39 @@ fn build_export_mesh(mesh: &Mesh) -> Result<ExportMesh, String> {
     let needs_expand =
         corner_normals.is_some() || vertex_uvs.is_some() || vertex_colors.is_some();
 
-    let (tri_indices, tri_corners) = if mesh.indices.is_empty() {
+    let (tri_indices, tri_corners, tri_faces) = if mesh.indices.is_empty() {
         if !mesh.positions.len().is_multiple_of(3) {
             return Err("Mesh has no indices and non-triangular vertex count".to_string());
         }
         let indices = (0..mesh.positions.len() as u32).collect::<Vec<_>>();
         let corners = (0..mesh.positions.len()).collect::<Vec<_>>();
-        (indices, corners)
+        (indices, corners, Vec::new())
     } else {
         let triangulation = mesh.triangulate();
         if triangulation.indices.is_empty() {
             return Err("Mesh has no triangles to export".to_string());
         }
-        (triangulation.indices, triangulation.corner_indices)
+        (
+            triangulation.indices,
+            triangulation.corner_indices,
+            triangulation.tri_to_face,
+        )
     };
 
-    if needs_expand {
+    let mut export = if needs_expand {
         let mut positions = Vec::with_capacity(tri_indices.len());
         let mut indices = Vec::with_capacity(tri_indices.len());
         let mut normals = Vec::new();
         let mut uvs = Vec::new();
         let mut colors = Vec::new();
+        let mut attributes: Vec<ExportAttribute> = point_attributes
+            .iter()
+            .map(|attribute| ExportAttribute {
+                name: attribute.name.clone(),
+                components: attribute.components,
+                values: Vec::with_capacity(tri_indices.len() * attribute.components),
+            })
+            .collect();
 
         for (corner_idx, &idx) in tri_indices.iter().enumerate() {
             let pos = mesh
@@ -270,31 +305,139 @@ fn build_export_mesh(mesh: &Mesh) -> Result<ExportMesh, String> {
                     colors.push(*color);
                 }
             }
+            for (source, target) in point_attributes.iter().zip(attributes.iter_mut()) {
+                let start = idx as usize * source.components;
+                target
+                    .values
+                    .extend_from_slice(&source.values[start..start + source.components]);
+            }
             indices.push(corner_idx as u32);
         }
 
-        Ok(ExportMesh {
+        ExportMesh {
             positions,
-            indices,
             normals: if normals.is_empty() { None } else { Some(normals) },
             uvs: if uvs.is_empty() { None } else { Some(uvs) },
             colors: if colors.is_empty() { None } else { Some(colors) },
-        })
+            attributes,
+            primitives: vec![(None, indices)],
+        }
     } else {
-        Ok(ExportMesh {
-            positions: mesh.positions.clone(),
-            indices: tri_indices,
-            normals: point_normals.cloned(),
+        ExportMesh {
+            positions: mesh.positions.to_vec(),
+            normals: point_normals.map(|normals| normals.to_vec()),
             uvs: point_uvs,
             colors: point_colors,
-        })
+            attributes: point_attributes,
+            primitives: vec![(None, tri_indices)],
+        }
+    };
+
+    if let Some(materials) = face_materials(mesh).filter(|_| !tri_faces.is_empty()) {
+        let (_, indices) = export.primitives.pop().unwrap_or_default();
+        let mut lookup: HashMap<&str, usize> = HashMap::new();
+        for (tri, face) in indices.chunks_exact(3).zip(tri_faces.iter()) {
+            let name = materials.value(*face).unwrap_or("");
+            let slot = *lookup.entry(name).or_insert_with(|| {
+                let material = (!name.trim().is_empty()).then(|| name.to_string());
+                export.primitives.push((material, Vec::new()));
+                export.primitives.len() - 1
+            });
+            export.primitives[slot].1.extend_from_slice(tri);
+        }
+    }
+    Ok(export)
+}
+
+/// Line segments for every curve, with curve points compacted out of the mesh.
+fn build_export_curves(mesh: Option<&Mesh>, curves: &[Curve]) -> Option<ExportCurves> {
+    let mesh = mesh?;
+    let colors = point_colors(mesh);
+    let mut remap: HashMap<u32, u32> = HashMap::new();
+    let mut export = ExportCurves {
+        positions: Vec::new(),
+        colors: colors.as_ref().map(|_| Vec::new()),
+        indices: Vec::new(),
+    };
+    for curve in curves {
+        let mut points = Vec::with_capacity(curve.indices.len());
+        for &index in &curve.indices {
+            let Some(position) = mesh.positions.get(index as usize) else {
+                continue;
+            };
+            let mapped = *remap.entry(index).or_insert_with(|| {
+                export.positions.push(*position);
+                if let (Some(out), Some(colors)) = (export.colors.as_mut(), colors.as_ref()) {
+                    out.push(colors[index as usize]);
+                }
+                export.positions.len() as u32 - 1
+            });
+            points.push(mapped);
+        }
+        for segment in points.windows(2) {
+            export.indices.extend_from_slice(segment);
+        }
+        if curve.closed && points.len() > 2 {
+            export
+                .indices
+                .extend_from_slice(&[points[points.len() - 1], points[0]]);
+        }
     }
+    if export.indices.is_empty() {
+        None
+    } else {
+        Some(export)
+    }
+}
+
+fn face_materials(mesh: &Mesh) -> Option<&StringTableAttribute> {
+    let AttributeRef::StringTable(table) =
+        mesh.attribute(AttributeDomain::Primitive, "material")?
+    else {
+        return None;
+    };
+    (table.indices.len() == mesh.face_count()).then_some(table)
+}
+
+/// Point attributes other than `Cd` and `uv`, as floats; glTF has no 32-bit
+/// integer vertex attributes, so ints are widened to float.
+fn point_attributes(mesh: &Mesh) -> Vec<ExportAttribute> {
+    let mut names: Vec<&String> = mesh
+        .attributes
+        .map(AttributeDomain::Point)
+        .keys()
+        .filter(|name| !matches!(name.as_str(), "Cd" | "uv"))
+        .collect();
+    names.sort();
+    let mut out = Vec::new();
+    for name in names {
+        let Some(attribute) = mesh.attribute(AttributeDomain::Point, name) else {
+            continue;
+        };
+        let (components, values): (usize, Vec<f32>) = match attribute {
+            AttributeRef::Float(values) => (1, values.to_vec()),
+            AttributeRef::Int(values) => (1, values.iter().map(|value| *value as f32).collect()),
+            AttributeRef::Vec2(values) => (2, values.iter().flatten().copied().collect()),
+            AttributeRef::Vec3(values) => (3, values.iter().flatten().copied().collect()),
+            AttributeRef::Vec4(values) => (4, values.iter().flatten().copied().collect()),
+            AttributeRef::StringTable(_) => continue,
+        };
+        if values.len() != mesh.positions.len() * components {
+            continue;
+        }
+        out.push(ExportAttribute {
+            name: name.clone(),
+            components,
+            values,
+        });
+    }
+    out
 }
 
 fn point_uvs(mesh: &Mesh) -> Option<Vec<[f32; 2]>> {
     if let Some(uvs) = &mesh.uvs {
         if uvs.len() == mesh.positions.len() {
-            return Some(uvs.clone());
+            return Some(uvs.to_vec());
         }
     }
     if let Some(AttributeRef::Vec2(values)) = mesh.attribute(AttributeDomain::Point, "uv") {
@@ -332,59 +475,49 @@ fn vertex_colors(mesh: &Mesh) -> Option<Vec<[f32; 3]>> {
     None
 }
 
+#[derive(Default)]
+struct GltfBuilder {
+    buffer: Vec<u8>,
+    buffer_views: Vec<serde_json::Value>,
+    accessors: Vec<serde_json::Value>,
+    materials: Vec<serde_json::Value>,
+    material_lookup: HashMap<String, usize>,
+    textures: Vec<serde_json::Value>,
+    images: Vec<serde_json::Value>,
+    texture_lookup: HashMap<String, Option<usize>>,
+}
+
 fn build_gltf_payload(
-    mesh: &ExportMesh,
+    geometry: &Geometry,
     path: &Path,
 ) -> Result<(serde_json::Value, Vec<u8>), String> {
-    let mut buffer = Vec::new();
-    let mut buffer_views = Vec::new();
-    let mut accessors = Vec::new();
-    let mut attributes = serde_json::Map::new();
-
-    let pos_view = push_vec3(&mut buffer, &mut buffer_views, &mesh.positions, 34962);
-    let (pos_min, pos_max) = min_max_vec3(&mesh.positions);
-    let pos_accessor = push_accessor(
-        &mut accessors,
-        pos_view,
-        5126,
-        mesh.positions.len(),
-        "VEC3",
-        Some(pos_min),
-        Some(pos_max),
-    );
-    attributes.insert("POSITION".to_string(), serde_json::json!(pos_accessor));
-
-    if let Some(normals) = &mesh.normals {
-        let normal_view = push_vec3(&mut buffer, &mut buffer_views, normals, 34962);
-        let normal_accessor =
-            push_accessor(&mut accessors, normal_view, 5126, normals.len(), "VEC3", None, None);
-        attributes.insert("NORMAL".to_string(), serde_json::json!(normal_accessor));
+    let mesh = geometry.merged_mesh();
+    let curves = build_export_curves(mesh.as_ref(), &geometry.curves);
+    let triangles = match mesh.as_ref() {
+        // Curve-only geometry keeps its points in a mesh without faces.
+        Some(mesh) if !(mesh.indices.is_empty() && curves.is_some()) => {
+            Some(build_export_mesh(mesh)?)
+        }
+        _ => None,
+    };
+    if triangles.is_none() && curves.is_none() {
+        return Err("GLTF export requires mesh or curve output".to_string());
+    }
+
+    let mut builder = GltfBuilder::default();
+    let mut meshes = Vec::new();
+    if let Some(mesh) = &triangles {
+        meshes.push(serde_json::json!({
+            "name": "mesh",
+            "primitives": builder.push_mesh_primitives(mesh, &geometry.materials)?
+        }));
+    }
+    if let Some(curves) = &curves {
+        meshes.push(serde_json::json!({
+            "name": "curves",
+            "primitives": [builder.push_curve_primitive(curves)?]
+        }));
     }
-    if let Some(uvs) = &mesh.uvs {
-        let uv_view = push_vec2(&mut buffer, &mut buffer_views, uvs, 34962);
-        let uv_accessor =
-            push_accessor(&mut accessors, uv_view, 5126, uvs.len(), "VEC2", None, None);
-        attributes.insert("TEXCOORD_0".to_string(), serde_json::json!(uv_accessor));
-    }
-    if let Some(colors) = &mesh.colors {
-        let color_view = push_vec3(&mut buffer, &mut buffer_views, colors, 34962);
-        let color_accessor =
-            push_accessor(&mut accessors, color_view, 5126, colors.len(), "VEC3", None, None);
-        attributes.insert("COLOR_0".to_string(), serde_json::json!(color_accessor));
-    }
-
-    let (index_bytes, index_component_type) =
-        encode_indices(&mesh.indices, mesh.positions.len())?;
-    let index_view = push_bytes(&mut buffer, &mut buffer_views, &index_bytes, 34963);
-    let index_accessor = push_accessor(
-        &mut accessors,
-        index_view,
-        index_component_type,
-        mesh.indices.len(),
-        "SCALAR",
-        None,
-        None,
-    );
 
     let bin_uri = if path
         .extension()
@@ -404,44 +537,290 @@ fn build_gltf_payload(
 
     let buffer_obj = if let Some(uri) = bin_uri {
         serde_json::json!({
-            "byteLength": buffer.len(),
+            "byteLength": builder.buffer.len(),
             "uri": uri
         })
     } else {
         serde_json::json!({
-            "byteLength": buffer.len()
+            "byteLength": builder.buffer.len()
         })
     };
 
-    let gltf = serde_json::json!({
+    let nodes: Vec<serde_json::Value> = (0..meshes.len())
+        .map(|index| serde_json::json!({ "mesh": index }))
+        .collect();
+    let mut gltf = serde_json::json!({
         "asset": {
             "version": "2.0",
             "generator": "Lobedo"
         },
         "scenes": [
-            { "nodes": [0] }
+            { "nodes": (0..nodes.len()).collect::<Vec<_>>() }
         ],
         "scene": 0,
-        "nodes": [
-            { "mesh": 0 }
-        ],
-        "meshes": [
-            {
-                "primitives": [
-                    {
-                        "attributes": attributes,
-                        "indices": index_accessor,
-                        "mode": 4
-                    }
-                ]
-            }
-        ],
+        "nodes": nodes,
+        "meshes": meshes,
         "buffers": [buffer_obj],
-        "bufferViews": buffer_views,
-        "accessors": accessors
+        "bufferViews": builder.buffer_views,
+        "accessors": builder.accessors
     });
+    // glTF forbids empty top-level arrays.
+    for (key, values) in [
+        ("materials", builder.materials),
+        ("textures", builder.textures),
+        ("images", builder.images),
+    ] {
+        if !values.is_empty() {
+            gltf[key] = serde_json::Value::Array(values);
+        }
+    }
+
+    Ok((gltf, builder.buffer))
+}
+
+impl GltfBuilder {
+    fn push_mesh_primitives(
+        &mut self,
+        mesh: &ExportMesh,
+        library: &MaterialLibrary,
+    ) -> Result<Vec<serde_json::Value>, String> {
+        let mut attributes = serde_json::Map::new();
+        let pos_view = push_vec3(
+            &mut self.buffer,
+            &mut self.buffer_views,
+            &mesh.positions,
+            34962,
+        );
+        let (pos_min, pos_max) = min_max_vec3(&mesh.positions);
+        let pos_accessor = push_accessor(
+            &mut self.accessors,
+            pos_view,
+            5126,
+            mesh.positions.len(),
+            "VEC3",
+            Some(pos_min),
+            Some(pos_max),
+        );
+        attributes.insert("POSITION".to_string(), serde_json::json!(pos_accessor));
+
+        if let Some(normals) = &mesh.normals {
+            let normal_view = push_vec3(&mut self.buffer, &mut self.buffer_views, normals, 34962);
+            let normal_accessor = push_accessor(
+                &mut self.accessors,
+                normal_view,
+                5126,
+                normals.len(),
+                "VEC3",
+                None,
+                None,
+            );
+            attributes.insert("NORMAL".to_string(), serde_json::json!(normal_accessor));
+        }
+        if let Some(uvs) = &mesh.uvs {
+            let uv_view = push_vec2(&mut self.buffer, &mut self.buffer_views, uvs, 34962);
+            let uv_accessor = push_accessor(
+                &mut self.accessors,
+                uv_view,
+                5126,
+                uvs.len(),
+                "VEC2",
+                None,
+                None,
+            );
+            attributes.insert("TEXCOORD_0".to_string(), serde_json::json!(uv_accessor));
+        }
+        if let Some(colors) = &mesh.colors {
+            let color_accessor = self.push_colors(colors);
+            attributes.insert("COLOR_0".to_string(), serde_json::json!(color_accessor));
+        }
+        for attribute in &mesh.attributes {
+            let view = push_f32(
+                &mut self.buffer,
+                &mut self.buffer_views,
+                &attribute.values,
+                34962,
+            );
+            let ty = match attribute.components {
+                1 => "SCALAR",
+                2 => "VEC2",
+                3 => "VEC3",
+                _ => "VEC4",
+            };
+            let accessor = push_accessor(
+                &mut self.accessors,
+                view,
+                5126,
+                mesh.positions.len(),
+                ty,
+                None,
+                None,
+            );
+            attributes.insert(format!("_{}", attribute.name), serde_json::json!(accessor));
+        }
+
+        let mut primitives = Vec::with_capacity(mesh.primitives.len());
+        for (material, indices) in &mesh.primitives {
+            let index_accessor = self.push_indices(indices, mesh.positions.len())?;
+            let mut primitive = serde_json::json!({
+                "attributes": attributes,
+                "indices": index_accessor,
+                "mode": 4
+            });
+            if let Some(name) = material {
+                primitive["material"] = serde_json::json!(self.material_index(name, library));
+            }
+            primitives.push(primitive);
+        }
+        Ok(primitives)
+    }
 
-    Ok((gltf, buffer))
+    fn push_curve_primitive(&mut self, curves: &ExportCurves) -> Result<serde_json::Value, String> {
+        let mut attributes = serde_json::Map::new();
+        let view = push_vec3(
+            &mut self.buffer,
+            &mut self.buffer_views,
+            &curves.positions,
+            34962,
+        );
+        let (min, max) = min_max_vec3(&curves.positions);
+        let accessor = push_accessor(
+            &mut self.accessors,
+            view,
+            5126,
+            curves.positions.len(),
+            "VEC3",
+            Some(min),
+            Some(max),
+        );
+        attributes.insert("POSITION".to_string(), serde_json::json!(accessor));
+        if let Some(colors) = &curves.colors {
+            let color_accessor = self.push_colors(colors);
+            attributes.insert("COLOR_0".to_string(), serde_json::json!(color_accessor));
+        }
+        let index_accessor = self.push_indices(&curves.indices, curves.positions.len())?;
+        Ok(serde_json::json!({
+            "attributes": attributes,
+            "indices": index_accessor,
+            "mode": 1
+        }))
+    }
+
+    fn push_colors(&mut self, colors: &[[f32; 3]]) -> usize {
+        let view = push_vec3(&mut self.buffer, &mut self.buffer_views, colors, 34962);
+        push_accessor(
+            &mut self.accessors,
+            view,
+            5126,
+            colors.len(),
+            "VEC3",
+            None,
+            None,
+        )
+    }
+
+    fn push_indices(&mut self, indices: &[u32], vertex_count: usize) -> Result<usize, String> {
+        let (bytes, component_type) = encode_indices(indices, vertex_count)?;
+        let view = push_bytes(
+            &mut self.buffer,
+            &mut self.buffer_views,
+            &bytes,
+            Some(34963),
+        );
+        Ok(push_accessor(
+            &mut self.accessors,
+            view,
+            component_type,
+            indices.len(),
+            "SCALAR",
+            None,
+            None,
+        ))
+    }
+
+    /// Index of the glTF material for `name`, added on first use. Names missing
+    /// from the library get a named default material.
+    fn material_index(&mut self, name: &str, library: &MaterialLibrary) -> usize {
+        if let Some(&index) = self.material_lookup.get(name) {
+            return index;
+        }
+        let mut material = serde_json::json!({ "name": name });
+        if let Some(source) = library.get(name) {
+            let [r, g, b] = source.base_color;
+            let mut pbr = serde_json::json!({
+                "baseColorFactor": [r, g, b, 1.0],
+                "metallicFactor": source.metallic.clamp(0.0, 1.0),
+                "roughnessFactor": source.roughness.clamp(0.0, 1.0)
+            });
+            if let Some(texture) = source
+                .base_color_texture
+                .as_deref()
+                .and_then(|path| self.texture_index(path))
+            {
+                pbr["baseColorTexture"] = serde_json::json!({ "index": texture });
+            }
+            material["pbrMetallicRoughness"] = pbr;
+        }
+        self.materials.push(material);
+        self.material_lookup
+            .insert(name.to_string(), self.materials.len() - 1);
+        self.materials.len() - 1
+    }
+
+    /// Embeds a PNG or JPEG texture in the buffer. Unreadable or unsupported
+    /// images are skipped with a warning so the geometry still exports.
+    fn texture_index(&mut self, path: &str) -> Option<usize> {
+        if let Some(index) = self.texture_lookup.get(path) {
+            return *index;
+        }
+        let index = match load_texture_bytes(path) {
+            Some(bytes) => match image_mime_type(&bytes) {
+                Some(mime_type) => {
+                    let view = push_bytes(&mut self.buffer, &mut self.buffer_views, &bytes, None);
+                    self.images.push(serde_json::json!({
+                        "bufferView": view,
+                        "mimeType": mime_type
+                    }));
+                    self.textures
+                        .push(serde_json::json!({ "source": self.images.len() - 1 }));
+                    Some(self.textures.len() - 1)
+                }
+                None => {
+                    tracing::warn!("glTF export: {path} is not a PNG or JPEG; texture skipped");
+                    None
+                }
+            },
+            None => {
+                tracing::warn!("glTF export: failed to read texture {path}");
+                None
+            }
+        };
+        self.texture_lookup.insert(path.to_string(), index);
+        index
+    }
+}
+
+fn load_texture_bytes(path: &str) -> Option<Vec<u8>> {
+    if let Some(bytes) = crate::assets::load_bytes(path) {
+        return Some(bytes);
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::fs::read(path).ok()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+}
+
+fn image_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        Some("image/jpeg")
+    } else {
+        None
+    }
 }
 
 fn push_vec3(
@@ -496,18 +875,20 @@ fn push_bytes(
     buffer: &mut Vec<u8>,
     buffer_views: &mut Vec<serde_json::Value>,
     data: &[u8],
-    target: u32,
+    target: Option<u32>,
 ) -> usize {
     align_to_four(buffer);
     let offset = buffer.len();
     buffer.extend_from_slice(data);
     let length = buffer.len() - offset;
-    let view = serde_json::json!({
+    let mut view = serde_json::json!({
         "buffer": 0,
         "byteOffset": offset,
-        "byteLength": length,
-        "target": target
+        "byteLength": length
     });
+    if let Some(target) = target {
+        view["target"] = serde_json::json!(target);
+    }
     buffer_views.push(view);
     buffer_views.len() - 1
 }
@@ -570,3 +951,84 @@ fn align_to_four(buffer: &mut Vec<u8>) {
     buffer.extend(std::iter::repeat_n(0u8, padding));
 }
 
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::build_gltf_payload;
+    use crate::attributes::{AttributeDomain, AttributeStorage, StringTableAttribute};
+    use crate::curve::Curve;
+    use crate::geometry::Geometry;
+    use crate::material::Material;
+    use crate::mesh::make_box;
+
+    #[test]
+    fn exports_materials_textures_attributes_and_curves() {
+        let mut mesh = make_box([1.0, 1.0, 1.0]);
+        let faces = mesh.face_count();
+        let materials = (0..faces).map(|face| (face % 2) as u32).collect();
+        mesh.set_attribute(
+            AttributeDomain::Primitive,
+            "material",
+            AttributeStorage::StringTable(StringTableAttribute::new(
+                vec!["red".to_string(), "plain".to_string()],
+                materials,
+            )),
+        )
+        .expect("material");
+        let weights = (0..mesh.positions.len()).map(|idx| idx as f32).collect();
+        mesh.set_attribute(
+            AttributeDomain::Point,
+            "weight",
+            AttributeStorage::Float(weights),
+        )
+        .expect("weight");
+
+        let mut geometry = Geometry::with_mesh(mesh);
+        geometry.curves.push(Curve::new(vec![0, 1, 2], true));
+        let texture = crate::assets::store_bytes(
+            "red.png".to_string(),
+            b"\x89PNG\r\n\x1a\nnot really an image".to_vec(),
+        );
+        let mut red = Material::new("red".to_string());
+        red.base_color = [1.0, 0.0, 0.0];
+        red.metallic = 0.25;
+        red.base_color_texture = Some(texture);
+        geometry.materials.insert(red);
+
+        let (json, bin) = build_gltf_payload(&geometry, Path::new("out.glb")).expect("payload");
+        let json_bytes = serde_json::to_vec(&json).expect("json");
+        let glb = gltf::binary::Glb {
+            header: gltf::binary::Header {
+                magic: *b"glTF",
+                version: 2,
+                length: 0,
+            },
+            json: std::borrow::Cow::Owned(json_bytes),
+            bin: Some(std::borrow::Cow::Owned(bin)),
+        };
+        let gltf = gltf::Gltf::from_slice(&glb.to_vec().expect("glb")).expect("valid glTF");
+
+        let meshes: Vec<_> = gltf.meshes().collect();
+        assert_eq!(meshes.len(), 2);
+        let primitives: Vec<_> = meshes[0].primitives().collect();
+        assert_eq!(primitives.len(), 2);
+        for primitive in &primitives {
+            assert!(primitive
+                .attributes()
+                .any(|(semantic, _)| semantic.to_string() == "_weight"));
+        }
+        let red = primitives[0].material();
+        assert_eq!(red.name(), Some("red"));
+        let pbr = red.pbr_metallic_roughness();
+        assert_eq!(pbr.base_color_factor(), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(pbr.metallic_factor(), 0.25);
+        assert!(pbr.base_color_texture().is_some());
+        assert_eq!(primitives[1].material().name(), Some("plain"));
+        assert_eq!(gltf.images().count(), 1);
+
+        let lines: Vec<_> = meshes[1].primitives().collect();
+        assert_eq!(lines[0].mode(), gltf::mesh::Mode::Lines);
+        assert_eq!(lines[0].indices().map(|accessor| accessor.count()), Some(6));
+    }
+}
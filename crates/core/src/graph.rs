@@ -1,8 +1,13 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use serde::{Deserialize, Serialize};
 
+use crate::animation::{Channel, Keyframe};
+use crate::geometry::Geometry;
+use crate::lock::LockedOutput;
 use crate::nodes_builtin;
+use crate::subnet::Subnet;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct NodeId(u64);
@@ -60,10 +65,20 @@ pub struct Graph {
     next_node_id: u64,
     next_pin_id: u64,
     next_link_id: u64,
-    #[serde(default)]
+    #[serde(skip, default = "next_revision")]
     revision: u64,
     #[serde(skip)]
     link_index: LinkIndex,
+    #[serde(skip)]
+    demand_all: HashSet<NodeId>,
+}
+
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(1);
+
+/// Revisions are unique across all graphs in the process, so two graphs only
+/// share one when one is an unedited clone of the other.
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
 }
 
 impl Default for Graph {
@@ -75,8 +90,9 @@ impl Default for Graph {
             next_node_id: 1,
             next_pin_id: 1,
             next_link_id: 1,
-            revision: 0,
+            revision: next_revision(),
             link_index: LinkIndex::default(),
+            demand_all: HashSet::new(),
         }
     }
 }
@@ -95,7 +111,7 @@ impl Graph {
     }
 
     fn bump_revision(&mut self) {
-        self.revision = self.revision.wrapping_add(1);
+        self.revision = next_revision();
     }
 
     pub fn rebuild_link_index(&mut self) {
@@ -103,6 +119,11 @@ impl Graph {
         for (id, link) in &self.links {
             self.link_index.insert(*id, link);
         }
+        for node in self.nodes.values_mut() {
+            if let Some(subnet) = node.subnet.as_mut() {
+                subnet.graph.rebuild_link_index();
+            }
+        }
     }
 
     pub fn display_node(&self) -> Option<NodeId> {
@@ -112,6 +133,73 @@ impl Graph {
             .map(|node| node.id)
     }
 
+    /// Position of an output pin within its node's outputs.
+    pub fn output_index(&self, pin_id: PinId) -> Option<usize> {
+        let pin = self.pins.get(&pin_id)?;
+        self.nodes
+            .get(&pin.node)?
+            .outputs
+            .iter()
+            .position(|id| *id == pin_id)
+    }
+
+    /// Displays the node owning `pin_id`, showing that particular output.
+    pub fn set_display_output(&mut self, pin_id: PinId) -> Result<(), GraphError> {
+        let index = self
+            .output_index(pin_id)
+            .ok_or(GraphError::MissingPin(pin_id))?;
+        let node_id = self.pins[&pin_id].node;
+        self.set_display_node(Some(node_id))?;
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        if node.display_output != index {
+            node.display_output = index;
+            self.bump_revision();
+        }
+        Ok(())
+    }
+
+    /// Which outputs of `node_id` are read: the first, the displayed one and any
+    /// with a downstream link, or all of them while `set_demand_all` is on.
+    /// Nodes only need to cook these.
+    pub fn demanded_outputs(&self, node_id: NodeId) -> Vec<bool> {
+        let Some(node) = self.nodes.get(&node_id) else {
+            return Vec::new();
+        };
+        let all = self.demand_all.contains(&node_id);
+        node.outputs
+            .iter()
+            .enumerate()
+            .map(|(index, pin_id)| {
+                all || index == 0
+                    || index == node.display_output
+                    || self.link_index.output_links(*pin_id).is_some()
+            })
+            .collect()
+    }
+
+    /// `demanded_outputs` for nodes that leave unread outputs empty, which makes
+    /// it part of what they cook; `None` for every other node.
+    pub fn output_demand(&self, node_id: NodeId) -> Option<Vec<bool>> {
+        let kind = self.nodes.get(&node_id)?.builtin_kind()?;
+        nodes_builtin::cooks_demanded_outputs(kind).then(|| self.demanded_outputs(node_id))
+    }
+
+    /// Makes every output of `node_id` demanded, e.g. while cooking it to lock.
+    /// Not saved with the graph.
+    pub fn set_demand_all(&mut self, node_id: NodeId, enabled: bool) {
+        let changed = if enabled {
+            self.demand_all.insert(node_id)
+        } else {
+            self.demand_all.remove(&node_id)
+        };
+        if changed {
+            self.bump_revision();
+        }
+    }
+
     pub fn template_nodes(&self) -> Vec<NodeId> {
         self.nodes
             .values()
@@ -199,6 +287,34 @@ impl Graph {
         Ok(())
     }
 
+    /// Replaces the node's output with `geometries` until it is unlocked.
+    pub fn lock_node(
+        &mut self,
+        node_id: NodeId,
+        geometries: Vec<Geometry>,
+    ) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        node.param_version = node.param_version.wrapping_add(1);
+        node.locked = Some(LockedOutput::new(geometries, node.param_version));
+        self.bump_revision();
+        Ok(())
+    }
+
+    pub fn unlock_node(&mut self, node_id: NodeId) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        if node.locked.take().is_some() {
+            node.param_version = node.param_version.wrapping_add(1);
+            self.bump_revision();
+        }
+        Ok(())
+    }
+
     pub fn pin(&self, id: PinId) -> Option<&Pin> {
         self.pins.get(&id)
     }
@@ -206,10 +322,17 @@ impl Graph {
     pub fn add_node(&mut self, def: NodeDefinition) -> NodeId {
         let node_id = self.alloc_node_id();
         let mut input_ids = Vec::new();
+        let mut param_input_ids = Vec::new();
         let mut output_ids = Vec::new();
 
         for input in def.inputs {
             let pin_id = self.alloc_pin_id();
+            // Value inputs drive the parameter they are named after.
+            if input.pin_type.is_value() {
+                param_input_ids.push(pin_id);
+            } else {
+                input_ids.push(pin_id);
+            }
             self.pins.insert(
                 pin_id,
                 Pin {
@@ -220,7 +343,6 @@ impl Graph {
                     pin_type: input.pin_type,
                 },
             );
-            input_ids.push(pin_id);
         }
 
         for output in def.outputs {
@@ -250,9 +372,16 @@ impl Graph {
                 category: def.category,
                 param_version: 0,
                 display: false,
+                display_output: 0,
                 template: false,
                 bypass: false,
                 position: None,
+                subnet: None,
+                expressions: BTreeMap::new(),
+                channels: BTreeMap::new(),
+                variadic_inputs: def.variadic_inputs,
+                param_inputs: param_input_ids,
+                locked: None,
             },
         );
         self.bump_revision();
@@ -260,12 +389,44 @@ impl Graph {
         node_id
     }
 
+    /// Copies a node from another graph, keeping its kind, parameters, flags and
+    /// subnet but not its links or display flag. Pins keep their order.
+    pub fn copy_node_from(&mut self, source: &Graph, node_id: NodeId) -> Option<NodeId> {
+        let node = source.node(node_id)?;
+        let pin_definition = |pin_id: &PinId| {
+            source.pin(*pin_id).map(|pin| PinDefinition {
+                name: pin.name.clone(),
+                pin_type: pin.pin_type,
+            })
+        };
+        let definition = NodeDefinition {
+            name: node.name.clone(),
+            category: node.category.clone(),
+            inputs: node.input_pins().filter_map(pin_definition).collect(),
+            outputs: node.outputs.iter().filter_map(pin_definition).collect(),
+            variadic_inputs: node.variadic_inputs,
+        };
+        let new_id = self.add_node(definition);
+        let copy = self.nodes.get_mut(&new_id)?;
+        copy.kind_id = node.kind_id.clone();
+        copy.params = node.params.clone();
+        copy.display_output = node.display_output;
+        copy.template = node.template;
+        copy.bypass = node.bypass;
+        copy.position = node.position;
+        copy.subnet = node.subnet.clone();
+        copy.expressions = node.expressions.clone();
+        copy.channels = node.channels.clone();
+        copy.locked = node.locked.clone();
+        Some(new_id)
+    }
+
     pub fn remove_node(&mut self, node_id: NodeId) -> bool {
         let Some(node) = self.nodes.remove(&node_id) else {
             return false;
         };
 
-        let mut pins_to_remove: HashSet<PinId> = node.inputs.into_iter().collect();
+        let mut pins_to_remove: HashSet<PinId> = node.input_pins().copied().collect();
         pins_to_remove.extend(node.outputs);
 
         let mut links_to_remove: HashSet<LinkId> = HashSet::new();
@@ -325,14 +486,142 @@ impl Graph {
             });
         }
 
+        let to_node = to_pin.node;
         let link_id = self.alloc_link_id();
         let link = Link { id: link_id, from, to };
         self.links.insert(link_id, link.clone());
         self.link_index.insert(link_id, &link);
+        self.grow_variadic_inputs(to_node);
         self.bump_revision();
         Ok(link_id)
     }
 
+    /// Appends a free input pin to a variadic node whose last input is connected.
+    fn grow_variadic_inputs(&mut self, node_id: NodeId) {
+        let Some(node) = self.nodes.get(&node_id) else {
+            return;
+        };
+        if !node.variadic_inputs {
+            return;
+        }
+        let index = node.inputs.len();
+        let last = node.inputs.last().copied();
+        if last.is_some_and(|pin| self.link_index.input_link(pin).is_none()) {
+            return;
+        }
+        let pin_type = last
+            .and_then(|pin| self.pins.get(&pin))
+            .map(|pin| pin.pin_type)
+            .unwrap_or(PinType::Geometry);
+        let pin_id = self.alloc_pin_id();
+        self.pins.insert(
+            pin_id,
+            Pin {
+                id: pin_id,
+                node: node_id,
+                name: variadic_pin_name(index),
+                kind: PinKind::Input,
+                pin_type,
+            },
+        );
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            node.inputs.push(pin_id);
+        }
+    }
+
+    /// Drops the disconnected input pins of variadic nodes, keeping one free pin
+    /// after the connected ones, and renumbers the rest. Link edits leave pins in
+    /// place so an input can be rewired; call this once an edit is complete.
+    pub fn compact_variadic_inputs(&mut self) -> bool {
+        let node_ids: Vec<NodeId> = self
+            .nodes
+            .values()
+            .filter(|node| node.variadic_inputs)
+            .map(|node| node.id)
+            .collect();
+        let mut changed = false;
+        for node_id in node_ids {
+            changed |= self.compact_node_inputs(node_id);
+        }
+        if changed {
+            self.bump_revision();
+        }
+        changed
+    }
+
+    fn compact_node_inputs(&mut self, node_id: NodeId) -> bool {
+        let Some(node) = self.nodes.get(&node_id) else {
+            return false;
+        };
+        let inputs = node.inputs.clone();
+        let connected = |pin: &PinId| self.link_index.input_link(*pin).is_some();
+        let mut keep: Vec<PinId> = inputs.iter().copied().filter(connected).collect();
+        let free = inputs.iter().copied().rfind(|pin| !connected(pin));
+        if let Some(free) = free {
+            keep.push(free);
+        }
+        let mut changed = keep != inputs;
+        for pin_id in inputs.iter().filter(|pin| !keep.contains(pin)) {
+            self.pins.remove(pin_id);
+        }
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            node.inputs = keep.clone();
+        }
+        if free.is_none() {
+            self.grow_variadic_inputs(node_id);
+            changed = true;
+        }
+        changed |= self.renumber_variadic_inputs(node_id);
+        changed
+    }
+
+    fn renumber_variadic_inputs(&mut self, node_id: NodeId) -> bool {
+        let Some(node) = self.nodes.get(&node_id) else {
+            return false;
+        };
+        let mut changed = false;
+        for (idx, pin_id) in node.inputs.iter().enumerate() {
+            if let Some(pin) = self.pins.get_mut(pin_id) {
+                let name = variadic_pin_name(idx);
+                if pin.name != name {
+                    pin.name = name;
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Moves a connected input of a variadic node to another position among the
+    /// connected inputs.
+    pub fn move_input(
+        &mut self,
+        node_id: NodeId,
+        from: usize,
+        to: usize,
+    ) -> Result<bool, GraphError> {
+        let node = self
+            .nodes
+            .get(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let connected = node
+            .inputs
+            .iter()
+            .rposition(|pin| self.link_index.input_link(*pin).is_some())
+            .map_or(0, |idx| idx + 1);
+        if !node.variadic_inputs || from >= connected || from == to {
+            return Ok(false);
+        }
+        let to = to.min(connected - 1);
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            let pin = node.inputs.remove(from);
+            node.inputs.insert(to, pin);
+        }
+        self.renumber_variadic_inputs(node_id);
+        self.bump_revision();
+        Ok(true)
+    }
+
     pub fn remove_link(&mut self, link_id: LinkId) -> bool {
         let removed = self.remove_link_internal(link_id);
         if removed {
@@ -409,6 +698,151 @@ impl Graph {
         Ok(())
     }
 
+    pub fn remove_param(&mut self, node_id: NodeId, key: &str) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        if node.params.values.remove(key).is_some() {
+            node.param_version = node.param_version.wrapping_add(1);
+            self.bump_revision();
+        }
+        Ok(())
+    }
+
+    /// Drives a parameter from an expression, or returns it to its literal value
+    /// when `expression` is `None` or blank.
+    pub fn set_param_expression(
+        &mut self,
+        node_id: NodeId,
+        key: impl Into<String>,
+        expression: Option<String>,
+    ) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let key = key.into();
+        let expression = expression.filter(|expression| !expression.trim().is_empty());
+        if node.expressions.get(&key) == expression.as_ref() {
+            return Ok(());
+        }
+        match expression {
+            Some(expression) => {
+                node.expressions.insert(key, expression);
+            }
+            None => {
+                node.expressions.remove(&key);
+            }
+        }
+        node.param_version = node.param_version.wrapping_add(1);
+        self.bump_revision();
+        Ok(())
+    }
+
+    /// Replaces the keyframes of `key`; `None` or a channel without keys removes
+    /// the animation.
+    pub fn set_param_channel(
+        &mut self,
+        node_id: NodeId,
+        key: impl Into<String>,
+        channel: Option<Channel>,
+    ) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let key = key.into();
+        let channel = channel.filter(|channel| !channel.keys.is_empty());
+        if node.channels.get(&key) == channel.as_ref() {
+            return Ok(());
+        }
+        match channel {
+            Some(channel) => {
+                node.channels.insert(key, channel);
+            }
+            None => {
+                node.channels.remove(&key);
+            }
+        }
+        node.param_version = node.param_version.wrapping_add(1);
+        self.bump_revision();
+        Ok(())
+    }
+
+    /// Sets a keyframe on `key`, replacing any key on the same frame.
+    pub fn set_keyframe(
+        &mut self,
+        node_id: NodeId,
+        key: impl Into<String>,
+        keyframe: Keyframe,
+    ) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        if !node.channels.entry(key.into()).or_default().set_key(keyframe) {
+            return Ok(());
+        }
+        node.param_version = node.param_version.wrapping_add(1);
+        self.bump_revision();
+        Ok(())
+    }
+
+    /// Name used to reference the node from expressions, e.g. `box_3` for a Box
+    /// node, unique within the graph. The base name never contains `_`, so the
+    /// id can't run into it.
+    pub fn node_path_name(&self, node_id: NodeId) -> Option<String> {
+        let node = self.nodes.get(&node_id)?;
+        let base: String = node
+            .name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        Some(format!("{}_{}", base, node_id.0))
+    }
+
+    /// The node with path name `name`; `None` when no node or several match.
+    pub fn find_node_by_path_name(&self, name: &str) -> Option<NodeId> {
+        let mut matches = self
+            .nodes
+            .keys()
+            .copied()
+            .filter(|node_id| self.node_path_name(*node_id).as_deref() == Some(name));
+        let node_id = matches.next()?;
+        matches.next().is_none().then_some(node_id)
+    }
+
+    /// Makes the evaluator treat the node as edited without changing it.
+    pub fn mark_node_changed(&mut self, node_id: NodeId) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        node.param_version = node.param_version.wrapping_add(1);
+        self.bump_revision();
+        Ok(())
+    }
+
+    pub fn set_node_subnet(
+        &mut self,
+        node_id: NodeId,
+        subnet: Option<Subnet>,
+    ) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        node.subnet = subnet.map(Box::new);
+        if let Some(subnet) = node.subnet.as_mut() {
+            subnet.graph.rebuild_link_index();
+        }
+        node.param_version = node.param_version.wrapping_add(1);
+        self.bump_revision();
+        Ok(())
+    }
+
     pub fn topo_sort_from(&self, output: NodeId) -> Result<Vec<NodeId>, GraphError> {
         if !self.nodes.contains_key(&output) {
             return Err(GraphError::MissingNode(output));
@@ -485,7 +919,7 @@ impl Graph {
         let Some(node) = self.nodes.get(&node_id) else {
             return upstream;
         };
-        for input_pin in &node.inputs {
+        for input_pin in node.input_pins() {
             let link_id = match self.link_index.input_link(*input_pin) {
                 Some(link_id) => link_id,
                 None => continue,
@@ -513,6 +947,101 @@ impl Graph {
         self.node_for_pin(link.from)
     }
 
+    /// For each input of `node_id`, the upstream node and output index it is wired to.
+    pub fn input_sources(&self, node_id: NodeId) -> Vec<Option<(NodeId, usize)>> {
+        let Some(node) = self.nodes.get(&node_id) else {
+            return Vec::new();
+        };
+        node.inputs
+            .iter()
+            .map(|pin_id| {
+                let link = self.input_link(*pin_id)?;
+                Some((self.node_for_pin(link.from)?, self.output_index(link.from)?))
+            })
+            .collect()
+    }
+
+    /// For each value input of `node_id`, the parameter it drives and the upstream
+    /// node and output index it is wired to.
+    pub fn param_input_sources(&self, node_id: NodeId) -> Vec<(String, Option<(NodeId, usize)>)> {
+        let Some(node) = self.nodes.get(&node_id) else {
+            return Vec::new();
+        };
+        node.param_inputs
+            .iter()
+            .filter_map(|pin_id| {
+                let pin = self.pins.get(pin_id)?;
+                let source = self.input_link(*pin_id).and_then(|link| {
+                    Some((self.node_for_pin(link.from)?, self.output_index(link.from)?))
+                });
+                Some((pin.name.clone(), source))
+            })
+            .collect()
+    }
+
+    /// Value input driving parameter `key` of `node_id`.
+    pub fn param_input(&self, node_id: NodeId, key: &str) -> Option<PinId> {
+        let node = self.nodes.get(&node_id)?;
+        node.param_inputs
+            .iter()
+            .copied()
+            .find(|pin_id| self.pins.get(pin_id).is_some_and(|pin| pin.name == key))
+    }
+
+    /// Adds a value input driving parameter `key`, so a link can set it. Returns
+    /// the existing input when there is one.
+    pub fn add_param_input(
+        &mut self,
+        node_id: NodeId,
+        key: &str,
+        pin_type: PinType,
+    ) -> Result<PinId, GraphError> {
+        if !self.nodes.contains_key(&node_id) {
+            return Err(GraphError::MissingNode(node_id));
+        }
+        if let Some(pin_id) = self.param_input(node_id, key) {
+            return Ok(pin_id);
+        }
+        if !pin_type.is_value() {
+            return Err(GraphError::IncompatiblePinTypes {
+                from: pin_type,
+                to: PinType::Float,
+            });
+        }
+        let pin_id = self.alloc_pin_id();
+        self.pins.insert(
+            pin_id,
+            Pin {
+                id: pin_id,
+                node: node_id,
+                name: key.to_string(),
+                kind: PinKind::Input,
+                pin_type,
+            },
+        );
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            node.param_inputs.push(pin_id);
+        }
+        self.bump_revision();
+        Ok(pin_id)
+    }
+
+    /// Removes the value input driving parameter `key` together with its link.
+    pub fn remove_param_input(&mut self, node_id: NodeId, key: &str) -> bool {
+        let Some(pin_id) = self.param_input(node_id, key) else {
+            return false;
+        };
+        if let Some(link_id) = self.link_index.input_link(pin_id) {
+            self.remove_link_internal(link_id);
+        }
+        self.pins.remove(&pin_id);
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            node.param_inputs.retain(|id| *id != pin_id);
+        }
+        self.bump_revision();
+        true
+    }
+
     pub fn input_link(&self, input_pin: PinId) -> Option<&Link> {
         let link_id = self.link_index.input_link(input_pin)?;
         self.links.get(&link_id)
@@ -555,6 +1084,80 @@ impl Graph {
                 _ => {}
             }
         }
+        if changed {
+            self.bump_revision();
+        }
+        changed
+    }
+
+    /// Marks nodes whose builtin kind has variadic inputs and brings their pins
+    /// into shape, including inside subnets.
+    pub fn migrate_variadic_inputs(&mut self) -> bool {
+        let mut changed = false;
+        for node in self.nodes.values_mut() {
+            if let Some(subnet) = node.subnet.as_mut() {
+                changed |= subnet.graph.migrate_variadic_inputs();
+            }
+            let variadic = node
+                .builtin_kind()
+                .is_some_and(|kind| nodes_builtin::node_definition(kind).variadic_inputs);
+            if variadic && !node.variadic_inputs {
+                node.variadic_inputs = true;
+                changed = true;
+            }
+        }
+        changed |= self.compact_variadic_inputs();
+        if changed {
+            self.bump_revision();
+        }
+        changed
+    }
+
+    /// Appends the output pins that Boolean Geo and Splat Outlier gained after
+    /// they were first released, including inside subnets.
+    pub fn migrate_missing_outputs(&mut self) -> bool {
+        let mut changed = false;
+        let mut missing = Vec::new();
+        for node in self.nodes.values_mut() {
+            if let Some(subnet) = node.subnet.as_mut() {
+                changed |= subnet.graph.migrate_missing_outputs();
+            }
+            let Some(kind) = node.builtin_kind().filter(|kind| {
+                matches!(
+                    kind,
+                    nodes_builtin::BuiltinNodeKind::BooleanGeo
+                        | nodes_builtin::BuiltinNodeKind::SplatOutlier
+                )
+            }) else {
+                continue;
+            };
+            let outputs = nodes_builtin::node_definition(kind).outputs;
+            if outputs.len() > node.outputs.len() {
+                missing.push((node.id, outputs[node.outputs.len()..].to_vec()));
+            }
+        }
+        for (node_id, outputs) in missing {
+            for output in outputs {
+                let pin_id = self.alloc_pin_id();
+                self.pins.insert(
+                    pin_id,
+                    Pin {
+                        id: pin_id,
+                        node: node_id,
+                        name: output.name,
+                        kind: PinKind::Output,
+                        pin_type: output.pin_type,
+                    },
+                );
+                if let Some(node) = self.nodes.get_mut(&node_id) {
+                    node.outputs.push(pin_id);
+                }
+            }
+            changed = true;
+        }
+        if changed {
+            self.bump_revision();
+        }
         changed
     }
 
@@ -566,6 +1169,9 @@ impl Graph {
                 renamed += 1;
             }
         }
+        if renamed > 0 {
+            self.bump_revision();
+        }
         renamed
     }
 
@@ -600,10 +1206,18 @@ impl Graph {
     }
 }
 
-fn pin_types_compatible(from: PinType, to: PinType) -> bool {
+fn variadic_pin_name(index: usize) -> String {
+    format!("in{}", index)
+}
+
+pub(crate) fn pin_types_compatible(from: PinType, to: PinType) -> bool {
     if from == to {
         return true;
     }
+    // Values convert to the type of the parameter they drive.
+    if from.is_value() && to.is_value() {
+        return true;
+    }
     matches!(
         (from, to),
         (PinType::Geometry, PinType::Mesh)
@@ -627,15 +1241,43 @@ pub struct Node {
     pub param_version: u64,
     #[serde(default)]
     pub display: bool,
+    /// Index into `outputs` of the output shown when this node is displayed.
+    #[serde(default)]
+    pub display_output: usize,
     #[serde(default)]
     pub template: bool,
     #[serde(default)]
     pub bypass: bool,
     #[serde(default)]
     pub position: Option<[f32; 2]>,
+    /// Inner network of subnet nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subnet: Option<Box<Subnet>>,
+    /// Expressions driving parameters, by parameter key. The literal value in
+    /// `params` keeps the parameter's type.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub expressions: BTreeMap<String, String>,
+    /// Keyframed parameters, by parameter key. An expression on the same key wins.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub channels: BTreeMap<String, Channel>,
+    /// Inputs grow with their connections; see `NodeDefinition::variadic_inputs`.
+    #[serde(default)]
+    pub variadic_inputs: bool,
+    /// Value inputs, each driving the parameter named like the pin. Kept apart
+    /// from `inputs` so geometry inputs keep their positions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub param_inputs: Vec<PinId>,
+    /// Output stored by locking the node; see [`LockedOutput`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked: Option<LockedOutput>,
 }
 
 impl Node {
+    /// Geometry inputs followed by value inputs.
+    pub fn input_pins(&self) -> impl Iterator<Item = &PinId> {
+        self.inputs.iter().chain(&self.param_inputs)
+    }
+
     pub fn builtin_kind(&self) -> Option<nodes_builtin::BuiltinNodeKind> {
         if !self.kind_id.is_empty() {
             nodes_builtin::builtin_kind_from_id(&self.kind_id)
@@ -729,6 +1371,9 @@ pub struct NodeDefinition {
     pub category: String,
     pub inputs: Vec<PinDefinition>,
     pub outputs: Vec<PinDefinition>,
+    /// Inputs grow with their connections: there is always one free input pin
+    /// after the connected ones, which keep their order.
+    pub variadic_inputs: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -749,6 +1394,27 @@ pub enum PinType {
     Vec3,
 }
 
+impl PinType {
+    /// Whether the pin carries a parameter value rather than geometry.
+    pub fn is_value(self) -> bool {
+        matches!(
+            self,
+            PinType::Float | PinType::Int | PinType::Bool | PinType::Vec2 | PinType::Vec3
+        )
+    }
+
+    pub fn for_value(value: &ParamValue) -> Option<Self> {
+        match value {
+            ParamValue::Float(_) => Some(PinType::Float),
+            ParamValue::Int(_) => Some(PinType::Int),
+            ParamValue::Bool(_) => Some(PinType::Bool),
+            ParamValue::Vec2(_) => Some(PinType::Vec2),
+            ParamValue::Vec3(_) => Some(PinType::Vec3),
+            ParamValue::String(_) => None,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PinKind {
     Input,
@@ -797,6 +1463,7 @@ mod tests {
                 name: "out".to_string(),
                 pin_type: PinType::Mesh,
             }],
+            variadic_inputs: false,
         }
     }
 
@@ -820,6 +1487,7 @@ mod tests {
                 name: "out".to_string(),
                 pin_type: PinType::Float,
             }],
+            variadic_inputs: false,
         });
         let b = graph.add_node(NodeDefinition {
             name: "B".to_string(),
@@ -829,6 +1497,7 @@ mod tests {
                 pin_type: PinType::Mesh,
             }],
             outputs: vec![],
+            variadic_inputs: false,
         });
 
         let from = graph.nodes.get(&a).unwrap().outputs[0];
@@ -914,6 +1583,7 @@ mod tests {
             outputs: (0..outputs)
                 .map(|i| make_pin(&format!("out{}", i)))
                 .collect(),
+            variadic_inputs: false,
         }
     }
 
@@ -958,4 +1628,85 @@ mod tests {
         let result = graph.topo_sort_from(a);
         assert!(matches!(result, Err(GraphError::CycleDetected(_))));
     }
+
+    #[test]
+    fn display_output_follows_selected_pin() {
+        let mut graph = Graph::default();
+        let mut definition = demo_node("Split");
+        definition.outputs.push(PinDefinition {
+            name: "rest".to_string(),
+            pin_type: PinType::Mesh,
+        });
+        let node_id = graph.add_node(definition);
+        let second = graph.node(node_id).unwrap().outputs[1];
+
+        graph.set_display_output(second).unwrap();
+        let node = graph.node(node_id).unwrap();
+        assert!(node.display);
+        assert_eq!(node.display_output, 1);
+        assert_eq!(graph.display_node(), Some(node_id));
+        assert_eq!(graph.output_index(second), Some(1));
+    }
+
+    #[test]
+    fn migration_adds_missing_outputs_and_tracks_demand() {
+        let mut graph = Graph::default();
+        let mut definition =
+            nodes_builtin::node_definition(nodes_builtin::BuiltinNodeKind::BooleanGeo);
+        let expected = definition.outputs.len();
+        definition.outputs.truncate(1);
+        let node_id = graph.add_node(definition);
+        let sink = graph.add_node(demo_node("Sink"));
+
+        assert!(graph.migrate_missing_outputs());
+        assert!(!graph.migrate_missing_outputs());
+        let outputs = graph.node(node_id).unwrap().outputs.clone();
+        assert_eq!(outputs.len(), expected);
+        assert_eq!(graph.demanded_outputs(node_id), vec![true, false, false, false]);
+
+        let sink_in = graph.node(sink).unwrap().inputs[0];
+        graph.add_link(outputs[3], sink_in).unwrap();
+        graph.set_display_output(outputs[1]).unwrap();
+        assert_eq!(graph.output_demand(node_id), Some(vec![true, true, false, true]));
+        assert_eq!(graph.output_demand(sink), None);
+        graph.set_demand_all(node_id, true);
+        assert_eq!(graph.output_demand(node_id), Some(vec![true; expected]));
+    }
+
+    #[test]
+    fn variadic_inputs_follow_connections() {
+        let mut graph = Graph::default();
+        let sources: Vec<NodeId> = (0..3)
+            .map(|idx| graph.add_node(node_def(&format!("S{}", idx), 0, 1)))
+            .collect();
+        let mut definition = node_def("Merge", 1, 1);
+        definition.variadic_inputs = true;
+        let merge = graph.add_node(definition);
+        for (idx, source) in sources.iter().enumerate() {
+            let from = graph.node(*source).unwrap().outputs[0];
+            let to = graph.node(merge).unwrap().inputs[idx];
+            graph.add_link(from, to).unwrap();
+        }
+        assert_eq!(graph.node(merge).unwrap().inputs.len(), 4);
+
+        // Rewiring a pin keeps it until the edit is compacted.
+        let middle = graph.node(merge).unwrap().inputs[1];
+        graph.remove_links_for_pin(middle);
+        assert_eq!(graph.node(merge).unwrap().inputs.len(), 4);
+        assert!(graph.compact_variadic_inputs());
+        let node = graph.node(merge).unwrap();
+        assert_eq!(node.inputs.len(), 3);
+        assert_eq!(graph.pin(node.inputs[1]).unwrap().name, "in1");
+        assert_eq!(graph.input_node(merge, 0), Some(sources[0]));
+        assert_eq!(graph.input_node(merge, 1), Some(sources[2]));
+        assert!(!graph.compact_variadic_inputs());
+
+        assert!(graph.move_input(merge, 1, 0).unwrap());
+        assert_eq!(graph.input_node(merge, 0), Some(sources[2]));
+        assert_eq!(graph.input_node(merge, 1), Some(sources[0]));
+        // The free pin stays last.
+        assert!(graph.move_input(merge, 0, 5).unwrap());
+        assert_eq!(graph.input_node(merge, 1), Some(sources[2]));
+        assert!(graph.input_node(merge, 2).is_none());
+    }
 }
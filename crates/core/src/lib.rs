@@ -1,15 +1,24 @@
+mod animation;
 mod attributes;
+mod buffer;
 mod eval;
+mod export;
+mod expression;
+mod for_each;
 mod color;
 mod gradient;
 mod curve;
+mod cook_cache;
 mod geometry;
 mod geometry_eval;
 mod gltf_io;
 mod groups;
+mod lock;
 mod graph;
 mod mesh;
 mod mesh_primitives;
+mod mesh_ply;
+mod mesh_stl;
 mod mesh_eval;
 mod material;
 mod noise;
@@ -19,23 +28,37 @@ mod node_help;
 mod node_help_io;
 mod node_help_splats;
 mod node_help_volumes;
+mod node_registry;
 mod parallel;
 mod param_spec;
 mod param_templates;
+mod profile;
 mod progress;
 mod project;
 mod scene;
 mod splat;
+mod splat_compressed_ply;
+mod splat_io;
+mod splat_packed;
 mod splat_ply;
 mod splat_eval;
+mod splat_spz;
+mod subnet;
+mod validate;
 mod volume;
+mod wedge;
 mod volume_sampling;
 mod wrangle;
 
+pub use animation::{is_animatable, Channel, Interpolation, Keyframe};
 pub use attributes::{
     AttributeDomain, AttributeError, AttributeInfo, AttributeRef, AttributeStorage, AttributeType,
     MeshAttributes,
 };
+pub use buffer::{ByteCounter, SharedVec};
+pub use expression::check_expression;
+pub use export::{write_geometry, write_geometry_with, ExportKind};
+pub use cook_cache::{node_cache_keys, DiskCookCache};
 pub use assets::{is_url, load_bytes, store_bytes, url_revision};
 pub use eval::{
     collect_dirty_nodes, collect_dirty_nodes_full, evaluate_from, evaluate_from_with,
@@ -43,7 +66,14 @@ pub use eval::{
     DirtyNodeReport, DirtyReason, EvalCacheStats, EvalError, EvalNodeReport, EvalReport,
     EvalState,
 };
-pub use progress::{report_progress, ProgressEvent, ProgressSink};
+pub use progress::{
+    check_cancelled, is_cancelled, progress_phase, report_progress, CancelToken, PhaseGuard,
+    PhaseReport, ProgressEvent, ProgressSink, CANCELLED_MESSAGE,
+};
+pub use profile::{
+    chrome_trace, profile_summary, write_eval_profile, NodeProfile, PhaseProfile, ProfileFormat,
+    ProfileSummary,
+};
 pub use color::{lerp_oklab, linear_srgb_to_oklab, oklab_to_linear_srgb};
 pub use gradient::{parse_color_gradient, ColorGradient, ColorStop};
 pub use curve::{encode_curve_points, parse_curve_points, sample_catmull_rom, Curve};
@@ -52,6 +82,7 @@ pub use graph::{
     Graph, GraphError, Link, LinkId, Node, NodeDefinition, NodeId, NodeParams, ParamValue, Pin,
     PinDefinition, PinId, PinKind, PinType,
 };
+pub use lock::{lock_node_output, LockedOutput};
 pub use mesh::{make_box, make_grid, make_tube, Aabb, Mesh};
 pub use material::{Material, MaterialLibrary};
 pub use mesh_eval::{evaluate_mesh_graph, MeshEvalResult, MeshEvalState};
@@ -62,24 +93,37 @@ pub use geometry_eval::{
 };
 pub use nodes_builtin::{
     builtin_definitions, builtin_kind_from_id, builtin_kind_from_name, compute_geometry_node,
-    compute_mesh_node, compute_splat_node, default_params, node_definition, node_specs,
-    menu_group, param_specs, param_specs_for_kind_id, param_specs_for_name, BuiltinNodeKind,
-    NodeSpec,
+    compute_geometry_outputs, compute_mesh_node, compute_splat_node, compute_value_outputs,
+    default_params,
+    node_definition, node_specs, menu_group, param_specs, param_specs_for_kind_id,
+    param_specs_for_name, BuiltinNodeKind, InputPolicy, NodeSpec,
 };
 pub use node_help::{help_summary, node_help_page, node_help_page_for_kind, NodeHelpPage};
+pub use node_registry::{
+    add_registered_node, node_type, node_type_for, node_types, register_node_type, NodeRegistry,
+    NodeType, BUILTIN_NAMESPACE,
+};
 pub use nodes::obj_output::write_obj;
+pub use mesh_ply::{write_ply_mesh, PlyMeshFormat};
+pub use mesh_stl::{write_stl, StlFormat};
 pub use nodes::splat_merge::build_skirt_preview_mesh;
 pub use gltf_io::write_gltf;
 pub use project::{
-    CameraSettings, GraphNote, PanelSettings, Project, ProjectSettings, RenderDebugSettings,
-    ShadingMode, SplatShadingMode, PROJECT_VERSION,
+    CameraSettings, DiskCacheSettings, GraphNote, PanelSettings, Project, ProjectSettings, RenderDebugSettings,
+    ShadingMode, SplatShadingMode, TimelineSettings, PROJECT_VERSION,
 };
 pub use scene::{
     scene_mesh_from_mesh, scene_snapshot_from_geometry, scene_snapshot_from_mesh,
     scene_snapshot_from_splats, SceneCurve, SceneDrawable, SceneMesh, SceneSnapshot, SceneSplats,
     SceneVolume, SceneVolumeKind, SceneMaterial,
 };
-pub use splat::{save_splat_ply_with_format, SplatGeo, SplatSaveFormat};
+pub use splat::{save_splat_ply_with_format, save_splats_with_format, SplatGeo, SplatSaveFormat};
 pub use splat_eval::{evaluate_splat_graph, SplatEvalResult, SplatEvalState};
+pub use subnet::{
+    collapse_to_subnet, expand_subnet, promote_param, promoted_param_specs, unpromote_param,
+    PromotedParam, Subnet, SubnetAsset, SUBNET_ASSET_VERSION,
+};
+pub use validate::{Diagnostic, Severity};
 pub use volume::{Volume, VolumeKind};
+pub use wedge::{run_wedge, Wedge, WedgeManifest, WedgeParam, WedgeRun, WedgeValues};
 mod assets;
@@ -0,0 +1,195 @@
+use std::hash::Hasher;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::cook_cache::StableHasher;
+use crate::geometry::Geometry;
+use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+use crate::graph::{Graph, NodeId};
+
+/// Cooked outputs stored on a locked node, one per output pin. The node returns
+/// them instead of cooking and its upstream is not evaluated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockedOutput {
+    pub geometries: Vec<Geometry>,
+    /// Stands in for the node's parameter version while locked, so editing
+    /// parameters does not dirty it.
+    pub version: u64,
+    content_hash: u64,
+}
+
+impl LockedOutput {
+    pub fn new(geometries: Vec<Geometry>, version: u64) -> Self {
+        let content_hash = bincode::serialize(&geometries)
+            .map(|bytes| hash_bytes(&bytes))
+            .unwrap_or_default();
+        Self {
+            geometries,
+            version,
+            content_hash,
+        }
+    }
+
+    /// Stable hash of the stored geometry, for disk cook cache keys.
+    pub fn content_hash(&self) -> u64 {
+        self.content_hash
+    }
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Project files hold the geometry as base64 encoded bincode; JSON arrays of
+/// splat attributes would be several times larger.
+#[derive(Serialize, Deserialize)]
+struct LockedOutputData {
+    version: u64,
+    data: String,
+}
+
+impl Serialize for LockedOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = bincode::serialize(&self.geometries).map_err(serde::ser::Error::custom)?;
+        LockedOutputData {
+            version: self.version,
+            data: STANDARD.encode(bytes),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LockedOutput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = LockedOutputData::deserialize(deserializer)?;
+        let bytes = STANDARD
+            .decode(data.data)
+            .map_err(serde::de::Error::custom)?;
+        let geometries = bincode::deserialize(&bytes).map_err(serde::de::Error::custom)?;
+        Ok(Self {
+            geometries,
+            version: data.version,
+            content_hash: hash_bytes(&bytes),
+        })
+    }
+}
+
+/// Cooks `node_id` and locks it to the result. Nodes with value outputs can't be
+/// locked, since only geometry is stored.
+pub fn lock_node_output(
+    graph: &mut Graph,
+    node_id: NodeId,
+    state: &mut GeometryEvalState,
+) -> Result<(), String> {
+    let node = graph
+        .node(node_id)
+        .ok_or_else(|| "missing node".to_string())?;
+    let value_outputs = node
+        .outputs
+        .iter()
+        .any(|pin| graph.pin(*pin).is_some_and(|pin| pin.pin_type.is_value()));
+    if value_outputs {
+        return Err(format!("{} outputs values and can't be locked", node.name));
+    }
+    // Cook every output, including ones nothing reads yet.
+    graph.set_demand_all(node_id, true);
+    let result = evaluate_geometry_graph(graph, node_id, state);
+    graph.set_demand_all(node_id, false);
+    let result = result.map_err(|err| format!("{:?}", err))?;
+    if !result.report.output_valid {
+        return Err("the node failed to cook".to_string());
+    }
+    let geometries = state
+        .outputs_for_node(node_id)
+        .ok_or_else(|| "the node produced no geometry".to_string())?
+        .to_vec();
+    graph
+        .lock_node(node_id, geometries)
+        .map_err(|err| format!("{:?}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::ParamValue;
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+    use crate::project::Project;
+
+    use super::lock_node_output;
+
+    #[test]
+    fn locked_nodes_skip_upstream_until_unlocked() {
+        let mut project = Project::default();
+        let graph = &mut project.graph;
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        let from = graph.node(source).unwrap().outputs[0];
+        let to = graph.node(transform).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+
+        let mut state = GeometryEvalState::new();
+        lock_node_output(graph, transform, &mut state).unwrap();
+        let locked = state.geometry_for_node(transform).unwrap().clone();
+
+        graph
+            .set_param(source, "size", ParamValue::Vec3([4.0, 4.0, 4.0]))
+            .unwrap();
+        graph
+            .set_param(transform, "translate", ParamValue::Vec3([1.0, 0.0, 0.0]))
+            .unwrap();
+        let mut fresh = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(graph, transform, &mut fresh).unwrap();
+        assert_eq!(result.report.ordered, vec![transform]);
+        assert_eq!(result.output.as_ref(), Some(&locked));
+
+        let data = serde_json::to_vec(&project).unwrap();
+        let mut loaded: Project = serde_json::from_slice(&data).unwrap();
+        loaded.graph.rebuild_link_index();
+        let result = evaluate_geometry_graph(&loaded.graph, transform, &mut fresh).unwrap();
+        assert!(result.report.computed.is_empty());
+        assert_eq!(result.output.as_ref(), Some(&locked));
+
+        loaded.graph.unlock_node(transform).unwrap();
+        let result = evaluate_geometry_graph(&loaded.graph, transform, &mut fresh).unwrap();
+        assert_eq!(result.report.computed, vec![source, transform]);
+        assert_ne!(result.output.as_ref(), Some(&locked));
+    }
+
+    #[test]
+    fn value_nodes_cannot_be_locked() {
+        let mut project = Project::default();
+        let graph = &mut project.graph;
+        let constant = graph.add_node(node_definition(BuiltinNodeKind::Constant));
+        let mut state = GeometryEvalState::new();
+        assert!(lock_node_output(graph, constant, &mut state).is_err());
+        assert!(graph.node(constant).unwrap().locked.is_none());
+    }
+
+    #[test]
+    fn locking_cooks_unread_outputs() {
+        let mut project = Project::default();
+        let graph = &mut project.graph;
+        let geo = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let cutter = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        graph
+            .set_param(cutter, "center", ParamValue::Vec3([0.5, 0.3, 0.2]))
+            .unwrap();
+        let boolean = graph.add_node(node_definition(BuiltinNodeKind::BooleanGeo));
+        for (source, index) in [(geo, 0), (cutter, 1)] {
+            let from = graph.node(source).unwrap().outputs[0];
+            let to = graph.node(boolean).unwrap().inputs[index];
+            graph.add_link(from, to).unwrap();
+        }
+        assert!(!graph.demanded_outputs(boolean)[3]);
+
+        let mut state = GeometryEvalState::new();
+        lock_node_output(graph, boolean, &mut state).unwrap();
+        let locked = graph.node(boolean).unwrap().locked.as_ref().unwrap();
+        assert!(!locked.geometries[3].meshes.is_empty());
+        assert!(!graph.demanded_outputs(boolean)[3]);
+    }
+}
@@ -1,7 +1,9 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use glam::{Mat4, Vec3};
+use serde::{Deserialize, Serialize};
 
+use crate::buffer::{ByteCounter, SharedVec};
 use crate::attributes::{
     AttributeDomain, AttributeError, AttributeInfo, AttributeRef, AttributeStorage, AttributeType,
     MeshAttributes, StringTableAttribute,
@@ -22,21 +24,21 @@ pub struct Triangulation {
     pub corner_indices: Vec<usize>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Mesh {
-    pub positions: Vec<[f32; 3]>,
+    pub positions: SharedVec<[f32; 3]>,
     /// Polygon vertex indices in winding order. Use `face_counts` to split into primitives.
-    pub indices: Vec<u32>,
+    pub indices: SharedVec<u32>,
     /// Vertex counts for each polygon primitive.
-    pub face_counts: Vec<u32>,
-    pub normals: Option<Vec<[f32; 3]>>,
-    pub corner_normals: Option<Vec<[f32; 3]>>,
-    pub uvs: Option<Vec<[f32; 2]>>,
+    pub face_counts: SharedVec<u32>,
+    pub normals: Option<SharedVec<[f32; 3]>>,
+    pub corner_normals: Option<SharedVec<[f32; 3]>>,
+    pub uvs: Option<SharedVec<[f32; 2]>>,
     pub attributes: MeshAttributes,
     pub groups: MeshGroups,
 }
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct MeshGroups {
     point: BTreeMap<String, Vec<bool>>,
     vertex: BTreeMap<String, Vec<bool>>,
@@ -61,6 +63,14 @@ impl MeshGroups {
             AttributeDomain::Detail => &mut self.primitive,
         }
     }
+
+    pub fn approx_bytes(&self) -> usize {
+        [&self.point, &self.vertex, &self.primitive]
+            .iter()
+            .flat_map(|map| map.values())
+            .map(Vec::len)
+            .sum()
+    }
 }
 
 impl Mesh {
@@ -77,9 +87,9 @@ impl Mesh {
             vec![indices.len() as u32]
         };
         Self {
-            positions,
-            indices,
-            face_counts,
+            positions: positions.into(),
+            indices: indices.into(),
+            face_counts: face_counts.into(),
             normals: None,
             corner_normals: None,
             uvs: None,
@@ -94,9 +104,9 @@ impl Mesh {
         face_counts: Vec<u32>,
     ) -> Self {
         let mut mesh = Self {
-            positions,
-            indices,
-            face_counts,
+            positions: positions.into(),
+            indices: indices.into(),
+            face_counts: face_counts.into(),
             normals: None,
             corner_normals: None,
             uvs: None,
@@ -112,9 +122,9 @@ impl Mesh {
             return;
         }
         if self.indices.len().is_multiple_of(3) {
-            self.face_counts = vec![3; self.indices.len() / 3];
+            self.face_counts = vec![3; self.indices.len() / 3].into();
         } else {
-            self.face_counts = vec![self.indices.len() as u32];
+            self.face_counts = vec![self.indices.len() as u32].into();
         }
     }
 
@@ -154,7 +164,7 @@ impl Mesh {
             let tri_count = self.indices.len() / 3;
             let tri_to_face = (0..tri_count).collect::<Vec<_>>();
             return Triangulation {
-                indices: self.indices.clone(),
+                indices: self.indices.to_vec(),
                 tri_to_face,
                 corner_indices: (0..self.indices.len()).collect(),
             };
@@ -364,6 +374,22 @@ impl Mesh {
         }
     }
 
+    /// Adds the mesh's buffers to `counter`; buffers it has already seen are
+    /// skipped.
+    pub fn count_bytes(&self, counter: &mut ByteCounter) {
+        counter.add(&self.positions);
+        counter.add(&self.indices);
+        counter.add(&self.face_counts);
+        for values in [&self.normals, &self.corner_normals].into_iter().flatten() {
+            counter.add(values);
+        }
+        if let Some(uvs) = &self.uvs {
+            counter.add(uvs);
+        }
+        self.attributes.count_bytes(counter);
+        counter.add_bytes(self.groups.approx_bytes());
+    }
+
     pub fn bounds(&self) -> Option<Aabb> {
         let mut iter = self.positions.iter();
         let first = iter.next()?;
@@ -453,7 +479,7 @@ impl Mesh {
                 Vec::new()
             }
         } else {
-            self.face_counts.clone()
+            self.face_counts.to_vec()
         };
 
         for &count in &face_counts {
@@ -562,7 +588,7 @@ impl Mesh {
         }
 
         let _ = self.compute_normals();
-        self.corner_normals = Some(corner_normals);
+        self.corner_normals = Some(corner_normals.into());
         true
     }
 
@@ -636,7 +662,7 @@ impl Mesh {
             for mesh in meshes {
                 normals.extend_from_slice(mesh.normals.as_ref().unwrap());
             }
-            merged.normals = Some(normals);
+            merged.normals = Some(normals.into());
         }
 
         if include_uvs {
@@ -644,7 +670,7 @@ impl Mesh {
             for mesh in meshes {
                 uvs.extend_from_slice(mesh.uvs.as_ref().unwrap());
             }
-            merged.uvs = Some(uvs);
+            merged.uvs = Some(uvs.into());
         }
 
         if include_corner_normals {
@@ -652,7 +678,7 @@ impl Mesh {
             for mesh in meshes {
                 corner_normals.extend_from_slice(mesh.corner_normals.as_ref().unwrap());
             }
-            merged.corner_normals = Some(corner_normals);
+            merged.corner_normals = Some(corner_normals.into());
         }
 
         merged.attributes = merge_attributes(meshes);
@@ -705,11 +731,11 @@ fn merge_attributes(meshes: &[Mesh]) -> MeshAttributes {
                 }
                 _ => {
                     let mut combined = match storage {
-                        AttributeStorage::Float(_) => AttributeStorage::Float(Vec::new()),
-                        AttributeStorage::Int(_) => AttributeStorage::Int(Vec::new()),
-                        AttributeStorage::Vec2(_) => AttributeStorage::Vec2(Vec::new()),
-                        AttributeStorage::Vec3(_) => AttributeStorage::Vec3(Vec::new()),
-                        AttributeStorage::Vec4(_) => AttributeStorage::Vec4(Vec::new()),
+                        AttributeStorage::Float(_) => AttributeStorage::Float(SharedVec::new()),
+                        AttributeStorage::Int(_) => AttributeStorage::Int(SharedVec::new()),
+                        AttributeStorage::Vec2(_) => AttributeStorage::Vec2(SharedVec::new()),
+                        AttributeStorage::Vec3(_) => AttributeStorage::Vec3(SharedVec::new()),
+                        AttributeStorage::Vec4(_) => AttributeStorage::Vec4(SharedVec::new()),
                         AttributeStorage::StringTable(_) => {
                             AttributeStorage::StringTable(StringTableAttribute::new(Vec::new(), Vec::new()))
                         }
@@ -0,0 +1,678 @@
+use std::collections::BTreeSet;
+
+use crate::attributes::{AttributeDomain, AttributeRef, AttributeStorage};
+use crate::mesh::Mesh;
+use crate::splat_ply::{
+    parse_header_bytes, read_scalar_f64, PlyElement, PlyFormat, PlyHeader, PlyScalarType,
+};
+
+const FACE_INDEX_NAMES: [&str; 2] = ["vertex_indices", "vertex_index"];
+const UV_NAMES: [(&str, &str); 4] = [
+    ("u", "v"),
+    ("s", "t"),
+    ("texture_u", "texture_v"),
+    ("texture_s", "texture_t"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlyMeshFormat {
+    BinaryLittle,
+    Ascii,
+}
+
+impl PlyMeshFormat {
+    /// Format for the PLY Output node's `format` parameter.
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => Self::Ascii,
+            _ => Self::BinaryLittle,
+        }
+    }
+}
+
+/// Values of one property across an element's rows.
+struct PlyColumn {
+    /// One value per row, or the concatenated entries of a list property.
+    values: Vec<f64>,
+    /// Entry count of each row for list properties.
+    counts: Option<Vec<u32>>,
+}
+
+pub fn load_ply_mesh(path: &str) -> Result<Mesh, String> {
+    if let Some(data) = crate::assets::load_bytes(path) {
+        return load_ply_mesh_bytes(&data);
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        if crate::assets::is_url(path) {
+            return Err("PLY URL is downloading; retrying shortly.".to_string());
+        }
+        return Err("PLY import is not supported in web builds without a picked file".to_string());
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if crate::assets::is_url(path) {
+            return Err(format!("Failed to download URL: {path}"));
+        }
+        let data = std::fs::read(path).map_err(|err| format!("PLY load failed: {err}"))?;
+        load_ply_mesh_bytes(&data)
+    }
+}
+
+/// Reads a PLY mesh: the `vertex` element and, when present, a `face` element
+/// with a `vertex_indices` list. Normals, `red`/`green`/`blue` colors and UVs
+/// map to `N`, `Cd` and `uv`; a face `texcoord` list becomes vertex `uv`. Any
+/// other scalar property becomes a point or primitive attribute of the same
+/// name, integer types as int attributes.
+pub fn load_ply_mesh_bytes(data: &[u8]) -> Result<Mesh, String> {
+    let (header, data_start) = parse_header_bytes(data)?;
+    let tables = read_elements(&data[data_start..], &header)?;
+    let vertex_slot = header
+        .elements
+        .iter()
+        .position(|element| element.name == "vertex")
+        .ok_or_else(|| "PLY has no vertex element".to_string())?;
+    let vertex = &header.elements[vertex_slot];
+    let vertex_columns = &tables[vertex_slot];
+    let mut used = BTreeSet::new();
+
+    let [x, y, z] = ["x", "y", "z"].map(|name| scalar_column(vertex, vertex_columns, name));
+    let (Some(x), Some(y), Some(z)) = (x, y, z) else {
+        return Err("PLY is missing vertex positions (x, y, z)".to_string());
+    };
+    used.extend(["x", "y", "z"]);
+    let positions: Vec<[f32; 3]> = (0..vertex.count)
+        .map(|idx| [x[idx] as f32, y[idx] as f32, z[idx] as f32])
+        .collect();
+
+    let face_slot = header
+        .elements
+        .iter()
+        .position(|element| element.name == "face");
+    let mut indices = Vec::new();
+    let mut face_counts = Vec::new();
+    if let Some(slot) = face_slot {
+        let face = &header.elements[slot];
+        let column = FACE_INDEX_NAMES
+            .iter()
+            .filter_map(|name| face.property_index(name))
+            .find_map(|index| {
+                let column = &tables[slot][index];
+                column.counts.as_ref().map(|counts| (column, counts))
+            });
+        let Some((column, counts)) = column else {
+            return Err("PLY face element has no vertex_indices list".to_string());
+        };
+        for value in &column.values {
+            if *value < 0.0 || *value >= positions.len() as f64 {
+                return Err(format!("PLY face references missing vertex {value}"));
+            }
+            indices.push(*value as u32);
+        }
+        face_counts = counts.clone();
+    }
+
+    let mut mesh = Mesh::with_positions_faces(positions, indices, face_counts);
+    if let Some(normals) = vec3_column(vertex, vertex_columns, ["nx", "ny", "nz"], &mut used) {
+        mesh.normals = Some(normals.into());
+    }
+    if let Some(colors) = color_column(vertex, vertex_columns, &mut used) {
+        let _ = mesh.set_attribute(
+            AttributeDomain::Point,
+            "Cd",
+            AttributeStorage::Vec3(colors.into()),
+        );
+    }
+    for (u_name, v_name) in UV_NAMES {
+        let (Some(u), Some(v)) = (
+            scalar_column(vertex, vertex_columns, u_name),
+            scalar_column(vertex, vertex_columns, v_name),
+        ) else {
+            continue;
+        };
+        let uvs: Vec<[f32; 2]> = u
+            .iter()
+            .zip(v.iter())
+            .map(|(u, v)| [*u as f32, *v as f32])
+            .collect();
+        mesh.uvs = Some(uvs.clone().into());
+        let _ = mesh.set_attribute(
+            AttributeDomain::Point,
+            "uv",
+            AttributeStorage::Vec2(uvs.into()),
+        );
+        used.extend([u_name, v_name]);
+        break;
+    }
+    import_scalar_attributes(
+        &mut mesh,
+        AttributeDomain::Point,
+        vertex,
+        vertex_columns,
+        &used,
+    );
+
+    if let Some(slot) = face_slot {
+        let face = &header.elements[slot];
+        let face_columns = &tables[slot];
+        let mut used = BTreeSet::new();
+        if let Some(colors) = color_column(face, face_columns, &mut used) {
+            let _ = mesh.set_attribute(
+                AttributeDomain::Primitive,
+                "Cd",
+                AttributeStorage::Vec3(colors.into()),
+            );
+        }
+        if let Some(column) = face
+            .property_index("texcoord")
+            .map(|index| &face_columns[index])
+        {
+            if column.counts.is_some() && column.values.len() == mesh.indices.len() * 2 {
+                let uvs: Vec<[f32; 2]> = column
+                    .values
+                    .chunks_exact(2)
+                    .map(|uv| [uv[0] as f32, uv[1] as f32])
+                    .collect();
+                let _ = mesh.set_attribute(
+                    AttributeDomain::Vertex,
+                    "uv",
+                    AttributeStorage::Vec2(uvs.into()),
+                );
+            }
+        }
+        import_scalar_attributes(
+            &mut mesh,
+            AttributeDomain::Primitive,
+            face,
+            face_columns,
+            &used,
+        );
+    }
+
+    if mesh.normals.is_none() && !mesh.indices.is_empty() {
+        mesh.compute_normals();
+    }
+    Ok(mesh)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write_ply_mesh(_path: &str, _mesh: &Mesh, _format: PlyMeshFormat) -> Result<(), String> {
+    Err("PLY Output is not supported in web builds".to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_ply_mesh(path: &str, mesh: &Mesh, format: PlyMeshFormat) -> Result<(), String> {
+    std::fs::write(path, encode_ply_mesh(mesh, format)?).map_err(|err| err.to_string())
+}
+
+/// Writes positions, point normals, `Cd` as uchar colors and point `uv` as
+/// `u`/`v`, then polygons as a `vertex_indices` list with primitive `Cd` and
+/// vertex `uv` as a `texcoord` list. Float and int point and primitive
+/// attributes are written as extra scalar properties.
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+fn encode_ply_mesh(mesh: &Mesh, format: PlyMeshFormat) -> Result<Vec<u8>, String> {
+    let point_count = mesh.positions.len();
+    let mut vertex_columns: Vec<(String, PlyScalarType, Vec<f64>)> = Vec::new();
+    for (axis, name) in ["x", "y", "z"].into_iter().enumerate() {
+        let values = mesh.positions.iter().map(|p| p[axis] as f64).collect();
+        vertex_columns.push((name.to_string(), PlyScalarType::Float32, values));
+    }
+    if let Some(normals) = mesh.normals.as_ref().filter(|n| n.len() == point_count) {
+        for (axis, name) in ["nx", "ny", "nz"].into_iter().enumerate() {
+            let values = normals.iter().map(|n| n[axis] as f64).collect();
+            vertex_columns.push((name.to_string(), PlyScalarType::Float32, values));
+        }
+    }
+    if let Some(AttributeRef::Vec3(colors)) = mesh.attribute(AttributeDomain::Point, "Cd") {
+        push_color_columns(&mut vertex_columns, colors);
+    }
+    let point_uvs = mesh
+        .uvs
+        .as_ref()
+        .filter(|uvs| uvs.len() == point_count)
+        .map(|uvs| uvs.to_vec())
+        .or_else(|| match mesh.attribute(AttributeDomain::Point, "uv") {
+            Some(AttributeRef::Vec2(values)) => Some(values.to_vec()),
+            _ => None,
+        });
+    if let Some(uvs) = &point_uvs {
+        for (axis, name) in ["u", "v"].into_iter().enumerate() {
+            let values = uvs.iter().map(|uv| uv[axis] as f64).collect();
+            vertex_columns.push((name.to_string(), PlyScalarType::Float32, values));
+        }
+    }
+    push_scalar_attributes(&mut vertex_columns, mesh, AttributeDomain::Point);
+
+    let mut faces = mesh.clone();
+    faces.ensure_face_counts();
+    let has_faces = !faces.indices.is_empty();
+    let mut face_columns: Vec<(String, PlyScalarType, Vec<f64>)> = Vec::new();
+    let mut corner_uvs = None;
+    if has_faces {
+        if let Some(AttributeRef::Vec3(colors)) = mesh.attribute(AttributeDomain::Primitive, "Cd") {
+            push_color_columns(&mut face_columns, colors);
+        }
+        if point_uvs.is_none() {
+            if let Some(AttributeRef::Vec2(values)) = mesh.attribute(AttributeDomain::Vertex, "uv")
+            {
+                corner_uvs = Some(values);
+            }
+        }
+        push_scalar_attributes(&mut face_columns, mesh, AttributeDomain::Primitive);
+    }
+    let max_face = faces.face_counts.iter().copied().max().unwrap_or(0);
+    let count_type = if max_face * 2 <= u8::MAX as u32 {
+        PlyScalarType::Uint8
+    } else {
+        PlyScalarType::Int32
+    };
+
+    let mut header = String::from("ply\n");
+    header.push_str(match format {
+        PlyMeshFormat::Ascii => "format ascii 1.0\n",
+        PlyMeshFormat::BinaryLittle => "format binary_little_endian 1.0\n",
+    });
+    header.push_str(&format!("element vertex {point_count}\n"));
+    for (name, data_type, _) in &vertex_columns {
+        header.push_str(&format!("property {} {name}\n", data_type.ply_name()));
+    }
+    if has_faces {
+        header.push_str(&format!("element face {}\n", faces.face_counts.len()));
+        header.push_str(&format!(
+            "property list {} int vertex_indices\n",
+            count_type.ply_name()
+        ));
+        if corner_uvs.is_some() {
+            header.push_str(&format!(
+                "property list {} float texcoord\n",
+                count_type.ply_name()
+            ));
+        }
+        for (name, data_type, _) in &face_columns {
+            header.push_str(&format!("property {} {name}\n", data_type.ply_name()));
+        }
+    }
+    header.push_str("end_header\n");
+
+    let mut out = PlyWriter {
+        data: header.into_bytes(),
+        ascii: format == PlyMeshFormat::Ascii,
+    };
+    for idx in 0..point_count {
+        for (_, data_type, values) in &vertex_columns {
+            out.value(*data_type, values[idx]);
+        }
+        out.end_row();
+    }
+    let mut cursor = 0usize;
+    for (face, count) in faces.face_counts.iter().enumerate() {
+        let count = *count as usize;
+        let corners = faces
+            .indices
+            .get(cursor..cursor + count)
+            .ok_or_else(|| "PLY export: face counts exceed indices".to_string())?;
+        out.value(count_type, count as f64);
+        for index in corners {
+            out.value(PlyScalarType::Int32, *index as f64);
+        }
+        if let Some(uvs) = corner_uvs {
+            out.value(count_type, (count * 2) as f64);
+            for uv in &uvs[cursor..cursor + count] {
+                out.value(PlyScalarType::Float32, uv[0] as f64);
+                out.value(PlyScalarType::Float32, uv[1] as f64);
+            }
+        }
+        for (_, data_type, values) in &face_columns {
+            out.value(*data_type, values[face]);
+        }
+        out.end_row();
+        cursor += count;
+    }
+    Ok(out.data)
+}
+
+struct PlyWriter {
+    data: Vec<u8>,
+    ascii: bool,
+}
+
+impl PlyWriter {
+    fn value(&mut self, data_type: PlyScalarType, value: f64) {
+        if self.ascii {
+            let token = match data_type {
+                PlyScalarType::Float32 => (value as f32).to_string(),
+                PlyScalarType::Float64 => value.to_string(),
+                _ => (value.round() as i64).to_string(),
+            };
+            self.data.extend_from_slice(token.as_bytes());
+            self.data.push(b' ');
+            return;
+        }
+        match data_type {
+            PlyScalarType::Int8 => self.data.push(value.round() as i8 as u8),
+            PlyScalarType::Uint8 => self.data.push(value.round() as u8),
+            PlyScalarType::Int16 => self
+                .data
+                .extend_from_slice(&(value.round() as i16).to_le_bytes()),
+            PlyScalarType::Uint16 => self
+                .data
+                .extend_from_slice(&(value.round() as u16).to_le_bytes()),
+            PlyScalarType::Int32 => self
+                .data
+                .extend_from_slice(&(value.round() as i32).to_le_bytes()),
+            PlyScalarType::Uint32 => self
+                .data
+                .extend_from_slice(&(value.round() as u32).to_le_bytes()),
+            PlyScalarType::Float32 => self.data.extend_from_slice(&(value as f32).to_le_bytes()),
+            PlyScalarType::Float64 => self.data.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    fn end_row(&mut self) {
+        if self.ascii {
+            if self.data.last() == Some(&b' ') {
+                self.data.pop();
+            }
+            self.data.push(b'\n');
+        }
+    }
+}
+
+fn read_elements(data: &[u8], header: &PlyHeader) -> Result<Vec<Vec<PlyColumn>>, String> {
+    match header.format {
+        PlyFormat::Ascii => {
+            let text =
+                std::str::from_utf8(data).map_err(|_| "PLY ASCII data is not UTF-8".to_string())?;
+            let mut tokens = text.split_ascii_whitespace();
+            read_rows(header, data.len(), |_| {
+                tokens
+                    .next()
+                    .ok_or_else(|| "Unexpected end of PLY data".to_string())?
+                    .parse::<f64>()
+                    .map_err(|_| "Invalid PLY value".to_string())
+            })
+        }
+        PlyFormat::BinaryLittle | PlyFormat::BinaryBig => {
+            let little_endian = header.format == PlyFormat::BinaryLittle;
+            let mut cursor = 0usize;
+            read_rows(header, data.len(), |data_type| {
+                let end = cursor + data_type.size();
+                let bytes = data
+                    .get(cursor..end)
+                    .ok_or_else(|| "Unexpected end of binary PLY data".to_string())?;
+                cursor = end;
+                read_scalar_f64(bytes, data_type, little_endian)
+            })
+        }
+    }
+}
+
+/// Reads every element in file order, pulling each value from `next`. Every
+/// value takes at least a byte of the `data_len` bytes, which bounds how many
+/// rows are reserved up front whatever counts the header claims.
+fn read_rows(
+    header: &PlyHeader,
+    data_len: usize,
+    mut next: impl FnMut(PlyScalarType) -> Result<f64, String>,
+) -> Result<Vec<Vec<PlyColumn>>, String> {
+    let mut tables = Vec::with_capacity(header.elements.len());
+    for element in &header.elements {
+        let rows = element.count.min(data_len / element.properties.len().max(1));
+        let mut columns: Vec<PlyColumn> = element
+            .properties
+            .iter()
+            .map(|prop| PlyColumn {
+                values: Vec::with_capacity(rows),
+                counts: prop.list_count.map(|_| Vec::with_capacity(rows)),
+            })
+            .collect();
+        for _ in 0..element.count {
+            for (prop, column) in element.properties.iter().zip(columns.iter_mut()) {
+                match (prop.list_count, column.counts.as_mut()) {
+                    (Some(count_type), Some(counts)) => {
+                        let count = next(count_type)?;
+                        if count < 0.0 || count > u32::MAX as f64 {
+                            return Err(format!("Invalid PLY list length {count}"));
+                        }
+                        counts.push(count as u32);
+                        for _ in 0..count as usize {
+                            column.values.push(next(prop.data_type)?);
+                        }
+                    }
+                    _ => column.values.push(next(prop.data_type)?),
+                }
+            }
+        }
+        tables.push(columns);
+    }
+    Ok(tables)
+}
+
+fn scalar_column<'a>(
+    element: &PlyElement,
+    columns: &'a [PlyColumn],
+    name: &str,
+) -> Option<&'a [f64]> {
+    let index = element.property_index(name)?;
+    let column = &columns[index];
+    column.counts.is_none().then_some(column.values.as_slice())
+}
+
+fn vec3_column<'a>(
+    element: &PlyElement,
+    columns: &[PlyColumn],
+    names: [&'a str; 3],
+    used: &mut BTreeSet<&'a str>,
+) -> Option<Vec<[f32; 3]>> {
+    let [a, b, c] = names.map(|name| scalar_column(element, columns, name));
+    let (a, b, c) = (a?, b?, c?);
+    used.extend(names);
+    Some(
+        (0..element.count)
+            .map(|idx| [a[idx] as f32, b[idx] as f32, c[idx] as f32])
+            .collect(),
+    )
+}
+
+/// `red`/`green`/`blue` as 0..1 colors, integer channels scaled by their range.
+fn color_column(
+    element: &PlyElement,
+    columns: &[PlyColumn],
+    used: &mut BTreeSet<&str>,
+) -> Option<Vec<[f32; 3]>> {
+    let names = ["red", "green", "blue"];
+    let mut colors = vec3_column(element, columns, names, used)?;
+    let index = element.property_index("red")?;
+    let scale = match element.properties[index].data_type {
+        PlyScalarType::Uint16 => 1.0 / u16::MAX as f32,
+        PlyScalarType::Float32 | PlyScalarType::Float64 => 1.0,
+        _ => 1.0 / u8::MAX as f32,
+    };
+    for color in &mut colors {
+        *color = color.map(|value| value * scale);
+    }
+    Some(colors)
+}
+
+fn import_scalar_attributes(
+    mesh: &mut Mesh,
+    domain: AttributeDomain,
+    element: &PlyElement,
+    columns: &[PlyColumn],
+    used: &BTreeSet<&str>,
+) {
+    for (prop, column) in element.properties.iter().zip(columns) {
+        if column.counts.is_some() || used.contains(prop.name.as_str()) {
+            continue;
+        }
+        let storage = if prop.data_type.is_integer() {
+            AttributeStorage::Int(column.values.iter().map(|v| *v as i32).collect())
+        } else {
+            AttributeStorage::Float(column.values.iter().map(|v| *v as f32).collect())
+        };
+        let _ = mesh.set_attribute(domain, prop.name.clone(), storage);
+    }
+}
+
+fn push_color_columns(columns: &mut Vec<(String, PlyScalarType, Vec<f64>)>, colors: &[[f32; 3]]) {
+    for (channel, name) in ["red", "green", "blue"].into_iter().enumerate() {
+        let values = colors
+            .iter()
+            .map(|color| (color[channel].clamp(0.0, 1.0) * 255.0).round() as f64)
+            .collect();
+        columns.push((name.to_string(), PlyScalarType::Uint8, values));
+    }
+}
+
+fn push_scalar_attributes(
+    columns: &mut Vec<(String, PlyScalarType, Vec<f64>)>,
+    mesh: &Mesh,
+    domain: AttributeDomain,
+) {
+    let mut names: Vec<&String> = mesh.attributes.map(domain).keys().collect();
+    names.sort();
+    for name in names {
+        if columns.iter().any(|(existing, _, _)| existing == name) || name.contains(' ') {
+            continue;
+        }
+        match mesh.attribute(domain, name) {
+            Some(AttributeRef::Float(values)) => columns.push((
+                name.clone(),
+                PlyScalarType::Float32,
+                values.iter().map(|v| *v as f64).collect(),
+            )),
+            Some(AttributeRef::Int(values)) => columns.push((
+                name.clone(),
+                PlyScalarType::Int32,
+                values.iter().map(|v| *v as f64).collect(),
+            )),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attributes::{AttributeDomain, AttributeRef, AttributeStorage};
+    use crate::mesh::Mesh;
+
+    use super::{encode_ply_mesh, load_ply_mesh_bytes, PlyMeshFormat};
+
+    #[test]
+    fn reads_ascii_polygons_and_extra_properties() {
+        let data = b"ply
+format ascii 1.0
+comment photogrammetry export
+element vertex 5
+property float x
+property float y
+property float z
+property uchar red
+property uchar green
+property uchar blue
+property float quality
+element face 2
+property list uchar int vertex_indices
+property int segment
+end_header
+0 0 0 255 0 0 0.5
+1 0 0 0 255 0 0.25
+1 1 0 0 0 255 1
+0 1 0 255 255 255 0
+2 2 0 0 0 0 0.75
+4 0 1 2 3 7
+3 1 4 2 9
+";
+        let mesh = load_ply_mesh_bytes(data).expect("load");
+        assert_eq!(mesh.positions.len(), 5);
+        assert_eq!(mesh.face_counts.to_vec(), vec![4, 3]);
+        assert_eq!(mesh.indices.to_vec(), vec![0, 1, 2, 3, 1, 4, 2]);
+        assert!(mesh.normals.is_some());
+        let Some(AttributeRef::Vec3(colors)) = mesh.attribute(AttributeDomain::Point, "Cd") else {
+            panic!("missing Cd");
+        };
+        assert_eq!(colors[1], [0.0, 1.0, 0.0]);
+        let Some(AttributeRef::Float(quality)) = mesh.attribute(AttributeDomain::Point, "quality")
+        else {
+            panic!("missing quality");
+        };
+        assert_eq!(quality[4], 0.75);
+        let Some(AttributeRef::Int(segment)) =
+            mesh.attribute(AttributeDomain::Primitive, "segment")
+        else {
+            panic!("missing segment");
+        };
+        assert_eq!(segment.to_vec(), vec![7, 9]);
+        assert!(mesh.attribute(AttributeDomain::Point, "red").is_none());
+    }
+
+    #[test]
+    fn rejects_counts_past_the_data() {
+        let data = b"ply
+format binary_little_endian 1.0
+element vertex 4000000000
+property float x
+property float y
+property float z
+end_header
+";
+        let err = load_ply_mesh_bytes(data).unwrap_err();
+        assert!(err.contains("Unexpected end"), "{err}");
+    }
+
+    #[test]
+    fn binary_and_ascii_roundtrip() {
+        let mut mesh = Mesh::with_positions_faces(
+            vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.5],
+                [0.0, 1.0, 0.0],
+            ],
+            vec![0, 1, 2, 3],
+            vec![4],
+        );
+        mesh.normals = Some(vec![[0.0, 0.0, 1.0]; 4].into());
+        mesh.set_attribute(
+            AttributeDomain::Point,
+            "weight",
+            AttributeStorage::Float(vec![0.1, 0.2, 0.3, 0.4].into()),
+        )
+        .unwrap();
+        mesh.set_attribute(
+            AttributeDomain::Vertex,
+            "uv",
+            AttributeStorage::Vec2(vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]].into()),
+        )
+        .unwrap();
+        mesh.set_attribute(
+            AttributeDomain::Primitive,
+            "Cd",
+            AttributeStorage::Vec3(vec![[1.0, 0.5, 0.0]].into()),
+        )
+        .unwrap();
+
+        for format in [PlyMeshFormat::BinaryLittle, PlyMeshFormat::Ascii] {
+            let data = encode_ply_mesh(&mesh, format).expect("encode");
+            let loaded = load_ply_mesh_bytes(&data).expect("load");
+            assert_eq!(loaded.positions, mesh.positions);
+            assert_eq!(loaded.indices, mesh.indices);
+            assert_eq!(loaded.face_counts, mesh.face_counts);
+            assert_eq!(loaded.normals, mesh.normals);
+            assert_eq!(
+                loaded.attribute(AttributeDomain::Point, "weight"),
+                mesh.attribute(AttributeDomain::Point, "weight")
+            );
+            assert_eq!(
+                loaded.attribute(AttributeDomain::Vertex, "uv"),
+                mesh.attribute(AttributeDomain::Vertex, "uv")
+            );
+            let Some(AttributeRef::Vec3(colors)) =
+                loaded.attribute(AttributeDomain::Primitive, "Cd")
+            else {
+                panic!("missing primitive Cd");
+            };
+            assert!((colors[0][1] - 0.5).abs() < 1.0 / 255.0);
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+
+use crate::mesh::Mesh;
+
+const STL_HEADER_BYTES: usize = 80;
+const STL_FACET_BYTES: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StlFormat {
+    Binary,
+    Ascii,
+}
+
+impl StlFormat {
+    /// Format for the STL Output node's `format` parameter.
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => Self::Ascii,
+            _ => Self::Binary,
+        }
+    }
+}
+
+pub fn load_stl_mesh(path: &str) -> Result<Mesh, String> {
+    if let Some(data) = crate::assets::load_bytes(path) {
+        return load_stl_mesh_bytes(&data);
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        if crate::assets::is_url(path) {
+            return Err("STL URL is downloading; retrying shortly.".to_string());
+        }
+        return Err("STL import is not supported in web builds without a picked file".to_string());
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if crate::assets::is_url(path) {
+            return Err(format!("Failed to download URL: {path}"));
+        }
+        let data = std::fs::read(path).map_err(|err| format!("STL load failed: {err}"))?;
+        load_stl_mesh_bytes(&data)
+    }
+}
+
+/// Reads binary or ASCII STL. Facets share corners by exact position, so the
+/// result is a connected mesh rather than a triangle soup; stored facet
+/// normals are ignored and point normals recomputed.
+pub fn load_stl_mesh_bytes(data: &[u8]) -> Result<Mesh, String> {
+    // Some exporters pad binary files past the last facet, so a longer file
+    // is binary too when it doesn't read as ASCII.
+    let padded_binary = binary_stl_len(data).is_some_and(|len| len <= data.len());
+    let facets = if is_binary_stl(data) {
+        parse_binary_facets(data)?
+    } else if data.trim_ascii_start().starts_with(b"solid") {
+        match parse_ascii_facets(data) {
+            Ok(facets) if !facets.is_empty() => facets,
+            _ if padded_binary => parse_binary_facets(data)?,
+            result => result?,
+        }
+    } else if padded_binary {
+        parse_binary_facets(data)?
+    } else {
+        return Err("Not an STL file".to_string());
+    };
+    if facets.is_empty() {
+        return Err("STL has no facets".to_string());
+    }
+
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut lookup: HashMap<[u32; 3], u32> = HashMap::new();
+    let mut indices = Vec::new();
+    let mut face_counts = Vec::with_capacity(facets.len());
+    for facet in &facets {
+        for corner in facet {
+            // -0.0 and 0.0 are the same point.
+            let key = corner.map(|value| (value + 0.0).to_bits());
+            let index = *lookup.entry(key).or_insert_with(|| {
+                positions.push(*corner);
+                positions.len() as u32 - 1
+            });
+            indices.push(index);
+        }
+        face_counts.push(facet.len() as u32);
+    }
+
+    let mut mesh = Mesh::with_positions_faces(positions, indices, face_counts);
+    mesh.compute_normals();
+    Ok(mesh)
+}
+
+/// Whether `data` is binary STL or starts like ASCII STL.
+pub(crate) fn looks_like_stl(data: &[u8]) -> bool {
+    is_binary_stl(data) || data.trim_ascii_start().starts_with(b"solid")
+}
+
+/// Binary STL when the facet count in the header matches the file size; some
+/// exporters start binary headers with "solid" too.
+fn is_binary_stl(data: &[u8]) -> bool {
+    binary_stl_len(data) == Some(data.len())
+}
+
+/// Size of a binary STL with the facet count stored in `data`'s header.
+fn binary_stl_len(data: &[u8]) -> Option<usize> {
+    if data.len() < STL_HEADER_BYTES + 4 {
+        return None;
+    }
+    let count = read_u32(data, STL_HEADER_BYTES) as usize;
+    count
+        .checked_mul(STL_FACET_BYTES)
+        .and_then(|bytes| bytes.checked_add(STL_HEADER_BYTES + 4))
+}
+
+fn parse_binary_facets(data: &[u8]) -> Result<Vec<Vec<[f32; 3]>>, String> {
+    let count = read_u32(data, STL_HEADER_BYTES) as usize;
+    let mut facets = Vec::with_capacity(count);
+    for facet in 0..count {
+        let offset = STL_HEADER_BYTES + 4 + facet * STL_FACET_BYTES;
+        // Skip the 12-byte normal; the attribute byte count is unused.
+        let corners = (0..3)
+            .map(|corner| {
+                let start = offset + 12 + corner * 12;
+                std::array::from_fn(|axis| f32::from_bits(read_u32(data, start + axis * 4)))
+            })
+            .collect();
+        facets.push(corners);
+    }
+    Ok(facets)
+}
+
+fn parse_ascii_facets(data: &[u8]) -> Result<Vec<Vec<[f32; 3]>>, String> {
+    let text = std::str::from_utf8(data).map_err(|_| "STL ASCII data is not UTF-8".to_string())?;
+    let mut tokens = text.split_ascii_whitespace();
+    let mut facets = Vec::new();
+    let mut current: Option<Vec<[f32; 3]>> = None;
+    while let Some(token) = tokens.next() {
+        match token {
+            "facet" => current = Some(Vec::with_capacity(3)),
+            "vertex" => {
+                let Some(corners) = current.as_mut() else {
+                    return Err("STL vertex outside a facet".to_string());
+                };
+                let mut corner = [0.0f32; 3];
+                for value in &mut corner {
+                    *value = tokens
+                        .next()
+                        .and_then(|token| token.parse::<f32>().ok())
+                        .ok_or_else(|| "Invalid STL vertex".to_string())?;
+                }
+                corners.push(corner);
+            }
+            "endfacet" => {
+                let corners = current
+                    .take()
+                    .ok_or_else(|| "STL endfacet without facet".to_string())?;
+                if corners.len() < 3 {
+                    return Err("STL facet has fewer than 3 vertices".to_string());
+                }
+                facets.push(corners);
+            }
+            _ => {}
+        }
+    }
+    if current.is_some() {
+        return Err("Unexpected end of STL data".to_string());
+    }
+    Ok(facets)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write_stl(
+    _path: &str,
+    _mesh: &Mesh,
+    _format: StlFormat,
+    _facet_normals: bool,
+) -> Result<(), String> {
+    Err("STL Output is not supported in web builds".to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_stl(
+    path: &str,
+    mesh: &Mesh,
+    format: StlFormat,
+    facet_normals: bool,
+) -> Result<(), String> {
+    std::fs::write(path, encode_stl(mesh, format, facet_normals)?).map_err(|err| err.to_string())
+}
+
+/// Writes the mesh triangulated. Facet normals come from the triangle winding,
+/// or are zero when `facet_normals` is off, which readers treat as "compute
+/// from the winding".
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+fn encode_stl(mesh: &Mesh, format: StlFormat, facet_normals: bool) -> Result<Vec<u8>, String> {
+    let triangles = mesh.triangulate().indices;
+    if triangles.is_empty() {
+        return Err("STL export requires polygons".to_string());
+    }
+    let corners = |tri: &[u32]| -> Result<[[f32; 3]; 3], String> {
+        let mut out = [[0.0; 3]; 3];
+        for (slot, index) in tri.iter().enumerate() {
+            out[slot] = *mesh
+                .positions
+                .get(*index as usize)
+                .ok_or_else(|| format!("STL export: missing point {index}"))?;
+        }
+        Ok(out)
+    };
+    let normal = |corners: &[[f32; 3]; 3]| -> [f32; 3] {
+        if !facet_normals {
+            return [0.0; 3];
+        }
+        let [a, b, c] = corners.map(Vec3::from);
+        (b - a).cross(c - a).normalize_or_zero().to_array()
+    };
+
+    match format {
+        StlFormat::Binary => {
+            let count = triangles.len() / 3;
+            let mut data = Vec::with_capacity(STL_HEADER_BYTES + 4 + count * STL_FACET_BYTES);
+            let mut header = [0u8; STL_HEADER_BYTES];
+            let label = b"lobedo binary STL";
+            header[..label.len()].copy_from_slice(label);
+            data.extend_from_slice(&header);
+            data.extend_from_slice(&(count as u32).to_le_bytes());
+            for tri in triangles.chunks_exact(3) {
+                let corners = corners(tri)?;
+                for value in normal(&corners).iter().chain(corners.iter().flatten()) {
+                    data.extend_from_slice(&value.to_le_bytes());
+                }
+                data.extend_from_slice(&0u16.to_le_bytes());
+            }
+            Ok(data)
+        }
+        StlFormat::Ascii => {
+            let mut text = String::from("solid lobedo\n");
+            for tri in triangles.chunks_exact(3) {
+                let corners = corners(tri)?;
+                let [nx, ny, nz] = normal(&corners);
+                text.push_str(&format!("  facet normal {nx} {ny} {nz}\n    outer loop\n"));
+                for [x, y, z] in corners {
+                    text.push_str(&format!("      vertex {x} {y} {z}\n"));
+                }
+                text.push_str("    endloop\n  endfacet\n");
+            }
+            text.push_str("endsolid lobedo\n");
+            Ok(text.into_bytes())
+        }
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_stl, load_stl_mesh_bytes, StlFormat};
+    use crate::mesh::make_box;
+
+    #[test]
+    fn roundtrip_welds_shared_corners() {
+        let mesh = make_box([1.0, 2.0, 3.0]);
+        let triangles = mesh.triangulate().indices.len() / 3;
+        for format in [StlFormat::Binary, StlFormat::Ascii] {
+            let data = encode_stl(&mesh, format, true).expect("encode");
+            let loaded = load_stl_mesh_bytes(&data).expect("load");
+            assert_eq!(loaded.positions.len(), 8);
+            assert_eq!(loaded.face_counts.len(), triangles);
+            assert!(loaded.normals.is_some());
+        }
+    }
+
+    #[test]
+    fn reads_binary_starting_with_solid() {
+        let mesh = make_box([1.0, 1.0, 1.0]);
+        let mut data = encode_stl(&mesh, StlFormat::Binary, false).expect("encode");
+        data[..5].copy_from_slice(b"solid");
+        let loaded = load_stl_mesh_bytes(&data).expect("load");
+        assert_eq!(loaded.positions.len(), 8);
+
+        data.extend_from_slice(&[0u8; 16]);
+        let padded = load_stl_mesh_bytes(&data).expect("load padded");
+        assert_eq!(padded.positions.len(), 8);
+        assert_eq!(padded.face_counts.len(), loaded.face_counts.len());
+        assert!(load_stl_mesh_bytes(b"solid empty\nendsolid empty\n").is_err());
+    }
+}
@@ -7,6 +7,7 @@ pub struct NodeHelpPage {
     pub parameters: &'static [(&'static str, &'static str)],
 }
 
+use crate::node_registry::node_type;
 use crate::nodes_builtin::{builtin_kind_from_id, builtin_kind_from_name, BuiltinNodeKind};
 use crate::{node_help_io, node_help_splats, node_help_volumes};
 
@@ -19,9 +20,12 @@ pub fn help_summary(kind: BuiltinNodeKind) -> Option<&'static str> {
         .and_then(|page| page.description.first().copied())
 }
 
+/// Help page for a built-in kind id or name, or a registered plugin kind id.
 pub fn node_help_page(help_key: &str) -> Option<NodeHelpPage> {
-    let kind = resolve_kind(help_key)?;
-    node_help_page_for_kind(kind)
+    match resolve_kind(help_key) {
+        Some(kind) => node_help_page_for_kind(kind),
+        None => node_type(help_key)?.help_page(),
+    }
 }
 
 pub fn node_help_page_for_kind(kind: BuiltinNodeKind) -> Option<NodeHelpPage> {
@@ -159,12 +163,18 @@ pub fn node_help_page_for_kind(kind: BuiltinNodeKind) -> Option<NodeHelpPage> {
                 "Mesh-Mesh mode runs a manifold boolean and interpolates attributes from the source surfaces.",
                 "Mesh-SDF mode clips the mesh against an SDF volume while keeping the input mesh triangles (no remeshing).",
                 "Use it when you need attribute fidelity or topology that follows the original mesh.",
+                "The piece outputs always hold A - B, B - A and A ∩ B regardless of the operation, so one node can feed all three.",
             ],
             inputs: &[
                 "a: Mesh geometry to keep/modify.",
                 "b: Mesh or SDF volume used as the cutter.",
             ],
-            outputs: &["out: Booleaned mesh (other primitives pass through)."],
+            outputs: &[
+                "out: Booleaned mesh (other primitives pass through).",
+                "a_only: Part of A outside B.",
+                "b_only: Part of B outside A (empty with an SDF cutter).",
+                "intersection: Part of A inside B.",
+            ],
             parameters: &[
                 ("mode", "Auto picks Mesh-Mesh unless input B has an SDF volume; Mesh-SDF forces using an SDF volume."),
                 ("op", "Operation: Union, Difference (A - B), or Intersect."),
@@ -209,6 +219,169 @@ pub fn node_help_page_for_kind(kind: BuiltinNodeKind) -> Option<NodeHelpPage> {
                 ("group_type", "Group domain to use."),
             ],
         }),
+        BuiltinNodeKind::Split => Some(NodeHelpPage {
+            name: "Split",
+            description: &[
+                "Splits geometry into the part inside a group and the part outside it, similar to Houdini Split.",
+                "Triangles with only some of their points in the group go to the non-matching output.",
+                "Both outputs can be wired and displayed independently; an empty group sends everything to matching.",
+            ],
+            inputs: &["in: Geometry to split."],
+            outputs: &[
+                "matching: Geometry in the group.",
+                "nonmatching: Everything else.",
+            ],
+            parameters: &[
+                ("group", "Group sent to the matching output."),
+                ("group_type", "Group domain to use."),
+            ],
+        }),
+        BuiltinNodeKind::Subnet => Some(NodeHelpPage {
+            name: "Subnet",
+            description: &[
+                "Wraps a network of nodes into a single node, created with Collapse into subnet in the node menu.",
+                "Its pins come from the Subnet Input and Subnet Output nodes inside, ordered by their index.",
+                "Inner parameters can be promoted to the subnet's parameter panel, and the subnet can be exported as an asset and added to other projects from the Assets menu.",
+            ],
+            inputs: &["in: One input per Subnet Input node."],
+            outputs: &["out: One output per Subnet Output node."],
+            parameters: &[],
+        }),
+        BuiltinNodeKind::SubnetInput => Some(NodeHelpPage {
+            name: "Subnet Input",
+            description: &[
+                "Brings geometry wired into the subnet node into its inner network.",
+            ],
+            inputs: &[],
+            outputs: &["out: Geometry on the matching subnet input."],
+            parameters: &[
+                ("index", "Position of the pin on the subnet node."),
+                ("label", "Pin name shown on the subnet node."),
+            ],
+        }),
+        BuiltinNodeKind::SubnetOutput => Some(NodeHelpPage {
+            name: "Subnet Output",
+            description: &[
+                "Sends geometry from the inner network to an output of the subnet node.",
+            ],
+            inputs: &["in: Geometry to output."],
+            outputs: &[],
+            parameters: &[
+                ("index", "Position of the pin on the subnet node."),
+                ("label", "Pin name shown on the subnet node."),
+            ],
+        }),
+        BuiltinNodeKind::ForEachBegin => Some(NodeHelpPage {
+            name: "For-Each Begin",
+            description: &[
+                "Starts a for-each loop; the nodes between it and the matching For-Each End run once per iteration.",
+                "Iterates over the values of a piece attribute, over every primitive, or a fixed number of times with each result fed into the next iteration.",
+                "Each iteration's geometry carries the iteration, numiterations and value detail attributes. On its own the node shows the first iteration.",
+            ],
+            inputs: &["in: Geometry to loop over."],
+            outputs: &["out: Geometry for the current iteration."],
+            parameters: &[
+                ("mode", "Piece attribute, primitive, or count iteration."),
+                ("attribute", "Int or string primitive (or point) attribute naming each piece."),
+                ("iterations", "Number of iterations in count mode."),
+            ],
+        }),
+        BuiltinNodeKind::ForEachEnd => Some(NodeHelpPage {
+            name: "For-Each End",
+            description: &[
+                "Ends a for-each loop started by the nearest For-Each Begin upstream.",
+                "Merges the results of every iteration, or outputs the last one in count mode.",
+                "Changing any node inside the loop recooks the whole loop.",
+            ],
+            inputs: &["in: Result of one iteration."],
+            outputs: &["out: Combined loop result."],
+            parameters: &[],
+        }),
+        BuiltinNodeKind::Switch => Some(NodeHelpPage {
+            name: "Switch",
+            description: &[
+                "Passes one of its inputs through, picked by index or by an int detail attribute on the first input.",
+                "Only the selected branch is cooked; the other inputs are not evaluated and their errors do not affect the result.",
+                "In Detail Attribute mode the first input is always cooked to read the attribute, even when another input is selected.",
+                "Drive the index with an expression (for example $F % 2) to switch over time.",
+            ],
+            inputs: &["in0..n: Alternative branches; a new input appears when the last one is connected."],
+            outputs: &["out: The selected input."],
+            parameters: &[
+                ("mode", "Pick by index or by detail attribute."),
+                ("index", "Input to pass through, counted from 0 and clamped to the inputs."),
+                ("attribute", "Int detail attribute on the first input holding the index."),
+            ],
+        }),
+        BuiltinNodeKind::Constant => Some(NodeHelpPage {
+            name: "Constant",
+            description: &[
+                "Outputs a single float, int, bool or vector value.",
+                "Wire it into a parameter input to share one value between several nodes.",
+            ],
+            inputs: &[],
+            outputs: &["value: The constant, converted to the type of the parameter it drives."],
+            parameters: &[
+                ("type", "Type of the value."),
+                ("float / int / bool / vec2 / vec3", "The value for the selected type."),
+            ],
+        }),
+        BuiltinNodeKind::ValueMath => Some(NodeHelpPage {
+            name: "Value Math",
+            description: &[
+                "Combines two float values with an arithmetic operation.",
+                "Dividing by zero is an error rather than producing infinity.",
+            ],
+            inputs: &[
+                "a: Optional value overriding the A parameter.",
+                "b: Optional value overriding the B parameter.",
+            ],
+            outputs: &["value: Result of the operation."],
+            parameters: &[
+                ("op", "Add, subtract, multiply, divide, min, max or power."),
+                ("a", "First operand."),
+                ("b", "Second operand."),
+            ],
+        }),
+        BuiltinNodeKind::RandomValue => Some(NodeHelpPage {
+            name: "Random Value",
+            description: &[
+                "Outputs a random float between min and max.",
+                "The value only changes with the seed, so cooks are repeatable.",
+            ],
+            inputs: &[],
+            outputs: &["value: The random value."],
+            parameters: &[
+                ("seed", "Seed of the random value."),
+                ("min", "Lowest value."),
+                ("max", "Highest value."),
+            ],
+        }),
+        BuiltinNodeKind::DetailToValue => Some(NodeHelpPage {
+            name: "Detail to Value",
+            description: &[
+                "Reads a detail attribute from the input geometry as a value.",
+                "Vector attributes output a vector; the value converts to the type of the parameter it drives.",
+            ],
+            inputs: &["in: Geometry holding the detail attribute."],
+            outputs: &["value: The attribute value."],
+            parameters: &[("attribute", "Name of the detail attribute to read.")],
+        }),
+        BuiltinNodeKind::BoundingBox => Some(NodeHelpPage {
+            name: "Bounding Box",
+            description: &[
+                "Measures the axis-aligned bounds of the input geometry.",
+                "Useful to size or place other geometry relative to the input.",
+            ],
+            inputs: &["in: Geometry to measure."],
+            outputs: &[
+                "size: Extent along each axis.",
+                "center: Center of the bounds.",
+                "min: Lowest corner.",
+                "max: Highest corner.",
+            ],
+            parameters: &[],
+        }),
         BuiltinNodeKind::Group => Some(NodeHelpPage {
             name: "Group",
             description: &[
@@ -321,8 +494,9 @@ pub fn node_help_page_for_kind(kind: BuiltinNodeKind) -> Option<NodeHelpPage> {
                 "Merges all incoming geometry streams into one output.",
                 "Point pools and primitive arrays are concatenated and attributes are reconciled.",
                 "Use this to recombine branches or add multiple sources together.",
+                "A new input appears whenever the last one is connected; reorder inputs in the inspector.",
             ],
-            inputs: &["input0..n: Geometry to merge."],
+            inputs: &["in0..n: Geometry to merge."],
             outputs: &["out: Merged geometry."],
             parameters: &[],
         }),
@@ -7,13 +7,15 @@ pub fn node_help_page(kind: BuiltinNodeKind) -> Option<NodeHelpPage> {
         BuiltinNodeKind::File => Some(NodeHelpPage {
             name: "File",
             description: &[
-                "Reads mesh geometry from OBJ or glTF/GLB files on disk or via URL.",
+                "Reads mesh geometry from OBJ, glTF/GLB, PLY or STL files on disk or via URL.",
                 "Positions, indices, normals, and UVs are imported when present.",
+                "PLY polygons are kept as-is; vertex colors become Cd and any other vertex or face property becomes a point or primitive attribute of the same name.",
+                "STL facets (ASCII or binary) are welded into shared points by exact position, and point normals are recomputed.",
                 "Materials are mapped into a primitive material attribute when available.",
             ],
             inputs: &[],
             outputs: &["out: Mesh geometry from file."],
-            parameters: &[("path", "Path or URL to an OBJ or glTF/GLB file.")],
+            parameters: &[("path", "Path or URL to an OBJ, glTF/GLB, PLY or STL file.")],
         }),
         BuiltinNodeKind::ObjOutput => Some(NodeHelpPage {
             name: "OBJ Output",
@@ -29,14 +31,44 @@ pub fn node_help_page(kind: BuiltinNodeKind) -> Option<NodeHelpPage> {
         BuiltinNodeKind::GltfOutput => Some(NodeHelpPage {
             name: "GLTF Output",
             description: &[
-                "Exports mesh geometry to glTF/GLB.",
-                "The exporter includes material parameters and UVs when present.",
+                "Exports mesh and curve geometry to glTF/GLB.",
+                "Each material in the primitive material attribute becomes its own primitive with a metallic-roughness material; base color textures (PNG or JPEG) are embedded in the file.",
+                "Normals, UVs and Cd are written as standard attributes, other point attributes as custom _name float attributes, and curves as line primitives.",
                 "Writing is performed when the Write button is pressed.",
             ],
             inputs: &["in: Mesh geometry to write."],
             outputs: &["out: Pass-through geometry."],
             parameters: &[("path", "Output glTF/GLB file path.")],
         }),
+        BuiltinNodeKind::PlyOutput => Some(NodeHelpPage {
+            name: "PLY Output",
+            description: &[
+                "Exports mesh geometry to a PLY file with polygon faces.",
+                "Normals, Cd colors (as 8-bit red/green/blue), UVs and float or int point and primitive attributes are written when present.",
+                "Writing is performed when the Write button is pressed.",
+            ],
+            inputs: &["in: Mesh geometry to write."],
+            outputs: &["out: Pass-through geometry."],
+            parameters: &[
+                ("path", "Output PLY file path."),
+                ("format", "Binary (smaller) or ASCII."),
+            ],
+        }),
+        BuiltinNodeKind::StlOutput => Some(NodeHelpPage {
+            name: "STL Output",
+            description: &[
+                "Exports mesh geometry to an STL file as triangles.",
+                "STL stores positions only; polygons are triangulated and attributes are dropped.",
+                "Writing is performed when the Write button is pressed.",
+            ],
+            inputs: &["in: Mesh geometry to write."],
+            outputs: &["out: Pass-through geometry."],
+            parameters: &[
+                ("path", "Output STL file path."),
+                ("format", "Binary (smaller) or ASCII."),
+                ("facet_normals", "Write per-facet normals from the triangle winding; when off, normals are zero."),
+            ],
+        }),
         BuiltinNodeKind::Output => Some(NodeHelpPage {
             name: "Output",
             description: &[
@@ -7,8 +7,11 @@ pub fn node_help_page(kind: BuiltinNodeKind) -> Option<NodeHelpPage> {
         BuiltinNodeKind::ReadSplats => Some(NodeHelpPage {
             name: "Splat Read",
             description: &[
-                "Loads Gaussian splats from a PLY file and produces splat primitives.",
-                "The reader expects 3DGS-style fields: position, rotation (quaternion), scale (log sigma), opacity (logit), and SH coefficients.",
+                "Loads Gaussian splats from a PLY, .splat, .ksplat or .spz file and produces splat primitives.",
+                "PLY files use 3DGS-style fields: position, rotation (quaternion), scale (log sigma), opacity (logit), and SH coefficients.",
+                ".splat (antimatter15) and .ksplat (GaussianSplats3D) are quantized web formats: colors and opacity are 8-bit, .splat rotations are 8-bit per component and carry no SH.",
+                "Compressed PLY (SuperSplat) and .spz (Niantic) are also read; compressed PLY is recognized by its chunk element.",
+                "The format is picked from the file contents or extension.",
                 "On load, values are validated and normalized so scales and opacity stay in a stable range.",
                 "Full SH keeps all bands for relighting and delighting workflows.",
                 "Color Only keeps just the DC color/alpha and zeros higher bands for faster loading and lower memory use.",
@@ -16,23 +19,26 @@ pub fn node_help_page(kind: BuiltinNodeKind) -> Option<NodeHelpPage> {
             inputs: &[],
             outputs: &["out: Splat geometry."],
             parameters: &[
-                ("path", "Path or URL to a splat PLY file."),
+                ("path", "Path or URL to a splat PLY, .splat, .ksplat or .spz file."),
                 ("read_mode", "Full SH keeps all SH bands; Color Only keeps only DC color/alpha."),
             ],
         }),
         BuiltinNodeKind::WriteSplats => Some(NodeHelpPage {
             name: "Splat Write",
             description: &[
-                "Writes Gaussian splats to a PLY file in a 3DGS-compatible layout.",
+                "Writes Gaussian splats to a PLY file in a 3DGS-compatible layout, or to the quantized .splat, .ksplat, compressed PLY and .spz formats.",
                 "The node passes geometry through; writing happens only when the Write button is pressed.",
                 "On save, splats are normalized so external viewers see stable scales and opacity.",
                 "Binary is faster and smaller; ASCII is useful for debugging or interchange.",
+                ".splat drops SH and stores color, opacity and rotation in 8 bits; .ksplat keeps SH as 16-bit floats and positions to about 1e-4 units.",
+                "Compressed PLY packs every 256 splats against their own min/max ranges and stores SH in 8 bits, roughly a quarter of binary PLY size; splats are reordered spatially.",
+                ".spz is gzipped, keeps positions to 1/4096 units and 8-bit color, scale and rotation, and quantizes SH more coarsely.",
             ],
             inputs: &["in: Splat geometry to write."],
             outputs: &["out: Pass-through geometry."],
             parameters: &[
-                ("path", "Output PLY file path."),
-                ("format", "PLY format: Binary (recommended) or ASCII."),
+                ("path", "Output splat file path."),
+                ("format", "Binary PLY (recommended), ASCII PLY, .splat, .ksplat, Compressed PLY or .spz."),
             ],
         }),
         BuiltinNodeKind::Prune => Some(NodeHelpPage {
@@ -0,0 +1,385 @@
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::geometry::Geometry;
+use crate::graph::{Graph, Node, NodeDefinition, NodeId, NodeParams, ParamValue};
+use crate::node_help::{node_help_page_for_kind, NodeHelpPage};
+use crate::nodes_builtin::{
+    builtin_kind_from_name, compute_geometry_outputs, compute_value_outputs, default_params,
+    input_policy, menu_group, node_definition, node_specs, param_specs, BuiltinNodeKind,
+    InputPolicy,
+};
+use crate::param_spec::ParamSpec;
+
+/// Namespace of the kinds that ship with lobedo; plugins pick their own.
+pub const BUILTIN_NAMESPACE: &str = "builtin";
+
+/// A node kind the evaluator, the add-node menu and the help pages can use.
+/// Plugins implement it and register it at startup with [`register_node_type`].
+pub trait NodeType: Send + Sync {
+    /// Namespaced id stored in projects, such as `studio:scatter_rocks`.
+    fn kind_id(&self) -> &str;
+    fn definition(&self) -> NodeDefinition;
+    fn default_params(&self) -> NodeParams {
+        NodeParams::default()
+    }
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        Vec::new()
+    }
+    /// Which inputs must be connected before the node cooks.
+    fn input_policy(&self) -> InputPolicy {
+        InputPolicy::RequireAll
+    }
+    /// Submenu of the add-node menu the node is listed under.
+    fn menu_group(&self) -> Option<&'static str> {
+        None
+    }
+    fn help_page(&self) -> Option<NodeHelpPage> {
+        None
+    }
+    /// Cooks one geometry per output pin.
+    fn compute_geometry(
+        &self,
+        params: &NodeParams,
+        inputs: &[Geometry],
+    ) -> Result<Vec<Geometry>, String>;
+    /// Like `compute_geometry`, told which outputs something reads so the
+    /// others can be left empty.
+    fn compute_demanded_geometry(
+        &self,
+        params: &NodeParams,
+        inputs: &[Geometry],
+        _demanded: &[bool],
+    ) -> Result<Vec<Geometry>, String> {
+        self.compute_geometry(params, inputs)
+    }
+    /// Cooks the outputs of value nodes; `None` for geometry nodes.
+    fn compute_values(
+        &self,
+        _params: &NodeParams,
+        _inputs: &[Geometry],
+    ) -> Option<Result<Vec<ParamValue>, String>> {
+        None
+    }
+    /// The built-in kind behind this type, for the nodes the evaluator cooks itself
+    /// (switches, loops and subnets).
+    fn builtin_kind(&self) -> Option<BuiltinNodeKind> {
+        None
+    }
+}
+
+struct BuiltinNode(BuiltinNodeKind);
+
+impl NodeType for BuiltinNode {
+    fn kind_id(&self) -> &str {
+        self.0.id()
+    }
+
+    fn definition(&self) -> NodeDefinition {
+        node_definition(self.0)
+    }
+
+    fn default_params(&self) -> NodeParams {
+        default_params(self.0)
+    }
+
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        param_specs(self.0)
+    }
+
+    fn input_policy(&self) -> InputPolicy {
+        input_policy(self.0)
+    }
+
+    fn menu_group(&self) -> Option<&'static str> {
+        menu_group(self.0)
+    }
+
+    fn help_page(&self) -> Option<NodeHelpPage> {
+        node_help_page_for_kind(self.0)
+    }
+
+    fn compute_geometry(
+        &self,
+        params: &NodeParams,
+        inputs: &[Geometry],
+    ) -> Result<Vec<Geometry>, String> {
+        compute_geometry_outputs(self.0, params, inputs, &[])
+    }
+
+    fn compute_demanded_geometry(
+        &self,
+        params: &NodeParams,
+        inputs: &[Geometry],
+        demanded: &[bool],
+    ) -> Result<Vec<Geometry>, String> {
+        compute_geometry_outputs(self.0, params, inputs, demanded)
+    }
+
+    fn compute_values(
+        &self,
+        params: &NodeParams,
+        inputs: &[Geometry],
+    ) -> Option<Result<Vec<ParamValue>, String>> {
+        compute_value_outputs(self.0, params, inputs)
+    }
+
+    fn builtin_kind(&self) -> Option<BuiltinNodeKind> {
+        Some(self.0)
+    }
+}
+
+/// Built-in node kinds followed by the ones registered at runtime, in
+/// registration order.
+#[derive(Clone)]
+pub struct NodeRegistry {
+    types: Vec<Arc<dyn NodeType>>,
+}
+
+impl Default for NodeRegistry {
+    fn default() -> Self {
+        Self {
+            types: node_specs()
+                .iter()
+                .map(|spec| Arc::new(BuiltinNode(spec.kind)) as Arc<dyn NodeType>)
+                .collect(),
+        }
+    }
+}
+
+impl NodeRegistry {
+    pub fn register(&mut self, node_type: Arc<dyn NodeType>) -> Result<(), String> {
+        let kind_id = node_type.kind_id();
+        validate_kind_id(kind_id)?;
+        if self.get(kind_id).is_some() {
+            return Err(format!("node kind {} is already registered", kind_id));
+        }
+        self.types.push(node_type);
+        Ok(())
+    }
+
+    pub fn get(&self, kind_id: &str) -> Option<Arc<dyn NodeType>> {
+        self.types
+            .iter()
+            .find(|node_type| node_type.kind_id() == kind_id)
+            .cloned()
+    }
+
+    pub fn types(&self) -> &[Arc<dyn NodeType>] {
+        &self.types
+    }
+}
+
+/// Kind ids are `namespace:name`; the `builtin` namespace is reserved.
+fn validate_kind_id(kind_id: &str) -> Result<(), String> {
+    let valid_part = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    };
+    match kind_id.split_once(':') {
+        Some((namespace, name)) if valid_part(namespace) && valid_part(name) => {
+            if namespace == BUILTIN_NAMESPACE {
+                Err(format!(
+                    "node kind {}: the '{}' namespace is reserved",
+                    kind_id, BUILTIN_NAMESPACE
+                ))
+            } else {
+                Ok(())
+            }
+        }
+        _ => Err(format!(
+            "node kind {} must look like namespace:name",
+            kind_id
+        )),
+    }
+}
+
+static REGISTRY: OnceLock<RwLock<NodeRegistry>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<NodeRegistry> {
+    REGISTRY.get_or_init(|| RwLock::new(NodeRegistry::default()))
+}
+
+/// Makes a plugin node kind available to every graph in the process.
+pub fn register_node_type(node_type: impl NodeType + 'static) -> Result<(), String> {
+    registry()
+        .write()
+        .expect("node registry lock")
+        .register(Arc::new(node_type))
+}
+
+pub fn node_type(kind_id: &str) -> Option<Arc<dyn NodeType>> {
+    registry().read().expect("node registry lock").get(kind_id)
+}
+
+/// Type of `node`, by kind id or, for built-in nodes saved before kind ids, by
+/// name. Plugin nodes whose kind is not registered have none.
+pub fn node_type_for(node: &Node) -> Option<Arc<dyn NodeType>> {
+    if !node.kind_id.is_empty() {
+        if let Some(node_type) = node_type(&node.kind_id) {
+            return Some(node_type);
+        }
+        let plugin = node
+            .kind_id
+            .split_once(':')
+            .is_some_and(|(namespace, _)| namespace != BUILTIN_NAMESPACE);
+        if plugin {
+            return None;
+        }
+    }
+    builtin_kind_from_name(&node.name).map(|kind| Arc::new(BuiltinNode(kind)) as Arc<dyn NodeType>)
+}
+
+pub fn node_types() -> Vec<Arc<dyn NodeType>> {
+    registry()
+        .read()
+        .expect("node registry lock")
+        .types()
+        .to_vec()
+}
+
+/// Adds a node of a registered kind with its default parameters.
+pub fn add_registered_node(graph: &mut Graph, kind_id: &str) -> Option<NodeId> {
+    let node_type = node_type(kind_id)?;
+    let node_id = graph.add_node(node_type.definition());
+    graph.set_node_kind_id(node_id, kind_id);
+    for (key, value) in node_type.default_params().values {
+        let _ = graph.set_param(node_id, key, value);
+    }
+    Some(node_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_registered_node, node_type, node_types, register_node_type, NodeType};
+    use crate::geometry::Geometry;
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::{NodeDefinition, NodeParams, ParamValue, PinDefinition, PinType};
+    use crate::mesh::make_box;
+    use crate::node_help::{node_help_page, NodeHelpPage};
+    use crate::nodes_builtin::{node_definition, param_specs_for_kind_id, BuiltinNodeKind};
+    use crate::param_spec::ParamSpec;
+    use crate::project::Project;
+
+    struct Crate(&'static str);
+
+    impl NodeType for Crate {
+        fn kind_id(&self) -> &str {
+            self.0
+        }
+
+        fn definition(&self) -> NodeDefinition {
+            NodeDefinition {
+                name: "Crate".to_string(),
+                category: "Studio".to_string(),
+                inputs: Vec::new(),
+                outputs: vec![PinDefinition {
+                    name: "out".to_string(),
+                    pin_type: PinType::Geometry,
+                }],
+                variadic_inputs: false,
+            }
+        }
+
+        fn default_params(&self) -> NodeParams {
+            NodeParams {
+                values: [("size".to_string(), ParamValue::Float(2.0))].into(),
+            }
+        }
+
+        fn param_specs(&self) -> Vec<ParamSpec> {
+            vec![ParamSpec::float("size", "Size")]
+        }
+
+        fn help_page(&self) -> Option<NodeHelpPage> {
+            Some(NodeHelpPage {
+                name: "Crate",
+                description: &["A cube of the given size."],
+                inputs: &[],
+                outputs: &["out: The crate."],
+                parameters: &[("size", "Edge length.")],
+            })
+        }
+
+        fn compute_geometry(
+            &self,
+            params: &NodeParams,
+            _inputs: &[Geometry],
+        ) -> Result<Vec<Geometry>, String> {
+            let size = params.get_float("size", 1.0);
+            Ok(vec![Geometry::with_mesh(make_box([size; 3]))])
+        }
+    }
+
+    #[test]
+    fn plugin_nodes_cook_and_show_up_everywhere() {
+        register_node_type(Crate("studio:crate")).unwrap();
+        assert!(register_node_type(Crate("studio:crate")).is_err());
+        assert!(register_node_type(Crate("builtin:crate")).is_err());
+        assert!(register_node_type(Crate("crate")).is_err());
+        assert!(register_node_type(Crate("studio:")).is_err());
+
+        assert!(node_types()
+            .iter()
+            .any(|node_type| node_type.kind_id() == "studio:crate"));
+        assert!(node_type(BuiltinNodeKind::Box.id()).is_some());
+        assert_eq!(param_specs_for_kind_id("studio:crate").len(), 1);
+        assert_eq!(node_help_page("studio:crate").unwrap().name, "Crate");
+
+        let mut project = Project::default();
+        let node = add_registered_node(&mut project.graph, "studio:crate").unwrap();
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&project.graph, node, &mut state).unwrap();
+        assert!(
+            result.report.errors.is_empty(),
+            "{:?}",
+            result.report.errors
+        );
+        let bounds = state
+            .geometry_for_node(node)
+            .unwrap()
+            .merged_mesh()
+            .unwrap()
+            .bounds()
+            .unwrap();
+        assert_eq!(bounds.max, [1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn unregistered_plugin_nodes_are_kept() {
+        let mut project = Project::default();
+        let mut definition = Crate("missing:crate").definition();
+        definition.inputs.push(PinDefinition {
+            name: "in".to_string(),
+            pin_type: PinType::Geometry,
+        });
+        // Named like a built-in so a name lookup would pick the wrong kind.
+        definition.name = "Box".to_string();
+        let node = project.graph.add_node(definition);
+        project.graph.set_node_kind_id(node, "missing:crate");
+        project
+            .graph
+            .set_param(node, "size", ParamValue::Float(3.0))
+            .unwrap();
+        let source = project
+            .graph
+            .add_node(node_definition(BuiltinNodeKind::Box));
+        let from = project.graph.node(source).unwrap().outputs[0];
+        let to = project.graph.node(node).unwrap().inputs[0];
+        project.graph.add_link(from, to).unwrap();
+
+        let data = serde_json::to_vec(&project).unwrap();
+        let mut loaded: Project = serde_json::from_slice(&data).unwrap();
+        loaded.migrate_to_latest();
+        let kept = loaded.graph.node(node).unwrap();
+        assert_eq!(kept.kind_id, "missing:crate");
+        assert_eq!(kept.params.get_float("size", 0.0), 3.0);
+        assert_eq!(loaded.graph.input_node(node, 0), Some(source));
+
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&loaded.graph, node, &mut state).unwrap();
+        assert_eq!(result.report.errors.len(), 1);
+        assert!(loaded.graph.node(node).is_some());
+    }
+}
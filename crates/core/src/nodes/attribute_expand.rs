@@ -21,6 +21,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -107,10 +108,10 @@ pub(crate) fn apply_to_mesh(params: &NodeParams, mesh: &mut Mesh) -> Result<(),
 
     let storage = match attr_ref {
         AttributeRef::Float(values) => {
-            AttributeStorage::Float(expand_scalar(values, &neighbors, mask.as_deref(), iterations, mode))
+            AttributeStorage::Float(expand_scalar(values, &neighbors, mask.as_deref(), iterations, mode).into())
         }
         AttributeRef::Int(values) => {
-            AttributeStorage::Int(expand_int(values, &neighbors, mask.as_deref(), iterations, mode))
+            AttributeStorage::Int(expand_int(values, &neighbors, mask.as_deref(), iterations, mode).into())
         }
         AttributeRef::Vec2(values) => AttributeStorage::Vec2(expand_vec2(
             values,
@@ -118,21 +119,21 @@ pub(crate) fn apply_to_mesh(params: &NodeParams, mesh: &mut Mesh) -> Result<(),
             mask.as_deref(),
             iterations,
             mode,
-        )),
+        ).into()),
         AttributeRef::Vec3(values) => AttributeStorage::Vec3(expand_vec3(
             values,
             &neighbors,
             mask.as_deref(),
             iterations,
             mode,
-        )),
+        ).into()),
         AttributeRef::Vec4(values) => AttributeStorage::Vec4(expand_vec4(
             values,
             &neighbors,
             mask.as_deref(),
             iterations,
             mode,
-        )),
+        ).into()),
         AttributeRef::StringTable(_) => {
             warn!("Attribute Expand: string attributes are not supported; passing input through");
             return Ok(());
@@ -178,10 +179,10 @@ pub(crate) fn apply_to_splats(params: &NodeParams, splats: &mut SplatGeo) -> Res
 
     let storage = match attr_ref {
         AttributeRef::Float(values) => {
-            AttributeStorage::Float(expand_scalar(values, &neighbors, mask.as_deref(), iterations, mode))
+            AttributeStorage::Float(expand_scalar(values, &neighbors, mask.as_deref(), iterations, mode).into())
         }
         AttributeRef::Int(values) => {
-            AttributeStorage::Int(expand_int(values, &neighbors, mask.as_deref(), iterations, mode))
+            AttributeStorage::Int(expand_int(values, &neighbors, mask.as_deref(), iterations, mode).into())
         }
         AttributeRef::Vec2(values) => AttributeStorage::Vec2(expand_vec2(
             values,
@@ -189,21 +190,21 @@ pub(crate) fn apply_to_splats(params: &NodeParams, splats: &mut SplatGeo) -> Res
             mask.as_deref(),
             iterations,
             mode,
-        )),
+        ).into()),
         AttributeRef::Vec3(values) => AttributeStorage::Vec3(expand_vec3(
             values,
             &neighbors,
             mask.as_deref(),
             iterations,
             mode,
-        )),
+        ).into()),
         AttributeRef::Vec4(values) => AttributeStorage::Vec4(expand_vec4(
             values,
             &neighbors,
             mask.as_deref(),
             iterations,
             mode,
-        )),
+        ).into()),
         AttributeRef::StringTable(_) => {
             warn!("Attribute Expand: string attributes are not supported; passing input through");
             return Ok(());
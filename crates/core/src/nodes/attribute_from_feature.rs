@@ -23,6 +23,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -151,7 +152,7 @@ fn apply_area_mesh(
                     vec![mesh.indices.len() as u32]
                 }
             } else {
-                mesh.face_counts.clone()
+                mesh.face_counts.to_vec()
             };
             let mut cursor = 0usize;
             for (prim_index, &count) in face_counts.iter().enumerate() {
@@ -193,7 +194,7 @@ fn apply_area_mesh(
                     vec![mesh.indices.len() as u32]
                 }
             } else {
-                mesh.face_counts.clone()
+                mesh.face_counts.to_vec()
             };
             let mut cursor = 0usize;
             for (prim_index, &count) in face_counts.iter().enumerate() {
@@ -244,7 +245,7 @@ fn apply_area_mesh(
         }
     }
 
-    mesh.set_attribute(domain, attr, AttributeStorage::Float(values))
+    mesh.set_attribute(domain, attr, AttributeStorage::Float(values.into()))
         .map_err(|err| format!("Attribute from Feature error: {:?}", err))?;
     Ok(())
 }
@@ -290,7 +291,7 @@ fn apply_gradient_mesh(
                         vec![mesh.indices.len() as u32]
                     }
                 } else {
-                    mesh.face_counts.clone()
+                    mesh.face_counts.to_vec()
                 };
                 let mut corner_normals = vec![[0.0, 1.0, 0.0]; mesh.indices.len()];
                 let mut cursor = 0usize;
@@ -303,7 +304,7 @@ fn apply_gradient_mesh(
                     }
                     cursor += count;
                 }
-                mesh.corner_normals = Some(corner_normals);
+                mesh.corner_normals = Some(corner_normals.into());
                 mesh.corner_normals.as_ref().unwrap()
             };
             for (idx, normal) in normals.iter().enumerate() {
@@ -355,7 +356,7 @@ fn apply_gradient_mesh(
         }
     }
 
-    mesh.set_attribute(domain, attr, AttributeStorage::Float(values))
+    mesh.set_attribute(domain, attr, AttributeStorage::Float(values.into()))
         .map_err(|err| format!("Attribute from Feature error: {:?}", err))?;
     Ok(())
 }
@@ -414,7 +415,7 @@ fn apply_area_splats(
     }
 
     splats
-        .set_attribute(domain, attr, AttributeStorage::Float(values))
+        .set_attribute(domain, attr, AttributeStorage::Float(values.into()))
         .map_err(|err| format!("Attribute from Feature error: {:?}", err))?;
     Ok(())
 }
@@ -462,7 +463,7 @@ fn apply_gradient_splats(
     }
 
     splats
-        .set_attribute(domain, attr, AttributeStorage::Float(values))
+        .set_attribute(domain, attr, AttributeStorage::Float(values.into()))
         .map_err(|err| format!("Attribute from Feature error: {:?}", err))?;
     Ok(())
 }
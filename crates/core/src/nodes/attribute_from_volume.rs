@@ -27,6 +27,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("geo"), geometry_in("volume")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -154,7 +155,7 @@ fn apply_to_mesh(
         *slot = sampler.sample_world(pos);
     });
 
-    mesh.set_attribute(domain, attr, AttributeStorage::Float(values))
+    mesh.set_attribute(domain, attr, AttributeStorage::Float(values.into()))
         .map_err(|err| format!("Attribute from Volume error: {:?}", err))?;
     Ok(())
 }
@@ -190,7 +191,7 @@ fn apply_to_splats(
     });
 
     splats
-        .set_attribute(domain, attr, AttributeStorage::Float(values))
+        .set_attribute(domain, attr, AttributeStorage::Float(values.into()))
         .map_err(|err| format!("Attribute from Volume error: {:?}", err))?;
     Ok(())
 }
@@ -24,6 +24,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -200,7 +201,7 @@ fn build_attribute_math_storage(
                     *slot = apply_op_f(v, value_f, op);
                 }
             }
-            AttributeStorage::Float(next)
+            AttributeStorage::Float(next.into())
         }
         crate::attributes::AttributeRef::Int(values) => {
             let expected_len = values.len();
@@ -221,7 +222,7 @@ fn build_attribute_math_storage(
                     *slot = apply_op_i(v, value_i, op);
                 }
             }
-            AttributeStorage::Int(next)
+            AttributeStorage::Int(next.into())
         }
         crate::attributes::AttributeRef::Vec2(values) => {
             let expected_len = values.len();
@@ -244,7 +245,7 @@ fn build_attribute_math_storage(
                     ];
                 }
             }
-            AttributeStorage::Vec2(next)
+            AttributeStorage::Vec2(next.into())
         }
         crate::attributes::AttributeRef::Vec3(values) => {
             let expected_len = values.len();
@@ -268,7 +269,7 @@ fn build_attribute_math_storage(
                     ];
                 }
             }
-            AttributeStorage::Vec3(next)
+            AttributeStorage::Vec3(next.into())
         }
         crate::attributes::AttributeRef::Vec4(values) => {
             let expected_len = values.len();
@@ -293,7 +294,7 @@ fn build_attribute_math_storage(
                     ];
                 }
             }
-            AttributeStorage::Vec4(next)
+            AttributeStorage::Vec4(next.into())
         }
         crate::attributes::AttributeRef::StringTable(values) => {
             warn!("Attribute Math: string attributes are not supported; passing through");
@@ -30,6 +30,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -199,7 +200,7 @@ pub(crate) fn apply_to_splats(params: &NodeParams, splats: &mut SplatGeo) -> Res
                 *value += n * amplitude;
             });
             splats
-                .set_attribute(domain, attr, AttributeStorage::Float(values))
+                .set_attribute(domain, attr, AttributeStorage::Float(values.into()))
                 .map_err(|err| format!("Attribute Noise error: {:?}", err))?;
         }
         1 => {
@@ -234,7 +235,7 @@ pub(crate) fn apply_to_splats(params: &NodeParams, splats: &mut SplatGeo) -> Res
                 value[1] += n1 * amplitude;
             });
             splats
-                .set_attribute(domain, attr, AttributeStorage::Vec2(values))
+                .set_attribute(domain, attr, AttributeStorage::Vec2(values.into()))
                 .map_err(|err| format!("Attribute Noise error: {:?}", err))?;
         }
         _ => {
@@ -283,7 +284,7 @@ pub(crate) fn apply_to_splats(params: &NodeParams, splats: &mut SplatGeo) -> Res
                 value[2] += n2 * amplitude;
             });
             splats
-                .set_attribute(domain, attr, AttributeStorage::Vec3(values))
+                .set_attribute(domain, attr, AttributeStorage::Vec3(values.into()))
                 .map_err(|err| format!("Attribute Noise error: {:?}", err))?;
         }
     }
@@ -344,7 +345,7 @@ fn apply_to_mesh(params: &NodeParams, mesh: &mut Mesh) -> Result<(), String> {
                 );
                 *value += n * amplitude;
             });
-            mesh.set_attribute(domain, attr, AttributeStorage::Float(values))
+            mesh.set_attribute(domain, attr, AttributeStorage::Float(values.into()))
                 .map_err(|err| format!("Attribute Noise error: {:?}", err))?;
         }
         1 => {
@@ -378,7 +379,7 @@ fn apply_to_mesh(params: &NodeParams, mesh: &mut Mesh) -> Result<(), String> {
                 value[0] += n0 * amplitude;
                 value[1] += n1 * amplitude;
             });
-            mesh.set_attribute(domain, attr, AttributeStorage::Vec2(values))
+            mesh.set_attribute(domain, attr, AttributeStorage::Vec2(values.into()))
                 .map_err(|err| format!("Attribute Noise error: {:?}", err))?;
         }
         _ => {
@@ -426,7 +427,7 @@ fn apply_to_mesh(params: &NodeParams, mesh: &mut Mesh) -> Result<(), String> {
                 value[1] += n1 * amplitude;
                 value[2] += n2 * amplitude;
             });
-            mesh.set_attribute(domain, attr, AttributeStorage::Vec3(values))
+            mesh.set_attribute(domain, attr, AttributeStorage::Vec3(values.into()))
                 .map_err(|err| format!("Attribute Noise error: {:?}", err))?;
         }
     }
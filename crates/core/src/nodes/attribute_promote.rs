@@ -56,6 +56,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -363,27 +364,27 @@ fn promote_attribute(
             values,
             mapping,
             method,
-        ))),
+        ).into())),
         AttributeRef::Int(values) => Some(AttributeStorage::Int(promote_i32(
             values,
             mapping,
             method,
-        ))),
+        ).into())),
         AttributeRef::Vec2(values) => Some(AttributeStorage::Vec2(promote_vec2(
             values,
             mapping,
             method,
-        ))),
+        ).into())),
         AttributeRef::Vec3(values) => Some(AttributeStorage::Vec3(promote_vec3(
             values,
             mapping,
             method,
-        ))),
+        ).into())),
         AttributeRef::Vec4(values) => Some(AttributeStorage::Vec4(promote_vec4(
             values,
             mapping,
             method,
-        ))),
+        ).into())),
         AttributeRef::StringTable(values) => Some(AttributeStorage::StringTable(
             promote_string_table(values, mapping, method),
         )),
@@ -752,7 +753,7 @@ fn build_mapping(mesh: &Mesh, source: AttributeDomain, target: AttributeDomain)
             vec![mesh.indices.len() as u32]
         }
     } else {
-        mesh.face_counts.clone()
+        mesh.face_counts.to_vec()
     };
     match (source, target) {
         (AttributeDomain::Point, AttributeDomain::Vertex) => {
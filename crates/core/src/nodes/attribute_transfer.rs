@@ -32,6 +32,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("target"), geometry_in("source")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -383,31 +384,31 @@ fn apply_transfer_to_mesh(
             AttributeSamples::Float { positions: src_pos, values } => {
                 let mut out = existing_float_attr_mesh(mesh, domain, name, count);
                 transfer_values(&positions, src_pos, values, mask.as_deref(), &mut out);
-                mesh.set_attribute(domain, name, AttributeStorage::Float(out))
+                mesh.set_attribute(domain, name, AttributeStorage::Float(out.into()))
                     .map_err(|err| format!("Attribute Transfer error: {:?}", err))?;
             }
             AttributeSamples::Int { positions: src_pos, values } => {
                 let mut out = existing_int_attr_mesh(mesh, domain, name, count);
                 transfer_values(&positions, src_pos, values, mask.as_deref(), &mut out);
-                mesh.set_attribute(domain, name, AttributeStorage::Int(out))
+                mesh.set_attribute(domain, name, AttributeStorage::Int(out.into()))
                     .map_err(|err| format!("Attribute Transfer error: {:?}", err))?;
             }
             AttributeSamples::Vec2 { positions: src_pos, values } => {
                 let mut out = existing_vec2_attr_mesh(mesh, domain, name, count);
                 transfer_values(&positions, src_pos, values, mask.as_deref(), &mut out);
-                mesh.set_attribute(domain, name, AttributeStorage::Vec2(out))
+                mesh.set_attribute(domain, name, AttributeStorage::Vec2(out.into()))
                     .map_err(|err| format!("Attribute Transfer error: {:?}", err))?;
             }
             AttributeSamples::Vec3 { positions: src_pos, values } => {
                 let mut out = existing_vec3_attr_mesh(mesh, domain, name, count);
                 transfer_values(&positions, src_pos, values, mask.as_deref(), &mut out);
-                mesh.set_attribute(domain, name, AttributeStorage::Vec3(out))
+                mesh.set_attribute(domain, name, AttributeStorage::Vec3(out.into()))
                     .map_err(|err| format!("Attribute Transfer error: {:?}", err))?;
             }
             AttributeSamples::Vec4 { positions: src_pos, values } => {
                 let mut out = existing_vec4_attr_mesh(mesh, domain, name, count);
                 transfer_values(&positions, src_pos, values, mask.as_deref(), &mut out);
-                mesh.set_attribute(domain, name, AttributeStorage::Vec4(out))
+                mesh.set_attribute(domain, name, AttributeStorage::Vec4(out.into()))
                     .map_err(|err| format!("Attribute Transfer error: {:?}", err))?;
             }
             AttributeSamples::StringTable { positions: src_pos, values } => {
@@ -494,35 +495,35 @@ fn apply_transfer_to_splats(
                 let mut out = existing_float_attr_splats(splats, domain, name, count);
                 transfer_values(&positions, src_pos, values, mask.as_deref(), &mut out);
                 splats
-                    .set_attribute(domain, name, AttributeStorage::Float(out))
+                    .set_attribute(domain, name, AttributeStorage::Float(out.into()))
                     .map_err(|err| format!("Attribute Transfer error: {:?}", err))?;
             }
             AttributeSamples::Int { positions: src_pos, values } => {
                 let mut out = existing_int_attr_splats(splats, domain, name, count);
                 transfer_values(&positions, src_pos, values, mask.as_deref(), &mut out);
                 splats
-                    .set_attribute(domain, name, AttributeStorage::Int(out))
+                    .set_attribute(domain, name, AttributeStorage::Int(out.into()))
                     .map_err(|err| format!("Attribute Transfer error: {:?}", err))?;
             }
             AttributeSamples::Vec2 { positions: src_pos, values } => {
                 let mut out = existing_vec2_attr_splats(splats, domain, name, count);
                 transfer_values(&positions, src_pos, values, mask.as_deref(), &mut out);
                 splats
-                    .set_attribute(domain, name, AttributeStorage::Vec2(out))
+                    .set_attribute(domain, name, AttributeStorage::Vec2(out.into()))
                     .map_err(|err| format!("Attribute Transfer error: {:?}", err))?;
             }
             AttributeSamples::Vec3 { positions: src_pos, values } => {
                 let mut out = existing_vec3_attr_splats(splats, domain, name, count);
                 transfer_values(&positions, src_pos, values, mask.as_deref(), &mut out);
                 splats
-                    .set_attribute(domain, name, AttributeStorage::Vec3(out))
+                    .set_attribute(domain, name, AttributeStorage::Vec3(out.into()))
                     .map_err(|err| format!("Attribute Transfer error: {:?}", err))?;
             }
             AttributeSamples::Vec4 { positions: src_pos, values } => {
                 let mut out = existing_vec4_attr_splats(splats, domain, name, count);
                 transfer_values(&positions, src_pos, values, mask.as_deref(), &mut out);
                 splats
-                    .set_attribute(domain, name, AttributeStorage::Vec4(out))
+                    .set_attribute(domain, name, AttributeStorage::Vec4(out.into()))
                     .map_err(|err| format!("Attribute Transfer error: {:?}", err))?;
             }
             AttributeSamples::StringTable { positions: src_pos, values } => {
@@ -47,7 +47,7 @@ pub fn mesh_sample_position(mesh: &Mesh, domain: AttributeDomain, index: usize)
                     vec![mesh.indices.len() as u32]
                 }
             } else {
-                mesh.face_counts.clone()
+                mesh.face_counts.to_vec()
             };
             let mut cursor = 0usize;
             for (face_idx, &count) in face_counts.iter().enumerate() {
@@ -115,7 +115,7 @@ pub fn mesh_positions_for_domain(mesh: &Mesh, domain: AttributeDomain) -> Vec<Ve
                     vec![mesh.indices.len() as u32]
                 }
             } else {
-                mesh.face_counts.clone()
+                mesh.face_counts.to_vec()
             };
             let mut positions = Vec::with_capacity(face_counts.len());
             let mut cursor = 0usize;
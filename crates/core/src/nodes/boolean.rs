@@ -28,6 +28,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("a"), geometry_in("b")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -10,19 +10,27 @@ use crate::mesh::{Mesh, MeshGroups};
 use crate::nodes::{geometry_in, geometry_out, recompute_mesh_normals, require_mesh_input};
 use crate::nodes::volume_to_mesh::volume_to_mesh;
 use crate::param_spec::ParamSpec;
+use crate::progress::check_cancelled;
 use crate::volume::{Volume, VolumeKind};
 use crate::volume_sampling::VolumeSampler;
 
 pub const NAME: &str = "Boolean Geo";
 const DEFAULT_MODE: &str = "auto";
 const DEFAULT_OP: i32 = 1;
+const CANCEL_CHECK_STRIDE: usize = 4096;
 
 pub fn definition() -> NodeDefinition {
     NodeDefinition {
         name: NAME.to_string(),
         category: "Operators".to_string(),
         inputs: vec![geometry_in("geo"), geometry_in("cutter")],
-        outputs: vec![geometry_out("out")],
+        outputs: vec![
+            geometry_out("out"),
+            geometry_out("a_only"),
+            geometry_out("b_only"),
+            geometry_out("intersection"),
+        ],
+        variadic_inputs: false,
     }
 }
 
@@ -76,18 +84,15 @@ pub fn apply_to_geometry(params: &NodeParams, inputs: &[Geometry]) -> Result<Geo
     };
 
     let op = params.get_int("op", DEFAULT_OP);
-    let mode = params.get_string("mode", DEFAULT_MODE).to_lowercase();
-    let force_mesh_sdf = mode.contains("sdf");
-    let force_mesh_mesh = mode.contains("mesh_mesh");
-    let auto = mode.contains("auto") || (!force_mesh_sdf && !force_mesh_mesh);
 
     let mut output = input_a.clone();
     output.materials.merge(&input_b.materials);
 
-    let mesh = if force_mesh_sdf || (auto && has_sdf_volume(input_b)) {
+    let mesh = if uses_sdf_cutter(params, input_b) {
         let volume = find_sdf_volume(input_b)
             .ok_or_else(|| "Boolean Geo requires an SDF volume on input B".to_string())?;
         let mut mesh = clip_mesh_with_sdf(&mesh_a, volume, op)?;
+        check_cancelled()?;
         let source = SourceMesh::new(&mesh_a)?;
         transfer_attributes_from_sources(&mut mesh, &[source]);
         let mesh = if op == 1 || op == 2 {
@@ -142,6 +147,52 @@ pub fn apply_to_geometry(params: &NodeParams, inputs: &[Geometry]) -> Result<Geo
     Ok(output)
 }
 
+/// Returns the main result followed by the A-only (A - B), B-only (B - A) and
+/// intersection pieces. An SDF cutter has no surface of its own to keep, so the
+/// B-only piece is empty in that mode.
+pub fn apply_to_geometry_outputs(
+    params: &NodeParams,
+    inputs: &[Geometry],
+) -> Result<Vec<Geometry>, String> {
+    let output = apply_to_geometry(params, inputs)?;
+    let (Some(input_a), Some(input_b)) = (inputs.first(), inputs.get(1)) else {
+        return Ok(vec![output, Geometry::default(), Geometry::default(), Geometry::default()]);
+    };
+    let op = params.get_int("op", DEFAULT_OP);
+    let piece = |piece_op: i32, inputs: &[Geometry]| {
+        let mut piece_params = params.clone();
+        piece_params
+            .values
+            .insert("op".to_string(), ParamValue::Int(piece_op));
+        apply_to_geometry(&piece_params, inputs)
+    };
+
+    let a_only = if op == 1 {
+        output.clone()
+    } else {
+        piece(1, inputs)?
+    };
+    let b_only = if uses_sdf_cutter(params, input_b) {
+        Geometry::default()
+    } else {
+        piece(1, &[input_b.clone(), input_a.clone()])?
+    };
+    let intersection = if op == 2 {
+        output.clone()
+    } else {
+        piece(2, inputs)?
+    };
+    Ok(vec![output, a_only, b_only, intersection])
+}
+
+fn uses_sdf_cutter(params: &NodeParams, input_b: &Geometry) -> bool {
+    let mode = params.get_string("mode", DEFAULT_MODE).to_lowercase();
+    let force_mesh_sdf = mode.contains("sdf");
+    let force_mesh_mesh = mode.contains("mesh_mesh");
+    let auto = mode.contains("auto") || (!force_mesh_sdf && !force_mesh_mesh);
+    force_mesh_sdf || (auto && has_sdf_volume(input_b))
+}
+
 fn cutter_inner_surface(volume: &Volume, mesh_a: &Mesh, op: i32) -> Option<Mesh> {
     if volume.kind != VolumeKind::Sdf {
         return None;
@@ -172,7 +223,7 @@ fn cutter_inner_surface(volume: &Volume, mesh_a: &Mesh, op: i32) -> Option<Mesh>
             }
         }
     }
-    cutter.indices = kept_indices;
+    cutter.indices = kept_indices.into();
     if cutter.indices.is_empty() {
         return None;
     }
@@ -195,6 +246,7 @@ fn boolean_mesh_mesh(params: &NodeParams, mesh_a: &Mesh, mesh_b: &Mesh) -> Resul
         .map_err(|err| format!("Boolean Geo input A: {err}"))?;
     let manifold_b = manifold_from_mesh(mesh_b)
         .map_err(|err| format!("Boolean Geo input B: {err}"))?;
+    check_cancelled()?;
     let manifold = match compute_boolean(&manifold_a, &manifold_b, op) {
         Ok(manifold) => manifold,
         Err(err) => {
@@ -218,6 +270,7 @@ fn boolean_mesh_mesh(params: &NodeParams, mesh_a: &Mesh, mesh_b: &Mesh) -> Resul
         .map(|idx| idx as u32)
         .collect::<Vec<_>>();
     let mut mesh = Mesh::with_positions_indices(positions, indices);
+    check_cancelled()?;
 
     let sources = [SourceMesh::new(mesh_a)?, SourceMesh::new(mesh_b)?];
     transfer_attributes_from_sources(&mut mesh, &sources);
@@ -332,11 +385,14 @@ fn clip_mesh_with_sdf(mesh: &Mesh, volume: &Volume, op: i32) -> Result<Mesh, Str
             vec![mesh.indices.len() as u32]
         }
     } else {
-        mesh.face_counts.clone()
+        mesh.face_counts.to_vec()
     };
 
     let mut cursor = 0usize;
-    for &count in &face_counts {
+    for (face_idx, &count) in face_counts.iter().enumerate() {
+        if face_idx.is_multiple_of(CANCEL_CHECK_STRIDE) {
+            check_cancelled()?;
+        }
         let count = count as usize;
         if count < 3 || cursor + count > mesh.indices.len() {
             cursor = cursor.saturating_add(count);
@@ -546,7 +602,7 @@ fn manifold_from_mesh(mesh: &Mesh) -> Result<Manifold, String> {
             Vec::new()
         }
     } else {
-        mesh.face_counts.clone()
+        mesh.face_counts.to_vec()
     };
 
     let mut cursor = 0usize;
@@ -654,7 +710,7 @@ fn append_mesh_with_defaults(dst: &mut Mesh, src: &Mesh) {
             vec![src.indices.len() as u32]
         }
     } else {
-        src.face_counts.clone()
+        src.face_counts.to_vec()
     };
     let src_prims = src_face_counts.len();
 
@@ -911,8 +967,8 @@ impl<'a> SourceMesh<'a> {
             tri_bounds,
             tri_to_face,
             tri_corner_indices,
-            point_uvs,
-            vertex_uvs,
+            point_uvs: point_uvs.map(Into::into),
+            vertex_uvs: vertex_uvs.map(Into::into),
         })
     }
 }
@@ -1085,7 +1141,7 @@ fn transfer_domain_attributes(
                 }
                 attributes
                     .map_mut(domain)
-                    .insert(name, AttributeStorage::Float(values));
+                    .insert(name, AttributeStorage::Float(values.into()));
             }
             AttributeType::Int => {
                 let mut values = vec![0i32; len];
@@ -1098,7 +1154,7 @@ fn transfer_domain_attributes(
                 }
                 attributes
                     .map_mut(domain)
-                    .insert(name, AttributeStorage::Int(values));
+                    .insert(name, AttributeStorage::Int(values.into()));
             }
             AttributeType::Vec2 => {
                 let mut values = vec![[0.0f32, 0.0f32]; len];
@@ -1111,7 +1167,7 @@ fn transfer_domain_attributes(
                 }
                 attributes
                     .map_mut(domain)
-                    .insert(name, AttributeStorage::Vec2(values));
+                    .insert(name, AttributeStorage::Vec2(values.into()));
             }
             AttributeType::Vec3 => {
                 let mut values = vec![[0.0f32, 0.0f32, 0.0f32]; len];
@@ -1124,7 +1180,7 @@ fn transfer_domain_attributes(
                 }
                 attributes
                     .map_mut(domain)
-                    .insert(name, AttributeStorage::Vec3(values));
+                    .insert(name, AttributeStorage::Vec3(values.into()));
             }
             AttributeType::Vec4 => {
                 let mut values = vec![[0.0f32, 0.0f32, 0.0f32, 0.0f32]; len];
@@ -1137,7 +1193,7 @@ fn transfer_domain_attributes(
                 }
                 attributes
                     .map_mut(domain)
-                    .insert(name, AttributeStorage::Vec4(values));
+                    .insert(name, AttributeStorage::Vec4(values.into()));
             }
             AttributeType::String => {
                 let mut builder = StringTableBuilder::default();
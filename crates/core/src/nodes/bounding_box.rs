@@ -0,0 +1,87 @@
+use crate::expression::bbox_param;
+use crate::geometry::Geometry;
+use crate::graph::{NodeDefinition, NodeParams, ParamValue, PinType};
+use crate::nodes::{geometry_in, value_out};
+use crate::param_spec::ParamSpec;
+
+pub const NAME: &str = "Bounding Box";
+
+pub fn definition() -> NodeDefinition {
+    NodeDefinition {
+        name: NAME.to_string(),
+        category: "Values".to_string(),
+        inputs: vec![geometry_in("in")],
+        outputs: vec![
+            value_out("size", PinType::Vec3),
+            value_out("center", PinType::Vec3),
+            value_out("min", PinType::Vec3),
+            value_out("max", PinType::Vec3),
+        ],
+        variadic_inputs: false,
+    }
+}
+
+pub fn default_params() -> NodeParams {
+    NodeParams::default()
+}
+
+pub fn param_specs() -> Vec<ParamSpec> {
+    Vec::new()
+}
+
+pub fn compute_values(
+    _params: &NodeParams,
+    inputs: &[Geometry],
+) -> Result<Vec<ParamValue>, String> {
+    let geometry = inputs
+        .first()
+        .ok_or_else(|| "Bounding Box requires an input".to_string())?;
+    ["bbox_size", "bbox_center", "bbox_min", "bbox_max"]
+        .into_iter()
+        .map(|name| bbox_param(name, geometry))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::{Graph, ParamValue, PinType};
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    #[test]
+    fn sizes_geometry_from_another_input() {
+        let mut graph = Graph::default();
+        let measured = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        graph
+            .set_param(measured, "size", ParamValue::Vec3([2.0, 4.0, 6.0]))
+            .unwrap();
+        let bbox = graph.add_node(node_definition(BuiltinNodeKind::BoundingBox));
+        let sized = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let measured_out = graph.node(measured).unwrap().outputs[0];
+        let bbox_in = graph.node(bbox).unwrap().inputs[0];
+        graph.add_link(measured_out, bbox_in).unwrap();
+        let size_out = graph.node(bbox).unwrap().outputs[0];
+        let size_in = graph.add_param_input(sized, "size", PinType::Vec3).unwrap();
+        graph.add_link(size_out, size_in).unwrap();
+
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&graph, sized, &mut state).unwrap();
+        assert!(
+            result.report.errors.is_empty(),
+            "{:?}",
+            result.report.errors
+        );
+        assert_eq!(
+            state.value_for_output(bbox, 1),
+            Some(&ParamValue::Vec3([0.0, 0.0, 0.0]))
+        );
+        let bounds = state
+            .geometry_for_node(sized)
+            .unwrap()
+            .merged_mesh()
+            .unwrap()
+            .bounds()
+            .unwrap();
+        assert_eq!(bounds.max, [1.0, 2.0, 3.0]);
+    }
+}
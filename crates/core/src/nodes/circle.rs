@@ -18,6 +18,7 @@ pub fn definition() -> NodeDefinition {
         category: "Sources".to_string(),
         inputs: Vec::new(),
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
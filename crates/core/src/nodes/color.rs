@@ -22,6 +22,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -111,7 +112,7 @@ pub fn compute(params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
         apply_gradient_to_values(&mut values, &samples, &gradient, mask.as_deref());
     }
     input
-        .set_attribute(domain, "Cd", AttributeStorage::Vec3(values))
+        .set_attribute(domain, "Cd", AttributeStorage::Vec3(values.into()))
         .map_err(|err| format!("Color attribute error: {:?}", err))?;
     Ok(input)
 }
@@ -143,7 +144,7 @@ pub(crate) fn apply_to_splats(params: &NodeParams, splats: &mut SplatGeo) -> Res
     }
 
     splats
-        .set_attribute(domain, "Cd", AttributeStorage::Vec3(values))
+        .set_attribute(domain, "Cd", AttributeStorage::Vec3(values.into()))
         .map_err(|err| format!("Color attribute error: {:?}", err))?;
     Ok(())
 }
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+
+use crate::geometry::Geometry;
+use crate::graph::{NodeDefinition, NodeParams, ParamValue, PinType};
+use crate::nodes::value_out;
+use crate::param_spec::ParamSpec;
+
+pub const NAME: &str = "Constant";
+
+const TYPE_FLOAT: i32 = 0;
+const TYPE_INT: i32 = 1;
+const TYPE_BOOL: i32 = 2;
+const TYPE_VEC2: i32 = 3;
+const TYPE_VEC3: i32 = 4;
+
+pub fn definition() -> NodeDefinition {
+    NodeDefinition {
+        name: NAME.to_string(),
+        category: "Values".to_string(),
+        inputs: Vec::new(),
+        outputs: vec![value_out("value", PinType::Float)],
+        variadic_inputs: false,
+    }
+}
+
+pub fn default_params() -> NodeParams {
+    NodeParams {
+        values: BTreeMap::from([
+            ("type".to_string(), ParamValue::Int(TYPE_FLOAT)),
+            ("float".to_string(), ParamValue::Float(0.0)),
+            ("int".to_string(), ParamValue::Int(0)),
+            ("bool".to_string(), ParamValue::Bool(false)),
+            ("vec2".to_string(), ParamValue::Vec2([0.0, 0.0])),
+            ("vec3".to_string(), ParamValue::Vec3([0.0, 0.0, 0.0])),
+        ]),
+    }
+}
+
+pub fn param_specs() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::int_enum(
+            "type",
+            "Type",
+            vec![
+                (TYPE_FLOAT, "Float"),
+                (TYPE_INT, "Int"),
+                (TYPE_BOOL, "Bool"),
+                (TYPE_VEC2, "Vector 2"),
+                (TYPE_VEC3, "Vector 3"),
+            ],
+        )
+        .with_help("Type of the output value."),
+        ParamSpec::float("float", "Value")
+            .with_help("Output value.")
+            .visible_when_int("type", TYPE_FLOAT),
+        ParamSpec::int("int", "Value")
+            .with_help("Output value.")
+            .visible_when_int("type", TYPE_INT),
+        ParamSpec::bool("bool", "Value")
+            .with_help("Output value.")
+            .visible_when_int("type", TYPE_BOOL),
+        ParamSpec::vec2("vec2", "Value")
+            .with_help("Output value.")
+            .visible_when_int("type", TYPE_VEC2),
+        ParamSpec::vec3("vec3", "Value")
+            .with_help("Output value.")
+            .visible_when_int("type", TYPE_VEC3),
+    ]
+}
+
+pub fn compute_values(
+    params: &NodeParams,
+    _inputs: &[Geometry],
+) -> Result<Vec<ParamValue>, String> {
+    let value = match params.get_int("type", TYPE_FLOAT) {
+        TYPE_INT => ParamValue::Int(params.get_int("int", 0)),
+        TYPE_BOOL => ParamValue::Bool(params.get_bool("bool", false)),
+        TYPE_VEC2 => ParamValue::Vec2(params.get_vec2("vec2", [0.0, 0.0])),
+        TYPE_VEC3 => ParamValue::Vec3(params.get_vec3("vec3", [0.0, 0.0, 0.0])),
+        _ => ParamValue::Float(params.get_float("float", 0.0)),
+    };
+    Ok(vec![value])
+}
@@ -26,6 +26,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("source"), geometry_in("template")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -188,7 +189,7 @@ fn template_from_mesh<'a>(
     }
     Ok(TemplateData {
         positions: template.positions.as_slice(),
-        normals,
+        normals: normals.map(Into::into),
         selected,
         pscale_point: template.attribute(AttributeDomain::Point, "pscale"),
         pscale_detail: template.attribute(AttributeDomain::Detail, "pscale"),
@@ -474,7 +475,7 @@ fn apply_inherit_attributes(
                 mesh.set_attribute(
                     AttributeDomain::Point,
                     source.name.clone(),
-                    AttributeStorage::Float(vec![value; point_count]),
+                    AttributeStorage::Float(vec![value; point_count].into()),
                 )
                 .map_err(|err| format!("Copy to Points inherit error: {:?}", err))?;
             }
@@ -482,7 +483,7 @@ fn apply_inherit_attributes(
                 mesh.set_attribute(
                     AttributeDomain::Point,
                     source.name.clone(),
-                    AttributeStorage::Int(vec![value; point_count]),
+                    AttributeStorage::Int(vec![value; point_count].into()),
                 )
                 .map_err(|err| format!("Copy to Points inherit error: {:?}", err))?;
             }
@@ -490,7 +491,7 @@ fn apply_inherit_attributes(
                 mesh.set_attribute(
                     AttributeDomain::Point,
                     source.name.clone(),
-                    AttributeStorage::Vec2(vec![value; point_count]),
+                    AttributeStorage::Vec2(vec![value; point_count].into()),
                 )
                 .map_err(|err| format!("Copy to Points inherit error: {:?}", err))?;
             }
@@ -498,7 +499,7 @@ fn apply_inherit_attributes(
                 mesh.set_attribute(
                     AttributeDomain::Point,
                     source.name.clone(),
-                    AttributeStorage::Vec3(vec![value; point_count]),
+                    AttributeStorage::Vec3(vec![value; point_count].into()),
                 )
                 .map_err(|err| format!("Copy to Points inherit error: {:?}", err))?;
             }
@@ -506,7 +507,7 @@ fn apply_inherit_attributes(
                 mesh.set_attribute(
                     AttributeDomain::Point,
                     source.name.clone(),
-                    AttributeStorage::Vec4(vec![value; point_count]),
+                    AttributeStorage::Vec4(vec![value; point_count].into()),
                 )
                 .map_err(|err| format!("Copy to Points inherit error: {:?}", err))?;
             }
@@ -542,7 +543,7 @@ fn apply_inherit_attributes_splats(
                     .set_attribute(
                         AttributeDomain::Point,
                         source.name.clone(),
-                        AttributeStorage::Float(vec![value; point_count]),
+                        AttributeStorage::Float(vec![value; point_count].into()),
                     )
                     .map_err(|err| format!("Copy to Points inherit error: {:?}", err))?;
             }
@@ -551,7 +552,7 @@ fn apply_inherit_attributes_splats(
                     .set_attribute(
                         AttributeDomain::Point,
                         source.name.clone(),
-                        AttributeStorage::Int(vec![value; point_count]),
+                        AttributeStorage::Int(vec![value; point_count].into()),
                     )
                     .map_err(|err| format!("Copy to Points inherit error: {:?}", err))?;
             }
@@ -560,7 +561,7 @@ fn apply_inherit_attributes_splats(
                     .set_attribute(
                         AttributeDomain::Point,
                         source.name.clone(),
-                        AttributeStorage::Vec2(vec![value; point_count]),
+                        AttributeStorage::Vec2(vec![value; point_count].into()),
                     )
                     .map_err(|err| format!("Copy to Points inherit error: {:?}", err))?;
             }
@@ -569,7 +570,7 @@ fn apply_inherit_attributes_splats(
                     .set_attribute(
                         AttributeDomain::Point,
                         source.name.clone(),
-                        AttributeStorage::Vec3(vec![value; point_count]),
+                        AttributeStorage::Vec3(vec![value; point_count].into()),
                     )
                     .map_err(|err| format!("Copy to Points inherit error: {:?}", err))?;
             }
@@ -578,7 +579,7 @@ fn apply_inherit_attributes_splats(
                     .set_attribute(
                         AttributeDomain::Point,
                         source.name.clone(),
-                        AttributeStorage::Vec4(vec![value; point_count]),
+                        AttributeStorage::Vec4(vec![value; point_count].into()),
                     )
                     .map_err(|err| format!("Copy to Points inherit error: {:?}", err))?;
             }
@@ -610,7 +611,7 @@ fn apply_copy_index_attribute(
         return Ok(());
     }
     let values = vec![copy_idx as i32; count];
-    mesh.set_attribute(domain, name, AttributeStorage::Int(values))
+    mesh.set_attribute(domain, name, AttributeStorage::Int(values.into()))
         .map_err(|err| format!("Copy to Points attribute error: {:?}", err))?;
     Ok(())
 }
@@ -632,7 +633,7 @@ fn apply_copy_index_attribute_splats(
     }
     let values = vec![copy_idx as i32; count];
     splats
-        .set_attribute(domain, name, AttributeStorage::Int(values))
+        .set_attribute(domain, name, AttributeStorage::Int(values.into()))
         .map_err(|err| format!("Copy to Points attribute error: {:?}", err))?;
     Ok(())
 }
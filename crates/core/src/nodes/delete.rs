@@ -22,6 +22,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -88,10 +89,21 @@ fn delete_mesh_with_mapping(params: &NodeParams, mesh: &Mesh) -> DeleteResult {
         }
     }
 
+    filter_mesh_points(mesh, &keep_points, false)
+}
+
+/// Keeps the flagged points and the triangles built from them. A triangle with only
+/// some of its points flagged is kept when `keep_partial` is set, together with its
+/// unflagged points.
+pub(crate) fn filter_mesh_points(
+    mesh: &Mesh,
+    keep_points: &[bool],
+    keep_partial: bool,
+) -> DeleteResult {
     if mesh.indices.is_empty() {
-        let (mapping, _) = build_index_mapping(&keep_points);
+        let (mapping, _) = build_index_mapping(keep_points);
         return DeleteResult {
-            mesh: filter_point_cloud(mesh, &keep_points),
+            mesh: filter_point_cloud(mesh, keep_points),
             point_mapping: mapping,
         };
     }
@@ -99,12 +111,14 @@ fn delete_mesh_with_mapping(params: &NodeParams, mesh: &Mesh) -> DeleteResult {
     let mut kept_tris = Vec::new();
     let mut kept_indices = Vec::new();
     for (tri_index, tri) in mesh.indices.chunks_exact(3).enumerate() {
-        let a = tri[0] as usize;
-        let b = tri[1] as usize;
-        let c = tri[2] as usize;
-        let keep = keep_points.get(a).copied().unwrap_or(false)
-            && keep_points.get(b).copied().unwrap_or(false)
-            && keep_points.get(c).copied().unwrap_or(false);
+        let mut corners = tri
+            .iter()
+            .map(|index| keep_points.get(*index as usize).copied().unwrap_or(false));
+        let keep = if keep_partial {
+            corners.any(|keep| keep)
+        } else {
+            corners.all(|keep| keep)
+        };
         if keep {
             kept_tris.push(tri_index);
             kept_indices.extend_from_slice(tri);
@@ -158,12 +172,17 @@ fn delete_mesh_with_mapping(params: &NodeParams, mesh: &Mesh) -> DeleteResult {
         }
     });
 
-    let new_attributes = filter_mesh_attributes(mesh, &kept_points_indices, &kept_tris, &new_indices);
-    let new_groups = filter_mesh_groups(mesh, &kept_points_indices, &kept_tris, &new_indices);
+    let kept_corners: Vec<usize> = kept_tris
+        .iter()
+        .flat_map(|tri_index| tri_index * 3..tri_index * 3 + 3)
+        .collect();
+    let new_attributes =
+        filter_mesh_attributes(mesh, &kept_points_indices, &kept_corners, &kept_tris);
+    let new_groups = filter_mesh_groups(mesh, &kept_points_indices, &kept_corners, &kept_tris);
 
     let mut result = Mesh::with_positions_indices(new_positions, new_indices);
     result.normals = new_normals;
-    result.corner_normals = new_corner_normals;
+    result.corner_normals = new_corner_normals.map(Into::into);
     result.uvs = new_uvs;
     result.attributes = new_attributes;
     result.groups = new_groups;
@@ -174,6 +193,69 @@ fn delete_mesh_with_mapping(params: &NodeParams, mesh: &Mesh) -> DeleteResult {
     }
 }
 
+/// Keeps the flagged primitives and the points they use. Unlike
+/// [`filter_mesh_points`] this works on polygons rather than triangles.
+pub(crate) fn filter_mesh_primitives(mesh: &Mesh, keep_prims: &[bool]) -> DeleteResult {
+    let face_counts: Vec<u32> = if mesh.face_counts.is_empty() {
+        vec![3; mesh.indices.len() / 3]
+    } else {
+        mesh.face_counts.to_vec()
+    };
+
+    let mut kept_prims = Vec::new();
+    let mut kept_corners = Vec::new();
+    let mut new_face_counts = Vec::new();
+    let mut start = 0usize;
+    for (prim, count) in face_counts.iter().enumerate() {
+        let count = *count as usize;
+        if keep_prims.get(prim).copied().unwrap_or(false) && start + count <= mesh.indices.len() {
+            kept_prims.push(prim);
+            kept_corners.extend(start..start + count);
+            new_face_counts.push(count as u32);
+        }
+        start += count;
+    }
+
+    let mut used = vec![false; mesh.positions.len()];
+    for &corner in &kept_corners {
+        if let Some(slot) = used.get_mut(mesh.indices[corner] as usize) {
+            *slot = true;
+        }
+    }
+    let (mapping, kept_points) = build_index_mapping(&used);
+
+    let new_positions = kept_points.iter().map(|&i| mesh.positions[i]).collect();
+    let new_indices = kept_corners
+        .iter()
+        .map(|&corner| mapping[mesh.indices[corner] as usize])
+        .collect();
+
+    let mut result = Mesh::with_positions_faces(new_positions, new_indices, new_face_counts);
+    result.normals = mesh.normals.as_ref().map(|normals| {
+        kept_points.iter().map(|&i| normals[i]).collect()
+    });
+    result.uvs = mesh.uvs.as_ref().and_then(|uvs| {
+        if uvs.len() == mesh.positions.len() {
+            Some(kept_points.iter().map(|&i| uvs[i]).collect())
+        } else {
+            None
+        }
+    });
+    result.corner_normals = mesh.corner_normals.as_ref().and_then(|corner| {
+        if corner.len() == mesh.indices.len() {
+            Some(kept_corners.iter().map(|&i| corner[i]).collect())
+        } else {
+            None
+        }
+    });
+    result.attributes = filter_mesh_attributes(mesh, &kept_points, &kept_corners, &kept_prims);
+    result.groups = filter_mesh_groups(mesh, &kept_points, &kept_corners, &kept_prims);
+    DeleteResult {
+        mesh: result,
+        point_mapping: mapping,
+    }
+}
+
 fn filter_point_cloud(mesh: &Mesh, keep_points: &[bool]) -> Mesh {
     let mut kept_points = Vec::new();
     for (idx, keep) in keep_points.iter().copied().enumerate() {
@@ -213,8 +295,8 @@ fn filter_point_cloud(mesh: &Mesh, keep_points: &[bool]) -> Mesh {
 fn filter_mesh_attributes(
     mesh: &Mesh,
     kept_points: &[usize],
-    kept_tris: &[usize],
-    kept_indices: &[u32],
+    kept_corners: &[usize],
+    kept_prims: &[usize],
 ) -> MeshAttributes {
     let mut attributes = MeshAttributes::default();
 
@@ -223,19 +305,13 @@ fn filter_mesh_attributes(
         attributes.map_mut(AttributeDomain::Point).insert(name.clone(), filtered);
     }
     for (name, storage) in mesh.attributes.map(AttributeDomain::Vertex) {
-        if !kept_indices.is_empty() {
-            let mut kept_corners = Vec::with_capacity(kept_indices.len());
-            for tri_index in kept_tris {
-                kept_corners.push(tri_index * 3);
-                kept_corners.push(tri_index * 3 + 1);
-                kept_corners.push(tri_index * 3 + 2);
-            }
-            let filtered = filter_attribute_storage(storage, &kept_corners);
+        if !kept_corners.is_empty() {
+            let filtered = filter_attribute_storage(storage, kept_corners);
             attributes.map_mut(AttributeDomain::Vertex).insert(name.clone(), filtered);
         }
     }
     for (name, storage) in mesh.attributes.map(AttributeDomain::Primitive) {
-        let filtered = filter_attribute_storage(storage, kept_tris);
+        let filtered = filter_attribute_storage(storage, kept_prims);
         attributes
             .map_mut(AttributeDomain::Primitive)
             .insert(name.clone(), filtered);
@@ -252,8 +328,8 @@ fn filter_mesh_attributes(
 fn filter_mesh_groups(
     mesh: &Mesh,
     kept_points: &[usize],
-    kept_tris: &[usize],
-    kept_indices: &[u32],
+    kept_corners: &[usize],
+    kept_prims: &[usize],
 ) -> MeshGroups {
     let mut groups = MeshGroups::default();
 
@@ -262,21 +338,15 @@ fn filter_mesh_groups(
         groups.map_mut(AttributeDomain::Point).insert(name.clone(), filtered);
     }
     for (name, values) in mesh.groups.map(AttributeDomain::Vertex) {
-        if !kept_indices.is_empty() {
-            let mut kept_corners = Vec::with_capacity(kept_indices.len());
-            for tri_index in kept_tris {
-                kept_corners.push(tri_index * 3);
-                kept_corners.push(tri_index * 3 + 1);
-                kept_corners.push(tri_index * 3 + 2);
-            }
-            let filtered = filter_group_values(values, &kept_corners);
+        if !kept_corners.is_empty() {
+            let filtered = filter_group_values(values, kept_corners);
             groups
                 .map_mut(AttributeDomain::Vertex)
                 .insert(name.clone(), filtered);
         }
     }
     for (name, values) in mesh.groups.map(AttributeDomain::Primitive) {
-        let filtered = filter_group_values(values, kept_tris);
+        let filtered = filter_group_values(values, kept_prims);
         groups
             .map_mut(AttributeDomain::Primitive)
             .insert(name.clone(), filtered);
@@ -304,7 +374,7 @@ fn filter_attribute_storage(storage: &AttributeStorage, indices: &[usize]) -> At
                     out.push(*value);
                 }
             }
-            AttributeStorage::Float(out)
+            AttributeStorage::Float(out.into())
         }
         AttributeStorage::Int(values) => {
             let mut out = Vec::with_capacity(indices.len());
@@ -313,7 +383,7 @@ fn filter_attribute_storage(storage: &AttributeStorage, indices: &[usize]) -> At
                     out.push(*value);
                 }
             }
-            AttributeStorage::Int(out)
+            AttributeStorage::Int(out.into())
         }
         AttributeStorage::Vec2(values) => {
             let mut out = Vec::with_capacity(indices.len());
@@ -322,7 +392,7 @@ fn filter_attribute_storage(storage: &AttributeStorage, indices: &[usize]) -> At
                     out.push(*value);
                 }
             }
-            AttributeStorage::Vec2(out)
+            AttributeStorage::Vec2(out.into())
         }
         AttributeStorage::Vec3(values) => {
             let mut out = Vec::with_capacity(indices.len());
@@ -331,7 +401,7 @@ fn filter_attribute_storage(storage: &AttributeStorage, indices: &[usize]) -> At
                     out.push(*value);
                 }
             }
-            AttributeStorage::Vec3(out)
+            AttributeStorage::Vec3(out.into())
         }
         AttributeStorage::Vec4(values) => {
             let mut out = Vec::with_capacity(indices.len());
@@ -340,7 +410,7 @@ fn filter_attribute_storage(storage: &AttributeStorage, indices: &[usize]) -> At
                     out.push(*value);
                 }
             }
-            AttributeStorage::Vec4(out)
+            AttributeStorage::Vec4(out.into())
         }
         AttributeStorage::StringTable(values) => {
             let mut out = Vec::with_capacity(indices.len());
@@ -0,0 +1,41 @@
+use std::collections::BTreeMap;
+
+use crate::expression::detail_param;
+use crate::geometry::Geometry;
+use crate::graph::{NodeDefinition, NodeParams, ParamValue, PinType};
+use crate::nodes::{geometry_in, value_out};
+use crate::param_spec::ParamSpec;
+
+pub const NAME: &str = "Detail to Value";
+
+pub fn definition() -> NodeDefinition {
+    NodeDefinition {
+        name: NAME.to_string(),
+        category: "Values".to_string(),
+        inputs: vec![geometry_in("in")],
+        outputs: vec![value_out("value", PinType::Float)],
+        variadic_inputs: false,
+    }
+}
+
+pub fn default_params() -> NodeParams {
+    NodeParams {
+        values: BTreeMap::from([("attribute".to_string(), ParamValue::String(String::new()))]),
+    }
+}
+
+pub fn param_specs() -> Vec<ParamSpec> {
+    vec![ParamSpec::string("attribute", "Attribute")
+        .with_help("Detail attribute to read; ints, floats and 2 or 3 component vectors.")]
+}
+
+pub fn compute_values(params: &NodeParams, inputs: &[Geometry]) -> Result<Vec<ParamValue>, String> {
+    let geometry = inputs
+        .first()
+        .ok_or_else(|| "Detail to Value requires an input".to_string())?;
+    let name = params.get_string("attribute", "").trim();
+    if name.is_empty() {
+        return Err("Detail to Value needs an attribute name".to_string());
+    }
+    Ok(vec![detail_param(geometry, name)?])
+}
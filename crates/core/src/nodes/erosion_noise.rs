@@ -36,6 +36,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -185,7 +186,7 @@ pub(crate) fn apply_to_splats(params: &NodeParams, splats: &mut SplatGeo) -> Res
             .set_attribute(
                 AttributeDomain::Point,
                 "mask",
-                AttributeStorage::Float(mask_values),
+                AttributeStorage::Float(mask_values.into()),
             )
             .map_err(|err| format!("Erosion Noise error: {:?}", err))?;
     } else {
@@ -251,9 +252,9 @@ fn apply_to_mesh(params: &NodeParams, mesh: &mut Mesh) -> Result<(), String> {
     let mut normals = mesh
         .normals
         .clone()
-        .unwrap_or_else(|| vec![[0.0, 1.0, 0.0]; mesh.positions.len()]);
+        .unwrap_or_else(|| vec![[0.0, 1.0, 0.0]; mesh.positions.len()].into());
     if normals.len() != mesh.positions.len() {
-        normals = vec![[0.0, 1.0, 0.0]; mesh.positions.len()];
+        normals = vec![[0.0, 1.0, 0.0]; mesh.positions.len()].into();
     }
 
     let base_positions = mesh.positions.clone();
@@ -302,7 +303,7 @@ fn apply_to_mesh(params: &NodeParams, mesh: &mut Mesh) -> Result<(), String> {
         mesh.set_attribute(
             AttributeDomain::Point,
             "mask",
-            AttributeStorage::Float(mask_values),
+            AttributeStorage::Float(mask_values.into()),
         )
         .map_err(|err| format!("Erosion Noise error: {:?}", err))?;
     } else {
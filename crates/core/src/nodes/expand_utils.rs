@@ -66,7 +66,7 @@ pub fn expand_mask(
 
 fn face_counts(mesh: &Mesh) -> Vec<u32> {
     if !mesh.face_counts.is_empty() {
-        mesh.face_counts.clone()
+        mesh.face_counts.to_vec()
     } else if mesh.indices.len().is_multiple_of(3) {
         vec![3; mesh.indices.len() / 3]
     } else if mesh.indices.is_empty() {
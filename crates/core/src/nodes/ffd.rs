@@ -32,6 +32,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in"), geometry_in("lattice")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -243,7 +244,7 @@ fn build_lattice_from_mesh(
         params,
         Some(source),
         None,
-        lattice_positions.as_deref(),
+        lattice_positions.as_deref().map(Vec::as_slice),
     )
 }
 
@@ -327,12 +328,12 @@ fn build_lattice_from_positions(
 fn extract_lattice_positions(geo: &Geometry) -> Option<Vec<[f32; 3]>> {
     if let Some(mesh) = geo.merged_mesh() {
         if !mesh.positions.is_empty() {
-            return Some(mesh.positions);
+            return Some(mesh.positions.into_vec());
         }
     }
     if let Some(splats) = geo.merged_splats() {
         if !splats.positions.is_empty() {
-            return Some(splats.positions);
+            return Some(splats.positions.into_vec());
         }
     }
     None
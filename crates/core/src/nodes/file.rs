@@ -8,6 +8,8 @@ use crate::mesh::Mesh;
 use crate::nodes::geometry_out;
 use crate::param_spec::{ParamPathKind, ParamSpec};
 use crate::gltf_io;
+use crate::mesh_ply;
+use crate::mesh_stl;
 
 pub const NAME: &str = "File";
 
@@ -17,6 +19,7 @@ pub fn definition() -> NodeDefinition {
         category: "Sources".to_string(),
         inputs: Vec::new(),
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -31,7 +34,7 @@ pub fn default_params() -> NodeParams {
 
 pub fn param_specs() -> Vec<ParamSpec> {
     vec![ParamSpec::path("path", "Path", ParamPathKind::ReadMesh)
-        .with_help("Path or URL to an OBJ or glTF file.")]
+        .with_help("Path or URL to an OBJ, glTF, PLY or STL file.")]
 }
 
 pub fn compute(params: &NodeParams, _inputs: &[Mesh]) -> Result<Mesh, String> {
@@ -43,13 +46,22 @@ pub fn compute(params: &NodeParams, _inputs: &[Mesh]) -> Result<Mesh, String> {
 }
 
 fn load_mesh(path: &str) -> Result<Mesh, String> {
-    if is_gltf_path(path) {
-        return gltf_io::load_gltf_mesh(path);
+    match mesh_extension(path).as_str() {
+        "gltf" | "glb" => return gltf_io::load_gltf_mesh(path),
+        "ply" => return mesh_ply::load_ply_mesh(path),
+        "stl" => return mesh_stl::load_stl_mesh(path),
+        _ => {}
     }
     if let Some(data) = assets::load_bytes(path) {
         if is_glb_bytes(&data) {
             return gltf_io::load_gltf_mesh_bytes(&data);
         }
+        if data.starts_with(b"ply") {
+            return mesh_ply::load_ply_mesh_bytes(&data);
+        }
+        if mesh_stl::looks_like_stl(&data) {
+            return mesh_stl::load_stl_mesh_bytes(&data);
+        }
         return load_obj_mesh_bytes(&data);
     }
     load_obj_mesh(path)
@@ -105,18 +117,17 @@ fn load_obj_mesh_bytes(data: &[u8]) -> Result<Mesh, String> {
     build_mesh_from_models(models)
 }
 
-fn is_gltf_path(path: &str) -> bool {
+fn mesh_extension(path: &str) -> String {
     let name = if let Some(idx) = path.rfind("::") {
         &path[idx + 2..]
     } else {
         path
     };
-    let ext = std::path::Path::new(name)
+    std::path::Path::new(name)
         .extension()
         .and_then(|ext| ext.to_str())
         .unwrap_or("")
-        .to_lowercase();
-    matches!(ext.as_str(), "gltf" | "glb")
+        .to_lowercase()
 }
 
 fn is_glb_bytes(data: &[u8]) -> bool {
@@ -162,7 +173,7 @@ fn build_mesh_from_models(models: Vec<tobj::Model>) -> Result<Mesh, String> {
 
     let mut mesh = Mesh::with_positions_indices(positions, indices);
     if include_normals && !normals.is_empty() {
-        mesh.normals = Some(normals);
+        mesh.normals = Some(normals.into());
     }
     if include_uvs && !uvs.is_empty() {
         let corner_uvs: Vec<[f32; 2]> = mesh
@@ -174,10 +185,10 @@ fn build_mesh_from_models(models: Vec<tobj::Model>) -> Result<Mesh, String> {
             let _ = mesh.set_attribute(
                 AttributeDomain::Vertex,
                 "uv",
-                AttributeStorage::Vec2(corner_uvs),
+                AttributeStorage::Vec2(corner_uvs.into()),
             );
         }
-        mesh.uvs = Some(uvs);
+        mesh.uvs = Some(uvs.into());
     }
 
     if mesh.normals.is_none() && mesh.corner_normals.is_none() {
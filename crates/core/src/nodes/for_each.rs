@@ -0,0 +1,327 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::attributes::{AttributeDomain, AttributeRef, AttributeStorage, StringTableAttribute};
+use crate::geometry::Geometry;
+use crate::graph::{NodeDefinition, NodeParams, ParamValue};
+use crate::mesh::Mesh;
+use crate::nodes::{delete::filter_mesh_primitives, geometry_in, geometry_out, require_mesh_input};
+use crate::param_spec::ParamSpec;
+
+pub const BEGIN_NAME: &str = "For-Each Begin";
+pub const END_NAME: &str = "For-Each End";
+
+pub const MODE_PIECE_ATTRIBUTE: i32 = 0;
+pub const MODE_PRIMITIVE: i32 = 1;
+pub const MODE_COUNT: i32 = 2;
+
+pub fn begin_definition() -> NodeDefinition {
+    NodeDefinition {
+        name: BEGIN_NAME.to_string(),
+        category: "Operators".to_string(),
+        inputs: vec![geometry_in("in")],
+        outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
+    }
+}
+
+pub fn end_definition() -> NodeDefinition {
+    NodeDefinition {
+        name: END_NAME.to_string(),
+        category: "Operators".to_string(),
+        inputs: vec![geometry_in("in")],
+        outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
+    }
+}
+
+pub fn begin_default_params() -> NodeParams {
+    NodeParams {
+        values: BTreeMap::from([
+            ("mode".to_string(), ParamValue::Int(MODE_PIECE_ATTRIBUTE)),
+            ("attribute".to_string(), ParamValue::String("name".to_string())),
+            ("iterations".to_string(), ParamValue::Int(4)),
+        ]),
+    }
+}
+
+pub fn begin_param_specs() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::int_enum(
+            "mode",
+            "Mode",
+            vec![
+                (MODE_PIECE_ATTRIBUTE, "Piece Attribute"),
+                (MODE_PRIMITIVE, "Primitive"),
+                (MODE_COUNT, "Count"),
+            ],
+        )
+        .with_help("Iterate over attribute values, over primitives, or a fixed number of times feeding each result into the next."),
+        ParamSpec::string("attribute", "Piece Attribute")
+            .with_help("Int or string primitive (or point) attribute that names each piece.")
+            .visible_when_int("mode", MODE_PIECE_ATTRIBUTE),
+        ParamSpec::int("iterations", "Iterations")
+            .with_help("Number of iterations in count mode.")
+            .visible_when_int("mode", MODE_COUNT),
+    ]
+}
+
+pub fn end_default_params() -> NodeParams {
+    NodeParams::default()
+}
+
+pub fn end_param_specs() -> Vec<ParamSpec> {
+    Vec::new()
+}
+
+/// Value of the piece attribute for one iteration; the iteration number in
+/// primitive and count modes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PieceValue {
+    Int(i32),
+    String(String),
+}
+
+pub fn mode(params: &NodeParams) -> i32 {
+    params.get_int("mode", MODE_PIECE_ATTRIBUTE)
+}
+
+pub fn iteration_count(params: &NodeParams) -> usize {
+    params.get_int("iterations", 4).max(0) as usize
+}
+
+/// Writes the `iteration`, `numiterations` and `value` detail attributes on every
+/// mesh and splat set of `geometry`.
+pub fn tag_iteration(
+    geometry: &mut Geometry,
+    iteration: usize,
+    count: usize,
+    value: &PieceValue,
+) -> Result<(), String> {
+    let value = match value {
+        PieceValue::Int(value) => AttributeStorage::Int(vec![*value].into()),
+        PieceValue::String(value) => AttributeStorage::StringTable(StringTableAttribute::new(
+            vec![value.clone()],
+            vec![0],
+        )),
+    };
+    let tags = [
+        ("iteration", AttributeStorage::Int(vec![iteration as i32].into())),
+        ("numiterations", AttributeStorage::Int(vec![count as i32].into())),
+        ("value", value),
+    ];
+    for (name, storage) in tags {
+        for mesh in &mut geometry.meshes {
+            mesh.set_attribute(AttributeDomain::Detail, name, storage.clone())
+                .map_err(|err| format!("For-Each error: {:?}", err))?;
+        }
+        for splats in &mut geometry.splats {
+            splats
+                .set_attribute(AttributeDomain::Detail, name, storage.clone())
+                .map_err(|err| format!("For-Each error: {:?}", err))?;
+        }
+    }
+    Ok(())
+}
+
+/// Splits `input` into the tagged pieces iterated in attribute and primitive mode.
+/// Whatever cannot be split (volumes, splats in primitive mode, geometry without
+/// the piece attribute) rides along with the first piece.
+pub fn loop_pieces(params: &NodeParams, input: &Geometry) -> Result<Vec<Geometry>, String> {
+    let mesh = input.merged_mesh();
+    let mut keys: Vec<PieceValue> = Vec::new();
+    let mut lookup: HashMap<PieceValue, usize> = HashMap::new();
+    let mut key_of = |value: PieceValue| -> usize {
+        *lookup.entry(value.clone()).or_insert_with(|| {
+            keys.push(value);
+            keys.len() - 1
+        })
+    };
+
+    let (prim_pieces, splat_pieces) = if mode(params) == MODE_PRIMITIVE {
+        let prim_pieces: Vec<Option<usize>> = (0..mesh.as_ref().map_or(0, Mesh::face_count))
+            .map(|prim| Some(key_of(PieceValue::Int(prim as i32))))
+            .collect();
+        (Some(prim_pieces), vec![None; input.splats.len()])
+    } else {
+        let name = params.get_string("attribute", "name").trim();
+        if name.is_empty() {
+            return Err("For-Each requires a piece attribute".to_string());
+        }
+        let mut found = false;
+        let prim_pieces = match mesh.as_ref().and_then(|mesh| mesh_prim_values(mesh, name)) {
+            Some(values) => {
+                found = true;
+                Some(values?.into_iter().map(|value| value.map(&mut key_of)).collect::<Vec<_>>())
+            }
+            None => None,
+        };
+        let mut splat_pieces = Vec::new();
+        for splats in &input.splats {
+            let Some(attr) = splats.attribute(AttributeDomain::Point, name) else {
+                splat_pieces.push(None);
+                continue;
+            };
+            found = true;
+            check_piece_attribute(&attr, name)?;
+            splat_pieces.push(Some(
+                (0..splats.len())
+                    .map(|idx| piece_value(&attr, idx).map(&mut key_of))
+                    .collect::<Vec<_>>(),
+            ));
+        }
+        if !found {
+            return Err(format!("piece attribute '{}' not found", name));
+        }
+        (prim_pieces, splat_pieces)
+    };
+
+    let count = keys.len();
+    let mut pieces: Vec<Geometry> = (0..count)
+        .map(|_| Geometry {
+            materials: input.materials.clone(),
+            ..Default::default()
+        })
+        .collect();
+
+    match (&mesh, prim_pieces) {
+        (Some(mesh), Some(values)) => {
+            for (key, piece) in pieces.iter_mut().enumerate() {
+                let keep: Vec<bool> = values.iter().map(|value| *value == Some(key)).collect();
+                if !keep.contains(&true) {
+                    continue;
+                }
+                let filtered = filter_mesh_primitives(mesh, &keep);
+                for curve in &input.curves {
+                    if let Some(remapped) = curve.remap_indices(&filtered.point_mapping) {
+                        piece.curves.push(remapped);
+                    }
+                }
+                piece.meshes.push(filtered.mesh);
+            }
+        }
+        (Some(mesh), None) => {
+            if let Some(first) = pieces.first_mut() {
+                first.meshes.push(mesh.clone());
+                first.curves = input.curves.clone();
+            }
+        }
+        (None, _) => {}
+    }
+    for (splats, values) in input.splats.iter().zip(splat_pieces) {
+        match values {
+            Some(values) => {
+                for (key, piece) in pieces.iter_mut().enumerate() {
+                    let kept: Vec<usize> = values
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, value)| **value == Some(key))
+                        .map(|(idx, _)| idx)
+                        .collect();
+                    if !kept.is_empty() {
+                        piece.splats.push(splats.filter_by_indices(&kept));
+                    }
+                }
+            }
+            None => {
+                if let Some(first) = pieces.first_mut() {
+                    first.splats.push(splats.clone());
+                }
+            }
+        }
+    }
+    if let Some(first) = pieces.first_mut() {
+        first.volumes = input.volumes.clone();
+    }
+
+    for (iteration, (piece, value)) in pieces.iter_mut().zip(&keys).enumerate() {
+        tag_iteration(piece, iteration, count, value)?;
+    }
+    Ok(pieces)
+}
+
+/// Per-primitive piece values, read from a primitive attribute or from the first
+/// point of each primitive. `None` when the mesh has no such attribute.
+fn mesh_prim_values(
+    mesh: &Mesh,
+    name: &str,
+) -> Option<Result<Vec<Option<PieceValue>>, String>> {
+    let face_count = mesh.face_count();
+    if let Some(attr) = mesh.attribute(AttributeDomain::Primitive, name) {
+        return Some(
+            check_piece_attribute(&attr, name)
+                .map(|_| (0..face_count).map(|prim| piece_value(&attr, prim)).collect()),
+        );
+    }
+    let attr = mesh.attribute(AttributeDomain::Point, name)?;
+    if let Err(err) = check_piece_attribute(&attr, name) {
+        return Some(Err(err));
+    }
+    let mut values = Vec::with_capacity(face_count);
+    let mut start = 0usize;
+    for prim in 0..face_count {
+        let corners = mesh.face_counts.get(prim).copied().unwrap_or(3) as usize;
+        let value = mesh
+            .indices
+            .get(start)
+            .and_then(|point| piece_value(&attr, *point as usize));
+        values.push(value);
+        start += corners;
+    }
+    Some(Ok(values))
+}
+
+fn check_piece_attribute(attr: &AttributeRef<'_>, name: &str) -> Result<(), String> {
+    match attr {
+        AttributeRef::Int(_) | AttributeRef::StringTable(_) => Ok(()),
+        _ => Err(format!(
+            "piece attribute '{}' must be an int or string attribute",
+            name
+        )),
+    }
+}
+
+fn piece_value(attr: &AttributeRef<'_>, element: usize) -> Option<PieceValue> {
+    match attr {
+        AttributeRef::Int(values) => values.get(element).map(|value| PieceValue::Int(*value)),
+        AttributeRef::StringTable(values) => values
+            .value(element)
+            .map(|value| PieceValue::String(value.to_string())),
+        _ => None,
+    }
+}
+
+/// Outside a running loop the begin node shows the first iteration's input.
+pub fn compute_begin(params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
+    let input = require_mesh_input(inputs, 0, "For-Each Begin requires a mesh input")?;
+    let geometry = apply_begin_to_geometry(params, &[Geometry::with_mesh(input)])?;
+    Ok(geometry.merged_mesh().unwrap_or_default())
+}
+
+pub fn apply_begin_to_geometry(
+    params: &NodeParams,
+    inputs: &[Geometry],
+) -> Result<Geometry, String> {
+    let input = inputs.first().cloned().unwrap_or_default();
+    if mode(params) == MODE_COUNT {
+        let mut geometry = input;
+        tag_iteration(&mut geometry, 0, iteration_count(params), &PieceValue::Int(0))?;
+        return Ok(geometry);
+    }
+    Ok(loop_pieces(params, &input)?
+        .into_iter()
+        .next()
+        .unwrap_or_default())
+}
+
+/// The end node only loops when cooked by the geometry evaluator; on its own it
+/// passes its input through.
+pub fn compute_end(_params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
+    require_mesh_input(inputs, 0, "For-Each End requires a mesh input")
+}
+
+pub fn apply_end_to_geometry(
+    _params: &NodeParams,
+    inputs: &[Geometry],
+) -> Result<Geometry, String> {
+    Ok(inputs.first().cloned().unwrap_or_default())
+}
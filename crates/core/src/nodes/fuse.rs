@@ -19,6 +19,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -195,14 +196,14 @@ fn fuse_mesh(mesh: &Mesh, radius: f32) -> (Mesh, Vec<u32>) {
         indices.push(mapped);
     }
 
-    let mut out = Mesh::with_positions_faces(positions, indices, mesh.face_counts.clone());
+    let mut out = Mesh::with_positions_faces(positions, indices, mesh.face_counts.to_vec());
     out.attributes = remap_attributes_fused(mesh, &mapping, &clusters);
     out.groups = remap_groups_fused(mesh, &mapping, clusters.len());
     if let Some(uvs) = remap_uvs_fused(mesh, &mapping, clusters.len()) {
-        out.uvs = Some(uvs);
+        out.uvs = Some(uvs.into());
     }
     if let Some(normals) = remap_normals_fused(mesh, &mapping, clusters.len()) {
-        out.normals = Some(normals);
+        out.normals = Some(normals.into());
     }
 
     if !out.indices.is_empty() {
@@ -215,9 +216,9 @@ fn unfuse_mesh(mesh: &Mesh, point_sources: &[usize]) -> (Mesh, Vec<usize>) {
     let mut face_counts = mesh.face_counts.clone();
     if face_counts.is_empty() && !mesh.indices.is_empty() {
         if mesh.indices.len().is_multiple_of(3) {
-            face_counts = vec![3u32; mesh.indices.len() / 3];
+            face_counts = vec![3u32; mesh.indices.len() / 3].into();
         } else {
-            face_counts = vec![mesh.indices.len() as u32];
+            face_counts = vec![mesh.indices.len() as u32].into();
         }
     }
 
@@ -228,14 +229,14 @@ fn unfuse_mesh(mesh: &Mesh, point_sources: &[usize]) -> (Mesh, Vec<usize>) {
         .collect::<Vec<_>>();
     let indices = (0..corner_count as u32).collect::<Vec<_>>();
 
-    let mut out = Mesh::with_positions_faces(positions, indices, face_counts);
+    let mut out = Mesh::with_positions_faces(positions, indices, face_counts.into_vec());
     out.attributes = remap_attributes_unfused(mesh, point_sources);
     out.groups = remap_groups_unfused(mesh, point_sources);
     if let Some(normals) = remap_normals_unfused(mesh, point_sources) {
-        out.normals = Some(normals);
+        out.normals = Some(normals.into());
     }
     if let Some(uvs) = remap_uvs_unfused(mesh, point_sources) {
-        out.uvs = Some(uvs);
+        out.uvs = Some(uvs.into());
     }
     out.corner_normals = mesh.corner_normals.clone();
 
@@ -278,7 +279,7 @@ fn remap_attributes_fused(
                     }
                 }
                 out.map_mut(AttributeDomain::Point)
-                    .insert(name.clone(), AttributeStorage::Float(accum));
+                    .insert(name.clone(), AttributeStorage::Float(accum.into()));
             }
             AttributeStorage::Int(values) => {
                 let mut out_values = vec![0i32; count];
@@ -286,7 +287,7 @@ fn remap_attributes_fused(
                     out_values[cluster_idx] = *values.get(cluster.rep).unwrap_or(&0);
                 }
                 out.map_mut(AttributeDomain::Point)
-                    .insert(name.clone(), AttributeStorage::Int(out_values));
+                    .insert(name.clone(), AttributeStorage::Int(out_values.into()));
             }
             AttributeStorage::Vec2(values) => {
                 let mut accum = vec![[0.0f32, 0.0]; count];
@@ -304,7 +305,7 @@ fn remap_attributes_fused(
                     }
                 }
                 out.map_mut(AttributeDomain::Point)
-                    .insert(name.clone(), AttributeStorage::Vec2(accum));
+                    .insert(name.clone(), AttributeStorage::Vec2(accum.into()));
             }
             AttributeStorage::Vec3(values) => {
                 let mut accum = vec![[0.0f32, 0.0, 0.0]; count];
@@ -324,7 +325,7 @@ fn remap_attributes_fused(
                     }
                 }
                 out.map_mut(AttributeDomain::Point)
-                    .insert(name.clone(), AttributeStorage::Vec3(accum));
+                    .insert(name.clone(), AttributeStorage::Vec3(accum.into()));
             }
             AttributeStorage::Vec4(values) => {
                 let mut accum = vec![[0.0f32, 0.0, 0.0, 0.0]; count];
@@ -346,7 +347,7 @@ fn remap_attributes_fused(
                     }
                 }
                 out.map_mut(AttributeDomain::Point)
-                    .insert(name.clone(), AttributeStorage::Vec4(accum));
+                    .insert(name.clone(), AttributeStorage::Vec4(accum.into()));
             }
             AttributeStorage::StringTable(values) => {
                 let mut indices = Vec::with_capacity(count);
@@ -461,7 +462,7 @@ fn remap_attributes_unfused(mesh: &Mesh, point_sources: &[usize]) -> MeshAttribu
             AttributeStorage::Float(values) => {
                 out.map_mut(AttributeDomain::Point).insert(
                     name.clone(),
-                    AttributeStorage::Float(remap_storage_values(values, point_sources, 0.0)),
+                    AttributeStorage::Float(remap_storage_values(values, point_sources, 0.0).into()),
                 );
             }
             AttributeStorage::Int(values) => {
@@ -478,7 +479,7 @@ fn remap_attributes_unfused(mesh: &Mesh, point_sources: &[usize]) -> MeshAttribu
             AttributeStorage::Vec2(values) => {
                 out.map_mut(AttributeDomain::Point).insert(
                     name.clone(),
-                    AttributeStorage::Vec2(remap_storage_values(values, point_sources, [0.0, 0.0])),
+                    AttributeStorage::Vec2(remap_storage_values(values, point_sources, [0.0, 0.0]).into()),
                 );
             }
             AttributeStorage::Vec3(values) => {
@@ -488,7 +489,7 @@ fn remap_attributes_unfused(mesh: &Mesh, point_sources: &[usize]) -> MeshAttribu
                         values,
                         point_sources,
                         [0.0, 0.0, 0.0],
-                    )),
+                    ).into()),
                 );
             }
             AttributeStorage::Vec4(values) => {
@@ -498,7 +499,7 @@ fn remap_attributes_unfused(mesh: &Mesh, point_sources: &[usize]) -> MeshAttribu
                         values,
                         point_sources,
                         [0.0, 0.0, 0.0, 0.0],
-                    )),
+                    ).into()),
                 );
             }
             AttributeStorage::StringTable(values) => {
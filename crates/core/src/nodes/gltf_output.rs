@@ -13,6 +13,7 @@ pub fn definition() -> NodeDefinition {
         category: "Outputs".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
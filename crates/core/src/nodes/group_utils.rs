@@ -144,7 +144,7 @@ fn map_group_mask(
             vec![mesh.indices.len() as u32]
         }
     } else {
-        mesh.face_counts.clone()
+        mesh.face_counts.to_vec()
     };
     match (source_domain, target_domain) {
         (AttributeDomain::Point, AttributeDomain::Vertex) => {
@@ -9,8 +9,9 @@ pub fn definition() -> NodeDefinition {
     NodeDefinition {
         name: NAME.to_string(),
         category: "Operators".to_string(),
-        inputs: vec![geometry_in("a"), geometry_in("b")],
+        inputs: vec![geometry_in("in0")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: true,
     }
 }
 
@@ -6,16 +6,19 @@ pub mod attribute_noise;
 pub mod attribute_promote;
 pub mod attribute_transfer;
 pub mod attribute_expand;
+pub mod bounding_box;
 pub mod box_node;
 pub mod boolean;
 pub mod boolean_geo;
 pub mod circle;
 pub mod color;
+pub mod constant;
 pub mod copy_to_points;
 pub mod copy_transform;
 pub mod curve;
 pub mod erosion_noise;
 pub mod ffd;
+pub mod for_each;
 pub mod fuse;
 pub mod file;
 pub mod gltf_output;
@@ -30,8 +33,11 @@ pub mod noise;
 pub mod normal;
 pub mod polyframe;
 pub mod obj_output;
+pub mod ply_output;
+pub mod stl_output;
 pub mod output;
 pub mod prune;
+pub mod random_value;
 pub mod ray;
 pub mod read_splats;
 pub mod regularize;
@@ -48,7 +54,10 @@ pub mod splat_merge;
 pub mod splat_utils;
 pub mod splat_outlier;
 pub mod splat_outlier_sdf;
+pub mod split;
+pub mod subnet;
 pub mod sweep;
+pub mod switch;
 pub mod volume_from_geo;
 pub mod volume_from_splats;
 pub mod volume_combine;
@@ -56,6 +65,7 @@ pub mod volume_blur;
 pub mod volume_to_mesh;
 pub mod write_splats;
 pub mod delete;
+pub mod detail_to_value;
 pub mod scatter;
 pub mod sphere;
 pub mod smooth;
@@ -64,6 +74,7 @@ pub mod tube;
 pub mod uv_texture;
 pub mod uv_unwrap;
 pub mod uv_view;
+pub mod value_math;
 pub mod wrangle;
 
 use std::collections::BTreeMap;
@@ -85,6 +96,21 @@ pub fn geometry_out(name: &str) -> PinDefinition {
     }
 }
 
+/// Value input driving the parameter called `name`.
+pub fn value_in(name: &str, pin_type: PinType) -> PinDefinition {
+    PinDefinition {
+        name: name.to_string(),
+        pin_type,
+    }
+}
+
+pub fn value_out(name: &str, pin_type: PinType) -> PinDefinition {
+    PinDefinition {
+        name: name.to_string(),
+        pin_type,
+    }
+}
+
 pub fn require_mesh_input(
     inputs: &[Mesh],
     index: usize,
@@ -114,7 +140,7 @@ pub fn recompute_mesh_normals(mesh: &mut Mesh) {
                     .unwrap_or([0.0, 1.0, 0.0]);
                 corner.push(normal);
             }
-            mesh.corner_normals = Some(corner);
+            mesh.corner_normals = Some(corner.into());
         }
     }
 }
@@ -14,6 +14,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -68,7 +69,7 @@ pub fn compute(params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
     let mut point_normals = input
         .normals
         .take()
-        .unwrap_or_else(|| vec![[0.0, 1.0, 0.0]; point_len]);
+        .unwrap_or_else(|| vec![[0.0, 1.0, 0.0]; point_len].into());
     if let Some(next) = computed.normals.take() {
         for (idx, normal) in next.iter().enumerate() {
             if mask.get(idx).copied().unwrap_or(false) {
@@ -84,7 +85,7 @@ pub fn compute(params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
         let mut corner_normals = input
             .corner_normals
             .take()
-            .unwrap_or_else(|| vec![[0.0, 1.0, 0.0]; next_corner.len()]);
+            .unwrap_or_else(|| vec![[0.0, 1.0, 0.0]; next_corner.len()].into());
         for (idx, normal) in next_corner.iter().enumerate() {
             let point = input.indices.get(idx).copied().unwrap_or(0) as usize;
             if mask.get(point).copied().unwrap_or(false) {
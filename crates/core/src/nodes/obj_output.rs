@@ -15,6 +15,7 @@ pub fn definition() -> NodeDefinition {
         category: "Outputs".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -61,7 +62,7 @@ pub fn write_obj(path: &str, mesh: &Mesh) -> Result<(), String> {
     let mut uv_mode = UvMode::None;
     if let Some(uvs) = &mesh.uvs {
         if uvs.len() == mesh.positions.len() {
-            uv_mode = UvMode::PerVertex(uvs.clone());
+            uv_mode = UvMode::PerVertex(uvs.to_vec());
         }
     }
     if matches!(uv_mode, UvMode::None) {
@@ -117,7 +118,7 @@ pub fn write_obj(path: &str, mesh: &Mesh) -> Result<(), String> {
                 vec![mesh.indices.len() as u32]
             }
         } else {
-            mesh.face_counts.clone()
+            mesh.face_counts.to_vec()
         };
         let mut corner_uv_index = 1u32;
         let mut cursor = 0usize;
@@ -11,6 +11,7 @@ pub fn definition() -> NodeDefinition {
         category: "Outputs".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: Vec::new(),
+        variadic_inputs: false,
     }
 }
 
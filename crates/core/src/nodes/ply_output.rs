@@ -0,0 +1,44 @@
+use std::collections::BTreeMap;
+
+use crate::graph::{NodeDefinition, NodeParams, ParamValue};
+use crate::mesh::Mesh;
+use crate::nodes::{geometry_in, geometry_out, require_mesh_input};
+use crate::param_spec::{ParamPathKind, ParamSpec};
+
+pub const NAME: &str = "PLY Output";
+
+pub fn definition() -> NodeDefinition {
+    NodeDefinition {
+        name: NAME.to_string(),
+        category: "Outputs".to_string(),
+        inputs: vec![geometry_in("in")],
+        outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
+    }
+}
+
+pub fn default_params() -> NodeParams {
+    NodeParams {
+        values: BTreeMap::from([
+            (
+                "path".to_string(),
+                ParamValue::String("output.ply".to_string()),
+            ),
+            ("format".to_string(), ParamValue::Int(0)),
+        ]),
+    }
+}
+
+pub fn param_specs() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::path("path", "Path", ParamPathKind::WritePly)
+            .with_help("Output PLY mesh file path."),
+        ParamSpec::int_enum("format", "Format", vec![(0, "Binary"), (1, "ASCII")])
+            .with_help("Binary is smaller and faster; ASCII is readable."),
+    ]
+}
+
+pub fn compute(_params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
+    let input = require_mesh_input(inputs, 0, "PLY Output requires a mesh input")?;
+    Ok(input)
+}
@@ -27,6 +27,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -140,7 +141,7 @@ fn apply_polyframe(params: &NodeParams, input: &mut Mesh, curves: &[Curve]) -> R
             vec![input.indices.len() as u32]
         }
     } else {
-        input.face_counts.clone()
+        input.face_counts.to_vec()
     };
 
     let mut normal_sum = vec![Vec3::ZERO; point_count];
@@ -334,7 +335,7 @@ fn apply_polyframe(params: &NodeParams, input: &mut Mesh, curves: &[Curve]) -> R
             .set_attribute(
                 AttributeDomain::Point,
                 normal_name,
-                AttributeStorage::Vec3(normal_values),
+                AttributeStorage::Vec3(normal_values.into()),
             )
             .map_err(|err| format!("PolyFrame normal attribute error: {:?}", err))?;
     }
@@ -343,7 +344,7 @@ fn apply_polyframe(params: &NodeParams, input: &mut Mesh, curves: &[Curve]) -> R
             .set_attribute(
                 AttributeDomain::Point,
                 tangent_name,
-                AttributeStorage::Vec3(tangent_values),
+                AttributeStorage::Vec3(tangent_values.into()),
             )
             .map_err(|err| format!("PolyFrame tangent attribute error: {:?}", err))?;
     }
@@ -352,7 +353,7 @@ fn apply_polyframe(params: &NodeParams, input: &mut Mesh, curves: &[Curve]) -> R
             .set_attribute(
                 AttributeDomain::Point,
                 bitangent_name,
-                AttributeStorage::Vec3(bitangent_values),
+                AttributeStorage::Vec3(bitangent_values.into()),
             )
             .map_err(|err| format!("PolyFrame bitangent attribute error: {:?}", err))?;
     }
@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+use crate::geometry::Geometry;
+use crate::graph::{NodeDefinition, NodeParams, ParamValue, PinType};
+use crate::nodes::value_out;
+use crate::noise::hash_f32;
+use crate::param_spec::ParamSpec;
+
+pub const NAME: &str = "Random Value";
+
+pub fn definition() -> NodeDefinition {
+    NodeDefinition {
+        name: NAME.to_string(),
+        category: "Values".to_string(),
+        inputs: Vec::new(),
+        outputs: vec![value_out("value", PinType::Float)],
+        variadic_inputs: false,
+    }
+}
+
+pub fn default_params() -> NodeParams {
+    NodeParams {
+        values: BTreeMap::from([
+            ("seed".to_string(), ParamValue::Int(0)),
+            ("min".to_string(), ParamValue::Float(0.0)),
+            ("max".to_string(), ParamValue::Float(1.0)),
+        ]),
+    }
+}
+
+pub fn param_specs() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::int("seed", "Seed")
+            .with_help("Each seed gives a different value; drive it with $F for one per frame."),
+        ParamSpec::float("min", "Min").with_help("Lowest value."),
+        ParamSpec::float("max", "Max").with_help("Highest value."),
+    ]
+}
+
+pub fn compute_values(
+    params: &NodeParams,
+    _inputs: &[Geometry],
+) -> Result<Vec<ParamValue>, String> {
+    let seed = params.get_int("seed", 0);
+    let min = params.get_float("min", 0.0);
+    let max = params.get_float("max", 1.0);
+    let t = hash_f32(seed, 0, 0, 0x5eed);
+    Ok(vec![ParamValue::Float(min + (max - min) * t)])
+}
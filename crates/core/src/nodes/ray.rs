@@ -31,6 +31,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in"), geometry_in("target")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -391,7 +392,7 @@ fn apply_hit_attributes_mesh(
                         }
                     }
                 }
-                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Float(out))
+                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Float(out.into()))
                     .map_err(|err| format!("Ray error: {:?}", err))?;
             }
             AttributeType::Int => {
@@ -406,7 +407,7 @@ fn apply_hit_attributes_mesh(
                         }
                     }
                 }
-                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Int(out))
+                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Int(out.into()))
                     .map_err(|err| format!("Ray error: {:?}", err))?;
             }
             AttributeType::Vec2 => {
@@ -421,7 +422,7 @@ fn apply_hit_attributes_mesh(
                         }
                     }
                 }
-                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec2(out))
+                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec2(out.into()))
                     .map_err(|err| format!("Ray error: {:?}", err))?;
             }
             AttributeType::Vec3 => {
@@ -436,7 +437,7 @@ fn apply_hit_attributes_mesh(
                         }
                     }
                 }
-                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec3(out))
+                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec3(out.into()))
                     .map_err(|err| format!("Ray error: {:?}", err))?;
             }
             AttributeType::Vec4 => {
@@ -451,7 +452,7 @@ fn apply_hit_attributes_mesh(
                         }
                     }
                 }
-                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec4(out))
+                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec4(out.into()))
                     .map_err(|err| format!("Ray error: {:?}", err))?;
             }
             AttributeType::String => {}
@@ -494,7 +495,7 @@ fn apply_hit_attributes_splats(
                     }
                 }
                 splats
-                    .set_attribute(AttributeDomain::Point, name, AttributeStorage::Float(out))
+                    .set_attribute(AttributeDomain::Point, name, AttributeStorage::Float(out.into()))
                     .map_err(|err| format!("Ray error: {:?}", err))?;
             }
             AttributeType::Int => {
@@ -511,7 +512,7 @@ fn apply_hit_attributes_splats(
                     }
                 }
                 splats
-                    .set_attribute(AttributeDomain::Point, name, AttributeStorage::Int(out))
+                    .set_attribute(AttributeDomain::Point, name, AttributeStorage::Int(out.into()))
                     .map_err(|err| format!("Ray error: {:?}", err))?;
             }
             AttributeType::Vec2 => {
@@ -528,7 +529,7 @@ fn apply_hit_attributes_splats(
                     }
                 }
                 splats
-                    .set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec2(out))
+                    .set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec2(out.into()))
                     .map_err(|err| format!("Ray error: {:?}", err))?;
             }
             AttributeType::Vec3 => {
@@ -545,7 +546,7 @@ fn apply_hit_attributes_splats(
                     }
                 }
                 splats
-                    .set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec3(out))
+                    .set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec3(out.into()))
                     .map_err(|err| format!("Ray error: {:?}", err))?;
             }
             AttributeType::Vec4 => {
@@ -562,7 +563,7 @@ fn apply_hit_attributes_splats(
                     }
                 }
                 splats
-                    .set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec4(out))
+                    .set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec4(out.into()))
                     .map_err(|err| format!("Ray error: {:?}", err))?;
             }
             AttributeType::String => {}
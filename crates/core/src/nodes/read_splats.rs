@@ -3,7 +3,7 @@ use std::collections::BTreeMap;
 use crate::graph::{NodeDefinition, NodeParams, ParamValue};
 use crate::nodes::geometry_out;
 use crate::param_spec::{ParamPathKind, ParamSpec};
-use crate::splat::{load_splat_ply_with_mode, SplatGeo, SplatLoadMode};
+use crate::splat::{load_splats_with_mode, SplatGeo, SplatLoadMode};
 
 pub const NAME: &str = "Splat Read";
 pub const LEGACY_NAME: &str = "Read Splats";
@@ -14,6 +14,7 @@ pub fn definition() -> NodeDefinition {
         category: "Sources".to_string(),
         inputs: Vec::new(),
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -32,7 +33,7 @@ pub fn default_params() -> NodeParams {
 pub fn param_specs() -> Vec<ParamSpec> {
     vec![
         ParamSpec::path("path", "Path", ParamPathKind::ReadSplat)
-            .with_help("Path or URL to a splat PLY file."),
+            .with_help("Path or URL to a splat PLY, .splat, .ksplat or .spz file."),
         ParamSpec::int_enum(
             "read_mode",
             "Read Mode",
@@ -52,6 +53,6 @@ pub fn compute(params: &NodeParams) -> Result<SplatGeo, String> {
     } else {
         SplatLoadMode::Full
     };
-    load_splat_ply_with_mode(path, mode)
+    load_splats_with_mode(path, mode)
 }
 
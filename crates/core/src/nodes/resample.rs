@@ -23,6 +23,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -213,7 +214,7 @@ fn resample_mesh(params: &NodeParams, mesh: &Mesh) -> Mesh {
             for &rep in &rep_indices {
                 new_uvs.push(*uvs.get(rep).unwrap_or(&[0.0, 0.0]));
             }
-            out.uvs = Some(new_uvs);
+            out.uvs = Some(new_uvs.into());
         }
     }
 
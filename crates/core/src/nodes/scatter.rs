@@ -12,17 +12,21 @@ use crate::nodes::{
     require_mesh_input,
 };
 use crate::param_spec::ParamSpec;
+use crate::progress::check_cancelled;
 use crate::volume::{Volume, VolumeKind};
 use crate::volume_sampling::VolumeSampler;
 
 pub const NAME: &str = "Scatter";
 
+const CANCEL_CHECK_STRIDE: usize = 4096;
+
 pub fn definition() -> NodeDefinition {
     NodeDefinition {
         name: NAME.to_string(),
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -248,7 +252,10 @@ fn scatter_points(
     let mut positions = Vec::with_capacity(count);
     let mut normals = Vec::with_capacity(count);
 
-    for _ in 0..count {
+    for sample_idx in 0..count {
+        if sample_idx.is_multiple_of(CANCEL_CHECK_STRIDE) {
+            check_cancelled()?;
+        }
         let sample = rng.next_f32() * total;
         let tri_index = find_area_index(&areas, sample);
         let base = tri_index * 3;
@@ -298,7 +305,7 @@ fn scatter_points(
     }
 
     let mut mesh = Mesh::with_positions_indices(positions, Vec::new());
-    mesh.normals = Some(normals);
+    mesh.normals = Some(normals.into());
     apply_inherit_buffers(&mut mesh, inherit_buffers)?;
     Ok(mesh)
 }
@@ -365,7 +372,10 @@ fn scatter_curves(
     let mut rng = XorShift32::new(seed);
     let mut out_positions = Vec::with_capacity(count);
     let mut normals = Vec::with_capacity(count);
-    for _ in 0..count {
+    for sample_idx in 0..count {
+        if sample_idx.is_multiple_of(CANCEL_CHECK_STRIDE) {
+            check_cancelled()?;
+        }
         let sample = rng.next_f32() * total;
         let seg_index = find_area_index(&cumulative, sample).min(segments.len() - 1);
         let segment = &segments[seg_index];
@@ -383,7 +393,7 @@ fn scatter_curves(
     }
 
     let mut mesh = Mesh::with_positions_indices(out_positions, Vec::new());
-    mesh.normals = Some(normals);
+    mesh.normals = Some(normals.into());
     apply_inherit_buffers(&mut mesh, inherit_buffers)?;
     Ok(mesh)
 }
@@ -412,6 +422,9 @@ fn scatter_volume(volume: &Volume, count: usize, seed: u32) -> Result<Mesh, Stri
     let mut attempts = 0usize;
     let max_attempts = (count.max(1) * 50).max(100);
     while out_positions.len() < count && attempts < max_attempts {
+        if attempts.is_multiple_of(CANCEL_CHECK_STRIDE) {
+            check_cancelled()?;
+        }
         attempts += 1;
         let x = world_min.x + rng.next_f32() * (world_max.x - world_min.x);
         let y = world_min.y + rng.next_f32() * (world_max.y - world_min.y);
@@ -432,7 +445,7 @@ fn scatter_volume(volume: &Volume, count: usize, seed: u32) -> Result<Mesh, Stri
     }
 
     let mut mesh = Mesh::with_positions_indices(out_positions, Vec::new());
-    mesh.normals = Some(normals);
+    mesh.normals = Some(normals.into());
     Ok(mesh)
 }
 
@@ -767,23 +780,23 @@ fn apply_inherit_buffers(mesh: &mut Mesh, buffers: Vec<InheritBuffer>) -> Result
     for buffer in buffers {
         match buffer {
             InheritBuffer::Float { name, values } => {
-                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Float(values))
+                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Float(values.into()))
                     .map_err(|err| format!("Scatter inherit error: {:?}", err))?;
             }
             InheritBuffer::Int { name, values } => {
-                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Int(values))
+                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Int(values.into()))
                     .map_err(|err| format!("Scatter inherit error: {:?}", err))?;
             }
             InheritBuffer::Vec2 { name, values } => {
-                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec2(values))
+                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec2(values.into()))
                     .map_err(|err| format!("Scatter inherit error: {:?}", err))?;
             }
             InheritBuffer::Vec3 { name, values } => {
-                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec3(values))
+                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec3(values.into()))
                     .map_err(|err| format!("Scatter inherit error: {:?}", err))?;
             }
             InheritBuffer::Vec4 { name, values } => {
-                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec4(values))
+                mesh.set_attribute(AttributeDomain::Point, name, AttributeStorage::Vec4(values.into()))
                     .map_err(|err| format!("Scatter inherit error: {:?}", err))?;
             }
             InheritBuffer::StringTable { name, values, indices } => {
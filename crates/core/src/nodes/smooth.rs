@@ -44,6 +44,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -140,35 +141,35 @@ pub(crate) fn apply_to_splats(params: &NodeParams, splats: &mut SplatGeo) -> Res
                 let smoothed =
                     smooth_scalar(values, &neighbors, mask.as_deref(), iterations, strength);
                 splats
-                    .set_attribute(domain, attr, AttributeStorage::Float(smoothed))
+                    .set_attribute(domain, attr, AttributeStorage::Float(smoothed.into()))
                     .map_err(|err| format!("Smooth error: {:?}", err))?;
             }
             AttributeRef::Int(values) => {
                 let smoothed =
                     smooth_int(values, &neighbors, mask.as_deref(), iterations, strength);
                 splats
-                    .set_attribute(domain, attr, AttributeStorage::Int(smoothed))
+                    .set_attribute(domain, attr, AttributeStorage::Int(smoothed.into()))
                     .map_err(|err| format!("Smooth error: {:?}", err))?;
             }
             AttributeRef::Vec2(values) => {
                 let smoothed =
                     smooth_vec2(values, &neighbors, mask.as_deref(), iterations, strength);
                 splats
-                    .set_attribute(domain, attr, AttributeStorage::Vec2(smoothed))
+                    .set_attribute(domain, attr, AttributeStorage::Vec2(smoothed.into()))
                     .map_err(|err| format!("Smooth error: {:?}", err))?;
             }
             AttributeRef::Vec3(values) => {
                 let smoothed =
                     smooth_vec3(values, &neighbors, mask.as_deref(), iterations, strength);
                 splats
-                    .set_attribute(domain, attr, AttributeStorage::Vec3(smoothed))
+                    .set_attribute(domain, attr, AttributeStorage::Vec3(smoothed.into()))
                     .map_err(|err| format!("Smooth error: {:?}", err))?;
             }
             AttributeRef::Vec4(values) => {
                 let smoothed =
                     smooth_vec4(values, &neighbors, mask.as_deref(), iterations, strength);
                 splats
-                    .set_attribute(domain, attr, AttributeStorage::Vec4(smoothed))
+                    .set_attribute(domain, attr, AttributeStorage::Vec4(smoothed.into()))
                     .map_err(|err| format!("Smooth error: {:?}", err))?;
             }
             AttributeRef::StringTable(_) => {}
@@ -210,31 +211,31 @@ fn apply_to_mesh(params: &NodeParams, mesh: &mut Mesh) -> Result<(), String> {
             AttributeRef::Float(values) => {
                 let smoothed =
                     smooth_scalar(values, &neighbors, mask.as_deref(), iterations, strength);
-                mesh.set_attribute(domain, attr, AttributeStorage::Float(smoothed))
+                mesh.set_attribute(domain, attr, AttributeStorage::Float(smoothed.into()))
                     .map_err(|err| format!("Smooth error: {:?}", err))?;
             }
             AttributeRef::Int(values) => {
                 let smoothed =
                     smooth_int(values, &neighbors, mask.as_deref(), iterations, strength);
-                mesh.set_attribute(domain, attr, AttributeStorage::Int(smoothed))
+                mesh.set_attribute(domain, attr, AttributeStorage::Int(smoothed.into()))
                     .map_err(|err| format!("Smooth error: {:?}", err))?;
             }
             AttributeRef::Vec2(values) => {
                 let smoothed =
                     smooth_vec2(values, &neighbors, mask.as_deref(), iterations, strength);
-                mesh.set_attribute(domain, attr, AttributeStorage::Vec2(smoothed))
+                mesh.set_attribute(domain, attr, AttributeStorage::Vec2(smoothed.into()))
                     .map_err(|err| format!("Smooth error: {:?}", err))?;
             }
             AttributeRef::Vec3(values) => {
                 let smoothed =
                     smooth_vec3(values, &neighbors, mask.as_deref(), iterations, strength);
-                mesh.set_attribute(domain, attr, AttributeStorage::Vec3(smoothed))
+                mesh.set_attribute(domain, attr, AttributeStorage::Vec3(smoothed.into()))
                     .map_err(|err| format!("Smooth error: {:?}", err))?;
             }
             AttributeRef::Vec4(values) => {
                 let smoothed =
                     smooth_vec4(values, &neighbors, mask.as_deref(), iterations, strength);
-                mesh.set_attribute(domain, attr, AttributeStorage::Vec4(smoothed))
+                mesh.set_attribute(domain, attr, AttributeStorage::Vec4(smoothed.into()))
                     .map_err(|err| format!("Smooth error: {:?}", err))?;
             }
             AttributeRef::StringTable(_) => {}
@@ -302,7 +303,7 @@ fn surface_neighbors(mesh: &Mesh, domain: AttributeDomain, radius: f32) -> Vec<V
 
 fn face_counts_for_mesh(mesh: &Mesh) -> Vec<u32> {
     if !mesh.face_counts.is_empty() {
-        return mesh.face_counts.clone();
+        return mesh.face_counts.to_vec();
     }
     if mesh.indices.is_empty() {
         return Vec::new();
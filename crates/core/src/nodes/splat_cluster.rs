@@ -28,6 +28,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -148,7 +149,7 @@ pub fn apply_to_splats(params: &NodeParams, splats: &SplatGeo) -> Result<SplatGe
         .set_attribute(
             AttributeDomain::Point,
             attr_name.to_string(),
-            AttributeStorage::Int(values),
+            AttributeStorage::Int(values.into()),
         )
         .map_err(|err| format!("Splat Cluster error: {:?}", err))?;
     Ok(output)
@@ -25,6 +25,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("source"), geometry_in("deform")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -133,9 +134,9 @@ pub fn apply_to_geometry(
 
 fn extract_target_positions(geo: &Geometry) -> Option<Vec<[f32; 3]>> {
     if let Some(splats) = geo.merged_splats() {
-        return Some(splats.positions);
+        return Some(splats.positions.into_vec());
     }
-    geo.merged_mesh().map(|mesh| mesh.positions)
+    geo.merged_mesh().map(|mesh| mesh.positions.into_vec())
 }
 
 fn deform_pair(
@@ -13,6 +13,7 @@ use crate::nodes::splat_utils::{split_splats_by_group, SpatialHash};
 use crate::nodes::{geometry_in, geometry_out, require_mesh_input};
 use crate::parallel;
 use crate::param_spec::ParamSpec;
+use crate::progress::{check_cancelled, progress_phase};
 use crate::splat::SplatGeo;
 use crate::volume::{Volume, VolumeKind};
 
@@ -49,6 +50,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in"), geometry_in("sdf")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -226,6 +228,7 @@ pub fn apply_to_geometry(
     }
 
     if params.get_bool("preview_surface", DEFAULT_PREVIEW_SURFACE) {
+        check_cancelled()?;
         if let Some(splat) = input.merged_splats() {
             if let Ok(Some(preview)) = build_preview_surface(params, &splat, external_sdf) {
                 meshes.push(preview);
@@ -299,13 +302,20 @@ struct NewSplat {
 }
 
 fn heal_voxel_close(params: &NodeParams, source: &SplatGeo) -> Result<Vec<NewSplat>, String> {
-    let grid = build_density_grid(params, source)?;
+    let grid = {
+        let _phase = progress_phase("density grid");
+        build_density_grid(params, source)?
+    };
     if grid.values.is_empty() {
         return Ok(Vec::new());
     }
     let occ = occupancy_from_grid(&grid.values, grid.iso, grid.inside_is_greater);
     let close_radius = params.get_int("close_radius", DEFAULT_CLOSE_RADIUS);
-    let closed = close_occupancy(&occ, &grid.spec, close_radius);
+    let closed = {
+        let _phase = progress_phase("close");
+        close_occupancy(&occ, &grid.spec, close_radius)
+    };
+    check_cancelled()?;
     let mut candidates = vec![0u8; occ.len()];
     parallel::for_each_indexed_mut(&mut candidates, |idx, slot| {
         let filled = closed.get(idx).copied().unwrap_or(0) != 0;
@@ -339,19 +349,26 @@ fn heal_sdf_patch(
     source: &SplatGeo,
     external_sdf: Option<&Volume>,
 ) -> Result<Vec<NewSplat>, String> {
-    let density = build_density_grid(params, source)?;
+    let density = {
+        let _phase = progress_phase("density grid");
+        build_density_grid(params, source)?
+    };
     if density.values.is_empty() {
         return Ok(Vec::new());
     }
-    let sdf = if let Some(volume) = external_sdf {
-        sdf_grid_from_volume(volume, Some(&density.spec))?
-    } else {
-        let sdf = build_sdf_grid(params, source)?;
-        if !grid_spec_matches(&density.spec, &sdf.spec) {
-            return Err("Splat Heal: density/SDF grids have mismatched resolution".to_string());
+    let sdf = {
+        let _phase = progress_phase("sdf grid");
+        if let Some(volume) = external_sdf {
+            sdf_grid_from_volume(volume, Some(&density.spec))?
+        } else {
+            let sdf = build_sdf_grid(params, source)?;
+            if !grid_spec_matches(&density.spec, &sdf.spec) {
+                return Err("Splat Heal: density/SDF grids have mismatched resolution".to_string());
+            }
+            sdf
         }
-        sdf
     };
+    check_cancelled()?;
 
     let mut candidates = vec![0u8; density.values.len()];
     let mut band = params.get_float("sdf_band", DEFAULT_SDF_BAND);
@@ -834,7 +851,7 @@ fn append_new_splats(
                 }
             }
         }
-        output.sh_rest = upgraded;
+        output.sh_rest = upgraded.into();
         output.sh_coeffs = max_coeffs;
     }
 
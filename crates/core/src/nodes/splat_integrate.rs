@@ -15,6 +15,7 @@ use crate::nodes::{
 };
 use crate::parallel;
 use crate::param_spec::ParamSpec;
+use crate::progress::progress_phase;
 #[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
 use crate::splat::SplatGeo;
@@ -41,6 +42,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("source"), geometry_in("target")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -209,12 +211,18 @@ fn apply_to_splats_internal(
     let high_band_mode = params.get_int("high_band_mode", 0).clamp(0, 1);
     let eps_scale = params.get_float("eps", 1.0e-3).abs().max(1.0e-8);
 
-    let source_env = build_env_coeffs(params, splats, mask, EnvSource::Source);
-    let target_env = build_target_env_coeffs(params, target);
+    let (source_env, target_env) = {
+        let _phase = progress_phase("environment");
+        (
+            build_env_coeffs(params, splats, mask, EnvSource::Source),
+            build_target_env_coeffs(params, target),
+        )
+    };
     let eps = eps_from_env(&source_env, eps_scale);
     let (ratio_min, ratio_max) = ratio_bounds(params);
     let ratios = build_ratio_table(&source_env, &target_env, eps, ratio_min, ratio_max);
 
+    let _phase = progress_phase("relight");
     let mut next_sh0 = splats.sh0.clone();
     let mut next_rest = splats.sh_rest.clone();
 
@@ -239,7 +247,10 @@ fn apply_to_splats_internal(
         1 => {
             let env_l2 = env_l2_from_coeffs(&target_env);
             let albedo_max = params.get_float("albedo_max", 2.0).max(0.0);
-            let normals = estimate_splat_normals(splats);
+            let normals = {
+                let _phase = progress_phase("normals");
+                estimate_splat_normals(splats)
+            };
             for_each_splat_mut(&mut next_sh0, &mut next_rest, sh_coeffs, |idx, sh0, rest| {
                 if !selected(mask, idx) {
                     return;
@@ -263,7 +274,10 @@ fn apply_to_splats_internal(
         _ => {
             let env_l2 = env_l2_from_coeffs(&target_env);
             let albedo_max = params.get_float("albedo_max", 2.0).max(0.0);
-            let normals = estimate_splat_normals(splats);
+            let normals = {
+                let _phase = progress_phase("normals");
+                estimate_splat_normals(splats)
+            };
             for_each_splat_mut(&mut next_sh0, &mut next_rest, sh_coeffs, |idx, sh0, rest| {
                 if !selected(mask, idx) {
                     return;
@@ -13,6 +13,7 @@ use crate::nodes::{
 };
 use crate::parallel;
 use crate::param_spec::ParamSpec;
+use crate::progress::progress_phase;
 use crate::splat::SplatGeo;
 
 pub const NAME: &str = "Splat LOD";
@@ -23,6 +24,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -97,7 +99,10 @@ pub fn apply_to_splats(params: &NodeParams, splats: &SplatGeo) -> SplatGeo {
     }
     let inv_cell = 1.0 / voxel_size;
 
-    let clusters = build_clusters(&splats.positions, &selected, min, inv_cell);
+    let clusters = {
+        let _phase = progress_phase("cluster");
+        build_clusters(&splats.positions, &selected, min, inv_cell)
+    };
     if clusters.len() >= selected.len() {
         return splats.clone();
     }
@@ -136,9 +141,12 @@ pub fn apply_to_splats(params: &NodeParams, splats: &SplatGeo) -> SplatGeo {
 
     let mut cluster_outputs: Vec<ClusterOutput> =
         (0..cluster_sets.len()).map(|_| ClusterOutput::default()).collect();
-    parallel::for_each_indexed_mut(&mut cluster_outputs, |cluster_idx, output| {
-        *output = compute_cluster_output(splats, &cluster_sets[cluster_idx], sh_coeffs);
-    });
+    {
+        let _phase = progress_phase("merge clusters");
+        parallel::for_each_indexed_mut(&mut cluster_outputs, |cluster_idx, output| {
+            *output = compute_cluster_output(splats, &cluster_sets[cluster_idx], sh_coeffs);
+        });
+    }
 
     for output in cluster_outputs {
         positions.push(output.position);
@@ -151,17 +159,18 @@ pub fn apply_to_splats(params: &NodeParams, splats: &SplatGeo) -> SplatGeo {
         }
     }
 
+    let _phase = progress_phase("attributes");
     let attributes = aggregate_attributes(splats, &unselected, &cluster_sets);
     let groups = aggregate_groups(splats, &unselected, &cluster_sets);
 
     SplatGeo {
-        positions,
-        rotations,
-        scales,
-        opacity,
-        sh0,
+        positions: positions.into(),
+        rotations: rotations.into(),
+        scales: scales.into(),
+        opacity: opacity.into(),
+        sh0: sh0.into(),
         sh_coeffs,
-        sh_rest,
+        sh_rest: sh_rest.into(),
         attributes,
         groups,
     }
@@ -413,7 +422,7 @@ fn aggregate_storage(
             for cluster in clusters {
                 out.push(avg_f32(values, cluster));
             }
-            AttributeStorage::Float(out)
+            AttributeStorage::Float(out.into())
         }
         AttributeStorage::Int(values) => {
             let mut out = Vec::with_capacity(unselected.len() + clusters.len());
@@ -423,7 +432,7 @@ fn aggregate_storage(
             for cluster in clusters {
                 out.push(avg_i32(values, cluster));
             }
-            AttributeStorage::Int(out)
+            AttributeStorage::Int(out.into())
         }
         AttributeStorage::Vec2(values) => {
             let mut out = Vec::with_capacity(unselected.len() + clusters.len());
@@ -433,7 +442,7 @@ fn aggregate_storage(
             for cluster in clusters {
                 out.push(avg_vec2(values, cluster));
             }
-            AttributeStorage::Vec2(out)
+            AttributeStorage::Vec2(out.into())
         }
         AttributeStorage::Vec3(values) => {
             let mut out = Vec::with_capacity(unselected.len() + clusters.len());
@@ -443,7 +452,7 @@ fn aggregate_storage(
             for cluster in clusters {
                 out.push(avg_vec3(values, cluster));
             }
-            AttributeStorage::Vec3(out)
+            AttributeStorage::Vec3(out.into())
         }
         AttributeStorage::Vec4(values) => {
             let mut out = Vec::with_capacity(unselected.len() + clusters.len());
@@ -453,7 +462,7 @@ fn aggregate_storage(
             for cluster in clusters {
                 out.push(avg_vec4(values, cluster));
             }
-            AttributeStorage::Vec4(out)
+            AttributeStorage::Vec4(out.into())
         }
         AttributeStorage::StringTable(values) => {
             let mut out = Vec::with_capacity(unselected.len() + clusters.len());
@@ -30,6 +30,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("a"), geometry_in("b")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -360,13 +361,13 @@ fn build_skirt_splats(
     }
 
     let mut splats = SplatGeo::with_len(positions.len());
-    splats.positions = positions;
-    splats.rotations = rotations;
-    splats.scales = scales;
-    splats.opacity = opacity;
-    splats.sh0 = sh0;
+    splats.positions = positions.into();
+    splats.rotations = rotations.into();
+    splats.scales = scales.into();
+    splats.opacity = opacity.into();
+    splats.sh0 = sh0.into();
     splats.sh_coeffs = max_coeffs;
-    splats.sh_rest = sh_rest;
+    splats.sh_rest = sh_rest.into();
     splats
 }
 
@@ -395,7 +396,7 @@ fn append_seam_splats(merged: &mut SplatGeo, seam: &SplatGeo) {
                 }
             }
         }
-        merged.sh_rest = upgraded;
+        merged.sh_rest = upgraded.into();
         merged.sh_coeffs = max_coeffs;
     }
 
@@ -10,6 +10,7 @@ use crate::nodes::{
     splat_utils::split_splats_by_group,
 };
 use crate::param_spec::ParamSpec;
+use crate::progress::progress_phase;
 use crate::splat::SplatGeo;
 
 use super::splat_cluster::{dbscan_labels, estimate_spacing};
@@ -25,7 +26,8 @@ pub fn definition() -> NodeDefinition {
         name: NAME.to_string(),
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
-        outputs: vec![geometry_out("out")],
+        outputs: vec![geometry_out("out"), geometry_out("removed")],
+        variadic_inputs: false,
     }
 }
 
@@ -74,16 +76,36 @@ pub fn compute(_params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
 }
 
 pub fn apply_to_splats(params: &NodeParams, splats: &SplatGeo) -> SplatGeo {
+    split_outliers(params, splats).0
+}
+
+/// Returns the kept splats and the removed outliers.
+pub fn split_outliers(params: &NodeParams, splats: &SplatGeo) -> (SplatGeo, SplatGeo) {
+    let Some(keep_flags) = outlier_keep_flags(params, splats) else {
+        return (splats.clone(), SplatGeo::default());
+    };
+    let mut kept = Vec::new();
+    let mut removed = Vec::new();
+    for (idx, keep) in keep_flags.iter().enumerate() {
+        if *keep {
+            kept.push(idx);
+        } else {
+            removed.push(idx);
+        }
+    }
+    (
+        splats.filter_by_indices(&kept),
+        splats.filter_by_indices(&removed),
+    )
+}
+
+fn outlier_keep_flags(params: &NodeParams, splats: &SplatGeo) -> Option<Vec<bool>> {
     if splats.is_empty() {
-        return splats.clone();
+        return None;
     }
-    let Some((selected, _unselected)) =
-        split_splats_by_group(splats, params, AttributeDomain::Point)
-    else {
-        return splats.clone();
-    };
+    let (selected, _unselected) = split_splats_by_group(splats, params, AttributeDomain::Point)?;
     if selected.len() <= 1 {
-        return splats.clone();
+        return None;
     }
 
     let mut positions = Vec::with_capacity(selected.len());
@@ -91,16 +113,22 @@ pub fn apply_to_splats(params: &NodeParams, splats: &SplatGeo) -> SplatGeo {
         positions.push(splats.positions[idx]);
     }
 
-    let spacing = estimate_spacing(&positions);
+    let spacing = {
+        let _phase = progress_phase("spacing");
+        estimate_spacing(&positions)
+    };
     let mut eps = params.get_float("eps", DEFAULT_EPS);
     if eps <= 0.0 {
         eps = spacing * 1.5;
     }
     if !eps.is_finite() || eps <= 1.0e-6 {
-        return splats.clone();
+        return None;
     }
     let min_pts = params.get_int("min_pts", DEFAULT_MIN_PTS).max(1) as usize;
-    let labels = dbscan_labels(&positions, eps, min_pts);
+    let labels = {
+        let _phase = progress_phase("cluster");
+        dbscan_labels(&positions, eps, min_pts)
+    };
 
     let mut cluster_sizes = Vec::new();
     for label in &labels {
@@ -131,13 +159,7 @@ pub fn apply_to_splats(params: &NodeParams, splats: &SplatGeo) -> SplatGeo {
             *slot = keep;
         }
     }
-
-    let kept: Vec<usize> = keep_flags
-        .iter()
-        .enumerate()
-        .filter_map(|(idx, keep)| (*keep).then_some(idx))
-        .collect();
-    splats.filter_by_indices(&kept)
+    Some(keep_flags)
 }
 
 #[cfg(test)]
@@ -147,7 +169,7 @@ mod tests {
     use crate::graph::{NodeParams, ParamValue};
     use crate::splat::SplatGeo;
 
-    use super::apply_to_splats;
+    use super::{apply_to_splats, split_outliers};
 
     #[test]
     fn outlier_removes_isolated_points() {
@@ -169,5 +191,10 @@ mod tests {
 
         let filtered = apply_to_splats(&params, &splats);
         assert_eq!(filtered.len(), 2);
+
+        let (kept, removed) = split_outliers(&params, &splats);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed.positions[0], [4.0, 0.0, 0.0]);
     }
 }
@@ -23,6 +23,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("splats"), geometry_in("sdf")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
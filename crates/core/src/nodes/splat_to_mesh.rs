@@ -11,6 +11,7 @@ use crate::mesh::Mesh;
 use crate::nodes::{geometry_in, geometry_out};
 use crate::parallel;
 use crate::param_spec::ParamSpec;
+use crate::progress::{check_cancelled, progress_phase};
 use crate::splat::SplatGeo;
 use crate::volume::{Volume, VolumeKind};
 use crate::volume_sampling::VolumeSampler;
@@ -30,6 +31,7 @@ const DEFAULT_MAX_VOXEL_DIM: i32 = 256;
 const DEFAULT_TRANSFER_COLOR: bool = true;
 const DEFAULT_OUTPUT_MODE: i32 = 0;
 const MAX_GRID_POINTS: u64 = 32_000_000;
+const CANCEL_CHECK_STRIDE: usize = 4096;
 
 pub fn definition() -> NodeDefinition {
     NodeDefinition {
@@ -37,6 +39,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("splats"), geometry_in("sdf")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -251,11 +254,18 @@ pub(crate) struct SplatGrid {
 }
 
 fn splats_to_mesh(params: &NodeParams, splats: &SplatGeo) -> Result<Mesh, String> {
-    let grid = build_splat_grid(params, splats, SplatOutputMode::Mesh)?;
-    let mut mesh =
-        marching_cubes(&grid.values, &grid.spec, grid.iso, grid.inside_is_greater)?;
+    let grid = {
+        let _phase = progress_phase("rasterize");
+        build_splat_grid(params, splats, SplatOutputMode::Mesh)?
+    };
+    check_cancelled()?;
+    let mut mesh = {
+        let _phase = progress_phase("marching cubes");
+        marching_cubes(&grid.values, &grid.spec, grid.iso, grid.inside_is_greater)?
+    };
     if let Some(color_grid) = grid.color_grid {
         if !mesh.positions.is_empty() {
+            let _phase = progress_phase("transfer color");
             let positions = &mesh.positions;
             let mut colors = vec![[1.0, 1.0, 1.0]; positions.len()];
             parallel::for_each_indexed_mut(&mut colors, |idx, slot| {
@@ -265,7 +275,7 @@ fn splats_to_mesh(params: &NodeParams, splats: &SplatGeo) -> Result<Mesh, String
             mesh.set_attribute(
                 AttributeDomain::Point,
                 "Cd",
-                AttributeStorage::Vec3(colors),
+                AttributeStorage::Vec3(colors.into()),
             )
             .map_err(|err| format!("Failed to set Cd attribute: {err:?}"))?;
         }
@@ -275,7 +285,10 @@ fn splats_to_mesh(params: &NodeParams, splats: &SplatGeo) -> Result<Mesh, String
 }
 
 fn splats_to_sdf(params: &NodeParams, splats: &SplatGeo) -> Result<Volume, String> {
-    let grid = build_splat_grid(params, splats, SplatOutputMode::Sdf)?;
+    let grid = {
+        let _phase = progress_phase("rasterize");
+        build_splat_grid(params, splats, SplatOutputMode::Sdf)?
+    };
     let dims = [grid.spec.nx as u32, grid.spec.ny as u32, grid.spec.nz as u32];
     let mut volume = Volume::new(
         VolumeKind::Sdf,
@@ -457,17 +470,19 @@ pub(crate) fn build_splat_grid(
                 smooth_k,
                 shell_radius,
                 color_grid.as_mut(),
-            );
+            )?;
             (grid, color_grid, spec)
         }
         _ => {
             let spec = build_grid_spec(&samples, voxel_size, bounds_padding, max_voxel_dim)?;
             let mut color_grid = want_color.then(|| ColorGrid::new(spec.nx * spec.ny * spec.nz));
-            let grid = rasterize_density(&samples, &spec, n_sigma, max_m2, color_grid.as_mut());
+            let grid =
+                rasterize_density(&samples, &spec, n_sigma, max_m2, color_grid.as_mut())?;
             (grid, color_grid, spec)
         }
     };
 
+    check_cancelled()?;
     sanitize_grid(&mut values, iso, inside_is_greater);
     if matches!(output_mode, SplatOutputMode::Mesh) && algorithm == 0 && blur_iters > 0 {
         blur_grid(&mut values, &spec, blur_iters);
@@ -631,10 +646,13 @@ fn rasterize_density(
     n_sigma: f32,
     max_m2: f32,
     mut color_grid: Option<&mut ColorGrid>,
-) -> Vec<f32> {
+) -> Result<Vec<f32>, String> {
     let mut grid = vec![0.0f32; spec.nx * spec.ny * spec.nz];
     let cutoff_m2 = n_sigma * n_sigma;
-    for sample in samples {
+    for (sample_idx, sample) in samples.iter().enumerate() {
+        if sample_idx.is_multiple_of(CANCEL_CHECK_STRIDE) {
+            check_cancelled()?;
+        }
         let r = n_sigma * sample.max_sigma;
         let min = sample.mu - Vec3::splat(r);
         let max = sample.mu + Vec3::splat(r);
@@ -677,7 +695,7 @@ fn rasterize_density(
             }
         }
     }
-    grid
+    Ok(grid)
 }
 
 fn rasterize_smoothmin(
@@ -688,7 +706,7 @@ fn rasterize_smoothmin(
     smooth_k: f32,
     shell_radius: f32,
     mut color_grid: Option<&mut ColorGrid>,
-) -> Vec<f32> {
+) -> Result<Vec<f32>, String> {
     let mut grid = if smooth_k > 0.0 {
         vec![0.0f32; spec.nx * spec.ny * spec.nz]
     } else {
@@ -696,7 +714,10 @@ fn rasterize_smoothmin(
     };
     let cutoff_m2 = n_sigma * n_sigma;
 
-    for sample in samples {
+    for (sample_idx, sample) in samples.iter().enumerate() {
+        if sample_idx.is_multiple_of(CANCEL_CHECK_STRIDE) {
+            check_cancelled()?;
+        }
         let r = n_sigma * sample.max_sigma;
         let min = sample.mu - Vec3::splat(r);
         let max = sample.mu + Vec3::splat(r);
@@ -755,7 +776,7 @@ fn rasterize_smoothmin(
             }
         }
     }
-    grid
+    Ok(grid)
 }
 
 fn grid_index(spec: &GridSpec, ix: usize, iy: usize, iz: usize) -> usize {
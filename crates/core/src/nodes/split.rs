@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+
+use crate::attributes::AttributeDomain;
+use crate::geometry::Geometry;
+use crate::graph::{NodeDefinition, NodeParams, ParamValue};
+use crate::mesh::Mesh;
+use crate::nodes::{
+    delete::filter_mesh_points,
+    geometry_in,
+    geometry_out,
+    group_utils::{mesh_group_mask, splat_group_mask},
+    require_mesh_input,
+};
+use crate::param_spec::ParamSpec;
+use crate::splat::SplatGeo;
+
+pub const NAME: &str = "Split";
+
+pub fn definition() -> NodeDefinition {
+    NodeDefinition {
+        name: NAME.to_string(),
+        category: "Operators".to_string(),
+        inputs: vec![geometry_in("in")],
+        outputs: vec![geometry_out("matching"), geometry_out("nonmatching")],
+        variadic_inputs: false,
+    }
+}
+
+pub fn default_params() -> NodeParams {
+    NodeParams {
+        values: BTreeMap::from([
+            ("group".to_string(), ParamValue::String(String::new())),
+            ("group_type".to_string(), ParamValue::Int(0)),
+        ]),
+    }
+}
+
+pub fn param_specs() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::string("group", "Group")
+            .with_help("Group sent to the matching output; empty matches everything."),
+        ParamSpec::int_enum(
+            "group_type",
+            "Group Type",
+            vec![
+                (0, "Auto"),
+                (1, "Vertex"),
+                (2, "Point"),
+                (3, "Primitive"),
+            ],
+        )
+        .with_help("Group domain to use."),
+    ]
+}
+
+pub fn compute(params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
+    let input = require_mesh_input(inputs, 0, "Split requires a mesh input")?;
+    let Some(mask) = mesh_group_mask(&input, params, AttributeDomain::Point) else {
+        return Ok(input);
+    };
+    Ok(filter_mesh_points(&input, &mask, false).mesh)
+}
+
+pub fn apply_to_geometry(params: &NodeParams, inputs: &[Geometry]) -> Result<Geometry, String> {
+    let mut outputs = apply_to_geometry_outputs(params, inputs, &[])?;
+    Ok(outputs.swap_remove(0))
+}
+
+/// Returns the matching and non-matching parts of the input. Triangles with only
+/// some of their points in the group go to the non-matching side; volumes always
+/// go to the matching side.
+pub fn apply_to_geometry_outputs(
+    params: &NodeParams,
+    inputs: &[Geometry],
+    _demanded: &[bool],
+) -> Result<Vec<Geometry>, String> {
+    let Some(input) = inputs.first() else {
+        return Ok(vec![Geometry::default(), Geometry::default()]);
+    };
+
+    let mut matching = Geometry {
+        volumes: input.volumes.clone(),
+        materials: input.materials.clone(),
+        ..Default::default()
+    };
+    let mut nonmatching = Geometry {
+        materials: input.materials.clone(),
+        ..Default::default()
+    };
+
+    if let Some(mesh) = input.merged_mesh() {
+        match mesh_group_mask(&mesh, params, AttributeDomain::Point) {
+            Some(mask) => {
+                let inverse: Vec<bool> = mask.iter().map(|value| !value).collect();
+                let inside = filter_mesh_points(&mesh, &mask, false);
+                let outside = filter_mesh_points(&mesh, &inverse, true);
+                for curve in &input.curves {
+                    if let Some(remapped) = curve.remap_indices(&inside.point_mapping) {
+                        matching.curves.push(remapped);
+                    } else if let Some(remapped) = curve.remap_indices(&outside.point_mapping) {
+                        nonmatching.curves.push(remapped);
+                    }
+                }
+                matching.meshes.push(inside.mesh);
+                nonmatching.meshes.push(outside.mesh);
+            }
+            None => {
+                matching.meshes.push(mesh);
+                matching.curves = input.curves.clone();
+            }
+        }
+    }
+
+    for splats in &input.splats {
+        let (inside, outside) = split_splats(params, splats);
+        matching.splats.push(inside);
+        if let Some(outside) = outside {
+            nonmatching.splats.push(outside);
+        }
+    }
+
+    Ok(vec![matching, nonmatching])
+}
+
+fn split_splats(params: &NodeParams, splats: &SplatGeo) -> (SplatGeo, Option<SplatGeo>) {
+    let Some(mask) = splat_group_mask(splats, params, AttributeDomain::Point) else {
+        return (splats.clone(), None);
+    };
+    let mut inside = Vec::new();
+    let mut outside = Vec::new();
+    for idx in 0..splats.len() {
+        if mask.get(idx).copied().unwrap_or(false) {
+            inside.push(idx);
+        } else {
+            outside.push(idx);
+        }
+    }
+    (
+        splats.filter_by_indices(&inside),
+        Some(splats.filter_by_indices(&outside)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attributes::AttributeDomain;
+    use crate::geometry::Geometry;
+    use crate::graph::{NodeParams, ParamValue};
+    use crate::mesh::Mesh;
+
+    use super::{apply_to_geometry_outputs, default_params};
+
+    #[test]
+    fn split_sends_group_and_remainder_to_separate_outputs() {
+        let positions = vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 2.0, 0.0],
+        ];
+        let mut mesh = Mesh::with_positions_indices(positions, vec![0, 1, 2, 1, 3, 2, 2, 3, 4]);
+        mesh.groups.map_mut(AttributeDomain::Point).insert(
+            "low".to_string(),
+            vec![true, true, true, false, false],
+        );
+
+        let mut params: NodeParams = default_params();
+        params
+            .values
+            .insert("group".to_string(), ParamValue::String("low".to_string()));
+        let outputs =
+            apply_to_geometry_outputs(&params, &[Geometry::with_mesh(mesh)], &[]).unwrap();
+
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].meshes[0].positions.len(), 3);
+        assert_eq!(outputs[0].meshes[0].indices.len(), 3);
+        // The triangle straddling the group boundary goes to the non-matching side.
+        assert_eq!(outputs[1].meshes[0].positions.len(), 4);
+        assert_eq!(outputs[1].meshes[0].indices.len(), 6);
+    }
+}
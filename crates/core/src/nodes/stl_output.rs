@@ -0,0 +1,46 @@
+use std::collections::BTreeMap;
+
+use crate::graph::{NodeDefinition, NodeParams, ParamValue};
+use crate::mesh::Mesh;
+use crate::nodes::{geometry_in, geometry_out, require_mesh_input};
+use crate::param_spec::{ParamPathKind, ParamSpec};
+
+pub const NAME: &str = "STL Output";
+
+pub fn definition() -> NodeDefinition {
+    NodeDefinition {
+        name: NAME.to_string(),
+        category: "Outputs".to_string(),
+        inputs: vec![geometry_in("in")],
+        outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
+    }
+}
+
+pub fn default_params() -> NodeParams {
+    NodeParams {
+        values: BTreeMap::from([
+            (
+                "path".to_string(),
+                ParamValue::String("output.stl".to_string()),
+            ),
+            ("format".to_string(), ParamValue::Int(0)),
+            ("facet_normals".to_string(), ParamValue::Bool(true)),
+        ]),
+    }
+}
+
+pub fn param_specs() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::path("path", "Path", ParamPathKind::WriteStl).with_help("Output STL file path."),
+        ParamSpec::int_enum("format", "Format", vec![(0, "Binary"), (1, "ASCII")])
+            .with_help("Binary is smaller and faster; ASCII is readable."),
+        ParamSpec::bool("facet_normals", "Facet Normals")
+            .with_help("Write a normal per facet; when off, normals are written as zero."),
+    ]
+}
+
+pub fn compute(_params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
+    let input = require_mesh_input(inputs, 0, "STL Output requires a mesh input")?;
+    Ok(input)
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use crate::geometry::Geometry;
+use crate::graph::{NodeDefinition, NodeParams, ParamValue};
+use crate::mesh::Mesh;
+use crate::nodes::{geometry_in, geometry_out};
+use crate::param_spec::ParamSpec;
+
+pub const NAME: &str = "Subnet";
+pub const INPUT_NAME: &str = "Subnet Input";
+pub const OUTPUT_NAME: &str = "Subnet Output";
+
+pub fn definition() -> NodeDefinition {
+    NodeDefinition {
+        name: NAME.to_string(),
+        category: "Subnet".to_string(),
+        inputs: vec![geometry_in("in")],
+        outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
+    }
+}
+
+pub fn default_params() -> NodeParams {
+    NodeParams::default()
+}
+
+pub fn param_specs() -> Vec<ParamSpec> {
+    Vec::new()
+}
+
+pub fn compute(_params: &NodeParams, _inputs: &[Mesh]) -> Result<Mesh, String> {
+    Err("Subnet only cooks through the geometry evaluator".to_string())
+}
+
+pub fn apply_to_geometry(_params: &NodeParams, _inputs: &[Geometry]) -> Result<Geometry, String> {
+    Err("Subnet only cooks through the geometry evaluator".to_string())
+}
+
+pub fn input_definition() -> NodeDefinition {
+    NodeDefinition {
+        name: INPUT_NAME.to_string(),
+        category: "Subnet".to_string(),
+        inputs: Vec::new(),
+        outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
+    }
+}
+
+pub fn output_definition() -> NodeDefinition {
+    NodeDefinition {
+        name: OUTPUT_NAME.to_string(),
+        category: "Subnet".to_string(),
+        inputs: vec![geometry_in("in")],
+        outputs: Vec::new(),
+        variadic_inputs: false,
+    }
+}
+
+pub fn pin_default_params() -> NodeParams {
+    NodeParams {
+        values: BTreeMap::from([
+            ("index".to_string(), ParamValue::Int(0)),
+            ("label".to_string(), ParamValue::String(String::new())),
+        ]),
+    }
+}
+
+pub fn pin_param_specs() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::int("index", "Index")
+            .with_help("Position of this pin on the subnet node."),
+        ParamSpec::string("label", "Label").with_help("Pin name shown on the subnet node."),
+    ]
+}
+
+/// Subnet inputs are injected by the evaluator; outside a subnet they are empty.
+pub fn compute_input(_params: &NodeParams, _inputs: &[Mesh]) -> Result<Mesh, String> {
+    Ok(Mesh::default())
+}
+
+pub fn apply_input_to_geometry(
+    _params: &NodeParams,
+    _inputs: &[Geometry],
+) -> Result<Geometry, String> {
+    Ok(Geometry::default())
+}
+
+pub fn compute_output(_params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
+    Ok(inputs.first().cloned().unwrap_or_default())
+}
+
+pub fn apply_output_to_geometry(
+    _params: &NodeParams,
+    inputs: &[Geometry],
+) -> Result<Geometry, String> {
+    Ok(inputs.first().cloned().unwrap_or_default())
+}
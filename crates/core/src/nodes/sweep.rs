@@ -17,6 +17,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("profile"), geometry_in("path")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+
+use crate::attributes::{AttributeDomain, AttributeRef};
+use crate::geometry::Geometry;
+use crate::graph::{NodeDefinition, NodeParams, ParamValue};
+use crate::mesh::Mesh;
+use crate::nodes::{geometry_in, geometry_out};
+use crate::param_spec::ParamSpec;
+
+pub const NAME: &str = "Switch";
+
+pub const MODE_INDEX: i32 = 0;
+pub const MODE_DETAIL_ATTRIBUTE: i32 = 1;
+
+pub fn definition() -> NodeDefinition {
+    NodeDefinition {
+        name: NAME.to_string(),
+        category: "Operators".to_string(),
+        inputs: vec![geometry_in("in0")],
+        outputs: vec![geometry_out("out")],
+        variadic_inputs: true,
+    }
+}
+
+pub fn default_params() -> NodeParams {
+    NodeParams {
+        values: BTreeMap::from([
+            ("mode".to_string(), ParamValue::Int(MODE_INDEX)),
+            ("index".to_string(), ParamValue::Int(0)),
+            (
+                "attribute".to_string(),
+                ParamValue::String("switch".to_string()),
+            ),
+        ]),
+    }
+}
+
+pub fn param_specs() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::int_enum(
+            "mode",
+            "Mode",
+            vec![
+                (MODE_INDEX, "Index"),
+                (MODE_DETAIL_ATTRIBUTE, "Detail Attribute"),
+            ],
+        )
+        .with_help("Pick the input by index, or by an int detail attribute on the first input."),
+        ParamSpec::int("index", "Index")
+            .with_help("Input to pass through, counted from 0. Can be driven by an expression."),
+        ParamSpec::string("attribute", "Attribute")
+            .with_help("Int detail attribute on the first input holding the index; falls back to Index when missing.")
+            .visible_when_int("mode", MODE_DETAIL_ATTRIBUTE),
+    ]
+}
+
+pub fn mode(params: &NodeParams) -> i32 {
+    params.get_int("mode", MODE_INDEX)
+}
+
+/// Selected input for an `index`, clamped to the inputs that exist.
+pub fn clamp_index(index: i32, input_count: usize) -> usize {
+    (index.max(0) as usize).min(input_count.saturating_sub(1))
+}
+
+/// Index stored in the selector detail attribute of `geometry`, if any.
+pub fn detail_index(params: &NodeParams, geometry: &Geometry) -> Option<i32> {
+    let name = params.get_string("attribute", "switch").trim();
+    if name.is_empty() {
+        return None;
+    }
+    let attr = geometry
+        .meshes
+        .iter()
+        .find_map(|mesh| mesh.attribute(AttributeDomain::Detail, name))
+        .or_else(|| {
+            geometry
+                .splats
+                .iter()
+                .find_map(|splats| splats.attribute(AttributeDomain::Detail, name))
+        })?;
+    match attr {
+        AttributeRef::Int(values) => values.first().copied(),
+        AttributeRef::Float(values) => values.first().map(|value| value.round() as i32),
+        _ => None,
+    }
+}
+
+fn selected_index(params: &NodeParams, first: Option<&Geometry>, input_count: usize) -> usize {
+    let index = params.get_int("index", 0);
+    let index = match (mode(params), first) {
+        (MODE_DETAIL_ATTRIBUTE, Some(geometry)) => detail_index(params, geometry).unwrap_or(index),
+        _ => index,
+    };
+    clamp_index(index, input_count)
+}
+
+/// Picks one of the connected inputs. The geometry evaluator only cooks the
+/// selected branch and hands it over alone.
+pub fn compute(params: &NodeParams, inputs: &[Mesh]) -> Result<Mesh, String> {
+    let first = inputs.first().map(|mesh| Geometry::with_mesh(mesh.clone()));
+    let index = selected_index(params, first.as_ref(), inputs.len());
+    inputs
+        .get(index)
+        .cloned()
+        .ok_or_else(|| "Switch requires a mesh input".to_string())
+}
+
+pub fn apply_to_geometry(params: &NodeParams, inputs: &[Geometry]) -> Result<Geometry, String> {
+    let index = selected_index(params, inputs.first(), inputs.len());
+    inputs
+        .get(index)
+        .cloned()
+        .ok_or_else(|| "Switch requires an input".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::{Graph, NodeId, ParamValue};
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    fn connect(graph: &mut Graph, from: NodeId, to: NodeId, input: usize) {
+        let from = graph.node(from).unwrap().outputs[0];
+        let to = graph.node(to).unwrap().inputs[input];
+        graph.add_link(from, to).unwrap();
+    }
+
+    fn point_count(state: &GeometryEvalState, node: NodeId) -> usize {
+        state
+            .geometry_for_node(node)
+            .unwrap()
+            .merged_mesh()
+            .unwrap()
+            .positions
+            .len()
+    }
+
+    #[test]
+    fn cooks_only_the_selected_input() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let broken = graph.add_node(node_definition(BuiltinNodeKind::File));
+        graph
+            .set_param(
+                broken,
+                "path",
+                ParamValue::String("missing.obj".to_string()),
+            )
+            .unwrap();
+        let switch = graph.add_node(node_definition(BuiltinNodeKind::Switch));
+        connect(&mut graph, source, switch, 0);
+        connect(&mut graph, broken, switch, 1);
+
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&graph, switch, &mut state).unwrap();
+        assert!(result.report.errors.is_empty());
+        assert!(!result.report.computed.contains(&broken));
+        assert_eq!(point_count(&state, switch), point_count(&state, source));
+
+        graph
+            .set_param(switch, "index", ParamValue::Int(1))
+            .unwrap();
+        let result = evaluate_geometry_graph(&graph, switch, &mut state).unwrap();
+        assert!(!result.report.errors.is_empty());
+
+        // Negative indices clamp to the first input.
+        graph
+            .set_param(switch, "index", ParamValue::Int(-3))
+            .unwrap();
+        let result = evaluate_geometry_graph(&graph, switch, &mut state).unwrap();
+        assert!(result.report.errors.is_empty());
+    }
+
+    #[test]
+    fn detail_attribute_picks_the_input() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let selector = graph.add_node(node_definition(BuiltinNodeKind::Wrangle));
+        graph
+            .set_param(selector, "mode", ParamValue::Int(3))
+            .unwrap();
+        graph
+            .set_param(
+                selector,
+                "code",
+                ParamValue::String("@switch = 1;".to_string()),
+            )
+            .unwrap();
+        let other = graph.add_node(node_definition(BuiltinNodeKind::Sphere));
+        let switch = graph.add_node(node_definition(BuiltinNodeKind::Switch));
+        graph
+            .set_param(
+                switch,
+                "mode",
+                ParamValue::Int(super::MODE_DETAIL_ATTRIBUTE),
+            )
+            .unwrap();
+        connect(&mut graph, source, selector, 0);
+        connect(&mut graph, selector, switch, 0);
+        connect(&mut graph, other, switch, 1);
+
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&graph, switch, &mut state).unwrap();
+        assert!(
+            result.report.errors.is_empty(),
+            "{:?}",
+            result.report.errors
+        );
+        assert_eq!(point_count(&state, switch), point_count(&state, other));
+        assert_eq!(state.eval.selected_input(switch), Some(1));
+    }
+}
@@ -16,6 +16,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -90,9 +91,9 @@ fn apply_uv_texture(params: &NodeParams, mesh: &mut Mesh) {
         let _ = mesh.set_attribute(
             AttributeDomain::Point,
             "uv",
-            AttributeStorage::Vec2(uvs.clone()),
+            AttributeStorage::Vec2(uvs.clone().into()),
         );
-        mesh.uvs = Some(uvs);
+        mesh.uvs = Some(uvs.into());
         return;
     }
 
@@ -145,7 +146,7 @@ fn apply_uv_texture(params: &NodeParams, mesh: &mut Mesh) {
     let _ = mesh.set_attribute(
         AttributeDomain::Vertex,
         "uv",
-        AttributeStorage::Vec2(corner_uvs),
+        AttributeStorage::Vec2(corner_uvs.into()),
     );
 }
 
@@ -263,7 +264,7 @@ fn compute_face_normals(mesh: &Mesh) -> Vec<Vec3> {
             vec![mesh.indices.len() as u32]
         }
     } else {
-        mesh.face_counts.clone()
+        mesh.face_counts.to_vec()
     };
 
     let mut normals = Vec::with_capacity(face_counts.len());
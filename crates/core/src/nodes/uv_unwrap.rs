@@ -16,6 +16,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -179,7 +180,7 @@ fn apply_uv_unwrap(params: &NodeParams, mesh: &mut Mesh) {
     let _ = mesh.set_attribute(
         AttributeDomain::Vertex,
         "uv",
-        AttributeStorage::Vec2(corner_uvs),
+        AttributeStorage::Vec2(corner_uvs.into()),
     );
 }
 
@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use crate::geometry::Geometry;
+use crate::graph::{NodeDefinition, NodeParams, ParamValue, PinType};
+use crate::nodes::{value_in, value_out};
+use crate::param_spec::ParamSpec;
+
+pub const NAME: &str = "Value Math";
+
+const OP_ADD: i32 = 0;
+const OP_SUBTRACT: i32 = 1;
+const OP_MULTIPLY: i32 = 2;
+const OP_DIVIDE: i32 = 3;
+const OP_MIN: i32 = 4;
+const OP_MAX: i32 = 5;
+const OP_POWER: i32 = 6;
+
+pub fn definition() -> NodeDefinition {
+    NodeDefinition {
+        name: NAME.to_string(),
+        category: "Values".to_string(),
+        inputs: vec![value_in("a", PinType::Float), value_in("b", PinType::Float)],
+        outputs: vec![value_out("value", PinType::Float)],
+        variadic_inputs: false,
+    }
+}
+
+pub fn default_params() -> NodeParams {
+    NodeParams {
+        values: BTreeMap::from([
+            ("op".to_string(), ParamValue::Int(OP_ADD)),
+            ("a".to_string(), ParamValue::Float(0.0)),
+            ("b".to_string(), ParamValue::Float(0.0)),
+        ]),
+    }
+}
+
+pub fn param_specs() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::int_enum(
+            "op",
+            "Operation",
+            vec![
+                (OP_ADD, "Add"),
+                (OP_SUBTRACT, "Subtract"),
+                (OP_MULTIPLY, "Multiply"),
+                (OP_DIVIDE, "Divide"),
+                (OP_MIN, "Min"),
+                (OP_MAX, "Max"),
+                (OP_POWER, "Power"),
+            ],
+        )
+        .with_help("Operation applied to A and B."),
+        ParamSpec::float("a", "A")
+            .with_help("First operand, used when the a input is not connected."),
+        ParamSpec::float("b", "B")
+            .with_help("Second operand, used when the b input is not connected."),
+    ]
+}
+
+pub fn compute_values(
+    params: &NodeParams,
+    _inputs: &[Geometry],
+) -> Result<Vec<ParamValue>, String> {
+    let a = params.get_float("a", 0.0);
+    let b = params.get_float("b", 0.0);
+    let value = match params.get_int("op", OP_ADD) {
+        OP_SUBTRACT => a - b,
+        OP_MULTIPLY => a * b,
+        OP_DIVIDE => {
+            if b == 0.0 {
+                return Err("Value Math: division by zero".to_string());
+            }
+            a / b
+        }
+        OP_MIN => a.min(b),
+        OP_MAX => a.max(b),
+        OP_POWER => a.powf(b),
+        _ => a + b,
+    };
+    Ok(vec![ParamValue::Float(value)])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::{Graph, NodeId, ParamValue, PinType};
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    fn max_x(state: &GeometryEvalState, node: NodeId) -> f32 {
+        state
+            .geometry_for_node(node)
+            .unwrap()
+            .merged_mesh()
+            .unwrap()
+            .bounds()
+            .unwrap()
+            .max[0]
+    }
+
+    #[test]
+    fn linked_values_drive_parameters() {
+        let mut graph = Graph::default();
+        let constant = graph.add_node(node_definition(BuiltinNodeKind::Constant));
+        graph
+            .set_param(constant, "float", ParamValue::Float(2.0))
+            .unwrap();
+        let math = graph.add_node(node_definition(BuiltinNodeKind::ValueMath));
+        graph
+            .set_param(math, "op", ParamValue::Int(super::OP_MULTIPLY))
+            .unwrap();
+        graph.set_param(math, "b", ParamValue::Float(3.0)).unwrap();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+
+        let constant_out = graph.node(constant).unwrap().outputs[0];
+        let math_a = graph.param_input(math, "a").unwrap();
+        graph.add_link(constant_out, math_a).unwrap();
+        let scale = graph
+            .add_param_input(transform, "scale", PinType::Vec3)
+            .unwrap();
+        let math_out = graph.node(math).unwrap().outputs[0];
+        graph.add_link(math_out, scale).unwrap();
+        let box_out = graph.node(source).unwrap().outputs[0];
+        let transform_in = graph.node(transform).unwrap().inputs[0];
+        graph.add_link(box_out, transform_in).unwrap();
+
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&graph, transform, &mut state).unwrap();
+        assert!(
+            result.report.errors.is_empty(),
+            "{:?}",
+            result.report.errors
+        );
+        assert_eq!(
+            state.value_for_output(math, 0),
+            Some(&ParamValue::Float(6.0))
+        );
+        assert!((max_x(&state, transform) - 3.0).abs() < 1.0e-5);
+
+        graph
+            .set_param(constant, "float", ParamValue::Float(1.0))
+            .unwrap();
+        let result = evaluate_geometry_graph(&graph, transform, &mut state).unwrap();
+        assert!(result.report.computed.contains(&transform));
+        assert!(!result.report.computed.contains(&source));
+        assert!((max_x(&state, transform) - 1.5).abs() < 1.0e-5);
+
+        // Without the input the literal scale applies again.
+        assert!(graph.remove_param_input(transform, "scale"));
+        evaluate_geometry_graph(&graph, transform, &mut state).unwrap();
+        assert!((max_x(&state, transform) - 0.5).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn division_by_zero_is_an_error() {
+        let mut graph = Graph::default();
+        let math = graph.add_node(node_definition(BuiltinNodeKind::ValueMath));
+        graph
+            .set_param(math, "op", ParamValue::Int(super::OP_DIVIDE))
+            .unwrap();
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&graph, math, &mut state).unwrap();
+        assert_eq!(result.report.errors.len(), 1);
+        assert!(state.value_for_output(math, 0).is_none());
+    }
+}
@@ -19,8 +19,9 @@ pub fn definition() -> NodeDefinition {
     NodeDefinition {
         name: NAME.to_string(),
         category: "Operators".to_string(),
-        inputs: vec![geometry_in("a"), geometry_in("b")],
+        inputs: vec![geometry_in("in0")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: true,
     }
 }
 
@@ -47,7 +48,7 @@ pub fn param_specs() -> Vec<ParamSpec> {
                 (5, "Average"),
             ],
         )
-        .with_help("How to combine the two volumes."),
+        .with_help("How to combine the volumes, folded in input order."),
         ParamSpec::int_enum(
             "resolution",
             "Resolution",
@@ -64,22 +65,25 @@ pub fn apply_to_geometry(
     let Some(left) = inputs.first() else {
         return Ok(Geometry::default());
     };
-    let Some(right) = inputs.get(1) else {
+    if inputs.len() < 2 {
         return Err("Volume Combine requires two inputs".to_string());
-    };
-    let Some(vol_a) = left.volumes.first() else {
+    }
+    let Some(first) = left.volumes.first() else {
         return Err("Volume Combine requires a volume on input 0".to_string());
     };
-    let Some(vol_b) = right.volumes.first() else {
-        return Err("Volume Combine requires a volume on input 1".to_string());
-    };
-    if vol_a.kind != vol_b.kind {
-        return Err("Volume Combine requires matching volume types".to_string());
-    }
 
     let op = params.get_int("op", DEFAULT_OP);
     let resolution = params.get_int("resolution", DEFAULT_RESOLUTION);
-    let combined = combine_volumes(vol_a, vol_b, op, resolution)?;
+    let mut combined = first.clone();
+    for (idx, input) in inputs.iter().enumerate().skip(1) {
+        let Some(volume) = input.volumes.first() else {
+            return Err(format!("Volume Combine requires a volume on input {}", idx));
+        };
+        if combined.kind != volume.kind {
+            return Err("Volume Combine requires matching volume types".to_string());
+        }
+        combined = combine_volumes(&combined, volume, op, resolution)?;
+    }
 
     let mut output = left.clone();
     output.volumes = vec![combined];
@@ -7,6 +7,7 @@ use crate::graph::{NodeDefinition, NodeParams, ParamValue};
 use crate::nodes::{geometry_in, geometry_out};
 use crate::param_spec::ParamSpec;
 use crate::parallel;
+use crate::progress::{check_cancelled, current_cancel_token, CancelToken};
 use crate::volume::{try_alloc_f32, Volume, VolumeKind};
 
 pub const NAME: &str = "Volume from Splats";
@@ -33,6 +34,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -230,6 +232,7 @@ pub fn apply_to_geometry(
         outlier_min_opacity,
     };
     let gathered = gather_splats(input, &gather_settings)?;
+    check_cancelled()?;
     let bounds_min = gathered.min;
     let bounds_max = gathered.max;
     let splats = gathered.splats;
@@ -267,7 +270,11 @@ pub fn apply_to_geometry(
     let dim_y = dims[1] as usize;
     let dim_z = dims[2] as usize;
     let stride_xy = dim_x.saturating_mul(dim_y).max(1);
+    let cancel = current_cancel_token();
     parallel::for_each_indexed_mut(&mut unsigned, |idx, slot| {
+        if cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+            return;
+        }
         let z = idx / stride_xy;
         let rem = idx - z * stride_xy;
         let y = rem / dim_x;
@@ -314,6 +321,7 @@ pub fn apply_to_geometry(
         }
         *slot = dist;
     });
+    check_cancelled()?;
 
     let grad = if fill_enabled && fill_normal_bias > 0.0 {
         Some(distance_gradient_magnitude(&unsigned, dims, voxel_size))
@@ -331,6 +339,7 @@ pub fn apply_to_geometry(
     } else {
         None
     };
+    check_cancelled()?;
 
     let mut values = try_alloc_f32(total as usize, "Volume from Splats")?;
     let shell_offset = shell_band.max(voxel_size * 0.5);
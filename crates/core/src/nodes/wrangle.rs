@@ -22,6 +22,7 @@ pub fn definition() -> NodeDefinition {
         category: "Operators".to_string(),
         inputs: vec![geometry_in("in"), geometry_in("input1")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
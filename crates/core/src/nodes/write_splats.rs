@@ -14,6 +14,7 @@ pub fn definition() -> NodeDefinition {
         category: "Outputs".to_string(),
         inputs: vec![geometry_in("in")],
         outputs: vec![geometry_out("out")],
+        variadic_inputs: false,
     }
 }
 
@@ -32,9 +33,22 @@ pub fn default_params() -> NodeParams {
 pub fn param_specs() -> Vec<ParamSpec> {
     vec![
         ParamSpec::path("path", "Path", ParamPathKind::WriteSplat)
-            .with_help("Output PLY file path."),
-        ParamSpec::int_enum("format", "Format", vec![(0, "Binary"), (1, "ASCII")])
-            .with_help("PLY file format (binary is faster)."),
+            .with_help("Output splat file path."),
+        ParamSpec::int_enum(
+            "format",
+            "Format",
+            vec![
+                (0, "Binary PLY"),
+                (1, "ASCII PLY"),
+                (2, ".splat"),
+                (3, ".ksplat"),
+                (4, "Compressed PLY"),
+                (5, ".spz"),
+            ],
+        )
+        .with_help(
+            "File format: PLY keeps full precision; .splat, .ksplat, compressed PLY and .spz are quantized.",
+        ),
     ]
 }
 
@@ -1,7 +1,8 @@
 use crate::attributes::AttributeDomain;
-use crate::graph::{NodeDefinition, NodeParams};
+use crate::graph::{NodeDefinition, NodeParams, ParamValue};
 use crate::geometry::{merge_splats, Geometry};
 use crate::mesh::Mesh;
+use crate::node_registry::node_type;
 use crate::nodes;
 use crate::parallel;
 use crate::param_spec::ParamSpec;
@@ -23,6 +24,7 @@ pub enum BuiltinNodeKind {
     BooleanSdf,
     BooleanGeo,
     Delete,
+    Split,
     Prune,
     Regularize,
     SplatLod,
@@ -69,7 +71,20 @@ pub enum BuiltinNodeKind {
     AttributeTransfer,
     AttributeMath,
     Wrangle,
+    Subnet,
+    SubnetInput,
+    SubnetOutput,
+    ForEachBegin,
+    ForEachEnd,
+    Switch,
+    Constant,
+    ValueMath,
+    RandomValue,
+    DetailToValue,
+    BoundingBox,
     ObjOutput,
+    PlyOutput,
+    StlOutput,
     Output,
 }
 
@@ -92,6 +107,12 @@ pub enum InputPolicy {
     RequireAtLeast(usize),
 }
 
+/// Cooks every output pin; the flags say which outputs something reads, and
+/// the others may be left empty.
+pub type GeometryOutputsFn =
+    fn(&NodeParams, &[Geometry], &[bool]) -> Result<Vec<Geometry>, String>;
+pub type ValueOutputsFn = fn(&NodeParams, &[Geometry]) -> Result<Vec<ParamValue>, String>;
+
 pub struct NodeSpec {
     pub kind: BuiltinNodeKind,
     pub id: &'static str,
@@ -102,6 +123,11 @@ pub struct NodeSpec {
     pub param_specs: fn() -> Vec<ParamSpec>,
     pub compute_mesh: fn(&NodeParams, &[Mesh]) -> Result<Mesh, String>,
     pub compute_geometry: fn(&NodeParams, &[Geometry]) -> Result<Geometry, String>,
+    /// Computes every output pin for nodes with more than one; `compute_geometry`
+    /// still produces the first output on its own.
+    pub compute_outputs: Option<GeometryOutputsFn>,
+    /// Computes the value outputs of value nodes, one per output pin.
+    pub compute_values: Option<ValueOutputsFn>,
     pub compute_splat: fn(&NodeParams, &[SplatGeo]) -> Result<SplatGeo, String>,
     pub menu_group: Option<&'static str>,
     pub input_policy: InputPolicy,
@@ -147,6 +173,15 @@ fn mesh_error_volume_to_mesh(_params: &NodeParams, _inputs: &[Mesh]) -> Result<M
     Err("Volume to Mesh expects volume geometry, not meshes".to_string())
 }
 
+fn mesh_error_value_node(_params: &NodeParams, _inputs: &[Mesh]) -> Result<Mesh, String> {
+    Err("value nodes output values, not meshes".to_string())
+}
+
+/// Value nodes have no geometry; their outputs come from `compute_values`.
+fn geometry_value_node(_params: &NodeParams, _inputs: &[Geometry]) -> Result<Geometry, String> {
+    Ok(Geometry::default())
+}
+
 fn mesh_error_attribute_from_volume(_params: &NodeParams, _inputs: &[Mesh]) -> Result<Mesh, String> {
     Err("Attribute from Volume requires volume input, not meshes".to_string())
 }
@@ -162,6 +197,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::box_node::param_specs,
         compute_mesh: nodes::box_node::compute,
         compute_geometry: compute_geometry_box,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::None,
@@ -176,6 +213,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::grid::param_specs,
         compute_mesh: nodes::grid::compute,
         compute_geometry: compute_geometry_grid,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::None,
@@ -190,6 +229,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::sphere::param_specs,
         compute_mesh: nodes::sphere::compute,
         compute_geometry: compute_geometry_sphere,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::None,
@@ -204,6 +245,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::tube::param_specs,
         compute_mesh: nodes::tube::compute,
         compute_geometry: compute_geometry_tube,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::None,
@@ -218,6 +261,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::circle::param_specs,
         compute_mesh: nodes::circle::compute,
         compute_geometry: compute_geometry_circle,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::None,
@@ -232,6 +277,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::curve::param_specs,
         compute_mesh: mesh_error_curve,
         compute_geometry: compute_geometry_curve,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::None,
@@ -246,6 +293,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::sweep::param_specs,
         compute_mesh: mesh_error_sweep,
         compute_geometry: nodes::sweep::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -260,6 +309,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::file::param_specs,
         compute_mesh: nodes::file::compute,
         compute_geometry: compute_geometry_file,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("IO"),
         input_policy: InputPolicy::None,
@@ -274,6 +325,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::read_splats::param_specs,
         compute_mesh: mesh_error_read_splats,
         compute_geometry: compute_geometry_read_splats,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: compute_splat_read_splats,
         menu_group: Some("IO"),
         input_policy: InputPolicy::None,
@@ -288,6 +341,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::write_splats::param_specs,
         compute_mesh: mesh_error_write_splats,
         compute_geometry: apply_write_splats,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("IO"),
         input_policy: InputPolicy::RequireAll,
@@ -302,6 +357,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::gltf_output::param_specs,
         compute_mesh: nodes::gltf_output::compute,
         compute_geometry: apply_obj_output,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("IO"),
         input_policy: InputPolicy::RequireAll,
@@ -316,6 +373,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::boolean::param_specs,
         compute_mesh: nodes::boolean::compute,
         compute_geometry: nodes::boolean::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -330,6 +389,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::boolean_geo::param_specs,
         compute_mesh: nodes::boolean_geo::compute,
         compute_geometry: nodes::boolean_geo::apply_to_geometry,
+        compute_outputs: Some(nodes::boolean_geo::apply_to_geometry_outputs),
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -344,6 +405,24 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::delete::param_specs,
         compute_mesh: nodes::delete::compute,
         compute_geometry: apply_delete,
+        compute_outputs: None,
+        compute_values: None,
+        compute_splat: splat_error_not_output,
+        menu_group: None,
+        input_policy: InputPolicy::RequireAll,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::Split,
+        id: "builtin:split",
+        name: nodes::split::NAME,
+        aliases: &[],
+        definition: nodes::split::definition,
+        default_params: nodes::split::default_params,
+        param_specs: nodes::split::param_specs,
+        compute_mesh: nodes::split::compute,
+        compute_geometry: nodes::split::apply_to_geometry,
+        compute_outputs: Some(nodes::split::apply_to_geometry_outputs),
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -358,6 +437,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::prune::param_specs,
         compute_mesh: nodes::prune::compute,
         compute_geometry: apply_prune,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAll,
@@ -372,6 +453,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::regularize::param_specs,
         compute_mesh: nodes::regularize::compute,
         compute_geometry: apply_regularize,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAll,
@@ -386,6 +469,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::splat_lod::param_specs,
         compute_mesh: nodes::splat_lod::compute,
         compute_geometry: apply_splat_lod,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAll,
@@ -400,6 +485,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::splat_to_mesh::param_specs,
         compute_mesh: mesh_error_splat_to_mesh,
         compute_geometry: nodes::splat_to_mesh::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAtLeast(1),
@@ -414,6 +501,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::splat_deform::param_specs,
         compute_mesh: nodes::splat_deform::compute,
         compute_geometry: nodes::splat_deform::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAll,
@@ -428,6 +517,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::splat_delight::param_specs,
         compute_mesh: nodes::splat_delight::compute,
         compute_geometry: apply_splat_delight,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAtLeast(1),
@@ -442,6 +533,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::splat_integrate::param_specs,
         compute_mesh: nodes::splat_integrate::compute,
         compute_geometry: nodes::splat_integrate::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAll,
@@ -456,6 +549,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::splat_heal::param_specs,
         compute_mesh: nodes::splat_heal::compute,
         compute_geometry: apply_splat_heal,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAtLeast(1),
@@ -470,6 +565,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::splat_outlier::param_specs,
         compute_mesh: nodes::splat_outlier::compute,
         compute_geometry: apply_splat_outlier,
+        compute_outputs: Some(apply_splat_outlier_outputs),
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAll,
@@ -484,6 +581,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::splat_outlier_sdf::param_specs,
         compute_mesh: nodes::splat_outlier_sdf::compute,
         compute_geometry: apply_mesh_outliers_sdf,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAll,
@@ -498,6 +597,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::splat_cluster::param_specs,
         compute_mesh: nodes::splat_cluster::compute,
         compute_geometry: apply_splat_cluster,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAll,
@@ -512,6 +613,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::splat_merge::param_specs,
         compute_mesh: nodes::splat_merge::compute,
         compute_geometry: nodes::splat_merge::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Splat"),
         input_policy: InputPolicy::RequireAll,
@@ -526,6 +629,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::volume_from_geo::param_specs,
         compute_mesh: mesh_error_volume_from_geo,
         compute_geometry: nodes::volume_from_geo::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Volume"),
         input_policy: InputPolicy::RequireAll,
@@ -540,6 +645,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::volume_from_splats::param_specs,
         compute_mesh: mesh_error_volume_from_splats,
         compute_geometry: nodes::volume_from_splats::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Volume"),
         input_policy: InputPolicy::RequireAll,
@@ -554,9 +661,11 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::volume_combine::param_specs,
         compute_mesh: mesh_error_volume_combine,
         compute_geometry: nodes::volume_combine::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Volume"),
-        input_policy: InputPolicy::RequireAll,
+        input_policy: InputPolicy::RequireAtLeast(2),
     },
     NodeSpec {
         kind: BuiltinNodeKind::VolumeBlur,
@@ -568,6 +677,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::volume_blur::param_specs,
         compute_mesh: mesh_error_volume_blur,
         compute_geometry: nodes::volume_blur::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAtLeast(1),
@@ -582,6 +693,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::volume_to_mesh::param_specs,
         compute_mesh: mesh_error_volume_to_mesh,
         compute_geometry: nodes::volume_to_mesh::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Volume"),
         input_policy: InputPolicy::RequireAll,
@@ -596,6 +709,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::group::param_specs,
         compute_mesh: nodes::group::compute,
         compute_geometry: apply_group,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -610,6 +725,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::group_expand::param_specs,
         compute_mesh: nodes::group_expand::compute,
         compute_geometry: apply_group_expand,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -624,6 +741,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::transform::param_specs,
         compute_mesh: nodes::transform::compute,
         compute_geometry: apply_transform,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -638,6 +757,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::fuse::param_specs,
         compute_mesh: nodes::fuse::compute,
         compute_geometry: nodes::fuse::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -652,6 +773,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::ffd::param_specs,
         compute_mesh: nodes::ffd::compute,
         compute_geometry: nodes::ffd::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAtLeast(1),
@@ -666,6 +789,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::copy_transform::param_specs,
         compute_mesh: nodes::copy_transform::compute,
         compute_geometry: apply_copy_transform,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -680,6 +805,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::merge::param_specs,
         compute_mesh: nodes::merge::compute,
         compute_geometry: compute_geometry_merge,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAtLeast(1),
@@ -694,6 +821,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::copy_to_points::param_specs,
         compute_mesh: nodes::copy_to_points::compute,
         compute_geometry: apply_copy_to_points,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -708,6 +837,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::scatter::param_specs,
         compute_mesh: nodes::scatter::compute,
         compute_geometry: nodes::scatter::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -722,6 +853,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::normal::param_specs,
         compute_mesh: nodes::normal::compute,
         compute_geometry: compute_geometry_normal,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -736,6 +869,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::polyframe::param_specs,
         compute_mesh: nodes::polyframe::compute,
         compute_geometry: nodes::polyframe::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -750,6 +885,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::color::param_specs,
         compute_mesh: nodes::color::compute,
         compute_geometry: compute_geometry_color,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -764,6 +901,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::noise::param_specs,
         compute_mesh: nodes::noise::compute,
         compute_geometry: compute_geometry_noise,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -778,6 +917,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::erosion_noise::param_specs,
         compute_mesh: nodes::erosion_noise::compute,
         compute_geometry: compute_geometry_erosion_noise,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -792,6 +933,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::smooth::param_specs,
         compute_mesh: nodes::smooth::compute,
         compute_geometry: compute_geometry_smooth,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -806,6 +949,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::resample::param_specs,
         compute_mesh: nodes::resample::compute,
         compute_geometry: nodes::resample::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -820,6 +965,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::uv_texture::param_specs,
         compute_mesh: nodes::uv_texture::compute,
         compute_geometry: compute_geometry_uv_texture,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -834,6 +981,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::uv_unwrap::param_specs,
         compute_mesh: nodes::uv_unwrap::compute,
         compute_geometry: compute_geometry_uv_unwrap,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -848,6 +997,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::uv_view::param_specs,
         compute_mesh: nodes::uv_view::compute,
         compute_geometry: compute_geometry_uv_view,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -862,6 +1013,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::material::param_specs,
         compute_mesh: nodes::material::compute,
         compute_geometry: nodes::material::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Materials"),
         input_policy: InputPolicy::RequireAll,
@@ -876,6 +1029,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::ray::param_specs,
         compute_mesh: nodes::ray::compute,
         compute_geometry: nodes::ray::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -890,6 +1045,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::attribute_noise::param_specs,
         compute_mesh: nodes::attribute_noise::compute,
         compute_geometry: compute_geometry_attribute_noise,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -904,6 +1061,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::attribute_promote::param_specs,
         compute_mesh: nodes::attribute_promote::compute,
         compute_geometry: compute_geometry_attribute_promote,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -918,6 +1077,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::attribute_expand::param_specs,
         compute_mesh: nodes::attribute_expand::compute,
         compute_geometry: compute_geometry_attribute_expand,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -932,6 +1093,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::attribute_from_feature::param_specs,
         compute_mesh: nodes::attribute_from_feature::compute,
         compute_geometry: compute_geometry_attribute_from_feature,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -946,6 +1109,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::attribute_from_volume::param_specs,
         compute_mesh: mesh_error_attribute_from_volume,
         compute_geometry: nodes::attribute_from_volume::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -960,6 +1125,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::attribute_transfer::param_specs,
         compute_mesh: nodes::attribute_transfer::compute,
         compute_geometry: apply_attribute_transfer,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -974,6 +1141,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::attribute_math::param_specs,
         compute_mesh: nodes::attribute_math::compute,
         compute_geometry: compute_geometry_attribute_math,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("Attribute"),
         input_policy: InputPolicy::RequireAll,
@@ -988,10 +1157,188 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::wrangle::param_specs,
         compute_mesh: nodes::wrangle::compute,
         compute_geometry: nodes::wrangle::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAtLeast(1),
     },
+    NodeSpec {
+        kind: BuiltinNodeKind::Subnet,
+        id: "builtin:subnet",
+        name: nodes::subnet::NAME,
+        aliases: &[],
+        definition: nodes::subnet::definition,
+        default_params: nodes::subnet::default_params,
+        param_specs: nodes::subnet::param_specs,
+        compute_mesh: nodes::subnet::compute,
+        compute_geometry: nodes::subnet::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
+        compute_splat: splat_error_not_output,
+        menu_group: None,
+        input_policy: InputPolicy::RequireAll,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::SubnetInput,
+        id: "builtin:subnet_input",
+        name: nodes::subnet::INPUT_NAME,
+        aliases: &[],
+        definition: nodes::subnet::input_definition,
+        default_params: nodes::subnet::pin_default_params,
+        param_specs: nodes::subnet::pin_param_specs,
+        compute_mesh: nodes::subnet::compute_input,
+        compute_geometry: nodes::subnet::apply_input_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
+        compute_splat: splat_error_not_output,
+        menu_group: None,
+        input_policy: InputPolicy::None,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::SubnetOutput,
+        id: "builtin:subnet_output",
+        name: nodes::subnet::OUTPUT_NAME,
+        aliases: &[],
+        definition: nodes::subnet::output_definition,
+        default_params: nodes::subnet::pin_default_params,
+        param_specs: nodes::subnet::pin_param_specs,
+        compute_mesh: nodes::subnet::compute_output,
+        compute_geometry: nodes::subnet::apply_output_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
+        compute_splat: splat_error_not_output,
+        menu_group: None,
+        input_policy: InputPolicy::RequireAll,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::ForEachBegin,
+        id: "builtin:for_each_begin",
+        name: nodes::for_each::BEGIN_NAME,
+        aliases: &[],
+        definition: nodes::for_each::begin_definition,
+        default_params: nodes::for_each::begin_default_params,
+        param_specs: nodes::for_each::begin_param_specs,
+        compute_mesh: nodes::for_each::compute_begin,
+        compute_geometry: nodes::for_each::apply_begin_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
+        compute_splat: splat_error_not_output,
+        menu_group: None,
+        input_policy: InputPolicy::RequireAll,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::ForEachEnd,
+        id: "builtin:for_each_end",
+        name: nodes::for_each::END_NAME,
+        aliases: &[],
+        definition: nodes::for_each::end_definition,
+        default_params: nodes::for_each::end_default_params,
+        param_specs: nodes::for_each::end_param_specs,
+        compute_mesh: nodes::for_each::compute_end,
+        compute_geometry: nodes::for_each::apply_end_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
+        compute_splat: splat_error_not_output,
+        menu_group: None,
+        input_policy: InputPolicy::RequireAll,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::Switch,
+        id: "builtin:switch",
+        name: nodes::switch::NAME,
+        aliases: &[],
+        definition: nodes::switch::definition,
+        default_params: nodes::switch::default_params,
+        param_specs: nodes::switch::param_specs,
+        compute_mesh: nodes::switch::compute,
+        compute_geometry: nodes::switch::apply_to_geometry,
+        compute_outputs: None,
+        compute_values: None,
+        compute_splat: splat_error_not_output,
+        menu_group: None,
+        input_policy: InputPolicy::None,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::Constant,
+        id: "builtin:constant",
+        name: nodes::constant::NAME,
+        aliases: &[],
+        definition: nodes::constant::definition,
+        default_params: nodes::constant::default_params,
+        param_specs: nodes::constant::param_specs,
+        compute_mesh: mesh_error_value_node,
+        compute_geometry: geometry_value_node,
+        compute_outputs: None,
+        compute_values: Some(nodes::constant::compute_values),
+        compute_splat: splat_error_not_output,
+        menu_group: Some("Values"),
+        input_policy: InputPolicy::None,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::ValueMath,
+        id: "builtin:value_math",
+        name: nodes::value_math::NAME,
+        aliases: &[],
+        definition: nodes::value_math::definition,
+        default_params: nodes::value_math::default_params,
+        param_specs: nodes::value_math::param_specs,
+        compute_mesh: mesh_error_value_node,
+        compute_geometry: geometry_value_node,
+        compute_outputs: None,
+        compute_values: Some(nodes::value_math::compute_values),
+        compute_splat: splat_error_not_output,
+        menu_group: Some("Values"),
+        input_policy: InputPolicy::None,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::RandomValue,
+        id: "builtin:random_value",
+        name: nodes::random_value::NAME,
+        aliases: &[],
+        definition: nodes::random_value::definition,
+        default_params: nodes::random_value::default_params,
+        param_specs: nodes::random_value::param_specs,
+        compute_mesh: mesh_error_value_node,
+        compute_geometry: geometry_value_node,
+        compute_outputs: None,
+        compute_values: Some(nodes::random_value::compute_values),
+        compute_splat: splat_error_not_output,
+        menu_group: Some("Values"),
+        input_policy: InputPolicy::None,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::DetailToValue,
+        id: "builtin:detail_to_value",
+        name: nodes::detail_to_value::NAME,
+        aliases: &[],
+        definition: nodes::detail_to_value::definition,
+        default_params: nodes::detail_to_value::default_params,
+        param_specs: nodes::detail_to_value::param_specs,
+        compute_mesh: mesh_error_value_node,
+        compute_geometry: geometry_value_node,
+        compute_outputs: None,
+        compute_values: Some(nodes::detail_to_value::compute_values),
+        compute_splat: splat_error_not_output,
+        menu_group: Some("Values"),
+        input_policy: InputPolicy::RequireAll,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::BoundingBox,
+        id: "builtin:bounding_box",
+        name: nodes::bounding_box::NAME,
+        aliases: &[],
+        definition: nodes::bounding_box::definition,
+        default_params: nodes::bounding_box::default_params,
+        param_specs: nodes::bounding_box::param_specs,
+        compute_mesh: mesh_error_value_node,
+        compute_geometry: geometry_value_node,
+        compute_outputs: None,
+        compute_values: Some(nodes::bounding_box::compute_values),
+        compute_splat: splat_error_not_output,
+        menu_group: Some("Values"),
+        input_policy: InputPolicy::RequireAll,
+    },
     NodeSpec {
         kind: BuiltinNodeKind::ObjOutput,
         id: "builtin:obj_output",
@@ -1002,6 +1349,40 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::obj_output::param_specs,
         compute_mesh: nodes::obj_output::compute,
         compute_geometry: apply_obj_output,
+        compute_outputs: None,
+        compute_values: None,
+        compute_splat: splat_error_not_output,
+        menu_group: Some("IO"),
+        input_policy: InputPolicy::RequireAll,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::PlyOutput,
+        id: "builtin:ply_output",
+        name: nodes::ply_output::NAME,
+        aliases: &[],
+        definition: nodes::ply_output::definition,
+        default_params: nodes::ply_output::default_params,
+        param_specs: nodes::ply_output::param_specs,
+        compute_mesh: nodes::ply_output::compute,
+        compute_geometry: apply_obj_output,
+        compute_outputs: None,
+        compute_values: None,
+        compute_splat: splat_error_not_output,
+        menu_group: Some("IO"),
+        input_policy: InputPolicy::RequireAll,
+    },
+    NodeSpec {
+        kind: BuiltinNodeKind::StlOutput,
+        id: "builtin:stl_output",
+        name: nodes::stl_output::NAME,
+        aliases: &[],
+        definition: nodes::stl_output::definition,
+        default_params: nodes::stl_output::default_params,
+        param_specs: nodes::stl_output::param_specs,
+        compute_mesh: nodes::stl_output::compute,
+        compute_geometry: apply_obj_output,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: Some("IO"),
         input_policy: InputPolicy::RequireAll,
@@ -1016,6 +1397,8 @@ static NODE_SPECS: &[NodeSpec] = &[
         param_specs: nodes::output::param_specs,
         compute_mesh: nodes::output::compute,
         compute_geometry: compute_geometry_output,
+        compute_outputs: None,
+        compute_values: None,
         compute_splat: splat_error_not_output,
         menu_group: None,
         input_policy: InputPolicy::RequireAll,
@@ -1083,9 +1466,10 @@ pub fn param_specs_for_name(name: &str) -> Vec<ParamSpec> {
         .unwrap_or_default()
 }
 
+/// Parameter specs of a built-in or registered plugin kind.
 pub fn param_specs_for_kind_id(kind_id: &str) -> Vec<ParamSpec> {
-    builtin_kind_from_id(kind_id)
-        .map(param_specs)
+    node_type(kind_id)
+        .map(|node_type| node_type.param_specs())
         .unwrap_or_default()
 }
 
@@ -1105,6 +1489,36 @@ pub fn compute_geometry_node(
     (node_spec(kind).compute_geometry)(params, inputs)
 }
 
+/// Whether `kind` leaves outputs nothing reads empty instead of cooking them.
+pub fn cooks_demanded_outputs(kind: BuiltinNodeKind) -> bool {
+    matches!(kind, BuiltinNodeKind::BooleanGeo)
+}
+
+/// Computes one geometry per output pin of `kind`.
+pub fn compute_geometry_outputs(
+    kind: BuiltinNodeKind,
+    params: &NodeParams,
+    inputs: &[Geometry],
+    demanded: &[bool],
+) -> Result<Vec<Geometry>, String> {
+    let spec = node_spec(kind);
+    match spec.compute_outputs {
+        Some(compute) => compute(params, inputs, demanded),
+        None => Ok(vec![(spec.compute_geometry)(params, inputs)?]),
+    }
+}
+
+/// Computes the value outputs of a value node, or `None` for geometry nodes.
+pub fn compute_value_outputs(
+    kind: BuiltinNodeKind,
+    params: &NodeParams,
+    inputs: &[Geometry],
+) -> Option<Result<Vec<ParamValue>, String>> {
+    node_spec(kind)
+        .compute_values
+        .map(|compute| compute(params, inputs))
+}
+
 fn compute_geometry_box(params: &NodeParams, _inputs: &[Geometry]) -> Result<Geometry, String> {
     Ok(Geometry::with_mesh(nodes::box_node::compute(params, &[])?))
 }
@@ -1428,6 +1842,33 @@ fn apply_splat_outlier(params: &NodeParams, inputs: &[Geometry]) -> Result<Geome
     })
 }
 
+fn apply_splat_outlier_outputs(
+    params: &NodeParams,
+    inputs: &[Geometry],
+    _demanded: &[bool],
+) -> Result<Vec<Geometry>, String> {
+    let Some(input) = inputs.first() else {
+        return Ok(vec![Geometry::default(), Geometry::default()]);
+    };
+    let mut pieces: Vec<(SplatGeo, SplatGeo)> = (0..input.splats.len())
+        .map(|_| (SplatGeo::default(), SplatGeo::default()))
+        .collect();
+    let input_splats = input.splats.as_slice();
+    parallel::for_each_indexed_mut(&mut pieces, |idx, slot| {
+        *slot = nodes::splat_outlier::split_outliers(params, &input_splats[idx]);
+    });
+    let (kept, removed) = pieces.into_iter().unzip();
+    let kept = Geometry {
+        splats: kept,
+        ..input.clone()
+    };
+    let removed = Geometry {
+        splats: removed,
+        ..Default::default()
+    };
+    Ok(vec![kept, removed])
+}
+
 fn apply_mesh_outliers_sdf(params: &NodeParams, inputs: &[Geometry]) -> Result<Geometry, String> {
     nodes::splat_outlier_sdf::apply_to_geometry(params, inputs)
 }
@@ -1703,6 +2144,7 @@ fn apply_obj_output(params: &NodeParams, inputs: &[Geometry]) -> Result<Geometry
         }
         if !output.meshes.is_empty() {
             output.curves = input.curves.clone();
+            output.materials = input.materials.clone();
         }
     }
     Ok(output)
@@ -324,7 +324,7 @@ fn gradient(x: i32, y: i32, z: i32, seed: u32) -> Vec3 {
     }
 }
 
-fn hash_f32(x: i32, y: i32, z: i32, seed: u32) -> f32 {
+pub(crate) fn hash_f32(x: i32, y: i32, z: i32, seed: u32) -> f32 {
     let h = hash_u32(x, y, z, seed);
     (h as f32) / (u32::MAX as f32)
 }
@@ -64,3 +64,33 @@ where
         f(idx);
     }
 }
+
+/// Maps coarse-grained tasks (e.g. whole node cooks) in parallel whenever there is
+/// more than one, unlike the element helpers above which wait for large slices.
+pub fn map_tasks<T, R, F>(tasks: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if tasks.len() > 1 {
+            return tasks.par_iter().map(&f).collect();
+        }
+    }
+
+    tasks.iter().map(f).collect()
+}
+
+/// Index of the rayon worker running the caller; `None` on any other thread.
+pub fn worker_index() -> Option<usize> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        rayon::current_thread_index()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+}
@@ -35,6 +35,8 @@ pub enum ParamPathKind {
     ReadMesh,
     WriteObj,
     WriteGltf,
+    WritePly,
+    WriteStl,
     ReadSplat,
     WriteSplat,
     ReadTexture,
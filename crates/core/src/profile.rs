@@ -0,0 +1,279 @@
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::eval::{DirtyReason, EvalError, EvalReport};
+use crate::graph::{Graph, NodeId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileFormat {
+    /// Chrome trace events, for `chrome://tracing` or Perfetto.
+    ChromeTrace,
+    /// Per-node timings as plain JSON, for diffing between runs.
+    Summary,
+}
+
+impl ProfileFormat {
+    /// `*.trace.json` files get trace events, anything else the summary.
+    pub fn for_path(path: &Path) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        if name.ends_with(".trace.json") {
+            Self::ChromeTrace
+        } else {
+            Self::Summary
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProfileSummary {
+    pub lobedo_version: String,
+    pub total_ms: f32,
+    pub computed: usize,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    pub output_valid: bool,
+    pub cancelled: bool,
+    pub nodes: Vec<NodeProfile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NodeProfile {
+    pub name: String,
+    pub kind: String,
+    pub start_ms: f32,
+    pub duration_ms: f32,
+    pub cache_hit: bool,
+    pub disk_hit: bool,
+    pub dirty: Option<String>,
+    pub thread: Option<usize>,
+    pub input_bytes: usize,
+    pub output_bytes: usize,
+    pub error: Option<String>,
+    pub phases: Vec<PhaseProfile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PhaseProfile {
+    pub name: String,
+    pub depth: usize,
+    pub start_ms: f32,
+    pub duration_ms: f32,
+}
+
+/// Flattens a report into per-node entries in evaluation order.
+pub fn profile_summary(graph: &Graph, report: &EvalReport) -> ProfileSummary {
+    let nodes: Vec<NodeProfile> = report
+        .ordered
+        .iter()
+        .filter_map(|node_id| {
+            let entry = report.node_reports.get(node_id)?;
+            let node = graph.node(*node_id);
+            Some(NodeProfile {
+                name: node.map_or_else(|| format!("{:?}", node_id), |node| node.name.clone()),
+                kind: node.map(|node| node.kind_id.clone()).unwrap_or_default(),
+                start_ms: entry.start_ms,
+                duration_ms: entry.duration_ms,
+                cache_hit: entry.cache_hit,
+                disk_hit: report.disk_hits.contains(node_id),
+                dirty: report
+                    .dirty
+                    .iter()
+                    .find(|dirty| dirty.node == *node_id)
+                    .map(|dirty| dirty_reason_name(dirty.reason).to_string()),
+                thread: entry.thread,
+                input_bytes: entry.input_bytes,
+                output_bytes: entry.output_bytes,
+                error: entry.error.as_ref().map(|err| error_message(graph, err)),
+                phases: entry
+                    .phases
+                    .iter()
+                    .map(|phase| PhaseProfile {
+                        name: phase.name.clone(),
+                        depth: phase.depth,
+                        start_ms: phase.start_ms,
+                        duration_ms: phase.duration_ms,
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+    let total_ms = nodes
+        .iter()
+        .map(|node| node.start_ms + node.duration_ms)
+        .fold(0.0, f32::max);
+    ProfileSummary {
+        lobedo_version: env!("CARGO_PKG_VERSION").to_string(),
+        total_ms,
+        computed: report.computed.len(),
+        cache_hits: report.cache_hits,
+        cache_misses: report.cache_misses,
+        output_valid: report.output_valid,
+        cancelled: report.cancelled,
+        nodes,
+    }
+}
+
+/// Trace events with one track per thread: nodes that cooked are complete events
+/// with their phases nested below, cache hits are instant events.
+pub fn chrome_trace(graph: &Graph, report: &EvalReport) -> Value {
+    let summary = profile_summary(graph, report);
+    let tid = |thread: Option<usize>| thread.map_or(0, |index| index + 1);
+    let mut threads: Vec<usize> = summary.nodes.iter().map(|node| tid(node.thread)).collect();
+    threads.sort_unstable();
+    threads.dedup();
+
+    let mut events: Vec<Value> = threads
+        .into_iter()
+        .map(|tid| {
+            let name = if tid == 0 {
+                "eval".to_string()
+            } else {
+                format!("worker {}", tid - 1)
+            };
+            json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": tid,
+                "args": { "name": name },
+            })
+        })
+        .collect();
+    for node in &summary.nodes {
+        let ts = millis_to_micros(node.start_ms);
+        if node.cache_hit {
+            events.push(json!({
+                "name": node.name,
+                "cat": "cache",
+                "ph": "i",
+                "s": "t",
+                "ts": ts,
+                "pid": 1,
+                "tid": tid(node.thread),
+            }));
+            continue;
+        }
+        events.push(json!({
+            "name": node.name,
+            "cat": "node",
+            "ph": "X",
+            "ts": ts,
+            "dur": millis_to_micros(node.duration_ms),
+            "pid": 1,
+            "tid": tid(node.thread),
+            "args": {
+                "kind": node.kind,
+                "dirty": node.dirty,
+                "disk_hit": node.disk_hit,
+                "input_bytes": node.input_bytes,
+                "output_bytes": node.output_bytes,
+                "error": node.error,
+            },
+        }));
+        for phase in &node.phases {
+            events.push(json!({
+                "name": phase.name,
+                "cat": "phase",
+                "ph": "X",
+                "ts": ts + millis_to_micros(phase.start_ms),
+                "dur": millis_to_micros(phase.duration_ms),
+                "pid": 1,
+                "tid": tid(node.thread),
+                "args": { "node": node.name, "depth": phase.depth },
+            }));
+        }
+    }
+    json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ms",
+        "otherData": { "lobedo_version": summary.lobedo_version },
+    })
+}
+
+pub fn write_eval_profile(
+    graph: &Graph,
+    report: &EvalReport,
+    path: &Path,
+    format: ProfileFormat,
+) -> Result<(), String> {
+    let data = match format {
+        ProfileFormat::ChromeTrace => serde_json::to_vec_pretty(&chrome_trace(graph, report)),
+        ProfileFormat::Summary => serde_json::to_vec_pretty(&profile_summary(graph, report)),
+    }
+    .map_err(|err| err.to_string())?;
+    std::fs::write(path, data).map_err(|err| err.to_string())
+}
+
+fn millis_to_micros(ms: f32) -> f64 {
+    f64::from(ms) * 1000.0
+}
+
+fn dirty_reason_name(reason: DirtyReason) -> &'static str {
+    match reason {
+        DirtyReason::NewNode => "new",
+        DirtyReason::ParamChanged => "param",
+        DirtyReason::UpstreamChanged => "upstream",
+        DirtyReason::ParamAndUpstreamChanged => "param+upstream",
+    }
+}
+
+fn error_message(graph: &Graph, err: &EvalError) -> String {
+    let name = |node_id: &NodeId| {
+        graph
+            .node(*node_id)
+            .map_or_else(|| format!("{:?}", node_id), |node| node.name.clone())
+    };
+    match err {
+        EvalError::Node { message, .. } => message.clone(),
+        EvalError::Upstream { upstream, .. } => {
+            let upstream: Vec<String> = upstream.iter().map(name).collect();
+            format!("upstream failed ({})", upstream.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::Graph;
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    use super::{chrome_trace, profile_summary};
+
+    #[test]
+    fn profiles_list_cooked_nodes_with_sizes() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        let from = graph.node(source).unwrap().outputs[0];
+        let to = graph.node(transform).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+
+        let mut state = GeometryEvalState::new();
+        let result = evaluate_geometry_graph(&graph, transform, &mut state).unwrap();
+        let summary = profile_summary(&graph, &result.report);
+        assert_eq!(summary.computed, 2);
+        let box_node = &summary.nodes[0];
+        let transform_node = &summary.nodes[1];
+        assert_eq!(box_node.dirty.as_deref(), Some("new"));
+        assert_eq!(box_node.input_bytes, 0);
+        assert!(box_node.output_bytes > 0);
+        assert_eq!(transform_node.input_bytes, box_node.output_bytes);
+        assert!(transform_node.start_ms >= box_node.start_ms + box_node.duration_ms);
+
+        let trace = chrome_trace(&graph, &result.report);
+        let events = trace["traceEvents"].as_array().unwrap();
+        let cooked = events.iter().filter(|event| event["ph"] == "X").count();
+        assert_eq!(cooked, 2);
+
+        let result = evaluate_geometry_graph(&graph, transform, &mut state).unwrap();
+        let trace = chrome_trace(&graph, &result.report);
+        let events = trace["traceEvents"].as_array().unwrap();
+        assert_eq!(events.iter().filter(|event| event["ph"] == "i").count(), 2);
+    }
+}
@@ -1,6 +1,12 @@
 use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
 use crate::graph::NodeId;
 
 #[derive(Clone, Debug)]
@@ -12,10 +18,46 @@ pub enum ProgressEvent {
 
 pub type ProgressSink = Arc<dyn Fn(ProgressEvent) + Send + Sync>;
 
+pub const CANCELLED_MESSAGE: &str = "cook cancelled";
+
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// A named section of a node's cook, recorded with [`progress_phase`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseReport {
+    pub name: String,
+    /// Number of phases that were open around this one.
+    pub depth: usize,
+    /// Offset from the start of the node's cook.
+    pub start_ms: f32,
+    pub duration_ms: f32,
+}
+
 #[derive(Default)]
 struct ProgressContext {
     sink: Option<ProgressSink>,
     node: Option<NodeId>,
+    cancel: Option<CancelToken>,
+    started: Option<Instant>,
+    open_phases: usize,
+    phases: Vec<PhaseReport>,
 }
 
 thread_local! {
@@ -35,8 +77,24 @@ impl Drop for ProgressGuard {
     }
 }
 
-pub fn set_progress_context(node: NodeId, sink: Option<ProgressSink>) -> ProgressGuard {
-    let prev = CONTEXT.with(|ctx| std::mem::replace(&mut *ctx.borrow_mut(), ProgressContext { sink, node: Some(node) }));
+pub fn set_progress_context(
+    node: NodeId,
+    sink: Option<ProgressSink>,
+    cancel: Option<CancelToken>,
+) -> ProgressGuard {
+    let prev = CONTEXT.with(|ctx| {
+        std::mem::replace(
+            &mut *ctx.borrow_mut(),
+            ProgressContext {
+                sink,
+                node: Some(node),
+                cancel,
+                started: Some(Instant::now()),
+                open_phases: 0,
+                phases: Vec::new(),
+            },
+        )
+    });
     ProgressGuard { prev }
 }
 
@@ -50,6 +108,77 @@ pub fn report_progress(fraction: f32) {
     });
 }
 
+pub struct PhaseGuard {
+    index: Option<usize>,
+    start: Instant,
+}
+
+impl Drop for PhaseGuard {
+    fn drop(&mut self) {
+        let Some(index) = self.index else {
+            return;
+        };
+        let duration_ms = self.start.elapsed().as_secs_f32() * 1000.0;
+        CONTEXT.with(|ctx| {
+            let mut ctx = ctx.borrow_mut();
+            ctx.open_phases = ctx.open_phases.saturating_sub(1);
+            if let Some(phase) = ctx.phases.get_mut(index) {
+                phase.duration_ms = duration_ms;
+            }
+        });
+    }
+}
+
+/// Times the rest of the scope as a phase of the node being cooked, for cook
+/// profiles. Phases opened while another is alive nest inside it.
+pub fn progress_phase(name: impl Into<String>) -> PhaseGuard {
+    let start = Instant::now();
+    let index = CONTEXT.with(|ctx| {
+        let mut ctx = ctx.borrow_mut();
+        let started = ctx.started?;
+        let depth = ctx.open_phases;
+        ctx.open_phases += 1;
+        ctx.phases.push(PhaseReport {
+            name: name.into(),
+            depth,
+            start_ms: start.duration_since(started).as_secs_f32() * 1000.0,
+            duration_ms: 0.0,
+        });
+        Some(ctx.phases.len() - 1)
+    });
+    PhaseGuard { index, start }
+}
+
+/// Phases recorded for the node cooking on this thread.
+pub(crate) fn take_phases() -> Vec<PhaseReport> {
+    CONTEXT.with(|ctx| std::mem::take(&mut ctx.borrow_mut().phases))
+}
+
+pub fn is_cancelled() -> bool {
+    CONTEXT.with(|ctx| {
+        ctx.borrow()
+            .cancel
+            .as_ref()
+            .is_some_and(CancelToken::is_cancelled)
+    })
+}
+
+/// Rayon workers do not inherit the thread-local context, so parallel loops grab
+/// the token up front and poll it directly.
+pub(crate) fn current_cancel_token() -> Option<CancelToken> {
+    CONTEXT.with(|ctx| ctx.borrow().cancel.clone())
+}
+
+/// Long-running nodes call this between phases (and every few thousand items in
+/// serial loops) so a stale cook can bail out with `?`.
+pub fn check_cancelled() -> Result<(), String> {
+    if is_cancelled() {
+        Err(CANCELLED_MESSAGE.to_string())
+    } else {
+        Ok(())
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 pub fn current_progress_context() -> Option<(NodeId, ProgressSink)> {
     CONTEXT.with(|ctx| {
@@ -1,15 +1,25 @@
+use std::path::{Path, PathBuf};
+
 use serde::{Deserialize, Serialize};
 
 use crate::graph::Graph;
 use crate::nodes;
+use crate::subnet::SubnetAsset;
+use crate::wedge::Wedge;
 
-pub const PROJECT_VERSION: u32 = 3;
+pub const PROJECT_VERSION: u32 = 5;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
     pub version: u32,
     pub settings: ProjectSettings,
     pub graph: Graph,
+    /// Subnet assets imported into the project, offered in the add-node menu.
+    #[serde(default)]
+    pub assets: Vec<SubnetAsset>,
+    /// Parameter sweeps, run from the app or with `--wedge` in headless mode.
+    #[serde(default)]
+    pub wedges: Vec<Wedge>,
 }
 
 impl Default for Project {
@@ -18,6 +28,8 @@ impl Default for Project {
             version: PROJECT_VERSION,
             settings: ProjectSettings::default(),
             graph: Graph::default(),
+            assets: Vec::new(),
+            wedges: Vec::new(),
         }
     }
 }
@@ -33,6 +45,25 @@ impl Project {
             self.version = 3;
         }
         self.graph.rebuild_link_index();
+        for asset in &mut self.assets {
+            asset.subnet.graph.rebuild_link_index();
+        }
+        if self.version < 4 {
+            // Merge, Switch and Volume Combine inputs became variadic.
+            self.graph.migrate_variadic_inputs();
+            for asset in &mut self.assets {
+                asset.subnet.graph.migrate_variadic_inputs();
+            }
+            self.version = 4;
+        }
+        if self.version < 5 {
+            // Boolean Geo and Splat Outlier gained extra outputs.
+            self.graph.migrate_missing_outputs();
+            for asset in &mut self.assets {
+                asset.subnet.graph.migrate_missing_outputs();
+            }
+            self.version = 5;
+        }
         self.graph
             .rename_nodes(nodes::read_splats::LEGACY_NAME, nodes::read_splats::NAME);
         self.graph
@@ -55,6 +86,10 @@ pub struct ProjectSettings {
     pub render_debug: RenderDebugSettings,
     pub graph_notes: Vec<GraphNote>,
     pub next_note_id: u64,
+    /// Memory budget for cached node outputs in MiB; 0 keeps every output.
+    pub cook_cache_budget_mb: u32,
+    pub disk_cache: DiskCacheSettings,
+    pub timeline: TimelineSettings,
 }
 
 impl Default for ProjectSettings {
@@ -68,6 +103,89 @@ impl Default for ProjectSettings {
             render_debug: RenderDebugSettings::default(),
             graph_notes: Vec::new(),
             next_note_id: 1,
+            cook_cache_budget_mb: 0,
+            disk_cache: DiskCacheSettings::default(),
+            timeline: TimelineSettings::default(),
+        }
+    }
+}
+
+impl ProjectSettings {
+    pub fn cook_cache_budget_bytes(&self) -> Option<usize> {
+        (self.cook_cache_budget_mb > 0)
+            .then(|| self.cook_cache_budget_mb as usize * 1024 * 1024)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiskCacheSettings {
+    pub enabled: bool,
+    /// Cache directory; relative paths resolve against the project file. Empty
+    /// uses `<project>.cache` next to it.
+    pub dir: String,
+    /// Only outputs that took at least this long to cook are written.
+    pub min_cook_ms: f32,
+}
+
+impl Default for DiskCacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: String::new(),
+            min_cook_ms: 100.0,
+        }
+    }
+}
+
+impl DiskCacheSettings {
+    /// Resolves the cache directory, or `None` when disabled or when the default
+    /// location is needed but the project has not been saved yet.
+    pub fn resolve_dir(&self, project_path: Option<&Path>) -> Option<PathBuf> {
+        if !self.enabled {
+            return None;
+        }
+        let project_dir = project_path.and_then(Path::parent);
+        let dir = self.dir.trim();
+        if !dir.is_empty() {
+            let dir = PathBuf::from(dir);
+            return Some(match project_dir {
+                Some(parent) if dir.is_relative() => parent.join(dir),
+                _ => dir,
+            });
+        }
+        let stem = project_path?.file_stem()?.to_string_lossy();
+        Some(project_dir?.join(format!("{}.cache", stem)))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimelineSettings {
+    pub frame_start: i32,
+    pub frame_end: i32,
+    pub current_frame: i32,
+    pub fps: f32,
+}
+
+impl Default for TimelineSettings {
+    fn default() -> Self {
+        Self {
+            frame_start: 1,
+            frame_end: 100,
+            current_frame: 1,
+            fps: 24.0,
+        }
+    }
+}
+
+impl TimelineSettings {
+    /// Frame after the current one, wrapping to the start of the range.
+    pub fn next_frame(&self) -> i32 {
+        if self.current_frame >= self.frame_end || self.current_frame < self.frame_start {
+            self.frame_start
+        } else {
+            self.current_frame + 1
         }
     }
 }
@@ -227,4 +345,37 @@ mod tests {
         let node = loaded.graph.node(a).expect("node");
         assert!(!node.kind_id.is_empty());
     }
+
+    #[test]
+    fn migrate_makes_merge_inputs_variadic() {
+        let mut project = Project {
+            version: 3,
+            ..Default::default()
+        };
+        let a = project
+            .graph
+            .add_node(node_definition(BuiltinNodeKind::Box));
+        // Merge used to have two fixed inputs.
+        let mut definition = node_definition(BuiltinNodeKind::Merge);
+        definition.inputs.push(definition.inputs[0].clone());
+        definition.variadic_inputs = false;
+        let merge = project.graph.add_node(definition);
+        project
+            .graph
+            .set_node_kind_id(merge, BuiltinNodeKind::Merge.id());
+        let from = project.graph.node(a).unwrap().outputs[0];
+        let to = project.graph.node(merge).unwrap().inputs[1];
+        project.graph.add_link(from, to).unwrap();
+
+        let data = serde_json::to_vec(&project).expect("serialize project");
+        let mut loaded: Project =
+            serde_json::from_slice(&data).expect("deserialize project");
+        loaded.migrate_to_latest();
+
+        let node = loaded.graph.node(merge).expect("node");
+        assert!(node.variadic_inputs);
+        assert_eq!(node.inputs.len(), 2);
+        assert_eq!(loaded.graph.input_node(merge, 0), Some(a));
+        assert!(loaded.graph.input_node(merge, 1).is_none());
+    }
 }
@@ -106,7 +106,7 @@ fn scene_mesh_from_mesh_with_materials(
             vec![mesh.indices.len() as u32]
         }
     } else {
-        mesh.face_counts.clone()
+        mesh.face_counts.to_vec()
     };
     let mut normals = fallback_normals(mesh);
     let mut corner_normals = mesh.corner_normals.clone();
@@ -116,7 +116,7 @@ fn scene_mesh_from_mesh_with_materials(
                 AttributeDomain::Vertex => {
                     if values.len() == mesh.indices.len() {
                         corner_normals =
-                            expand_corner_attribute(&values, &triangulation.corner_indices);
+                            expand_corner_attribute(&values, &triangulation.corner_indices).map(Into::into);
                     }
                 }
                 AttributeDomain::Point => {
@@ -129,7 +129,7 @@ fn scene_mesh_from_mesh_with_materials(
                     if let Some(expanded) =
                         expand_primitive_vec3(mesh, &values, &triangulation.tri_to_face)
                     {
-                        corner_normals = Some(expanded);
+                        corner_normals = Some(expanded.into());
                     }
                 }
                 AttributeDomain::Detail => {
@@ -138,7 +138,7 @@ fn scene_mesh_from_mesh_with_materials(
                             normals = vec![value; mesh.positions.len()];
                             corner_normals = None;
                         } else {
-                            corner_normals = Some(vec![value; tri_indices.len()]);
+                            corner_normals = Some(vec![value; tri_indices.len()].into());
                         }
                     }
                 }
@@ -186,14 +186,14 @@ fn scene_mesh_from_mesh_with_materials(
     let corner_materials = mesh_materials(mesh, material_lookup, &triangulation.tri_to_face);
 
     SceneMesh {
-        positions: mesh.positions.clone(),
+        positions: mesh.positions.to_vec(),
         normals,
         indices: tri_indices,
         tri_to_face,
         corner_indices,
-        poly_indices,
+        poly_indices: poly_indices.into_vec(),
         poly_face_counts,
-        corner_normals,
+        corner_normals: corner_normals.map(Into::into),
         colors,
         corner_colors,
         uvs,
@@ -227,14 +227,14 @@ fn scene_splats_from_splats(splats: &SplatGeo) -> SceneSplats {
             .any(|value| value[0] < 0.0 || value[1] < 0.0 || value[2] < 0.0);
 
     SceneSplats {
-        positions: splats.positions.clone(),
-        sh0,
+        positions: splats.positions.to_vec(),
+        sh0: sh0.into_vec(),
         sh_coeffs,
-        sh_rest,
+        sh_rest: sh_rest.into_vec(),
         sh0_is_coeff,
-        opacity,
-        scales,
-        rotations: splats.rotations.clone(),
+        opacity: opacity.into_vec(),
+        scales: scales.into_vec(),
+        rotations: splats.rotations.to_vec(),
     }
 }
 
@@ -274,11 +274,12 @@ fn scene_material_from_material(material: &Material) -> SceneMaterial {
 
 fn fallback_normals(mesh: &Mesh) -> Vec<[f32; 3]> {
     match &mesh.normals {
-        Some(normals) => normals.clone(),
+        Some(normals) => normals.to_vec(),
         None => {
             let mut temp = mesh.clone();
             temp.compute_normals();
             temp.normals
+                .map(|normals| normals.into_vec())
                 .unwrap_or_else(|| vec![[0.0, 1.0, 0.0]; mesh.positions.len()])
         }
     }
@@ -306,7 +307,7 @@ fn mesh_uvs(mesh: &Mesh, tri_indices: &[u32], corner_indices: &[usize]) -> UvDat
     if let Some(attr) = mesh.attribute(AttributeDomain::Point, "uv") {
         if let Some(values) = attr_vec2(attr) {
             if values.len() == mesh.positions.len() {
-                uvs = Some(values);
+                uvs = Some(values.into());
             }
         }
     }
@@ -331,7 +332,7 @@ fn mesh_uvs(mesh: &Mesh, tri_indices: &[u32], corner_indices: &[usize]) -> UvDat
         }
     }
 
-    (uvs, corner_uvs)
+    (uvs.map(Into::into), corner_uvs)
 }
 
 fn mesh_materials(
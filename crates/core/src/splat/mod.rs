@@ -1,24 +1,26 @@
+use serde::{Deserialize, Serialize};
+
 use crate::attributes::MeshAttributes;
+use crate::buffer::{ByteCounter, SharedVec};
 use crate::mesh::MeshGroups;
 
-pub use crate::splat_ply::{
-    load_splat_ply_with_mode, save_splat_ply_with_format, SplatLoadMode, SplatSaveFormat,
-};
+pub use crate::splat_io::{load_splats_with_mode, save_splats_with_format, SplatSaveFormat};
+pub use crate::splat_ply::{save_splat_ply_with_format, SplatLoadMode};
 
 const SPLAT_LOG_SCALE_MIN: f32 = -10.0;
 const SPLAT_LOG_SCALE_MAX: f32 = 10.0;
 const SPLAT_ALPHA_MIN: f32 = 1.0e-4;
 const SPLAT_ALPHA_MAX: f32 = 1.0 - 1.0e-4;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct SplatGeo {
-    pub positions: Vec<[f32; 3]>,
-    pub rotations: Vec<[f32; 4]>,
-    pub scales: Vec<[f32; 3]>,
-    pub opacity: Vec<f32>,
-    pub sh0: Vec<[f32; 3]>,
+    pub positions: SharedVec<[f32; 3]>,
+    pub rotations: SharedVec<[f32; 4]>,
+    pub scales: SharedVec<[f32; 3]>,
+    pub opacity: SharedVec<f32>,
+    pub sh0: SharedVec<[f32; 3]>,
     pub sh_coeffs: usize,
-    pub sh_rest: Vec<[f32; 3]>,
+    pub sh_rest: SharedVec<[f32; 3]>,
     pub attributes: MeshAttributes,
     pub groups: MeshGroups,
 }
@@ -26,13 +28,13 @@ pub struct SplatGeo {
 impl SplatGeo {
     pub fn with_len(count: usize) -> Self {
         Self {
-            positions: vec![[0.0, 0.0, 0.0]; count],
-            rotations: vec![[0.0, 0.0, 0.0, 1.0]; count],
-            scales: vec![[0.0, 0.0, 0.0]; count],
-            opacity: vec![1.0; count],
-            sh0: vec![[1.0, 1.0, 1.0]; count],
+            positions: vec![[0.0, 0.0, 0.0]; count].into(),
+            rotations: vec![[0.0, 0.0, 0.0, 1.0]; count].into(),
+            scales: vec![[0.0, 0.0, 0.0]; count].into(),
+            opacity: vec![1.0; count].into(),
+            sh0: vec![[1.0, 1.0, 1.0]; count].into(),
             sh_coeffs: 0,
-            sh_rest: Vec::new(),
+            sh_rest: Vec::new().into(),
             attributes: MeshAttributes::default(),
             groups: MeshGroups::default(),
         }
@@ -42,7 +44,7 @@ impl SplatGeo {
         let mut splats = Self::with_len(count);
         if sh_coeffs > 0 {
             splats.sh_coeffs = sh_coeffs;
-            splats.sh_rest = vec![[0.0, 0.0, 0.0]; count * sh_coeffs];
+            splats.sh_rest = vec![[0.0, 0.0, 0.0]; count * sh_coeffs].into();
         }
         splats
     }
@@ -54,6 +56,17 @@ impl SplatGeo {
     pub fn is_empty(&self) -> bool {
         self.positions.is_empty()
     }
+
+    pub fn count_bytes(&self, counter: &mut ByteCounter) {
+        counter.add(&self.positions);
+        counter.add(&self.rotations);
+        counter.add(&self.scales);
+        counter.add(&self.opacity);
+        counter.add(&self.sh0);
+        counter.add(&self.sh_rest);
+        self.attributes.count_bytes(counter);
+        counter.add_bytes(self.groups.approx_bytes());
+    }
 }
 
 mod attributes;
@@ -62,5 +75,7 @@ mod sh;
 mod transform;
 mod validate;
 
+#[cfg(test)]
+pub(crate) mod test_support;
 #[cfg(test)]
 mod tests;
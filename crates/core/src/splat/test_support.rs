@@ -0,0 +1,48 @@
+use crate::splat_packed::dc_color;
+
+use super::SplatGeo;
+
+pub(crate) fn sample_splats(count: usize, sh_coeffs: usize) -> SplatGeo {
+    let mut splats = SplatGeo::with_len_and_sh(count, sh_coeffs);
+    for idx in 0..count {
+        let t = idx as f32;
+        splats.positions[idx] = [t * 0.02 - 3.0, (t * 0.37).sin() * 4.0, (t * 0.11).cos()];
+        splats.scales[idx] = [-3.0, -2.5 + (t * 0.3).sin(), -4.0 + t * 0.001];
+        let half_angle = t * 0.01;
+        splats.rotations[idx] = [half_angle.cos(), 0.0, half_angle.sin(), 0.0];
+        splats.opacity[idx] = (t * 0.1).sin() * 3.0;
+        splats.sh0[idx] = if sh_coeffs > 0 {
+            [(t * 0.2).sin(), 0.5, -0.5]
+        } else {
+            [0.5 + (t * 0.2).sin() * 0.4, 0.25, 0.75]
+        };
+    }
+    for (idx, coeff) in splats.sh_rest.iter_mut().enumerate() {
+        *coeff = [(idx as f32 * 0.05).sin() * 0.5, -0.2, 0.3];
+    }
+    splats
+}
+
+/// Compressed PLY and `.ksplat` reorder splats; the samples have distinct x.
+pub(crate) fn sorted_by_x(splats: &SplatGeo) -> SplatGeo {
+    let mut order: Vec<usize> = (0..splats.len()).collect();
+    order.sort_by(|a, b| splats.positions[*a][0].total_cmp(&splats.positions[*b][0]));
+    splats.filter_by_indices(&order)
+}
+
+pub(crate) fn max_position_error(a: &SplatGeo, b: &SplatGeo) -> f32 {
+    a.positions
+        .iter()
+        .zip(b.positions.iter())
+        .flat_map(|(a, b)| (0..3).map(move |axis| (a[axis] - b[axis]).abs()))
+        .fold(0.0, f32::max)
+}
+
+pub(crate) fn max_color_error(a: &SplatGeo, b: &SplatGeo) -> f32 {
+    (0..a.len())
+        .flat_map(|idx| {
+            let (a, b) = (dc_color(a, idx), dc_color(b, idx));
+            (0..3).map(move |channel| (a[channel] - b[channel]).abs())
+        })
+        .fold(0.0, f32::max)
+}
@@ -1,6 +1,13 @@
 use glam::{Mat4, Quat, Vec3};
 
-use super::SplatGeo;
+use crate::splat_compressed_ply::encode_compressed_ply;
+use crate::splat_ply::parse_splat_ply_bytes_with_mode;
+use crate::splat_spz::{encode_spz, parse_spz_bytes};
+
+use super::test_support::{max_color_error, max_position_error, sample_splats, sorted_by_x};
+use super::{
+    load_splats_with_mode, save_splats_with_format, SplatGeo, SplatLoadMode, SplatSaveFormat,
+};
 
 #[test]
 fn transform_updates_positions_and_scales() {
@@ -95,3 +102,94 @@ fn validate_rejects_nan_sh_coeffs() {
     splats.sh_rest[1] = [f32::NAN, 0.0, 0.0];
     assert!(splats.validate().is_err());
 }
+
+fn max_sh_error(a: &SplatGeo, b: &SplatGeo) -> f32 {
+    a.sh_rest
+        .iter()
+        .zip(b.sh_rest.iter())
+        .flat_map(|(a, b)| (0..3).map(move |channel| (a[channel] - b[channel]).abs()))
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn compressed_ply_roundtrip_error() {
+    let splats = sample_splats(600, 8);
+    let data = encode_compressed_ply(&splats).expect("encode");
+    let loaded = parse_splat_ply_bytes_with_mode(&data, SplatLoadMode::Full).expect("parse");
+    assert_eq!(loaded.len(), splats.len());
+    assert_eq!(loaded.sh_coeffs, 8);
+    let loaded = sorted_by_x(&loaded);
+
+    let position_error = max_position_error(&splats, &loaded);
+    let color_error = max_color_error(&splats, &loaded);
+    assert!(position_error < 0.01, "position error {position_error}");
+    assert!(color_error < 1.0 / 255.0, "color error {color_error}");
+    assert!(max_sh_error(&splats, &loaded) < 0.02);
+
+    let color_only =
+        parse_splat_ply_bytes_with_mode(&data, SplatLoadMode::ColorOnly).expect("parse");
+    assert_eq!(color_only.sh_coeffs, 0);
+    assert!(max_color_error(&splats, &sorted_by_x(&color_only)) < 1.0 / 255.0);
+}
+
+#[test]
+fn spz_rejects_invalid_fractional_bits() {
+    use std::io::{Read, Write};
+
+    let data = encode_spz(&sample_splats(4, 0)).expect("encode");
+    let mut raw = Vec::new();
+    flate2::read::GzDecoder::new(data.as_slice())
+        .read_to_end(&mut raw)
+        .unwrap();
+    raw[13] = 32;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&raw).unwrap();
+    let data = encoder.finish().unwrap();
+    let err = parse_spz_bytes(&data, SplatLoadMode::Full).unwrap_err();
+    assert!(err.contains("fractional bits"), "{err}");
+}
+
+#[test]
+fn spz_roundtrip_error() {
+    let splats = sample_splats(300, 3);
+    let data = encode_spz(&splats).expect("encode");
+    let loaded = parse_spz_bytes(&data, SplatLoadMode::Full).expect("parse");
+    assert_eq!(loaded.len(), splats.len());
+    assert_eq!(loaded.sh_coeffs, 3);
+
+    let position_error = max_position_error(&splats, &loaded);
+    let color_error = max_color_error(&splats, &loaded);
+    assert!(position_error < 2.0e-4, "position error {position_error}");
+    assert!(color_error < 4.0e-3, "color error {color_error}");
+    assert!(max_sh_error(&splats, &loaded) < 0.035);
+    for idx in 0..splats.len() {
+        let mut expected = splats.scales[idx];
+        let mut actual = loaded.scales[idx];
+        expected.sort_by(f32::total_cmp);
+        actual.sort_by(f32::total_cmp);
+        for axis in 0..3 {
+            assert!((expected[axis] - actual[axis]).abs() < 0.05);
+        }
+    }
+}
+
+#[test]
+fn save_and_load_picks_compressed_formats_by_path() {
+    let splats = sample_splats(40, 0);
+    for (name, format) in [
+        (
+            "lobedo_splats.compressed.ply",
+            SplatSaveFormat::CompressedPly,
+        ),
+        ("lobedo_splats.spz", SplatSaveFormat::Spz),
+    ] {
+        let path = std::env::temp_dir().join(name);
+        assert_eq!(SplatSaveFormat::for_path(&path), Some(format));
+        let path_str = path.to_str().unwrap();
+        save_splats_with_format(path_str, &splats, format).expect("save");
+        let loaded = load_splats_with_mode(path_str, SplatLoadMode::Full).expect("load");
+        assert_eq!(loaded.len(), splats.len());
+        assert!(max_color_error(&splats, &sorted_by_x(&loaded)) < 4.0e-3);
+        let _ = std::fs::remove_file(path);
+    }
+}
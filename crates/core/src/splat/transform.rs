@@ -344,7 +344,7 @@ fn filter_attribute_storage(storage: &AttributeStorage, indices: &[usize]) -> At
                     out.push(*value);
                 }
             }
-            AttributeStorage::Float(out)
+            AttributeStorage::Float(out.into())
         }
         AttributeStorage::Int(values) => {
             let mut out = Vec::with_capacity(indices.len());
@@ -353,7 +353,7 @@ fn filter_attribute_storage(storage: &AttributeStorage, indices: &[usize]) -> At
                     out.push(*value);
                 }
             }
-            AttributeStorage::Int(out)
+            AttributeStorage::Int(out.into())
         }
         AttributeStorage::Vec2(values) => {
             let mut out = Vec::with_capacity(indices.len());
@@ -362,7 +362,7 @@ fn filter_attribute_storage(storage: &AttributeStorage, indices: &[usize]) -> At
                     out.push(*value);
                 }
             }
-            AttributeStorage::Vec2(out)
+            AttributeStorage::Vec2(out.into())
         }
         AttributeStorage::Vec3(values) => {
             let mut out = Vec::with_capacity(indices.len());
@@ -371,7 +371,7 @@ fn filter_attribute_storage(storage: &AttributeStorage, indices: &[usize]) -> At
                     out.push(*value);
                 }
             }
-            AttributeStorage::Vec3(out)
+            AttributeStorage::Vec3(out.into())
         }
         AttributeStorage::Vec4(values) => {
             let mut out = Vec::with_capacity(indices.len());
@@ -380,7 +380,7 @@ fn filter_attribute_storage(storage: &AttributeStorage, indices: &[usize]) -> At
                     out.push(*value);
                 }
             }
-            AttributeStorage::Vec4(out)
+            AttributeStorage::Vec4(out.into())
         }
         AttributeStorage::StringTable(values) => {
             let mut out = Vec::with_capacity(indices.len());
@@ -0,0 +1,423 @@
+use std::f32::consts::{FRAC_1_SQRT_2, SQRT_2};
+
+use crate::splat::{SplatGeo, SplatLoadMode};
+use crate::splat_packed::{
+    alpha_to_opacity, color_to_dc, dc_color, finish_load, opacity_to_alpha, prepare_save,
+    sh_coeffs_for_degree, sh_degree_for_coeffs,
+};
+use crate::splat_ply::{read_scalar, PlyElement, PlyFormat, PlyHeader, PlyScalarType};
+
+const CHUNK_SIZE: usize = 256;
+const CHUNK_PROPERTIES: [&str; 18] = [
+    "min_x",
+    "min_y",
+    "min_z",
+    "max_x",
+    "max_y",
+    "max_z",
+    "min_scale_x",
+    "min_scale_y",
+    "min_scale_z",
+    "max_scale_x",
+    "max_scale_y",
+    "max_scale_z",
+    "min_r",
+    "min_g",
+    "min_b",
+    "max_r",
+    "max_g",
+    "max_b",
+];
+const PACKED_PROPERTIES: [&str; 4] = [
+    "packed_position",
+    "packed_rotation",
+    "packed_scale",
+    "packed_color",
+];
+/// Range covered by the 8-bit SH values.
+const SH_RANGE: f32 = 8.0;
+
+/// Per-chunk ranges the packed vertex values are normalized against.
+#[derive(Debug, Clone, Copy)]
+struct ChunkRanges {
+    position: ([f32; 3], [f32; 3]),
+    scale: ([f32; 3], [f32; 3]),
+    color: ([f32; 3], [f32; 3]),
+}
+
+/// Parses the SuperSplat compressed PLY: a `chunk` element with min/max ranges
+/// for every 256 splats, a `vertex` element of packed uint32 position (11-10-11
+/// bits), log scale (11-10-11), rotation (largest component index plus three
+/// 10-bit components) and RGBA8 color, and an optional `sh` element of 8-bit
+/// `f_rest_*` values. Files without color ranges store color in 0..1.
+pub(crate) fn parse_compressed_ply(
+    data: &[u8],
+    header: &PlyHeader,
+    data_start: usize,
+    mode: SplatLoadMode,
+) -> Result<SplatGeo, String> {
+    if header.format != PlyFormat::BinaryLittle {
+        return Err("Compressed PLY must be binary little endian".to_string());
+    }
+    if header.elements.iter().any(PlyElement::has_lists) {
+        return Err("Compressed PLY cannot have list properties".to_string());
+    }
+    let chunk = header
+        .element("chunk")
+        .ok_or_else(|| "Compressed PLY has no chunk element".to_string())?;
+    let vertex = header
+        .element("vertex")
+        .ok_or_else(|| "Compressed PLY has no vertex element".to_string())?;
+    if chunk.count * CHUNK_SIZE < vertex.count {
+        return Err(format!(
+            "Compressed PLY has {} chunks for {} splats",
+            chunk.count, vertex.count
+        ));
+    }
+    let data_end = data_start
+        + header
+            .elements
+            .iter()
+            .map(|element| element.count * element.row_size())
+            .sum::<usize>();
+    if data_end > data.len() {
+        return Err("Unexpected end of compressed PLY data".to_string());
+    }
+
+    let chunks = read_chunks(data, data_start + header.element_offset("chunk"), chunk)?;
+    let mut packed_offsets = [0usize; 4];
+    for (slot, name) in PACKED_PROPERTIES.iter().enumerate() {
+        let index = vertex
+            .property_index(name)
+            .ok_or_else(|| format!("Compressed PLY is missing {name}"))?;
+        if !matches!(
+            vertex.properties[index].data_type,
+            PlyScalarType::Uint32 | PlyScalarType::Int32
+        ) {
+            return Err(format!("Compressed PLY {name} must be a 32-bit integer"));
+        }
+        packed_offsets[slot] = property_offset(vertex, index);
+    }
+
+    let sh = header
+        .element("sh")
+        .filter(|_| matches!(mode, SplatLoadMode::Full));
+    let sh_coeffs = sh.map_or(0, |sh| sh.properties.len() / 3);
+    if let Some(sh) = sh {
+        if sh.count != vertex.count {
+            return Err("Compressed PLY sh element does not match the splat count".to_string());
+        }
+    }
+
+    let mut splats = SplatGeo::with_len_and_sh(vertex.count, sh_coeffs);
+    let vertex_start = data_start + header.element_offset("vertex");
+    let vertex_stride = vertex.row_size();
+    for idx in 0..vertex.count {
+        let row = vertex_start + idx * vertex_stride;
+        let [position, rotation, scale, color] =
+            packed_offsets.map(|offset| read_u32(data, row + offset));
+        let ranges = &chunks[idx / CHUNK_SIZE];
+
+        splats.positions[idx] = lerp3(ranges.position, unpack_111011(position));
+        splats.scales[idx] = lerp3(ranges.scale, unpack_111011(scale));
+        splats.rotations[idx] = unpack_rotation(rotation);
+        let rgba = [24, 16, 8, 0].map(|shift| unpack_unorm(color >> shift, 8));
+        let rgb = lerp3(ranges.color, [rgba[0], rgba[1], rgba[2]]);
+        splats.opacity[idx] = alpha_to_opacity(rgba[3]);
+        splats.sh0[idx] = if sh_coeffs > 0 {
+            rgb.map(color_to_dc)
+        } else {
+            rgb
+        };
+    }
+
+    if let Some(sh) = sh.filter(|_| sh_coeffs > 0) {
+        let sh_start = data_start + header.element_offset("sh");
+        let sh_stride = sh.row_size();
+        for idx in 0..vertex.count {
+            let row = sh_start + idx * sh_stride;
+            let mut offset = row;
+            for (component, prop) in sh.properties.iter().enumerate().take(sh_coeffs * 3) {
+                let value = read_scalar(&data[offset..], prop.data_type, true)?;
+                offset += prop.data_type.size();
+                let (channel, coeff) = (component / sh_coeffs, component % sh_coeffs);
+                splats.sh_rest[idx * sh_coeffs + coeff][channel] = unpack_sh(value);
+            }
+        }
+    }
+    finish_load(splats)
+}
+
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+/// Writes a SuperSplat compressed PLY. Splats are reordered along a Morton
+/// curve so each 256-splat chunk is spatially tight; positions and scales keep
+/// 10-11 bits of their chunk range, color and alpha 8 bits, and SH up to
+/// degree 3 is quantized to 8 bits over -4..4.
+pub(crate) fn encode_compressed_ply(splats: &SplatGeo) -> Result<Vec<u8>, String> {
+    let splats = prepare_save(splats)?;
+    let splats = splats.filter_by_indices(&morton_order(&splats.positions));
+    let count = splats.len();
+    let chunk_count = count.div_ceil(CHUNK_SIZE);
+    let sh_coeffs = sh_coeffs_for_degree(sh_degree_for_coeffs(splats.sh_coeffs));
+
+    let mut header = String::from("ply\nformat binary_little_endian 1.0\n");
+    header.push_str(&format!("element chunk {chunk_count}\n"));
+    for name in CHUNK_PROPERTIES {
+        header.push_str(&format!("property float {name}\n"));
+    }
+    header.push_str(&format!("element vertex {count}\n"));
+    for name in PACKED_PROPERTIES {
+        header.push_str(&format!("property uint {name}\n"));
+    }
+    if sh_coeffs > 0 {
+        header.push_str(&format!("element sh {count}\n"));
+        for index in 0..sh_coeffs * 3 {
+            header.push_str(&format!("property uchar f_rest_{index}\n"));
+        }
+    }
+    header.push_str("end_header\n");
+
+    let mut data = header.into_bytes();
+    data.reserve(chunk_count * CHUNK_PROPERTIES.len() * 4 + count * (16 + sh_coeffs * 3));
+    let colors: Vec<[f32; 3]> = (0..count).map(|idx| dc_color(&splats, idx)).collect();
+    let mut chunks = Vec::with_capacity(chunk_count);
+    for start in (0..count).step_by(CHUNK_SIZE) {
+        let members = start..(start + CHUNK_SIZE).min(count);
+        let ranges = ChunkRanges {
+            position: bounds(&splats.positions[members.clone()]),
+            scale: bounds(&splats.scales[members.clone()]),
+            color: bounds(&colors[members]),
+        };
+        for pair in [ranges.position, ranges.scale, ranges.color] {
+            for value in pair.0.iter().chain(pair.1.iter()) {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        chunks.push(ranges);
+    }
+
+    for idx in 0..count {
+        let ranges = &chunks[idx / CHUNK_SIZE];
+        let position = pack_111011(normalize3(ranges.position, splats.positions[idx]));
+        let rotation = pack_rotation(splats.rotations[idx]);
+        let scale = pack_111011(normalize3(ranges.scale, splats.scales[idx]));
+        let [r, g, b] = normalize3(ranges.color, colors[idx]);
+        let alpha = opacity_to_alpha(splats.opacity[idx]);
+        let color = [r, g, b, alpha]
+            .into_iter()
+            .fold(0u32, |packed, value| (packed << 8) | pack_unorm(value, 8));
+        for value in [position, rotation, scale, color] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    if sh_coeffs > 0 {
+        for idx in 0..count {
+            let base = idx * splats.sh_coeffs;
+            for channel in 0..3 {
+                for coeff in 0..sh_coeffs {
+                    data.push(pack_sh(splats.sh_rest[base + coeff][channel]));
+                }
+            }
+        }
+    }
+    Ok(data)
+}
+
+fn read_chunks(data: &[u8], start: usize, chunk: &PlyElement) -> Result<Vec<ChunkRanges>, String> {
+    let indices: Vec<Option<usize>> = CHUNK_PROPERTIES
+        .iter()
+        .map(|name| chunk.property_index(name))
+        .collect();
+    if indices[..12].iter().any(Option::is_none) {
+        return Err("Compressed PLY chunk element is missing position or scale ranges".to_string());
+    }
+    let stride = chunk.row_size();
+    let mut chunks = Vec::with_capacity(chunk.count);
+    for row in 0..chunk.count {
+        let mut values = [0.0f32; 18];
+        for (slot, index) in indices.iter().enumerate() {
+            values[slot] = match index {
+                Some(index) => {
+                    let offset = start + row * stride + property_offset(chunk, *index);
+                    read_scalar(&data[offset..], chunk.properties[*index].data_type, true)?
+                }
+                // Older files store color in 0..1 without a range.
+                None if slot < 15 => 0.0,
+                None => 1.0,
+            };
+        }
+        let vec3 = |offset: usize| [values[offset], values[offset + 1], values[offset + 2]];
+        chunks.push(ChunkRanges {
+            position: (vec3(0), vec3(3)),
+            scale: (vec3(6), vec3(9)),
+            color: (vec3(12), vec3(15)),
+        });
+    }
+    Ok(chunks)
+}
+
+/// Byte offset of property `index` within a binary row.
+fn property_offset(element: &PlyElement, index: usize) -> usize {
+    element.properties[..index]
+        .iter()
+        .map(|prop| prop.data_type.size())
+        .sum()
+}
+
+fn bounds(values: &[[f32; 3]]) -> ([f32; 3], [f32; 3]) {
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for value in values {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(value[axis]);
+            max[axis] = max[axis].max(value[axis]);
+        }
+    }
+    (min, max)
+}
+
+fn normalize3((min, max): ([f32; 3], [f32; 3]), value: [f32; 3]) -> [f32; 3] {
+    std::array::from_fn(|axis| {
+        let range = max[axis] - min[axis];
+        if range > 0.0 {
+            (value[axis] - min[axis]) / range
+        } else {
+            0.0
+        }
+    })
+}
+
+fn lerp3((min, max): ([f32; 3], [f32; 3]), t: [f32; 3]) -> [f32; 3] {
+    std::array::from_fn(|axis| min[axis] + (max[axis] - min[axis]) * t[axis])
+}
+
+fn pack_unorm(value: f32, bits: u32) -> u32 {
+    let max = ((1u32 << bits) - 1) as f32;
+    (value * max + 0.5).floor().clamp(0.0, max) as u32
+}
+
+fn unpack_unorm(value: u32, bits: u32) -> f32 {
+    let max = (1u32 << bits) - 1;
+    (value & max) as f32 / max as f32
+}
+
+fn pack_111011(value: [f32; 3]) -> u32 {
+    (pack_unorm(value[0], 11) << 21) | (pack_unorm(value[1], 10) << 11) | pack_unorm(value[2], 11)
+}
+
+fn unpack_111011(value: u32) -> [f32; 3] {
+    [
+        unpack_unorm(value >> 21, 11),
+        unpack_unorm(value >> 11, 10),
+        unpack_unorm(value, 11),
+    ]
+}
+
+/// Packs a (w, x, y, z) quaternion in (x, y, z, w) order: the index of the
+/// largest component in the top 2 bits, then the other three in 10 bits each.
+fn pack_rotation(rotation: [f32; 4]) -> u32 {
+    let [w, x, y, z] = rotation;
+    let mut q = [x, y, z, w];
+    let length = q.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if length > 0.0 && length.is_finite() {
+        q = q.map(|value| value / length);
+    } else {
+        q = [0.0, 0.0, 0.0, 1.0];
+    }
+    let largest = (0..4)
+        .max_by(|a, b| q[*a].abs().total_cmp(&q[*b].abs()))
+        .unwrap_or(3);
+    if q[largest] < 0.0 {
+        q = q.map(|value| -value);
+    }
+    (0..4)
+        .filter(|index| *index != largest)
+        .fold(largest as u32, |packed, index| {
+            (packed << 10) | pack_unorm(q[index] * FRAC_1_SQRT_2 + 0.5, 10)
+        })
+}
+
+fn unpack_rotation(value: u32) -> [f32; 4] {
+    let largest = (value >> 30) as usize;
+    let mut q = [0.0f32; 4];
+    let mut shift = 30;
+    let mut sum = 0.0;
+    for (index, component) in q.iter_mut().enumerate() {
+        if index == largest {
+            continue;
+        }
+        shift -= 10;
+        *component = (unpack_unorm(value >> shift, 10) - 0.5) * SQRT_2;
+        sum += *component * *component;
+    }
+    q[largest] = (1.0 - sum).max(0.0).sqrt();
+    [q[3], q[0], q[1], q[2]]
+}
+
+fn pack_sh(value: f32) -> u8 {
+    ((value / SH_RANGE + 0.5) * 256.0).trunc().clamp(0.0, 255.0) as u8
+}
+
+fn unpack_sh(value: f32) -> f32 {
+    let t = match value as u8 {
+        0 => 0.0,
+        255 => 1.0,
+        n => (n as f32 + 0.5) / 256.0,
+    };
+    (t - 0.5) * SH_RANGE
+}
+
+/// Splat order along a 30-bit Morton curve over the bounding box.
+fn morton_order(positions: &[[f32; 3]]) -> Vec<usize> {
+    let (min, max) = bounds(positions);
+    let mut keyed: Vec<(u32, usize)> = positions
+        .iter()
+        .enumerate()
+        .map(|(idx, position)| {
+            let cell = normalize3((min, max), *position).map(|t| pack_unorm(t, 10));
+            (
+                spread_bits(cell[0]) | (spread_bits(cell[1]) << 1) | (spread_bits(cell[2]) << 2),
+                idx,
+            )
+        })
+        .collect();
+    keyed.sort_by_key(|(code, _)| *code);
+    keyed.into_iter().map(|(_, idx)| idx).collect()
+}
+
+/// Spreads the low 10 bits of `value` so two zero bits follow each one.
+fn spread_bits(value: u32) -> u32 {
+    let mut x = value & 0x3ff;
+    x = (x | (x << 16)) & 0x030000ff;
+    x = (x | (x << 8)) & 0x0300f00f;
+    x = (x | (x << 4)) & 0x030c30c3;
+    (x | (x << 2)) & 0x09249249
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pack_rotation, unpack_rotation};
+
+    #[test]
+    fn rotation_packing_keeps_sign_and_order() {
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        for rotation in [
+            [1.0, 0.0, 0.0, 0.0],
+            [half, 0.0, half, 0.0],
+            [0.1, -0.9, 0.3, 0.2],
+        ] {
+            let length = rotation.iter().map(|v: &f32| v * v).sum::<f32>().sqrt();
+            let expected = rotation.map(|v| v / length);
+            let actual = unpack_rotation(pack_rotation(rotation));
+            let dot: f32 = expected.iter().zip(actual.iter()).map(|(a, b)| a * b).sum();
+            assert!(dot.abs() > 0.999, "{expected:?} vs {actual:?}");
+        }
+    }
+}
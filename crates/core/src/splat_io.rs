@@ -0,0 +1,191 @@
+use std::path::Path;
+
+use crate::assets;
+use crate::splat::{SplatGeo, SplatLoadMode};
+use crate::splat_compressed_ply::encode_compressed_ply;
+use crate::splat_packed::{
+    encode_dot_splat, encode_ksplat, looks_like_ksplat, parse_dot_splat_bytes, parse_ksplat_bytes,
+    SPLAT_RECORD_BYTES,
+};
+use crate::splat_ply::parse_splat_ply_bytes_with_mode;
+use crate::splat_spz::{encode_spz, looks_like_spz, parse_spz_bytes};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplatSaveFormat {
+    Ascii,
+    BinaryLittle,
+    /// antimatter15 `.splat` records.
+    Splat,
+    /// GaussianSplats3D `.ksplat`, compression level 1.
+    Ksplat,
+    /// SuperSplat chunk-quantized `.compressed.ply`.
+    CompressedPly,
+    /// Niantic `.spz`, version 2.
+    Spz,
+}
+
+impl SplatSaveFormat {
+    /// Format for the Splat Write node's `format` parameter.
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => Self::Ascii,
+            2 => Self::Splat,
+            3 => Self::Ksplat,
+            4 => Self::CompressedPly,
+            5 => Self::Spz,
+            _ => Self::BinaryLittle,
+        }
+    }
+
+    /// Format implied by the extension of `path`, PLY being binary unless the
+    /// name ends in `.compressed.ply`.
+    pub fn for_path(path: &Path) -> Option<Self> {
+        let path = path.to_str()?;
+        match extension(path).as_str() {
+            "ply" if path.to_ascii_lowercase().ends_with(".compressed.ply") => {
+                Some(Self::CompressedPly)
+            }
+            "ply" => Some(Self::BinaryLittle),
+            "splat" => Some(Self::Splat),
+            "ksplat" => Some(Self::Ksplat),
+            "spz" => Some(Self::Spz),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SplatFileKind {
+    Ply,
+    Splat,
+    Ksplat,
+    Spz,
+}
+
+/// Loads splats from a PLY (plain or compressed), `.splat`, `.ksplat` or `.spz`
+/// file or URL. The format comes from the PLY magic, then the extension, then
+/// the `.ksplat` header or gzip magic; anything else made of whole 32-byte
+/// records is read as `.splat`.
+pub fn load_splats_with_mode(path: &str, mode: SplatLoadMode) -> Result<SplatGeo, String> {
+    let data = load_splat_bytes(path)?;
+    parse_splat_bytes(path, &data, mode)
+}
+
+fn parse_splat_bytes(path: &str, data: &[u8], mode: SplatLoadMode) -> Result<SplatGeo, String> {
+    let kind = detect_kind(path, data).ok_or_else(|| {
+        format!("Unrecognized splat file {path} (expected .ply, .splat, .ksplat or .spz)")
+    })?;
+    match kind {
+        SplatFileKind::Ply => parse_splat_ply_bytes_with_mode(data, mode),
+        SplatFileKind::Splat => parse_dot_splat_bytes(data),
+        SplatFileKind::Ksplat => parse_ksplat_bytes(data, mode),
+        SplatFileKind::Spz => parse_spz_bytes(data, mode),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_splats_with_format(
+    path: &str,
+    splats: &SplatGeo,
+    format: SplatSaveFormat,
+) -> Result<(), String> {
+    let data = match format {
+        SplatSaveFormat::Ascii | SplatSaveFormat::BinaryLittle => {
+            return crate::splat_ply::save_splat_ply_with_format(path, splats, format);
+        }
+        SplatSaveFormat::Splat => encode_dot_splat(splats)?,
+        SplatSaveFormat::Ksplat => encode_ksplat(splats)?,
+        SplatSaveFormat::CompressedPly => encode_compressed_ply(splats)?,
+        SplatSaveFormat::Spz => encode_spz(splats)?,
+    };
+    std::fs::write(path, data).map_err(|err| err.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_splats_with_format(
+    _path: &str,
+    _splats: &SplatGeo,
+    _format: SplatSaveFormat,
+) -> Result<(), String> {
+    Err("Splat Write is not supported in web builds".to_string())
+}
+
+fn detect_kind(path: &str, data: &[u8]) -> Option<SplatFileKind> {
+    if data.starts_with(b"ply") {
+        return Some(SplatFileKind::Ply);
+    }
+    match extension(path).as_str() {
+        "ply" => return Some(SplatFileKind::Ply),
+        "splat" => return Some(SplatFileKind::Splat),
+        "ksplat" => return Some(SplatFileKind::Ksplat),
+        "spz" => return Some(SplatFileKind::Spz),
+        _ => {}
+    }
+    if looks_like_ksplat(data) {
+        Some(SplatFileKind::Ksplat)
+    } else if looks_like_spz(data) {
+        Some(SplatFileKind::Spz)
+    } else if !data.is_empty() && data.len().is_multiple_of(SPLAT_RECORD_BYTES) {
+        Some(SplatFileKind::Splat)
+    } else {
+        None
+    }
+}
+
+/// Lowercase extension of a path or URL, ignoring any query string.
+fn extension(path: &str) -> String {
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+fn load_splat_bytes(path: &str) -> Result<Vec<u8>, String> {
+    if let Some(data) = assets::load_bytes(path) {
+        return Ok(data);
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if assets::is_url(path) {
+            return Err(format!("Failed to download URL: {path}"));
+        }
+        std::fs::read(path).map_err(|err| err.to_string())
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        if assets::is_url(path) {
+            Err("Splat URL is downloading; retrying shortly.".to_string())
+        } else {
+            Err("Splat Read is not supported in web builds without a picked file".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::splat::{SplatGeo, SplatLoadMode};
+    use crate::splat_packed::{encode_dot_splat, encode_ksplat};
+
+    use super::{detect_kind, parse_splat_bytes, SplatFileKind};
+
+    #[test]
+    fn detects_format_by_magic_then_extension() {
+        let splats = SplatGeo::with_len(4);
+        let dot_splat = encode_dot_splat(&splats).unwrap();
+        let ksplat = encode_ksplat(&splats).unwrap();
+
+        assert_eq!(detect_kind("a.bin", b"ply\n"), Some(SplatFileKind::Ply));
+        assert_eq!(
+            detect_kind("https://host/a.KSPLAT?v=2", &[]),
+            Some(SplatFileKind::Ksplat)
+        );
+        assert_eq!(detect_kind("a.bin", &ksplat), Some(SplatFileKind::Ksplat));
+        assert_eq!(detect_kind("a.bin", &dot_splat), Some(SplatFileKind::Splat));
+        assert_eq!(detect_kind("a.bin", &[1, 2, 3]), None);
+
+        let loaded = parse_splat_bytes("mem://a.splat", &dot_splat, SplatLoadMode::Full).unwrap();
+        assert_eq!(loaded.len(), 4);
+    }
+}
@@ -0,0 +1,644 @@
+use std::collections::BTreeMap;
+
+use half::f16;
+
+use crate::splat::{SplatGeo, SplatLoadMode};
+
+#[allow(clippy::excessive_precision)]
+const SH_C0: f32 = 0.28209479177387814;
+
+/// Alpha is clamped away from 0 and 1 so the logit stays finite.
+const ALPHA_EPSILON: f32 = 1.0e-4;
+/// Smallest linear scale kept when converting to log scale.
+const MIN_LINEAR_SCALE: f32 = 1.0e-10;
+
+pub(crate) const SPLAT_RECORD_BYTES: usize = 32;
+
+const KSPLAT_HEADER_BYTES: usize = 4096;
+const KSPLAT_SECTION_HEADER_BYTES: usize = 1024;
+const KSPLAT_VERSION: (u8, u8) = (0, 1);
+const KSPLAT_BUCKET_SIZE: usize = 256;
+const KSPLAT_BUCKET_BLOCK_SIZE: f32 = 5.0;
+const KSPLAT_BUCKET_STORAGE_BYTES: usize = 12;
+const KSPLAT_COMPRESSION_SCALE_RANGE: u32 = 32767;
+const KSPLAT_DEFAULT_SH_RANGE: (f32, f32) = (-1.5, 1.5);
+
+/// Parses antimatter15's `.splat`: headerless 32-byte records of float32
+/// position and linear scale, RGBA8 color and a quaternion (w, x, y, z) with
+/// each component stored as `q * 128 + 128` in a byte. Color and alpha keep
+/// 8 bits, rotation components are good to about 1/128, and there is no SH.
+pub(crate) fn parse_dot_splat_bytes(data: &[u8]) -> Result<SplatGeo, String> {
+    if data.is_empty() || !data.len().is_multiple_of(SPLAT_RECORD_BYTES) {
+        return Err(format!(
+            ".splat data must be a whole number of {}-byte records",
+            SPLAT_RECORD_BYTES
+        ));
+    }
+    let count = data.len() / SPLAT_RECORD_BYTES;
+    let mut splats = SplatGeo::with_len(count);
+    for (idx, record) in data.chunks_exact(SPLAT_RECORD_BYTES).enumerate() {
+        splats.positions[idx] = read_f32x3(&record[0..12]);
+        splats.scales[idx] = read_f32x3(&record[12..24]).map(linear_to_log_scale);
+        let [r, g, b, a] = [record[24], record[25], record[26], record[27]];
+        splats.sh0[idx] = [unorm8(r), unorm8(g), unorm8(b)];
+        splats.opacity[idx] = alpha_to_opacity(unorm8(a));
+        splats.rotations[idx] = [record[28], record[29], record[30], record[31]]
+            .map(|value| (value as f32 - 128.0) / 128.0);
+    }
+    finish_load(splats)
+}
+
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+pub(crate) fn encode_dot_splat(splats: &SplatGeo) -> Result<Vec<u8>, String> {
+    let splats = prepare_save(splats)?;
+    let mut data = Vec::with_capacity(splats.len() * SPLAT_RECORD_BYTES);
+    for idx in 0..splats.len() {
+        for value in splats.positions[idx] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in splats.scales[idx] {
+            data.extend_from_slice(&value.exp().to_le_bytes());
+        }
+        data.extend_from_slice(&color_bytes(&splats, idx));
+        for value in splats.rotations[idx] {
+            data.push((value * 128.0 + 128.0).round().clamp(0.0, 255.0) as u8);
+        }
+    }
+    Ok(data)
+}
+
+/// Whether `data` starts with a `.ksplat` header we can read.
+pub(crate) fn looks_like_ksplat(data: &[u8]) -> bool {
+    let Ok(header) = KsplatHeader::parse(data) else {
+        return false;
+    };
+    header.section_count <= header.max_section_count && header.compression_level <= 2
+}
+
+/// Parses a GaussianSplats3D `.ksplat` (format version 0.1): sections of
+/// splats grouped into spatial buckets. Compression level 0 stores float32
+/// values; level 1 stores positions as 16-bit offsets from the bucket center
+/// and scale, rotation and SH as float16; level 2 additionally quantizes SH to
+/// 8 bits between the header's SH range. Color and alpha are always RGBA8, and
+/// SH coefficients are interleaved per coefficient (r, g, b).
+pub(crate) fn parse_ksplat_bytes(data: &[u8], mode: SplatLoadMode) -> Result<SplatGeo, String> {
+    let header = KsplatHeader::parse(data)?;
+    if header.compression_level > 2 {
+        return Err(format!(
+            "Unsupported .ksplat compression level {}",
+            header.compression_level
+        ));
+    }
+    let mut sections = Vec::with_capacity(header.section_count);
+    for index in 0..header.section_count {
+        let offset = KSPLAT_HEADER_BYTES + index * KSPLAT_SECTION_HEADER_BYTES;
+        sections.push(KsplatSection::parse(data, offset)?);
+    }
+
+    let keep_sh = matches!(mode, SplatLoadMode::Full);
+    let sh_coeffs = if keep_sh {
+        sections
+            .iter()
+            .map(|section| sh_coeffs_for_degree(section.sh_degree))
+            .max()
+            .unwrap_or(0)
+    } else {
+        0
+    };
+    // Check every section fits in the data before allocating for its splats.
+    let mut spans = Vec::with_capacity(sections.len());
+    let mut section_start =
+        KSPLAT_HEADER_BYTES + header.max_section_count * KSPLAT_SECTION_HEADER_BYTES;
+    for section in &sections {
+        let layout = KsplatLayout::new(header.compression_level, section.sh_degree);
+        let (buckets_start, splats_start, splats_end) = section
+            .span(section_start, layout.bytes_per_splat, data.len())
+            .ok_or_else(|| "Unexpected end of .ksplat data".to_string())?;
+        spans.push((section_start, buckets_start, splats_start, layout));
+        section_start =
+            section_start.saturating_add(section.storage_bytes.max(splats_end - section_start));
+    }
+    let total: usize = sections.iter().map(|section| section.splat_count).sum();
+    let mut splats = SplatGeo::with_len_and_sh(total, sh_coeffs);
+
+    let mut out = 0usize;
+    for (section, (section_start, buckets_start, splats_start, layout)) in
+        sections.iter().zip(spans)
+    {
+        let partial_lengths: Vec<usize> = (0..section.partial_bucket_count)
+            .map(|index| read_u32(data, section_start + index * 4) as usize)
+            .collect();
+        let bucket_center = |bucket: usize| -> Result<[f32; 3], String> {
+            if bucket >= section.bucket_count || section.bucket_storage_bytes < 12 {
+                return Err("Invalid .ksplat bucket".to_string());
+            }
+            let offset = buckets_start + bucket * section.bucket_storage_bytes;
+            Ok(read_f32x3(&data[offset..offset + 12]))
+        };
+        let position_scale =
+            section.bucket_block_size / 2.0 / section.compression_scale_range.max(1) as f32;
+        let full_bucket_splats = section.full_bucket_count.saturating_mul(section.bucket_size);
+        let mut partial_bucket = 0usize;
+        let mut partial_end = full_bucket_splats;
+
+        for local in 0..section.splat_count {
+            let record = &data[splats_start + local * layout.bytes_per_splat..];
+            let position = if header.compression_level == 0 {
+                read_f32x3(&record[0..12])
+            } else {
+                let bucket = if local < full_bucket_splats {
+                    local / section.bucket_size.max(1)
+                } else {
+                    while local >= partial_end {
+                        partial_end += partial_lengths
+                            .get(partial_bucket)
+                            .copied()
+                            .ok_or_else(|| "Invalid .ksplat bucket lengths".to_string())?;
+                        partial_bucket += 1;
+                    }
+                    section.full_bucket_count + partial_bucket - 1
+                };
+                let center = bucket_center(bucket)?;
+                let range = section.compression_scale_range as f32;
+                std::array::from_fn(|axis| {
+                    let quantized = read_u16(record, axis * 2) as f32;
+                    (quantized - range) * position_scale + center[axis]
+                })
+            };
+            let (scale, rotation) = if header.compression_level == 0 {
+                (
+                    read_f32x3(&record[12..24]),
+                    std::array::from_fn(|i| read_f32(record, 24 + i * 4)),
+                )
+            } else {
+                (
+                    std::array::from_fn(|i| read_f16(record, 6 + i * 2)),
+                    std::array::from_fn(|i| read_f16(record, 12 + i * 2)),
+                )
+            };
+            let color = &record[layout.color_offset..layout.color_offset + 4];
+
+            let idx = out + local;
+            splats.positions[idx] = position;
+            splats.scales[idx] = scale.map(linear_to_log_scale);
+            splats.rotations[idx] = rotation;
+            splats.opacity[idx] = alpha_to_opacity(unorm8(color[3]));
+            let rgb = [unorm8(color[0]), unorm8(color[1]), unorm8(color[2])];
+            if sh_coeffs > 0 {
+                splats.sh0[idx] = rgb.map(color_to_dc);
+                let stored = sh_coeffs_for_degree(section.sh_degree);
+                let base = idx * sh_coeffs;
+                for coeff in 0..stored.min(sh_coeffs) {
+                    splats.sh_rest[base + coeff] = std::array::from_fn(|channel| {
+                        let component = coeff * 3 + channel;
+                        match header.compression_level {
+                            0 => read_f32(record, layout.sh_offset + component * 4),
+                            1 => read_f16(record, layout.sh_offset + component * 2),
+                            _ => {
+                                let (min, max) = header.sh_range;
+                                let value = record[layout.sh_offset + component] as f32 / 255.0;
+                                min + value * (max - min)
+                            }
+                        }
+                    });
+                }
+            } else {
+                splats.sh0[idx] = rgb;
+            }
+        }
+        out += section.splat_count;
+    }
+    finish_load(splats)
+}
+
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+/// Writes a single-section `.ksplat` at compression level 1: positions are
+/// within `5 / 65534` of the source, scale and rotation keep float16 precision
+/// and color and alpha 8 bits. SH is kept up to degree 3 as float16. Splats
+/// come out grouped by bucket rather than in their original order.
+pub(crate) fn encode_ksplat(splats: &SplatGeo) -> Result<Vec<u8>, String> {
+    let splats = prepare_save(splats)?;
+    let sh_degree = sh_degree_for_coeffs(splats.sh_coeffs);
+    let sh_coeffs = sh_coeffs_for_degree(sh_degree);
+    let layout = KsplatLayout::new(1, sh_degree);
+    let count = splats.len();
+
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for position in splats.positions.iter() {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(position[axis]);
+            max[axis] = max[axis].max(position[axis]);
+        }
+    }
+    if count == 0 {
+        min = [0.0; 3];
+        max = [0.0; 3];
+    }
+
+    // Bucket splats by the grid block they fall in, full buckets first.
+    let mut cells: BTreeMap<[i32; 3], Vec<usize>> = BTreeMap::new();
+    for (idx, position) in splats.positions.iter().enumerate() {
+        let cell = std::array::from_fn(|axis| {
+            ((position[axis] - min[axis]) / KSPLAT_BUCKET_BLOCK_SIZE).floor() as i32
+        });
+        cells.entry(cell).or_default().push(idx);
+    }
+    let mut full = Vec::new();
+    let mut partial = Vec::new();
+    for (cell, members) in &cells {
+        let center: [f32; 3] = std::array::from_fn(|axis| {
+            min[axis] + (cell[axis] as f32 + 0.5) * KSPLAT_BUCKET_BLOCK_SIZE
+        });
+        for chunk in members.chunks(KSPLAT_BUCKET_SIZE) {
+            if chunk.len() == KSPLAT_BUCKET_SIZE {
+                full.push((center, chunk));
+            } else {
+                partial.push((center, chunk));
+            }
+        }
+    }
+    let bucket_count = full.len() + partial.len();
+    let bucket_bytes = partial.len() * 4 + bucket_count * KSPLAT_BUCKET_STORAGE_BYTES;
+    let storage_bytes = bucket_bytes + count * layout.bytes_per_splat;
+
+    let mut data = vec![0u8; KSPLAT_HEADER_BYTES + KSPLAT_SECTION_HEADER_BYTES];
+    data[0] = KSPLAT_VERSION.0;
+    data[1] = KSPLAT_VERSION.1;
+    write_u32(&mut data, 4, 1);
+    write_u32(&mut data, 8, 1);
+    write_u32(&mut data, 12, count as u32);
+    write_u32(&mut data, 16, count as u32);
+    data[20..22].copy_from_slice(&1u16.to_le_bytes());
+    for axis in 0..3 {
+        write_f32(&mut data, 24 + axis * 4, (min[axis] + max[axis]) * 0.5);
+    }
+    write_f32(&mut data, 36, KSPLAT_DEFAULT_SH_RANGE.0);
+    write_f32(&mut data, 40, KSPLAT_DEFAULT_SH_RANGE.1);
+
+    let section = KSPLAT_HEADER_BYTES;
+    write_u32(&mut data, section, count as u32);
+    write_u32(&mut data, section + 4, count as u32);
+    write_u32(&mut data, section + 8, KSPLAT_BUCKET_SIZE as u32);
+    write_u32(&mut data, section + 12, bucket_count as u32);
+    write_f32(&mut data, section + 16, KSPLAT_BUCKET_BLOCK_SIZE);
+    data[section + 20..section + 22]
+        .copy_from_slice(&(KSPLAT_BUCKET_STORAGE_BYTES as u16).to_le_bytes());
+    write_u32(&mut data, section + 24, KSPLAT_COMPRESSION_SCALE_RANGE);
+    write_u32(&mut data, section + 28, storage_bytes as u32);
+    write_u32(&mut data, section + 32, full.len() as u32);
+    write_u32(&mut data, section + 36, partial.len() as u32);
+    data[section + 40..section + 42].copy_from_slice(&(sh_degree as u16).to_le_bytes());
+
+    data.reserve(storage_bytes);
+    for (_, chunk) in &partial {
+        data.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+    }
+    for (center, _) in full.iter().chain(&partial) {
+        for value in center {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    let range = KSPLAT_COMPRESSION_SCALE_RANGE as f32;
+    let position_scale = KSPLAT_BUCKET_BLOCK_SIZE / 2.0 / range;
+    for (center, chunk) in full.iter().chain(&partial) {
+        for &idx in chunk.iter() {
+            let position = splats.positions[idx];
+            for axis in 0..3 {
+                let offset = (position[axis] - center[axis]) / position_scale + range;
+                let quantized = offset.round().clamp(0.0, u16::MAX as f32) as u16;
+                data.extend_from_slice(&quantized.to_le_bytes());
+            }
+            for value in splats.scales[idx] {
+                data.extend_from_slice(&f16::from_f32(value.exp()).to_le_bytes());
+            }
+            for value in splats.rotations[idx] {
+                data.extend_from_slice(&f16::from_f32(value).to_le_bytes());
+            }
+            data.extend_from_slice(&color_bytes(&splats, idx));
+            let base = idx * splats.sh_coeffs;
+            for coeff in 0..sh_coeffs {
+                for value in splats.sh_rest[base + coeff] {
+                    data.extend_from_slice(&f16::from_f32(value).to_le_bytes());
+                }
+            }
+        }
+    }
+    Ok(data)
+}
+
+/// DC color of splat `idx` in 0..1, whether `sh0` holds an SH coefficient or,
+/// without higher bands, the color itself.
+pub(crate) fn dc_color(splats: &SplatGeo, idx: usize) -> [f32; 3] {
+    let sh0 = splats.sh0[idx];
+    if splats.sh_coeffs > 0 {
+        sh0.map(dc_to_color)
+    } else {
+        sh0
+    }
+}
+
+pub(crate) fn dc_to_color(dc: f32) -> f32 {
+    dc * SH_C0 + 0.5
+}
+
+pub(crate) fn color_to_dc(color: f32) -> f32 {
+    (color - 0.5) / SH_C0
+}
+
+pub(crate) fn opacity_to_alpha(opacity: f32) -> f32 {
+    1.0 / (1.0 + (-opacity).exp())
+}
+
+pub(crate) fn alpha_to_opacity(alpha: f32) -> f32 {
+    let alpha = alpha.clamp(ALPHA_EPSILON, 1.0 - ALPHA_EPSILON);
+    (alpha / (1.0 - alpha)).ln()
+}
+
+pub(crate) fn linear_to_log_scale(scale: f32) -> f32 {
+    scale.max(MIN_LINEAR_SCALE).ln()
+}
+
+/// Normalizes, converts to the file axis convention and validates, as the PLY
+/// writer does.
+pub(crate) fn prepare_save(splats: &SplatGeo) -> Result<SplatGeo, String> {
+    let mut normalized = splats.normalized_for_save();
+    normalized.flip_y_axis();
+    normalized.validate()?;
+    Ok(normalized)
+}
+
+pub(crate) fn finish_load(mut splats: SplatGeo) -> Result<SplatGeo, String> {
+    splats.normalize_on_load();
+    splats.flip_y_axis();
+    splats.validate()?;
+    Ok(splats)
+}
+
+fn color_bytes(splats: &SplatGeo, idx: usize) -> [u8; 4] {
+    let [r, g, b] = dc_color(splats, idx);
+    let alpha = opacity_to_alpha(splats.opacity[idx]);
+    [r, g, b, alpha].map(|value| (value * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+fn unorm8(value: u8) -> f32 {
+    value as f32 / 255.0
+}
+
+pub(crate) fn sh_coeffs_for_degree(degree: usize) -> usize {
+    match degree {
+        0 => 0,
+        1 => 3,
+        2 => 8,
+        _ => 15,
+    }
+}
+
+pub(crate) fn sh_degree_for_coeffs(coeffs: usize) -> usize {
+    match coeffs {
+        0..=2 => 0,
+        3..=7 => 1,
+        8..=14 => 2,
+        _ => 3,
+    }
+}
+
+struct KsplatHeader {
+    max_section_count: usize,
+    section_count: usize,
+    compression_level: u16,
+    sh_range: (f32, f32),
+}
+
+impl KsplatHeader {
+    fn parse(data: &[u8]) -> Result<Self, String> {
+        if data.len() < KSPLAT_HEADER_BYTES {
+            return Err(".ksplat header is missing".to_string());
+        }
+        // Like GaussianSplats3D, read newer minor versions of the current major.
+        let (major, minor) = (data[0], data[1]);
+        if major != KSPLAT_VERSION.0 || minor < KSPLAT_VERSION.1 {
+            return Err(format!("Unsupported .ksplat version {}.{}", major, minor));
+        }
+        let max_section_count = read_u32(data, 4) as usize;
+        let section_count = read_u32(data, 8) as usize;
+        let sections_end = max_section_count
+            .checked_mul(KSPLAT_SECTION_HEADER_BYTES)
+            .and_then(|bytes| bytes.checked_add(KSPLAT_HEADER_BYTES));
+        if section_count > max_section_count || sections_end.is_none_or(|end| end > data.len()) {
+            return Err("Invalid .ksplat section count".to_string());
+        }
+        let sh_min = read_f32(data, 36);
+        let sh_max = read_f32(data, 40);
+        let sh_range = if sh_max > sh_min {
+            (sh_min, sh_max)
+        } else {
+            KSPLAT_DEFAULT_SH_RANGE
+        };
+        Ok(Self {
+            max_section_count,
+            section_count,
+            compression_level: read_u16(data, 20),
+            sh_range,
+        })
+    }
+}
+
+struct KsplatSection {
+    splat_count: usize,
+    bucket_size: usize,
+    bucket_count: usize,
+    bucket_block_size: f32,
+    bucket_storage_bytes: usize,
+    compression_scale_range: u32,
+    storage_bytes: usize,
+    full_bucket_count: usize,
+    partial_bucket_count: usize,
+    sh_degree: usize,
+}
+
+impl KsplatSection {
+    fn parse(data: &[u8], offset: usize) -> Result<Self, String> {
+        let header = data
+            .get(offset..offset + KSPLAT_SECTION_HEADER_BYTES)
+            .ok_or_else(|| ".ksplat section header is missing".to_string())?;
+        let sh_degree = read_u16(header, 40) as usize;
+        if sh_degree > 3 {
+            return Err(format!("Unsupported .ksplat SH degree {}", sh_degree));
+        }
+        Ok(Self {
+            splat_count: read_u32(header, 0) as usize,
+            bucket_size: read_u32(header, 8) as usize,
+            bucket_count: read_u32(header, 12) as usize,
+            bucket_block_size: read_f32(header, 16),
+            bucket_storage_bytes: read_u16(header, 20) as usize,
+            compression_scale_range: read_u32(header, 24),
+            storage_bytes: read_u32(header, 28) as usize,
+            full_bucket_count: read_u32(header, 32) as usize,
+            partial_bucket_count: read_u32(header, 36) as usize,
+            sh_degree,
+        })
+    }
+
+    /// Where the bucket data and splat data of a section stored at `start`
+    /// begin and where its splats end, or `None` if they run past `data_len`.
+    fn span(
+        &self,
+        start: usize,
+        bytes_per_splat: usize,
+        data_len: usize,
+    ) -> Option<(usize, usize, usize)> {
+        let buckets_start = self.partial_bucket_count.checked_mul(4)?.checked_add(start)?;
+        let splats_start = self
+            .bucket_count
+            .checked_mul(self.bucket_storage_bytes)?
+            .checked_add(buckets_start)?;
+        let splats_end = self
+            .splat_count
+            .checked_mul(bytes_per_splat)?
+            .checked_add(splats_start)?;
+        (splats_end <= data_len).then_some((buckets_start, splats_start, splats_end))
+    }
+}
+
+struct KsplatLayout {
+    bytes_per_splat: usize,
+    color_offset: usize,
+    sh_offset: usize,
+}
+
+impl KsplatLayout {
+    fn new(compression_level: u16, sh_degree: usize) -> Self {
+        let (color_offset, sh_component_bytes) = match compression_level {
+            0 => (40, 4),
+            1 => (20, 2),
+            _ => (20, 1),
+        };
+        let sh_offset = color_offset + 4;
+        let sh_components = sh_coeffs_for_degree(sh_degree) * 3;
+        Self {
+            bytes_per_splat: sh_offset + sh_components * sh_component_bytes,
+            color_offset,
+            sh_offset,
+        }
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+fn read_f32(data: &[u8], offset: usize) -> f32 {
+    f32::from_bits(read_u32(data, offset))
+}
+
+fn read_f16(data: &[u8], offset: usize) -> f32 {
+    f16::from_bits(read_u16(data, offset)).to_f32()
+}
+
+fn read_f32x3(data: &[u8]) -> [f32; 3] {
+    std::array::from_fn(|axis| read_f32(data, axis * 4))
+}
+
+fn write_u32(data: &mut [u8], offset: usize, value: u32) {
+    data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn write_f32(data: &mut [u8], offset: usize, value: f32) {
+    write_u32(data, offset, value.to_bits());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::splat::test_support::{
+        max_color_error, max_position_error, sample_splats, sorted_by_x,
+    };
+    use crate::splat::SplatLoadMode;
+
+    use super::{
+        encode_dot_splat, encode_ksplat, looks_like_ksplat, parse_dot_splat_bytes,
+        parse_ksplat_bytes, KSPLAT_HEADER_BYTES,
+    };
+
+    fn sorted(mut values: [f32; 3]) -> [f32; 3] {
+        values.sort_by(f32::total_cmp);
+        values
+    }
+
+    #[test]
+    fn dot_splat_roundtrip_within_byte_precision() {
+        let splats = sample_splats(300, 0);
+        let data = encode_dot_splat(&splats).expect("encode");
+        assert_eq!(data.len(), splats.len() * 32);
+        let loaded = parse_dot_splat_bytes(&data).expect("parse");
+        assert_eq!(loaded.len(), splats.len());
+        assert_eq!(loaded.sh_coeffs, 0);
+        assert!(max_position_error(&splats, &loaded) < 1.0e-5);
+        assert!(max_color_error(&splats, &loaded) <= 0.5 / 255.0 + 1.0e-6);
+        // Loading refits scale and rotation from the covariance, which may
+        // reorder the axes, so compare the sorted extents.
+        for idx in 0..splats.len() {
+            let (expected, actual) = (sorted(splats.scales[idx]), sorted(loaded.scales[idx]));
+            for axis in 0..3 {
+                assert!((expected[axis] - actual[axis]).abs() < 0.02);
+            }
+        }
+    }
+
+    #[test]
+    fn ksplat_roundtrip_keeps_sh() {
+        let splats = sample_splats(300, 3);
+        let data = encode_ksplat(&splats).expect("encode");
+        assert!(looks_like_ksplat(&data));
+        let loaded = parse_ksplat_bytes(&data, SplatLoadMode::Full).expect("parse");
+        assert_eq!(loaded.len(), splats.len());
+        let loaded = sorted_by_x(&loaded);
+        assert_eq!(loaded.sh_coeffs, 3);
+        assert!(max_position_error(&splats, &loaded) < 1.0e-4);
+        assert!(max_color_error(&splats, &loaded) <= 0.5 / 255.0 + 1.0e-5);
+        for (a, b) in splats.sh_rest.iter().zip(loaded.sh_rest.iter()) {
+            for channel in 0..3 {
+                assert!((a[channel] - b[channel]).abs() < 1.0e-3);
+            }
+        }
+
+        let color_only = parse_ksplat_bytes(&data, SplatLoadMode::ColorOnly).expect("parse");
+        let color_only = sorted_by_x(&color_only);
+        assert_eq!(color_only.sh_coeffs, 0);
+        assert!(max_color_error(&splats, &color_only) <= 0.5 / 255.0 + 1.0e-5);
+    }
+
+    #[test]
+    fn rejects_truncated_dot_splat() {
+        assert!(parse_dot_splat_bytes(&[0u8; 33]).is_err());
+        assert!(!looks_like_ksplat(&[0u8; 64]));
+    }
+
+    #[test]
+    fn rejects_unknown_ksplat_versions() {
+        let mut data = encode_ksplat(&sample_splats(10, 0)).expect("encode");
+        for version in [(0, 0), (1, 0), (2, 1)] {
+            (data[0], data[1]) = version;
+            assert!(!looks_like_ksplat(&data));
+            let err = parse_ksplat_bytes(&data, SplatLoadMode::Full).unwrap_err();
+            assert!(err.contains("Unsupported .ksplat version"));
+        }
+        (data[0], data[1]) = (0, 2);
+        assert!(parse_ksplat_bytes(&data, SplatLoadMode::Full).is_ok());
+    }
+
+    #[test]
+    fn rejects_ksplat_section_past_end() {
+        let mut data = encode_ksplat(&sample_splats(300, 3)).expect("encode");
+        let count = KSPLAT_HEADER_BYTES..KSPLAT_HEADER_BYTES + 4;
+        data[count].copy_from_slice(&u32::MAX.to_le_bytes());
+        let err = parse_ksplat_bytes(&data, SplatLoadMode::Full).unwrap_err();
+        assert!(err.contains("Unexpected end"));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::splat::SplatGeo;
-use crate::assets;
+use crate::splat_compressed_ply::parse_compressed_ply;
+use crate::splat_io::SplatSaveFormat;
 
 #[allow(clippy::excessive_precision)]
 const SH_C0: f32 = 0.28209479177387814;
@@ -11,20 +12,14 @@ pub enum SplatLoadMode {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SplatSaveFormat {
-    Ascii,
-    BinaryLittle,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum PlyFormat {
+pub(crate) enum PlyFormat {
     Ascii,
     BinaryLittle,
     BinaryBig,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum PlyScalarType {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlyScalarType {
     Int8,
     Uint8,
     Int16,
@@ -36,7 +31,7 @@ enum PlyScalarType {
 }
 
 impl PlyScalarType {
-    fn size(self) -> usize {
+    pub(crate) fn size(self) -> usize {
         match self {
             PlyScalarType::Int8 | PlyScalarType::Uint8 => 1,
             PlyScalarType::Int16 | PlyScalarType::Uint16 => 2,
@@ -44,48 +39,91 @@ impl PlyScalarType {
             PlyScalarType::Float64 => 8,
         }
     }
+
+    pub(crate) fn is_integer(self) -> bool {
+        !matches!(self, PlyScalarType::Float32 | PlyScalarType::Float64)
+    }
+
+    /// Type name as written in headers.
+    pub(crate) fn ply_name(self) -> &'static str {
+        match self {
+            PlyScalarType::Int8 => "char",
+            PlyScalarType::Uint8 => "uchar",
+            PlyScalarType::Int16 => "short",
+            PlyScalarType::Uint16 => "ushort",
+            PlyScalarType::Int32 => "int",
+            PlyScalarType::Uint32 => "uint",
+            PlyScalarType::Float32 => "float",
+            PlyScalarType::Float64 => "double",
+        }
+    }
 }
 
 #[derive(Debug)]
-struct PlyProperty {
-    name: String,
-    data_type: PlyScalarType,
+pub(crate) struct PlyProperty {
+    pub(crate) name: String,
+    /// Item type; for list properties, the type of each list entry.
+    pub(crate) data_type: PlyScalarType,
+    /// Count type of list properties.
+    pub(crate) list_count: Option<PlyScalarType>,
 }
 
 #[derive(Debug)]
-struct PlyHeader {
-    format: PlyFormat,
-    vertex_count: usize,
-    vertex_properties: Vec<PlyProperty>,
+pub(crate) struct PlyElement {
+    pub(crate) name: String,
+    pub(crate) count: usize,
+    pub(crate) properties: Vec<PlyProperty>,
 }
 
-#[allow(dead_code)]
-pub fn load_splat_ply(path: &str) -> Result<SplatGeo, String> {
-    load_splat_ply_with_mode(path, SplatLoadMode::Full)
+impl PlyElement {
+    pub(crate) fn property_index(&self, name: &str) -> Option<usize> {
+        self.properties.iter().position(|prop| prop.name == name)
+    }
+
+    pub(crate) fn has_lists(&self) -> bool {
+        self.properties.iter().any(|prop| prop.list_count.is_some())
+    }
+
+    /// Bytes per row in binary files, for elements without list properties.
+    pub(crate) fn row_size(&self) -> usize {
+        self.properties.iter().map(|prop| prop.data_type.size()).sum()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct PlyHeader {
+    pub(crate) format: PlyFormat,
+    /// Elements in file order.
+    pub(crate) elements: Vec<PlyElement>,
 }
 
-pub fn load_splat_ply_with_mode(path: &str, mode: SplatLoadMode) -> Result<SplatGeo, String> {
-    if let Some(data) = assets::load_bytes(path) {
-        return parse_splat_ply_bytes_with_mode(&data, mode);
+impl PlyHeader {
+    pub(crate) fn element(&self, name: &str) -> Option<&PlyElement> {
+        self.elements.iter().find(|element| element.name == name)
     }
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        if assets::is_url(path) {
-            return Err(format!("Failed to download URL: {path}"));
-        }
-        let data = std::fs::read(path).map_err(|err| err.to_string())?;
-        parse_splat_ply_bytes_with_mode(&data, mode)
+
+    fn vertex(&self) -> &PlyElement {
+        self.element("vertex").expect("PLY header has a vertex element")
     }
-    #[cfg(target_arch = "wasm32")]
-    {
-        if assets::is_url(path) {
-            Err("Splat URL is downloading; retrying shortly.".to_string())
-        } else {
-            Err("Splat Read is not supported in web builds without a picked file".to_string())
-        }
+
+    /// Offset of element `name` in the data following a binary header, or in
+    /// lines for ASCII.
+    pub(crate) fn element_offset(&self, name: &str) -> usize {
+        self.elements
+            .iter()
+            .take_while(|element| element.name != name)
+            .map(|element| match self.format {
+                PlyFormat::Ascii => element.count,
+                _ => element.count * element.row_size(),
+            })
+            .sum()
     }
 }
 
+#[cfg(test)]
+fn load_splat_ply(path: &str) -> Result<SplatGeo, String> {
+    crate::splat_io::load_splats_with_mode(path, SplatLoadMode::Full)
+}
 
 #[cfg(not(target_arch = "wasm32"))]
 #[allow(dead_code)]
@@ -101,6 +139,9 @@ pub fn save_splat_ply_with_format(
 ) -> Result<(), String> {
     use std::io::{BufWriter, Write};
 
+    if !matches!(format, SplatSaveFormat::Ascii | SplatSaveFormat::BinaryLittle) {
+        return Err(format!("{:?} is not a PLY format", format));
+    }
     let mut normalized = splats.normalized_for_save();
     normalized.flip_y_axis();
     normalized.validate()?;
@@ -112,10 +153,7 @@ pub fn save_splat_ply_with_format(
         SplatSaveFormat::Ascii => {
             writeln!(file, "format ascii 1.0").map_err(|err| err.to_string())?
         }
-        SplatSaveFormat::BinaryLittle => {
-            writeln!(file, "format binary_little_endian 1.0")
-                .map_err(|err| err.to_string())?
-        }
+        _ => writeln!(file, "format binary_little_endian 1.0").map_err(|err| err.to_string())?,
     }
     writeln!(file, "element vertex {}", normalized.len()).map_err(|err| err.to_string())?;
     writeln!(file, "property float x").map_err(|err| err.to_string())?;
@@ -174,7 +212,7 @@ pub fn save_splat_ply_with_format(
                 writeln!(file).map_err(|err| err.to_string())?;
             }
         }
-        SplatSaveFormat::BinaryLittle => {
+        _ => {
             let float_count = 3 + 1 + 3 + 4 + 3 + normalized.sh_coeffs * 3;
             let mut buffer = Vec::with_capacity(float_count * 4);
             for idx in 0..normalized.len() {
@@ -235,24 +273,43 @@ fn parse_splat_ply_bytes(data: &[u8]) -> Result<SplatGeo, String> {
     parse_splat_ply_bytes_with_mode(data, SplatLoadMode::Full)
 }
 
-fn parse_splat_ply_bytes_with_mode(data: &[u8], mode: SplatLoadMode) -> Result<SplatGeo, String> {
+pub(crate) fn parse_splat_ply_bytes_with_mode(
+    data: &[u8],
+    mode: SplatLoadMode,
+) -> Result<SplatGeo, String> {
     let (header, data_start) = parse_header_bytes(data)?;
-    let indices = SplatPropertyIndices::from_properties(&header.vertex_properties, mode);
+    if header.element("chunk").is_some() {
+        return parse_compressed_ply(data, &header, data_start, mode);
+    }
+    let preceding = header
+        .elements
+        .iter()
+        .take_while(|element| element.name != "vertex");
+    if header.vertex().has_lists() || preceding.clone().any(PlyElement::has_lists) {
+        return Err("PLY list properties are not supported for splats".to_string());
+    }
+    let indices = SplatPropertyIndices::from_properties(&header.vertex().properties, mode);
     if indices.x.is_none() || indices.y.is_none() || indices.z.is_none() {
         return Err("PLY is missing position properties (x, y, z)".to_string());
     }
 
+    let offset = header.element_offset("vertex");
     match header.format {
         PlyFormat::Ascii => {
             let text = std::str::from_utf8(&data[data_start..])
                 .map_err(|_| "PLY ASCII data is not UTF-8".to_string())?;
-            parse_ascii_vertices(text, &header, &indices)
-        }
-        PlyFormat::BinaryLittle => {
-            parse_binary_vertices(&data[data_start..], &header, &indices, true)
+            let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+            for _ in 0..offset {
+                lines.next();
+            }
+            parse_ascii_vertices(lines, header.vertex(), &indices)
         }
-        PlyFormat::BinaryBig => {
-            parse_binary_vertices(&data[data_start..], &header, &indices, false)
+        PlyFormat::BinaryLittle | PlyFormat::BinaryBig => {
+            let data = data
+                .get(data_start + offset..)
+                .ok_or_else(|| "Unexpected end of binary PLY data".to_string())?;
+            let little_endian = header.format == PlyFormat::BinaryLittle;
+            parse_binary_vertices(data, header.vertex(), &indices, little_endian)
         }
     }
 }
@@ -269,9 +326,7 @@ where
     }
 
     let mut format = None;
-    let mut vertex_count = None;
-    let mut vertex_properties = Vec::new();
-    let mut in_vertex = false;
+    let mut elements: Vec<PlyElement> = Vec::new();
 
     for line in lines {
         let line = line.trim();
@@ -303,37 +358,45 @@ where
                     .ok_or_else(|| "Malformed PLY element".to_string())?
                     .parse::<usize>()
                     .map_err(|_| "Malformed PLY element count".to_string())?;
-                in_vertex = name == "vertex";
-                if in_vertex {
-                    vertex_count = Some(count);
-                }
+                elements.push(PlyElement {
+                    name: name.to_string(),
+                    count,
+                    properties: Vec::new(),
+                });
             }
-            "property" if in_vertex => {
-                let prop_type = parts.next().unwrap_or("");
+            "property" => {
+                let Some(element) = elements.last_mut() else {
+                    return Err("PLY property outside an element".to_string());
+                };
+                let mut prop_type = parts.next().unwrap_or("");
+                let mut list_count = None;
                 if prop_type == "list" {
-                    return Err("PLY vertex list properties are not supported".to_string());
+                    list_count = Some(parse_scalar_type(parts.next().unwrap_or(""))?);
+                    prop_type = parts.next().unwrap_or("");
                 }
                 let data_type = parse_scalar_type(prop_type)?;
                 let name = parts.next().unwrap_or("").to_string();
                 if name.is_empty() {
                     return Err("PLY property missing name".to_string());
                 }
-                vertex_properties.push(PlyProperty { name, data_type });
+                element.properties.push(PlyProperty {
+                    name,
+                    data_type,
+                    list_count,
+                });
             }
             _ => {}
         }
     }
 
     let format = format.ok_or_else(|| "PLY format not specified".to_string())?;
-    let vertex_count = vertex_count.ok_or_else(|| "PLY has no vertex element".to_string())?;
-    Ok(PlyHeader {
-        format,
-        vertex_count,
-        vertex_properties,
-    })
+    if !elements.iter().any(|element| element.name == "vertex") {
+        return Err("PLY has no vertex element".to_string());
+    }
+    Ok(PlyHeader { format, elements })
 }
 
-fn parse_header_bytes(data: &[u8]) -> Result<(PlyHeader, usize), String> {
+pub(crate) fn parse_header_bytes(data: &[u8]) -> Result<(PlyHeader, usize), String> {
     let mut line_start = 0usize;
     let mut header_end = None;
     for (idx, byte) in data.iter().enumerate() {
@@ -373,25 +436,22 @@ fn parse_scalar_type(value: &str) -> Result<PlyScalarType, String> {
     }
 }
 
-fn parse_ascii_vertices(
-    text: &str,
-    header: &PlyHeader,
+fn parse_ascii_vertices<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    vertex: &PlyElement,
     indices: &SplatPropertyIndices,
 ) -> Result<SplatGeo, String> {
-    let mut splats = SplatGeo::with_len_and_sh(header.vertex_count, indices.sh_coeffs());
+    let mut splats = SplatGeo::with_len_and_sh(vertex.count, indices.sh_coeffs());
     let mut read = 0usize;
-    for line in text.lines() {
-        if read >= header.vertex_count {
+    for line in lines {
+        if read >= vertex.count {
             break;
         }
-        if line.trim().is_empty() {
-            continue;
-        }
         let values: Vec<f32> = line
             .split_whitespace()
             .map(|token| token.parse::<f32>().map_err(|_| "Invalid PLY value".to_string()))
             .collect::<Result<Vec<_>, _>>()?;
-        if values.len() < header.vertex_properties.len() {
+        if values.len() < vertex.properties.len() {
             return Err("PLY vertex row has too few values".to_string());
         }
 
@@ -399,7 +459,7 @@ fn parse_ascii_vertices(
         read += 1;
     }
 
-    if read < header.vertex_count {
+    if read < vertex.count {
         return Err("Unexpected end of PLY vertex data".to_string());
     }
 
@@ -411,16 +471,16 @@ fn parse_ascii_vertices(
 
 fn parse_binary_vertices(
     data: &[u8],
-    header: &PlyHeader,
+    vertex: &PlyElement,
     indices: &SplatPropertyIndices,
     little_endian: bool,
 ) -> Result<SplatGeo, String> {
-    let mut splats = SplatGeo::with_len_and_sh(header.vertex_count, indices.sh_coeffs());
-    let mut values = vec![0.0f32; header.vertex_properties.len()];
+    let mut splats = SplatGeo::with_len_and_sh(vertex.count, indices.sh_coeffs());
+    let mut values = vec![0.0f32; vertex.properties.len()];
     let mut cursor = 0usize;
 
-    for read in 0..header.vertex_count {
-        for (idx, prop) in header.vertex_properties.iter().enumerate() {
+    for read in 0..vertex.count {
+        for (idx, prop) in vertex.properties.iter().enumerate() {
             let size = prop.data_type.size();
             let end = cursor + size;
             if end > data.len() {
@@ -438,16 +498,28 @@ fn parse_binary_vertices(
     Ok(splats)
 }
 
-fn read_scalar(data: &[u8], data_type: PlyScalarType, little_endian: bool) -> Result<f32, String> {
+pub(crate) fn read_scalar(
+    data: &[u8],
+    data_type: PlyScalarType,
+    little_endian: bool,
+) -> Result<f32, String> {
+    read_scalar_f64(data, data_type, little_endian).map(|value| value as f32)
+}
+
+pub(crate) fn read_scalar_f64(
+    data: &[u8],
+    data_type: PlyScalarType,
+    little_endian: bool,
+) -> Result<f64, String> {
     let value = match data_type {
         PlyScalarType::Int8 => data
             .first()
             .copied()
-            .ok_or_else(|| "Invalid PLY data".to_string())? as i8 as f32,
+            .ok_or_else(|| "Invalid PLY data".to_string())? as i8 as f64,
         PlyScalarType::Uint8 => data
             .first()
             .copied()
-            .ok_or_else(|| "Invalid PLY data".to_string())? as f32,
+            .ok_or_else(|| "Invalid PLY data".to_string())? as f64,
         PlyScalarType::Int16 => {
             if data.len() < 2 {
                 return Err("Invalid PLY data".to_string());
@@ -455,9 +527,9 @@ fn read_scalar(data: &[u8], data_type: PlyScalarType, little_endian: bool) -> Re
             let mut bytes = [0u8; 2];
             bytes.copy_from_slice(&data[..2]);
             if little_endian {
-                i16::from_le_bytes(bytes) as f32
+                i16::from_le_bytes(bytes) as f64
             } else {
-                i16::from_be_bytes(bytes) as f32
+                i16::from_be_bytes(bytes) as f64
             }
         }
         PlyScalarType::Uint16 => {
@@ -467,9 +539,9 @@ fn read_scalar(data: &[u8], data_type: PlyScalarType, little_endian: bool) -> Re
             let mut bytes = [0u8; 2];
             bytes.copy_from_slice(&data[..2]);
             if little_endian {
-                u16::from_le_bytes(bytes) as f32
+                u16::from_le_bytes(bytes) as f64
             } else {
-                u16::from_be_bytes(bytes) as f32
+                u16::from_be_bytes(bytes) as f64
             }
         }
         PlyScalarType::Int32 => {
@@ -479,9 +551,9 @@ fn read_scalar(data: &[u8], data_type: PlyScalarType, little_endian: bool) -> Re
             let mut bytes = [0u8; 4];
             bytes.copy_from_slice(&data[..4]);
             if little_endian {
-                i32::from_le_bytes(bytes) as f32
+                i32::from_le_bytes(bytes) as f64
             } else {
-                i32::from_be_bytes(bytes) as f32
+                i32::from_be_bytes(bytes) as f64
             }
         }
         PlyScalarType::Uint32 => {
@@ -491,9 +563,9 @@ fn read_scalar(data: &[u8], data_type: PlyScalarType, little_endian: bool) -> Re
             let mut bytes = [0u8; 4];
             bytes.copy_from_slice(&data[..4]);
             if little_endian {
-                u32::from_le_bytes(bytes) as f32
+                u32::from_le_bytes(bytes) as f64
             } else {
-                u32::from_be_bytes(bytes) as f32
+                u32::from_be_bytes(bytes) as f64
             }
         }
         PlyScalarType::Float32 => {
@@ -503,9 +575,9 @@ fn read_scalar(data: &[u8], data_type: PlyScalarType, little_endian: bool) -> Re
             let mut bytes = [0u8; 4];
             bytes.copy_from_slice(&data[..4]);
             if little_endian {
-                f32::from_le_bytes(bytes)
+                f32::from_le_bytes(bytes) as f64
             } else {
-                f32::from_be_bytes(bytes)
+                f32::from_be_bytes(bytes) as f64
             }
         }
         PlyScalarType::Float64 => {
@@ -515,9 +587,9 @@ fn read_scalar(data: &[u8], data_type: PlyScalarType, little_endian: bool) -> Re
             let mut bytes = [0u8; 8];
             bytes.copy_from_slice(&data[..8]);
             if little_endian {
-                f64::from_le_bytes(bytes) as f32
+                f64::from_le_bytes(bytes)
             } else {
-                f64::from_be_bytes(bytes) as f32
+                f64::from_be_bytes(bytes)
             }
         }
     };
@@ -0,0 +1,269 @@
+use std::f32::consts::FRAC_1_SQRT_2;
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use glam::{Mat4, Vec3};
+
+use crate::splat::{SplatGeo, SplatLoadMode};
+use crate::splat_packed::{
+    alpha_to_opacity, color_to_dc, dc_color, dc_to_color, opacity_to_alpha, sh_coeffs_for_degree,
+    sh_degree_for_coeffs,
+};
+
+const SPZ_MAGIC: u32 = 0x5053_474e;
+const SPZ_HEADER_BYTES: usize = 16;
+const SPZ_WRITE_VERSION: u32 = 2;
+const SPZ_FRACTIONAL_BITS: u8 = 12;
+const SPZ_COLOR_SCALE: f32 = 0.15;
+
+/// Whether `data` starts with the gzip magic SPZ files are wrapped in.
+pub(crate) fn looks_like_spz(data: &[u8]) -> bool {
+    data.starts_with(&[0x1f, 0x8b])
+}
+
+/// Parses a gzipped Niantic SPZ (versions 2 and 3): a 16-byte header followed
+/// by planar 24-bit fixed-point positions, 8-bit alpha, DC color, log scale,
+/// rotation and SH. Version 2 stores the rotation's x, y and z in a byte each
+/// with w rebuilt as positive; version 3 packs the three smallest components
+/// into 32 bits. SPZ is right-up-back, so z is flipped on top of the PLY y flip.
+pub(crate) fn parse_spz_bytes(data: &[u8], mode: SplatLoadMode) -> Result<SplatGeo, String> {
+    let mut raw = Vec::new();
+    GzDecoder::new(data)
+        .read_to_end(&mut raw)
+        .map_err(|err| format!("SPZ decompression failed: {err}"))?;
+    if raw.len() < SPZ_HEADER_BYTES {
+        return Err("SPZ header is missing".to_string());
+    }
+    if read_u32(&raw, 0) != SPZ_MAGIC {
+        return Err("Not an SPZ file".to_string());
+    }
+    let version = read_u32(&raw, 4);
+    if !(2..=3).contains(&version) {
+        return Err(format!("Unsupported SPZ version {version}"));
+    }
+    let count = read_u32(&raw, 8) as usize;
+    let sh_degree = raw[12] as usize;
+    if sh_degree > 3 {
+        return Err(format!("Unsupported SPZ SH degree {sh_degree}"));
+    }
+    let fractional_bits = raw[13] as u32;
+    if fractional_bits > 24 {
+        return Err(format!("Invalid SPZ fractional bits {fractional_bits}"));
+    }
+    let stored_coeffs = sh_coeffs_for_degree(sh_degree);
+    let rotation_bytes = if version >= 3 { 4 } else { 3 };
+    // Checked before the layout offsets, which could overflow on 32-bit targets.
+    let splat_bytes = 9 + 1 + 3 + 3 + rotation_bytes + stored_coeffs * 3;
+    if count
+        .checked_mul(splat_bytes)
+        .is_none_or(|bytes| bytes > raw.len() - SPZ_HEADER_BYTES)
+    {
+        return Err("Unexpected end of SPZ data".to_string());
+    }
+    let layout = SpzLayout::new(count, rotation_bytes, stored_coeffs);
+    let body = &raw[SPZ_HEADER_BYTES..];
+
+    let sh_coeffs = match mode {
+        SplatLoadMode::Full => stored_coeffs,
+        SplatLoadMode::ColorOnly => 0,
+    };
+    let mut splats = SplatGeo::with_len_and_sh(count, sh_coeffs);
+    let position_scale = 1.0 / (1u32 << fractional_bits) as f32;
+    for idx in 0..count {
+        splats.positions[idx] = std::array::from_fn(|axis| {
+            let offset = layout.positions + (idx * 3 + axis) * 3;
+            let bytes = [body[offset], body[offset + 1], body[offset + 2], 0];
+            // Shift the sign bit into place, then back with sign extension.
+            ((i32::from_le_bytes(bytes) << 8) >> 8) as f32 * position_scale
+        });
+        splats.opacity[idx] = alpha_to_opacity(body[layout.alphas + idx] as f32 / 255.0);
+        let dc: [f32; 3] = std::array::from_fn(|channel| {
+            (body[layout.colors + idx * 3 + channel] as f32 / 255.0 - 0.5) / SPZ_COLOR_SCALE
+        });
+        splats.sh0[idx] = if sh_coeffs > 0 {
+            dc
+        } else {
+            dc.map(dc_to_color)
+        };
+        splats.scales[idx] =
+            std::array::from_fn(|axis| body[layout.scales + idx * 3 + axis] as f32 / 16.0 - 10.0);
+        let rotation = &body[layout.rotations + idx * rotation_bytes..];
+        splats.rotations[idx] = if version >= 3 {
+            unpack_rotation_v3(rotation)
+        } else {
+            unpack_rotation_v2(rotation)
+        };
+        for coeff in 0..sh_coeffs {
+            splats.sh_rest[idx * sh_coeffs + coeff] = std::array::from_fn(|channel| {
+                let offset = layout.sh + (idx * stored_coeffs + coeff) * 3 + channel;
+                (body[offset] as f32 - 128.0) / 128.0
+            });
+        }
+    }
+
+    splats.normalize_on_load();
+    flip_rub(&mut splats);
+    splats.validate()?;
+    Ok(splats)
+}
+
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+/// Writes a gzipped SPZ version 2. Positions keep 1/4096 units, color, alpha
+/// and scale 8 bits, and rotation components about 1/128. SH up to degree 3
+/// is quantized to 5 bits for degree 1 and 4 bits above, as the reference
+/// encoder does.
+pub(crate) fn encode_spz(splats: &SplatGeo) -> Result<Vec<u8>, String> {
+    let mut splats = splats.normalized_for_save();
+    flip_rub(&mut splats);
+    splats.validate()?;
+    let count = splats.len();
+    let sh_degree = sh_degree_for_coeffs(splats.sh_coeffs);
+    let sh_coeffs = sh_coeffs_for_degree(sh_degree);
+    let layout = SpzLayout::new(count, 3, sh_coeffs);
+
+    let mut raw = vec![0u8; SPZ_HEADER_BYTES + layout.total];
+    raw[0..4].copy_from_slice(&SPZ_MAGIC.to_le_bytes());
+    raw[4..8].copy_from_slice(&SPZ_WRITE_VERSION.to_le_bytes());
+    raw[8..12].copy_from_slice(&(count as u32).to_le_bytes());
+    raw[12] = sh_degree as u8;
+    raw[13] = SPZ_FRACTIONAL_BITS;
+    let body = &mut raw[SPZ_HEADER_BYTES..];
+    let position_scale = (1u32 << SPZ_FRACTIONAL_BITS) as f32;
+    let position_limit = ((1 << 23) - 1) as f32;
+    for idx in 0..count {
+        for (axis, value) in splats.positions[idx].into_iter().enumerate() {
+            let fixed = (value * position_scale)
+                .round()
+                .clamp(-position_limit - 1.0, position_limit) as i32;
+            let offset = layout.positions + (idx * 3 + axis) * 3;
+            body[offset..offset + 3].copy_from_slice(&fixed.to_le_bytes()[..3]);
+        }
+        body[layout.alphas + idx] = to_u8(opacity_to_alpha(splats.opacity[idx]) * 255.0);
+        for (channel, color) in dc_color(&splats, idx).into_iter().enumerate() {
+            let dc = color_to_dc(color);
+            body[layout.colors + idx * 3 + channel] = to_u8(dc * SPZ_COLOR_SCALE * 255.0 + 127.5);
+        }
+        for (axis, value) in splats.scales[idx].into_iter().enumerate() {
+            body[layout.scales + idx * 3 + axis] = to_u8((value + 10.0) * 16.0);
+        }
+        let rotation = pack_rotation_v2(splats.rotations[idx]);
+        body[layout.rotations + idx * 3..layout.rotations + idx * 3 + 3].copy_from_slice(&rotation);
+        for coeff in 0..sh_coeffs {
+            let bucket = if coeff < 3 { 8 } else { 16 };
+            for channel in 0..3 {
+                let value = splats.sh_rest[idx * splats.sh_coeffs + coeff][channel];
+                body[layout.sh + (idx * sh_coeffs + coeff) * 3 + channel] =
+                    quantize_sh(value, bucket);
+            }
+        }
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&raw)
+        .and_then(|_| encoder.finish())
+        .map_err(|err| format!("SPZ compression failed: {err}"))
+}
+
+/// Byte offsets of the planar SPZ attribute blocks.
+struct SpzLayout {
+    positions: usize,
+    alphas: usize,
+    colors: usize,
+    scales: usize,
+    rotations: usize,
+    sh: usize,
+    total: usize,
+}
+
+impl SpzLayout {
+    fn new(count: usize, rotation_bytes: usize, sh_coeffs: usize) -> Self {
+        let positions = 0;
+        let alphas = positions + count * 9;
+        let colors = alphas + count;
+        let scales = colors + count * 3;
+        let rotations = scales + count * 3;
+        let sh = rotations + count * rotation_bytes;
+        Self {
+            positions,
+            alphas,
+            colors,
+            scales,
+            rotations,
+            sh,
+            total: sh + count * sh_coeffs * 3,
+        }
+    }
+}
+
+/// Converts between the internal frame and SPZ's right-up-back: the PLY y
+/// flip followed by PLY's right-down-front to right-up-back, a z flip overall.
+fn flip_rub(splats: &mut SplatGeo) {
+    if !splats.is_empty() {
+        splats.transform(Mat4::from_scale(Vec3::new(1.0, 1.0, -1.0)));
+    }
+}
+
+fn to_u8(value: f32) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+fn quantize_sh(value: f32, bucket: i32) -> u8 {
+    let quantized = ((value * 128.0).round() as i32 + 128).clamp(0, 255);
+    ((quantized + bucket / 2) / bucket * bucket).min(255) as u8
+}
+
+/// Packs x, y and z of a (w, x, y, z) quaternion with w made non-negative.
+fn pack_rotation_v2(rotation: [f32; 4]) -> [u8; 3] {
+    let length = rotation
+        .iter()
+        .map(|value| value * value)
+        .sum::<f32>()
+        .sqrt();
+    let mut q = if length > 0.0 && length.is_finite() {
+        rotation.map(|value| value / length)
+    } else {
+        [1.0, 0.0, 0.0, 0.0]
+    };
+    if q[0] < 0.0 {
+        q = q.map(|value| -value);
+    }
+    [q[1], q[2], q[3]].map(|value| to_u8(value * 127.5 + 127.5))
+}
+
+fn unpack_rotation_v2(bytes: &[u8]) -> [f32; 4] {
+    let [x, y, z] = [bytes[0], bytes[1], bytes[2]].map(|value| value as f32 / 127.5 - 1.0);
+    let w = (1.0 - (x * x + y * y + z * z)).max(0.0).sqrt();
+    [w, x, y, z]
+}
+
+/// Unpacks "smallest three": the largest component's index (x, y, z, w order)
+/// in the top 2 bits, then the others from w down to x as a sign bit and 9
+/// bits of magnitude scaled by 1/sqrt(2).
+fn unpack_rotation_v3(bytes: &[u8]) -> [f32; 4] {
+    let mut packed = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let largest = (packed >> 30) as usize;
+    let mask = (1u32 << 9) - 1;
+    let mut q = [0.0f32; 4];
+    let mut sum = 0.0;
+    for index in (0..4).rev() {
+        if index == largest {
+            continue;
+        }
+        let magnitude = FRAC_1_SQRT_2 * (packed & mask) as f32 / mask as f32;
+        let negative = (packed >> 9) & 1 == 1;
+        packed >>= 10;
+        q[index] = if negative { -magnitude } else { magnitude };
+        sum += q[index] * q[index];
+    }
+    q[largest] = (1.0 - sum).max(0.0).sqrt();
+    [q[3], q[0], q[1], q[2]]
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_le_bytes(bytes)
+}
@@ -0,0 +1,777 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::Geometry;
+use crate::geometry_eval::{
+    eval_failure_message, evaluate_geometry_graph_with_inputs, GeometryEvalState,
+};
+use crate::graph::{
+    Graph, Node, NodeDefinition, NodeId, NodeParams, ParamValue, PinDefinition, PinId, PinKind,
+    PinType,
+};
+use crate::nodes;
+use crate::nodes_builtin::{default_params, node_definition, param_specs, BuiltinNodeKind};
+use crate::param_spec::ParamSpec;
+use crate::progress::{current_cancel_token, CANCELLED_MESSAGE};
+
+pub const SUBNET_ASSET_VERSION: u32 = 1;
+
+/// Network nested inside a subnet node. Its Subnet Input and Subnet Output nodes,
+/// ordered by their `index` parameter, become the pins of the subnet node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Subnet {
+    pub graph: Graph,
+    #[serde(default)]
+    pub promoted: Vec<PromotedParam>,
+}
+
+/// An inner parameter exposed on the subnet node. The value lives in the subnet
+/// node's params under `key` and overrides the inner node's own value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromotedParam {
+    pub key: String,
+    pub node: NodeId,
+    pub param: String,
+}
+
+impl Subnet {
+    pub fn input_nodes(&self) -> Vec<NodeId> {
+        self.pin_nodes(BuiltinNodeKind::SubnetInput)
+    }
+
+    pub fn output_nodes(&self) -> Vec<NodeId> {
+        self.pin_nodes(BuiltinNodeKind::SubnetOutput)
+    }
+
+    fn pin_nodes(&self, kind: BuiltinNodeKind) -> Vec<NodeId> {
+        let mut nodes: Vec<(i32, NodeId)> = self
+            .graph
+            .nodes()
+            .filter(|node| node.builtin_kind() == Some(kind))
+            .map(|node| (node.params.get_int("index", 0), node.id))
+            .collect();
+        nodes.sort();
+        nodes.into_iter().map(|(_, node_id)| node_id).collect()
+    }
+
+    /// Definition of a subnet node whose pins match the inner pin nodes.
+    pub fn definition(&self, name: &str) -> NodeDefinition {
+        let pins = |node_ids: Vec<NodeId>, prefix: &str| -> Vec<PinDefinition> {
+            let count = node_ids.len();
+            node_ids
+                .into_iter()
+                .enumerate()
+                .map(|(idx, node_id)| {
+                    let label = self
+                        .graph
+                        .node(node_id)
+                        .map(|node| node.params.get_string("label", ""))
+                        .unwrap_or("");
+                    let name = if !label.is_empty() {
+                        label.to_string()
+                    } else if count == 1 {
+                        prefix.to_string()
+                    } else {
+                        format!("{}{}", prefix, idx + 1)
+                    };
+                    PinDefinition {
+                        name,
+                        pin_type: PinType::Geometry,
+                    }
+                })
+                .collect()
+        };
+        NodeDefinition {
+            name: name.to_string(),
+            inputs: pins(self.input_nodes(), "in"),
+            outputs: pins(self.output_nodes(), "out"),
+            ..nodes::subnet::definition()
+        }
+    }
+}
+
+/// A subnet saved on its own so it can be added to other projects.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubnetAsset {
+    pub version: u32,
+    pub name: String,
+    pub subnet: Subnet,
+    /// Default values of the promoted parameters.
+    #[serde(default)]
+    pub params: NodeParams,
+}
+
+impl SubnetAsset {
+    pub fn from_node(graph: &Graph, node_id: NodeId) -> Result<Self, String> {
+        let node = graph
+            .node(node_id)
+            .ok_or_else(|| "missing node".to_string())?;
+        let subnet = node
+            .subnet
+            .as_deref()
+            .ok_or_else(|| format!("{} is not a subnet", node.name))?;
+        let mut params = NodeParams::default();
+        for promoted in &subnet.promoted {
+            if let Some(value) = node.params.values.get(&promoted.key) {
+                params.values.insert(promoted.key.clone(), value.clone());
+            }
+        }
+        Ok(Self {
+            version: SUBNET_ASSET_VERSION,
+            name: node.name.clone(),
+            subnet: subnet.clone(),
+            params,
+        })
+    }
+
+    pub fn from_json(bytes: &[u8]) -> Result<Self, String> {
+        let mut asset: Self = serde_json::from_slice(bytes).map_err(|err| err.to_string())?;
+        if asset.version > SUBNET_ASSET_VERSION {
+            return Err(format!("unsupported asset version {}", asset.version));
+        }
+        asset.subnet.graph.rebuild_link_index();
+        asset.subnet.graph.migrate_variadic_inputs();
+        Ok(asset)
+    }
+
+    pub fn to_json(&self) -> Result<Vec<u8>, String> {
+        serde_json::to_vec_pretty(self).map_err(|err| err.to_string())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|err| err.to_string())?;
+        Self::from_json(&bytes)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_json()?).map_err(|err| err.to_string())
+    }
+
+    /// Adds a subnet node running this asset.
+    pub fn instantiate(&self, graph: &mut Graph) -> NodeId {
+        let node_id = graph.add_node(self.subnet.definition(&self.name));
+        graph.set_node_kind_id(node_id, BuiltinNodeKind::Subnet.id());
+        for (key, value) in &self.params.values {
+            let _ = graph.set_param(node_id, key.clone(), value.clone());
+        }
+        let _ = graph.set_node_subnet(node_id, Some(self.subnet.clone()));
+        node_id
+    }
+}
+
+/// Inner evaluation state kept per subnet node between cooks, so a change only
+/// recooks the inner nodes it affects.
+#[derive(Debug, Default)]
+pub(crate) struct SubnetEvalState {
+    graph: Graph,
+    revision: Option<u64>,
+    inputs: Vec<Geometry>,
+    eval: GeometryEvalState,
+}
+
+/// Cooks a subnet node and returns one geometry per Subnet Output.
+pub(crate) fn cook_subnet(
+    subnet: &Subnet,
+    params: &NodeParams,
+    inputs: &[Geometry],
+    state: &mut SubnetEvalState,
+    frame: f32,
+) -> Result<Vec<Geometry>, String> {
+    let revision = subnet.graph.revision();
+    if state.revision != Some(revision) {
+        let mut graph = subnet.graph.clone();
+        graph.rebuild_link_index();
+        *state = SubnetEvalState {
+            graph,
+            revision: Some(revision),
+            ..Default::default()
+        };
+    }
+
+    for promoted in &subnet.promoted {
+        if let Some(value) = params.values.get(&promoted.key) {
+            state
+                .graph
+                .set_param(promoted.node, promoted.param.clone(), value.clone())
+                .map_err(|err| format!("promoted parameter '{}': {:?}", promoted.key, err))?;
+        }
+    }
+
+    let mut injected = BTreeMap::new();
+    for (idx, node_id) in subnet.input_nodes().into_iter().enumerate() {
+        let geometry = inputs.get(idx).cloned().unwrap_or_default();
+        if state.inputs.get(idx) != Some(&geometry) {
+            state
+                .graph
+                .mark_node_changed(node_id)
+                .map_err(|err| format!("{:?}", err))?;
+        }
+        injected.insert(node_id, vec![geometry]);
+    }
+    state.inputs = inputs.to_vec();
+    state.eval.eval.set_frame(frame);
+
+    let cancel = current_cancel_token();
+    let mut outputs = Vec::new();
+    for node_id in subnet.output_nodes() {
+        let result = evaluate_geometry_graph_with_inputs(
+            &state.graph,
+            node_id,
+            &mut state.eval,
+            cancel.clone(),
+            &injected,
+        )
+        .map_err(|err| format!("{:?}", err))?;
+        if result.report.cancelled {
+            return Err(CANCELLED_MESSAGE.to_string());
+        }
+        if !result.report.output_valid {
+            return Err(eval_failure_message(&state.graph, &result.report));
+        }
+        outputs.push(result.output.unwrap_or_default());
+    }
+    Ok(outputs)
+}
+
+/// Moves `node_ids` into a new subnet node wired in their place. Links from
+/// outside the selection become Subnet Inputs and outputs used outside become
+/// Subnet Outputs.
+pub fn collapse_to_subnet(graph: &mut Graph, node_ids: &[NodeId]) -> Result<NodeId, String> {
+    let selected: BTreeSet<NodeId> = node_ids
+        .iter()
+        .copied()
+        .filter(|node_id| graph.node(*node_id).is_some())
+        .collect();
+    if selected.is_empty() {
+        return Err("no nodes to collapse".to_string());
+    }
+
+    let mut inner = Graph::default();
+    let mut node_map = HashMap::new();
+    for node_id in &selected {
+        let copy = inner
+            .copy_node_from(graph, *node_id)
+            .ok_or_else(|| "missing node".to_string())?;
+        node_map.insert(*node_id, copy);
+    }
+
+    let pin_node = |pin_id: PinId| graph.pin(pin_id).map(|pin| pin.node);
+    let mut input_sources: Vec<(PinId, Vec<PinId>)> = Vec::new();
+    let mut output_sources: Vec<(PinId, Vec<PinId>)> = Vec::new();
+    for link in graph.links() {
+        let from_inside = pin_node(link.from).is_some_and(|node| selected.contains(&node));
+        let to_inside = pin_node(link.to).is_some_and(|node| selected.contains(&node));
+        match (from_inside, to_inside) {
+            (true, true) => {
+                let from = corresponding_pin(graph, &inner, &node_map, link.from);
+                let to = corresponding_pin(graph, &inner, &node_map, link.to);
+                if let (Some(from), Some(to)) = (from, to) {
+                    inner.add_link(from, to).map_err(|err| format!("{:?}", err))?;
+                }
+            }
+            (false, true) => {
+                let Some(to) = corresponding_pin(graph, &inner, &node_map, link.to) else {
+                    continue;
+                };
+                push_grouped(&mut input_sources, link.from, to);
+            }
+            (true, false) => push_grouped(&mut output_sources, link.from, link.to),
+            (false, false) => {}
+        }
+    }
+
+    let display = graph
+        .display_node()
+        .filter(|node_id| selected.contains(node_id))
+        .and_then(|node_id| graph.node(node_id))
+        .and_then(|node| node.outputs.get(node.display_output).copied());
+    if let Some(pin) = display {
+        if !output_sources.iter().any(|(source, _)| *source == pin) {
+            output_sources.push((pin, Vec::new()));
+        }
+    }
+    if output_sources.is_empty() {
+        // Nothing leaves the selection; expose the last node nothing inside reads.
+        let sink = selected.iter().rev().find_map(|node_id| {
+            let node = graph.node(*node_id)?;
+            let read_inside = graph.links().any(|link| {
+                node.outputs.contains(&link.from)
+                    && pin_node(link.to).is_some_and(|node| selected.contains(&node))
+            });
+            (!read_inside).then(|| node.outputs.first().copied()).flatten()
+        });
+        if let Some(pin) = sink {
+            output_sources.push((pin, Vec::new()));
+        }
+    }
+
+    let positions: Vec<[f32; 2]> = selected
+        .iter()
+        .filter_map(|node_id| graph.node_position(*node_id))
+        .collect();
+    let (min_x, max_x, center) = position_bounds(&positions);
+
+    for (idx, (_, targets)) in input_sources.iter().enumerate() {
+        let label = targets
+            .first()
+            .and_then(|pin| inner.pin(*pin))
+            .map(|pin| pin.name.clone())
+            .unwrap_or_default();
+        let position = [min_x - 240.0, center[1] + idx as f32 * 120.0];
+        let node_id = add_pin_node(&mut inner, BuiltinNodeKind::SubnetInput, idx, label, position);
+        let out = inner.node(node_id).map(|node| node.outputs[0]);
+        for target in targets {
+            if let Some(out) = out {
+                inner
+                    .add_link(out, *target)
+                    .map_err(|err| format!("{:?}", err))?;
+            }
+        }
+    }
+    for (idx, (source, _)) in output_sources.iter().enumerate() {
+        let Some(from) = corresponding_pin(graph, &inner, &node_map, *source) else {
+            continue;
+        };
+        let label = inner.pin(from).map(|pin| pin.name.clone()).unwrap_or_default();
+        let position = [max_x + 240.0, center[1] + idx as f32 * 120.0];
+        let node_id = add_pin_node(&mut inner, BuiltinNodeKind::SubnetOutput, idx, label, position);
+        if let Some(to) = inner.node(node_id).map(|node| node.inputs[0]) {
+            inner.add_link(from, to).map_err(|err| format!("{:?}", err))?;
+        }
+    }
+
+    let subnet = Subnet {
+        graph: inner,
+        promoted: Vec::new(),
+    };
+    let subnet_id = graph.add_node(subnet.definition(nodes::subnet::NAME));
+    graph.set_node_kind_id(subnet_id, BuiltinNodeKind::Subnet.id());
+    if !positions.is_empty() {
+        let _ = graph.set_node_position(subnet_id, center);
+    }
+    graph
+        .set_node_subnet(subnet_id, Some(subnet))
+        .map_err(|err| format!("{:?}", err))?;
+    for node_id in &selected {
+        graph.remove_node(*node_id);
+    }
+
+    let (inputs, outputs) = graph
+        .node(subnet_id)
+        .map(|node| (node.inputs.clone(), node.outputs.clone()))
+        .unwrap_or_default();
+    for ((source, _), to) in input_sources.iter().zip(&inputs) {
+        graph
+            .add_link(*source, *to)
+            .map_err(|err| format!("{:?}", err))?;
+    }
+    for ((source, targets), from) in output_sources.iter().zip(&outputs) {
+        for to in targets {
+            graph
+                .add_link(*from, *to)
+                .map_err(|err| format!("{:?}", err))?;
+        }
+        if Some(*source) == display {
+            graph
+                .set_display_output(*from)
+                .map_err(|err| format!("{:?}", err))?;
+        }
+    }
+    Ok(subnet_id)
+}
+
+/// Replaces a subnet node with its inner nodes, applying promoted values to them.
+/// Returns the ids of the added nodes.
+pub fn expand_subnet(graph: &mut Graph, subnet_id: NodeId) -> Result<Vec<NodeId>, String> {
+    let node = graph
+        .node(subnet_id)
+        .ok_or_else(|| "missing node".to_string())?;
+    let subnet = node
+        .subnet
+        .as_deref()
+        .cloned()
+        .ok_or_else(|| format!("{} is not a subnet", node.name))?;
+    let params = node.params.clone();
+    let outer_inputs = node.inputs.clone();
+    let outer_outputs = node.outputs.clone();
+    let displayed_output = node.display.then_some(node.display_output);
+    let center = node.position;
+    let inner = &subnet.graph;
+    let input_nodes = subnet.input_nodes();
+    let output_nodes = subnet.output_nodes();
+
+    let copied: Vec<&Node> = inner
+        .nodes()
+        .filter(|node| {
+            !matches!(
+                node.builtin_kind(),
+                Some(BuiltinNodeKind::SubnetInput | BuiltinNodeKind::SubnetOutput)
+            )
+        })
+        .collect();
+    let positions: Vec<[f32; 2]> = copied.iter().filter_map(|node| node.position).collect();
+    let (_, _, inner_center) = position_bounds(&positions);
+    let mut node_map = HashMap::new();
+    for inner_node in &copied {
+        let copy = graph
+            .copy_node_from(inner, inner_node.id)
+            .ok_or_else(|| "missing node".to_string())?;
+        if let (Some(center), Some(position)) = (center, inner_node.position) {
+            let position = [
+                center[0] + position[0] - inner_center[0],
+                center[1] + position[1] - inner_center[1],
+            ];
+            let _ = graph.set_node_position(copy, position);
+        }
+        node_map.insert(inner_node.id, copy);
+    }
+    for promoted in &subnet.promoted {
+        if let (Some(copy), Some(value)) =
+            (node_map.get(&promoted.node), params.values.get(&promoted.key))
+        {
+            let _ = graph.set_param(*copy, promoted.param.clone(), value.clone());
+        }
+    }
+
+    // Outer pins feeding each Subnet Input.
+    let input_sources: HashMap<NodeId, PinId> = input_nodes
+        .iter()
+        .zip(&outer_inputs)
+        .filter_map(|(node_id, pin)| Some((*node_id, graph.input_link(*pin)?.from)))
+        .collect();
+    let resolve = |graph: &Graph, pin_id: PinId| -> Option<PinId> {
+        let pin = inner.pin(pin_id)?;
+        match input_sources.get(&pin.node) {
+            Some(source) => Some(*source),
+            None => corresponding_pin(inner, graph, &node_map, pin_id),
+        }
+    };
+
+    let mut links = Vec::new();
+    for link in inner.links() {
+        let to_node = inner.pin(link.to).map(|pin| pin.node);
+        if to_node.is_some_and(|node_id| output_nodes.contains(&node_id)) {
+            continue;
+        }
+        let from = resolve(graph, link.from);
+        let to = corresponding_pin(inner, graph, &node_map, link.to);
+        if let (Some(from), Some(to)) = (from, to) {
+            links.push((from, to));
+        }
+    }
+    let mut display = None;
+    for (idx, (node_id, outer_pin)) in output_nodes.iter().zip(&outer_outputs).enumerate() {
+        let source = inner
+            .node(*node_id)
+            .and_then(|node| node.inputs.first())
+            .and_then(|pin| inner.input_link(*pin))
+            .and_then(|link| resolve(graph, link.from));
+        let Some(source) = source else {
+            continue;
+        };
+        for link in graph.links().filter(|link| link.from == *outer_pin) {
+            links.push((source, link.to));
+        }
+        if displayed_output == Some(idx) {
+            display = Some(source);
+        }
+    }
+
+    graph.remove_node(subnet_id);
+    for (from, to) in links {
+        graph.add_link(from, to).map_err(|err| format!("{:?}", err))?;
+    }
+    if let Some(pin) = display {
+        graph
+            .set_display_output(pin)
+            .map_err(|err| format!("{:?}", err))?;
+    }
+    Ok(copied.iter().filter_map(|node| node_map.get(&node.id).copied()).collect())
+}
+
+/// Exposes `param` of an inner node on the subnet node and returns its key there.
+pub fn promote_param(
+    graph: &mut Graph,
+    subnet_id: NodeId,
+    inner_node: NodeId,
+    param: &str,
+) -> Result<String, String> {
+    let node = graph
+        .node(subnet_id)
+        .ok_or_else(|| "missing node".to_string())?;
+    let mut subnet = node
+        .subnet
+        .as_deref()
+        .cloned()
+        .ok_or_else(|| format!("{} is not a subnet", node.name))?;
+    if let Some(existing) = subnet
+        .promoted
+        .iter()
+        .find(|promoted| promoted.node == inner_node && promoted.param == param)
+    {
+        return Ok(existing.key.clone());
+    }
+    let value = subnet
+        .graph
+        .node(inner_node)
+        .and_then(|node| node.params.values.get(param))
+        .cloned()
+        .ok_or_else(|| format!("no parameter '{}' to promote", param))?;
+    let mut key = param.to_string();
+    let mut suffix = 2;
+    while node.params.values.contains_key(&key) {
+        key = format!("{}_{}", param, suffix);
+        suffix += 1;
+    }
+
+    subnet.promoted.push(PromotedParam {
+        key: key.clone(),
+        node: inner_node,
+        param: param.to_string(),
+    });
+    graph
+        .set_node_subnet(subnet_id, Some(subnet))
+        .map_err(|err| format!("{:?}", err))?;
+    graph
+        .set_param(subnet_id, key.clone(), value)
+        .map_err(|err| format!("{:?}", err))?;
+    Ok(key)
+}
+
+/// Removes a promoted parameter, keeping its current value on the inner node.
+pub fn unpromote_param(graph: &mut Graph, subnet_id: NodeId, key: &str) -> Result<(), String> {
+    let node = graph
+        .node(subnet_id)
+        .ok_or_else(|| "missing node".to_string())?;
+    let mut subnet = node
+        .subnet
+        .as_deref()
+        .cloned()
+        .ok_or_else(|| format!("{} is not a subnet", node.name))?;
+    let idx = subnet
+        .promoted
+        .iter()
+        .position(|promoted| promoted.key == key)
+        .ok_or_else(|| format!("'{}' is not promoted", key))?;
+    let promoted = subnet.promoted.remove(idx);
+    if let Some(value) = node.params.values.get(key).cloned() {
+        let _ = subnet.graph.set_param(promoted.node, promoted.param, value);
+    }
+    graph
+        .set_node_subnet(subnet_id, Some(subnet))
+        .map_err(|err| format!("{:?}", err))?;
+    graph
+        .remove_param(subnet_id, key)
+        .map_err(|err| format!("{:?}", err))
+}
+
+/// Specs of a subnet node's promoted parameters, taken from the inner nodes they
+/// drive. Parameters without a spec are left out.
+pub fn promoted_param_specs(node: &Node) -> Vec<(PromotedParam, ParamSpec)> {
+    let Some(subnet) = node.subnet.as_deref() else {
+        return Vec::new();
+    };
+    subnet
+        .promoted
+        .iter()
+        .filter_map(|promoted| {
+            let kind = subnet.graph.node(promoted.node)?.builtin_kind()?;
+            let spec = param_specs(kind)
+                .into_iter()
+                .find(|spec| spec.key == promoted.param)?;
+            Some((promoted.clone(), spec))
+        })
+        .collect()
+}
+
+fn add_pin_node(
+    graph: &mut Graph,
+    kind: BuiltinNodeKind,
+    index: usize,
+    label: String,
+    position: [f32; 2],
+) -> NodeId {
+    let node_id = graph.add_node(node_definition(kind));
+    graph.set_node_kind_id(node_id, kind.id());
+    for (key, value) in default_params(kind).values {
+        let _ = graph.set_param(node_id, key, value);
+    }
+    let _ = graph.set_param(node_id, "index", ParamValue::Int(index as i32));
+    let _ = graph.set_param(node_id, "label", ParamValue::String(label));
+    let _ = graph.set_node_position(node_id, position);
+    node_id
+}
+
+fn push_grouped(groups: &mut Vec<(PinId, Vec<PinId>)>, key: PinId, value: PinId) {
+    match groups.iter_mut().find(|(existing, _)| *existing == key) {
+        Some((_, values)) => values.push(value),
+        None => groups.push((key, vec![value])),
+    }
+}
+
+/// The pin at the same position on the copy of its node in `target`.
+fn corresponding_pin(
+    source: &Graph,
+    target: &Graph,
+    node_map: &HashMap<NodeId, NodeId>,
+    pin_id: PinId,
+) -> Option<PinId> {
+    let pin = source.pin(pin_id)?;
+    let node = source.node(pin.node)?;
+    let copy = target.node(*node_map.get(&pin.node)?)?;
+    match pin.kind {
+        PinKind::Input => {
+            let idx = node.input_pins().position(|id| *id == pin_id)?;
+            copy.input_pins().nth(idx).copied()
+        }
+        PinKind::Output => {
+            let idx = node.outputs.iter().position(|id| *id == pin_id)?;
+            copy.outputs.get(idx).copied()
+        }
+    }
+}
+
+/// Minimum x, maximum x and center of a set of node positions.
+fn position_bounds(positions: &[[f32; 2]]) -> (f32, f32, [f32; 2]) {
+    if positions.is_empty() {
+        return (0.0, 0.0, [0.0, 0.0]);
+    }
+    let mut min_x = f32::MAX;
+    let mut max_x = f32::MIN;
+    let mut sum = [0.0, 0.0];
+    for position in positions {
+        min_x = min_x.min(position[0]);
+        max_x = max_x.max(position[0]);
+        sum[0] += position[0];
+        sum[1] += position[1];
+    }
+    let count = positions.len() as f32;
+    (min_x, max_x, [sum[0] / count, sum[1] / count])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry_eval::{evaluate_geometry_graph, GeometryEvalState};
+    use crate::graph::{Graph, NodeId, NodeParams, ParamValue};
+    use crate::nodes_builtin::{default_params, node_definition, BuiltinNodeKind};
+
+    use super::{
+        collapse_to_subnet, cook_subnet, expand_subnet, promote_param, SubnetAsset,
+        SubnetEvalState,
+    };
+
+    fn add(graph: &mut Graph, kind: BuiltinNodeKind) -> NodeId {
+        let node_id = graph.add_node(node_definition(kind));
+        graph.set_node_kind_id(node_id, kind.id());
+        for (key, value) in default_params(kind).values {
+            graph.set_param(node_id, key, value).unwrap();
+        }
+        node_id
+    }
+
+    fn link(graph: &mut Graph, from: NodeId, to: NodeId) {
+        let from = graph.node(from).unwrap().outputs[0];
+        let to = graph.node(to).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+    }
+
+    #[test]
+    fn collapse_and_expand_keep_the_cooked_result() {
+        let mut graph = Graph::default();
+        let source = add(&mut graph, BuiltinNodeKind::Box);
+        let transform = add(&mut graph, BuiltinNodeKind::Transform);
+        let output = add(&mut graph, BuiltinNodeKind::Output);
+        link(&mut graph, source, transform);
+        link(&mut graph, transform, output);
+        graph
+            .set_param(transform, "translate", ParamValue::Vec3([1.0, 2.0, 3.0]))
+            .unwrap();
+        graph.set_display_node(Some(transform)).unwrap();
+
+        let mut state = GeometryEvalState::new();
+        let expected = evaluate_geometry_graph(&graph, output, &mut state)
+            .unwrap()
+            .output
+            .unwrap();
+
+        let subnet = collapse_to_subnet(&mut graph, &[transform]).unwrap();
+        assert_eq!(graph.nodes().count(), 3);
+        assert_eq!(graph.display_node(), Some(subnet));
+        let node = graph.node(subnet).unwrap();
+        assert_eq!((node.inputs.len(), node.outputs.len()), (1, 1));
+        let collapsed = evaluate_geometry_graph(&graph, output, &mut state)
+            .unwrap()
+            .output
+            .unwrap();
+        assert_eq!(collapsed, expected);
+
+        // Round-trip through an asset file before expanding.
+        let asset = SubnetAsset::from_node(&graph, subnet).unwrap();
+        let asset = SubnetAsset::from_json(&asset.to_json().unwrap()).unwrap();
+        let instance = asset.instantiate(&mut graph);
+        assert_eq!(graph.node(instance).unwrap().inputs.len(), 1);
+        graph.remove_node(instance);
+
+        let added = expand_subnet(&mut graph, subnet).unwrap();
+        assert_eq!(added.len(), 1);
+        assert_eq!(graph.display_node(), Some(added[0]));
+        let expanded = evaluate_geometry_graph(&graph, output, &mut state)
+            .unwrap()
+            .output
+            .unwrap();
+        assert_eq!(expanded, expected);
+    }
+
+    #[test]
+    fn promoted_params_recook_only_affected_inner_nodes() {
+        let mut graph = Graph::default();
+        let source = add(&mut graph, BuiltinNodeKind::Box);
+        let transform = add(&mut graph, BuiltinNodeKind::Transform);
+        link(&mut graph, source, transform);
+        let subnet_id = collapse_to_subnet(&mut graph, &[source, transform]).unwrap();
+
+        let subnet = graph.node(subnet_id).unwrap().subnet.clone().unwrap();
+        let inner_transform = subnet
+            .graph
+            .nodes()
+            .find(|node| node.builtin_kind() == Some(BuiltinNodeKind::Transform))
+            .unwrap()
+            .id;
+        let key = promote_param(&mut graph, subnet_id, inner_transform, "translate").unwrap();
+        assert_eq!(key, "translate");
+        let subnet = graph.node(subnet_id).unwrap().subnet.clone().unwrap();
+
+        let mut state = SubnetEvalState::default();
+        let mut params = NodeParams::default();
+        let first = cook_subnet(&subnet, &params, &[], &mut state, 1.0).unwrap();
+        assert_eq!(state.eval.eval.stats.misses, 3);
+
+        params
+            .values
+            .insert(key, ParamValue::Vec3([0.0, 5.0, 0.0]));
+        let second = cook_subnet(&subnet, &params, &[], &mut state, 1.0).unwrap();
+        // The box is reused; the transform and the subnet output recook.
+        assert_eq!(state.eval.eval.stats.misses, 5);
+        let offset =
+            second[0].meshes[0].positions[0][1] - first[0].meshes[0].positions[0][1];
+        assert!((offset - 5.0).abs() < 1.0e-5);
+
+        // Editing the inner graph resets the kept state.
+        let mut edited = subnet.clone();
+        let inner_box = edited
+            .graph
+            .nodes()
+            .find(|node| node.builtin_kind() == Some(BuiltinNodeKind::Box))
+            .unwrap()
+            .id;
+        edited
+            .graph
+            .set_param(inner_box, "size", ParamValue::Vec3([2.0, 2.0, 2.0]))
+            .unwrap();
+        let third = cook_subnet(&edited, &params, &[], &mut state, 1.0).unwrap();
+        assert_eq!(state.eval.eval.stats.misses, 3);
+        assert_ne!(third, second);
+    }
+}
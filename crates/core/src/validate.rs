@@ -0,0 +1,391 @@
+use crate::expression::check_expression;
+use crate::graph::{pin_types_compatible, Graph, Node, NodeId, NodeParams, ParamValue, PinId};
+use crate::node_registry::node_type_for;
+use crate::nodes_builtin::InputPolicy;
+use crate::param_spec::{ParamKind, ParamSpec};
+use crate::subnet::promoted_param_specs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A problem found by [`Graph::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub node: NodeId,
+    pub pin: Option<PinId>,
+    pub param: Option<String>,
+    pub message: String,
+    /// How to fix it, when there is an obvious way.
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, node: NodeId, message: String) -> Self {
+        Self {
+            severity,
+            node,
+            pin: None,
+            param: None,
+            message,
+            suggestion: None,
+        }
+    }
+
+    fn on_pin(mut self, pin: PinId) -> Self {
+        self.pin = Some(pin);
+        self
+    }
+
+    fn on_param(mut self, key: &str) -> Self {
+        self.param = Some(key.to_string());
+        self
+    }
+
+    fn suggest(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+}
+
+impl Graph {
+    /// Lints the graph without cooking it: unknown node kinds, required inputs
+    /// left unconnected, links between incompatible pins, parameters no spec
+    /// knows about and expressions that do not parse. Problems inside a subnet
+    /// are reported on the subnet node.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for node in self.nodes() {
+            self.validate_node(node, &mut diagnostics);
+        }
+        diagnostics
+    }
+
+    fn validate_node(&self, node: &Node, diagnostics: &mut Vec<Diagnostic>) {
+        self.validate_links(node, diagnostics);
+        validate_expressions(node, diagnostics);
+        if let Some(subnet) = node.subnet.as_deref() {
+            for inner in subnet.graph.validate() {
+                let name = subnet
+                    .graph
+                    .node(inner.node)
+                    .map(|inner| inner.name.clone())
+                    .unwrap_or_default();
+                diagnostics.push(Diagnostic {
+                    node: node.id,
+                    pin: None,
+                    param: None,
+                    message: format!("{}: {}", name, inner.message),
+                    ..inner
+                });
+            }
+        }
+
+        let Some(node_type) = node_type_for(node) else {
+            let kind = if node.kind_id.is_empty() {
+                &node.name
+            } else {
+                &node.kind_id
+            };
+            diagnostics.push(
+                Diagnostic::new(
+                    Severity::Error,
+                    node.id,
+                    format!("unknown node kind {}", kind),
+                )
+                .suggest("Register the plugin providing this node kind, or delete the node"),
+            );
+            return;
+        };
+        if !node.bypass && node.locked.is_none() {
+            self.validate_inputs(node, node_type.input_policy(), diagnostics);
+        }
+        let (specs, defaults): (Vec<(String, ParamSpec)>, _) = if node.subnet.is_some() {
+            let specs = promoted_param_specs(node)
+                .into_iter()
+                .map(|(promoted, spec)| (promoted.key, spec))
+                .collect();
+            (specs, NodeParams::default())
+        } else {
+            let specs = node_type
+                .param_specs()
+                .into_iter()
+                .map(|spec| (spec.key.to_string(), spec))
+                .collect();
+            (specs, node_type.default_params())
+        };
+        self.validate_params(node, &specs, &defaults, diagnostics);
+    }
+
+    fn validate_inputs(&self, node: &Node, policy: InputPolicy, diagnostics: &mut Vec<Diagnostic>) {
+        let mut inputs: Vec<(PinId, bool)> = node
+            .inputs
+            .iter()
+            .map(|pin_id| (*pin_id, self.input_link(*pin_id).is_some()))
+            .collect();
+        // The free pin at the end of a variadic node is never required.
+        if node.variadic_inputs && inputs.last().is_some_and(|(_, connected)| !connected) {
+            inputs.pop();
+        }
+        match policy {
+            InputPolicy::None => {}
+            InputPolicy::RequireAll => {
+                for (pin_id, connected) in inputs {
+                    if connected {
+                        continue;
+                    }
+                    let name = self
+                        .pin(pin_id)
+                        .map(|pin| pin.name.as_str())
+                        .unwrap_or("in");
+                    diagnostics.push(
+                        Diagnostic::new(
+                            Severity::Error,
+                            node.id,
+                            format!("input '{}' is not connected", name),
+                        )
+                        .on_pin(pin_id)
+                        .suggest("Connect the input or bypass the node"),
+                    );
+                }
+            }
+            InputPolicy::RequireAtLeast(min) => {
+                let connected = inputs.iter().filter(|(_, connected)| *connected).count();
+                if connected < min {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            Severity::Error,
+                            node.id,
+                            format!("needs at least {} connected inputs, has {}", min, connected),
+                        )
+                        .suggest("Connect more inputs or bypass the node"),
+                    );
+                }
+            }
+        }
+    }
+
+    fn validate_links(&self, node: &Node, diagnostics: &mut Vec<Diagnostic>) {
+        for pin_id in node.input_pins() {
+            let Some(link) = self.input_link(*pin_id) else {
+                continue;
+            };
+            let (Some(from), Some(to)) = (self.pin(link.from), self.pin(*pin_id)) else {
+                continue;
+            };
+            if pin_types_compatible(from.pin_type, to.pin_type) {
+                continue;
+            }
+            diagnostics.push(
+                Diagnostic::new(
+                    Severity::Error,
+                    node.id,
+                    format!(
+                        "input '{}' ({:?}) is linked to an incompatible {:?} output",
+                        to.name, to.pin_type, from.pin_type
+                    ),
+                )
+                .on_pin(*pin_id)
+                .suggest("Delete the link or connect an output of a matching type"),
+            );
+        }
+        for pin_id in &node.param_inputs {
+            let Some(pin) = self.pin(*pin_id) else {
+                continue;
+            };
+            if !node.params.values.contains_key(&pin.name) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        Severity::Warning,
+                        node.id,
+                        format!("input '{}' drives no parameter", pin.name),
+                    )
+                    .on_pin(*pin_id)
+                    .suggest("Remove the input"),
+                );
+            }
+        }
+    }
+
+    fn validate_params(
+        &self,
+        node: &Node,
+        specs: &[(String, ParamSpec)],
+        defaults: &NodeParams,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        // Nodes without specs edit their parameters as raw values.
+        if specs.is_empty() {
+            return;
+        }
+        for (key, value) in &node.params.values {
+            let Some((_, spec)) = specs.iter().find(|(spec_key, _)| spec_key == key) else {
+                // Some nodes keep state the inspector does not show, like selections.
+                if defaults.values.contains_key(key) {
+                    continue;
+                }
+                diagnostics.push(
+                    Diagnostic::new(
+                        Severity::Warning,
+                        node.id,
+                        format!("parameter '{}' is not used by this node", key),
+                    )
+                    .on_param(key)
+                    .suggest("Remove the parameter"),
+                );
+                continue;
+            };
+            if !value_matches_kind(value, spec.kind) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        Severity::Error,
+                        node.id,
+                        format!(
+                            "parameter '{}' holds {} but expects {:?}",
+                            key,
+                            value_kind_name(value),
+                            spec.kind
+                        ),
+                    )
+                    .on_param(key)
+                    .suggest("Reset the parameter to its default"),
+                );
+            }
+        }
+    }
+}
+
+fn validate_expressions(node: &Node, diagnostics: &mut Vec<Diagnostic>) {
+    for (key, expression) in &node.expressions {
+        if let Err(err) = check_expression(expression) {
+            diagnostics.push(
+                Diagnostic::new(
+                    Severity::Error,
+                    node.id,
+                    format!("expression on '{}' does not parse: {}", key, err),
+                )
+                .on_param(key)
+                .suggest("Fix the expression or remove it"),
+            );
+        }
+    }
+}
+
+/// Ints and floats read as each other; everything else must match.
+fn value_matches_kind(value: &ParamValue, kind: ParamKind) -> bool {
+    matches!(
+        (value, kind),
+        (
+            ParamValue::Float(_) | ParamValue::Int(_),
+            ParamKind::Float | ParamKind::Int
+        ) | (ParamValue::Bool(_), ParamKind::Bool)
+            | (ParamValue::Vec2(_), ParamKind::Vec2)
+            | (ParamValue::Vec3(_), ParamKind::Vec3)
+            | (ParamValue::String(_), ParamKind::String)
+    )
+}
+
+fn value_kind_name(value: &ParamValue) -> &'static str {
+    match value {
+        ParamValue::Float(_) => "a float",
+        ParamValue::Int(_) => "an int",
+        ParamValue::Bool(_) => "a bool",
+        ParamValue::Vec2(_) => "a vec2",
+        ParamValue::Vec3(_) => "a vec3",
+        ParamValue::String(_) => "a string",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Severity;
+    use crate::graph::{Graph, NodeDefinition, ParamValue, PinDefinition, PinType};
+    use crate::node_registry::add_registered_node;
+    use crate::nodes_builtin::{node_specs, BuiltinNodeKind};
+
+    #[test]
+    fn fresh_builtin_nodes_only_miss_inputs() {
+        for spec in node_specs() {
+            let mut graph = Graph::default();
+            add_registered_node(&mut graph, spec.id).unwrap();
+            for diagnostic in graph.validate() {
+                assert!(
+                    diagnostic.message.contains("not connected")
+                        || diagnostic.message.contains("needs at least"),
+                    "{}: {}",
+                    spec.name,
+                    diagnostic.message
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reports_broken_graphs() {
+        let mut graph = Graph::default();
+        let source = add_registered_node(&mut graph, BuiltinNodeKind::Box.id()).unwrap();
+        let transform = add_registered_node(&mut graph, BuiltinNodeKind::Transform.id()).unwrap();
+        let from = graph.node(source).unwrap().outputs[0];
+        let to = graph.node(transform).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+        assert!(graph.validate().is_empty());
+
+        graph
+            .set_param(transform, "scale", ParamValue::String("big".to_string()))
+            .unwrap();
+        graph
+            .set_param(transform, "stale", ParamValue::Float(1.0))
+            .unwrap();
+        graph
+            .set_param_expression(transform, "translate", Some("vec3(1,".to_string()))
+            .unwrap();
+        let unknown = graph.add_node(NodeDefinition {
+            name: "Mystery".to_string(),
+            category: "Studio".to_string(),
+            inputs: vec![PinDefinition {
+                name: "in".to_string(),
+                pin_type: PinType::Geometry,
+            }],
+            outputs: Vec::new(),
+            variadic_inputs: false,
+        });
+        graph.set_node_kind_id(unknown, "studio:mystery");
+        let output = add_registered_node(&mut graph, BuiltinNodeKind::Output.id()).unwrap();
+
+        let diagnostics = graph.validate();
+        let messages: Vec<(Severity, &str)> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.message.as_str()))
+            .collect();
+        assert!(messages.contains(&(
+            Severity::Error,
+            "parameter 'scale' holds a string but expects Vec3"
+        )));
+        assert!(messages.contains(&(
+            Severity::Warning,
+            "parameter 'stale' is not used by this node"
+        )));
+        assert!(messages.contains(&(Severity::Error, "unknown node kind studio:mystery")));
+        assert!(diagnostics.iter().any(|diagnostic| {
+            diagnostic.param.as_deref() == Some("translate")
+                && diagnostic
+                    .message
+                    .starts_with("expression on 'translate' does not parse")
+        }));
+        let missing = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.node == output)
+            .unwrap();
+        assert_eq!(missing.pin, Some(graph.node(output).unwrap().inputs[0]));
+        assert!(missing.suggestion.is_some());
+
+        graph.set_bypass_node(output, true).unwrap();
+        assert!(graph
+            .validate()
+            .iter()
+            .all(|diagnostic| diagnostic.node != output));
+    }
+}
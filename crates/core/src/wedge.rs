@@ -0,0 +1,447 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::export::write_geometry;
+use crate::expression::coerce_param;
+use crate::geometry_eval::{
+    eval_failure_message, evaluate_geometry_graph_with_progress, GeometryEvalState,
+};
+use crate::graph::{Graph, Node, NodeId, ParamValue};
+use crate::node_registry::node_type_for;
+use crate::progress::CancelToken;
+use crate::project::Project;
+use crate::subnet::promoted_param_specs;
+
+/// A sweep over parameter values, saved with the project. Every combination of
+/// the parameters' values cooks `node` in a fresh evaluation and writes its
+/// output to `output_path`, where `${wedge}` is the combination index and
+/// `${<node>.<param>}` the value of a wedged parameter; `${<param>}` works too
+/// while no other wedged parameter has the same name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Wedge {
+    pub name: String,
+    /// Name of the node to cook.
+    pub node: String,
+    pub output_path: String,
+    /// Where the manifest goes; defaults to `<name>.wedge.json` next to the
+    /// first output.
+    #[serde(default)]
+    pub manifest_path: String,
+    pub params: Vec<WedgeParam>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WedgeParam {
+    /// Name of the node owning the parameter.
+    pub node: String,
+    pub param: String,
+    pub values: WedgeValues,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WedgeValues {
+    /// `steps` evenly spaced values from `start` to `end`, both included.
+    Range {
+        start: f32,
+        end: f32,
+        steps: usize,
+    },
+    List {
+        values: Vec<ParamValue>,
+    },
+}
+
+impl WedgeValues {
+    pub fn values(&self) -> Vec<ParamValue> {
+        match self {
+            WedgeValues::Range { start, end, steps } => match *steps {
+                0 => Vec::new(),
+                1 => vec![ParamValue::Float(*start)],
+                steps => (0..steps)
+                    .map(|step| {
+                        let t = step as f32 / (steps - 1) as f32;
+                        ParamValue::Float(start + (end - start) * t)
+                    })
+                    .collect(),
+            },
+            WedgeValues::List { values } => values.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WedgeManifest {
+    pub wedge: String,
+    pub node: String,
+    pub total_ms: f32,
+    pub runs: Vec<WedgeRun>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WedgeRun {
+    pub index: usize,
+    /// Values by `node.param`.
+    pub values: BTreeMap<String, ParamValue>,
+    pub output: String,
+    pub duration_ms: f32,
+    pub error: Option<String>,
+}
+
+impl WedgeManifest {
+    pub fn failed_runs(&self) -> usize {
+        self.runs.iter().filter(|run| run.error.is_some()).count()
+    }
+}
+
+impl Wedge {
+    /// Every combination of the parameters' values, the last parameter varying
+    /// fastest.
+    pub fn combinations(&self) -> Vec<Vec<ParamValue>> {
+        let mut combinations = vec![Vec::new()];
+        for param in &self.params {
+            let values = param.values.values();
+            combinations = combinations
+                .into_iter()
+                .flat_map(|prefix| {
+                    values.iter().map(move |value| {
+                        let mut combination = prefix.clone();
+                        combination.push(value.clone());
+                        combination
+                    })
+                })
+                .collect();
+        }
+        combinations
+    }
+}
+
+/// Runs `wedge` on a copy of the project's graph, writing one output per
+/// combination and the manifest. Relative paths resolve against `base_dir`.
+/// Every combination must expand the output path to a different file. Failed
+/// combinations are recorded in the manifest; only problems with the wedge
+/// itself are errors.
+pub fn run_wedge(
+    project: &Project,
+    wedge: &Wedge,
+    base_dir: Option<&Path>,
+    cancel: Option<CancelToken>,
+) -> Result<WedgeManifest, String> {
+    let graph = &project.graph;
+    let output = find_node(graph, &wedge.node)?;
+    let mut targets = Vec::with_capacity(wedge.params.len());
+    for param in &wedge.params {
+        let node_id = find_node(graph, &param.node)?;
+        if !graph
+            .node(node_id)
+            .is_some_and(|node| has_param(node, &param.param))
+        {
+            return Err(format!(
+                "node {} has no parameter {}",
+                param.node, param.param
+            ));
+        }
+        if targets.contains(&(node_id, param.param.as_str())) {
+            return Err(format!(
+                "wedge {} sets {}.{} twice",
+                wedge.name, param.node, param.param
+            ));
+        }
+        let shared = wedge
+            .params
+            .iter()
+            .filter(|other| other.param == param.param)
+            .count()
+            > 1;
+        if shared && wedge.output_path.contains(&format!("${{{}}}", param.param)) {
+            return Err(format!(
+                "${{{0}}} is ambiguous in the output path of wedge {1}; use ${{<node>.{0}}}",
+                param.param, wedge.name
+            ));
+        }
+        targets.push((node_id, param.param.as_str()));
+    }
+    if wedge.output_path.trim().is_empty() {
+        return Err(format!("wedge {} has no output path", wedge.name));
+    }
+    let resolve = |path: &str| -> PathBuf {
+        let path = PathBuf::from(path);
+        match base_dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path,
+        }
+    };
+
+    // Expand every path up front so runs can't overwrite each other's files.
+    let mut planned = Vec::new();
+    let mut seen = HashSet::new();
+    for (index, combination) in wedge.combinations().into_iter().enumerate() {
+        let mut path = wedge.output_path.replace("${wedge}", &index.to_string());
+        let mut values = BTreeMap::new();
+        let mut assignments = Vec::with_capacity(targets.len());
+        for ((param, (node_id, _)), value) in wedge.params.iter().zip(&targets).zip(combination) {
+            let token = value_token(&value);
+            path = path
+                .replace(&format!("${{{}.{}}}", param.node, param.param), &token)
+                .replace(&format!("${{{}}}", param.param), &token);
+            values.insert(format!("{}.{}", param.node, param.param), value.clone());
+            assignments.push((*node_id, param.param.as_str(), value));
+        }
+        let path = resolve(&path);
+        if !seen.insert(path.clone()) {
+            return Err(format!(
+                "wedge {} writes {} more than once; add ${{wedge}} or a token \
+                 for every parameter to the output path",
+                wedge.name,
+                path.display()
+            ));
+        }
+        planned.push((index, values, assignments, path));
+    }
+
+    let frame = project.settings.timeline.current_frame as f32;
+    let start = Instant::now();
+    let mut runs = Vec::new();
+    for (index, values, assignments, path) in planned {
+        if cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+            break;
+        }
+        let run_start = Instant::now();
+        let result = cook_run(
+            graph.clone(),
+            output,
+            &assignments,
+            &path,
+            frame,
+            cancel.clone(),
+        );
+        runs.push(WedgeRun {
+            index,
+            values,
+            output: path.to_string_lossy().into_owned(),
+            duration_ms: run_start.elapsed().as_secs_f32() * 1000.0,
+            error: result.err(),
+        });
+    }
+
+    let manifest = WedgeManifest {
+        wedge: wedge.name.clone(),
+        node: wedge.node.clone(),
+        total_ms: start.elapsed().as_secs_f32() * 1000.0,
+        runs,
+    };
+    let manifest_path = if wedge.manifest_path.trim().is_empty() {
+        let first = manifest
+            .runs
+            .first()
+            .map(|run| PathBuf::from(&run.output))
+            .unwrap_or_else(|| resolve(&wedge.output_path));
+        first
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(format!("{}.wedge.json", wedge.name))
+    } else {
+        resolve(&wedge.manifest_path)
+    };
+    create_parent_dir(&manifest_path)?;
+    let data = serde_json::to_vec_pretty(&manifest).map_err(|err| err.to_string())?;
+    std::fs::write(&manifest_path, data)
+        .map_err(|err| format!("failed to write {}: {}", manifest_path.display(), err))?;
+    Ok(manifest)
+}
+
+fn cook_run(
+    mut graph: Graph,
+    output: NodeId,
+    assignments: &[(NodeId, &str, ParamValue)],
+    path: &Path,
+    frame: f32,
+    cancel: Option<CancelToken>,
+) -> Result<(), String> {
+    for (node_id, key, value) in assignments {
+        let node = graph
+            .node(*node_id)
+            .ok_or_else(|| format!("missing node {:?}", node_id))?;
+        let value = coerce_param(node, key, value)?;
+        // The wedged value replaces any expression or animation on the parameter.
+        let error = |err| format!("{}: {:?}", key, err);
+        graph
+            .set_param_expression(*node_id, *key, None)
+            .map_err(error)?;
+        graph
+            .set_param_channel(*node_id, *key, None)
+            .map_err(error)?;
+        graph.set_param(*node_id, *key, value).map_err(error)?;
+    }
+    let mut state = GeometryEvalState::new();
+    state.eval.set_frame(frame);
+    let result = evaluate_geometry_graph_with_progress(&graph, output, &mut state, None, cancel)
+        .map_err(|err| format!("{:?}", err))?;
+    if result.report.cancelled {
+        return Err(crate::progress::CANCELLED_MESSAGE.to_string());
+    }
+    if !result.report.output_valid {
+        return Err(eval_failure_message(&graph, &result.report));
+    }
+    let geometry = result
+        .output
+        .ok_or_else(|| "the node produced no geometry".to_string())?;
+    create_parent_dir(path)?;
+    write_geometry(&geometry, path)
+}
+
+fn create_parent_dir(path: &Path) -> Result<(), String> {
+    match path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        Some(dir) => std::fs::create_dir_all(dir).map_err(|err| err.to_string()),
+        None => Ok(()),
+    }
+}
+
+fn find_node(graph: &Graph, name: &str) -> Result<NodeId, String> {
+    let mut matches = graph.nodes().filter(|node| node.name == name);
+    match (matches.next(), matches.next()) {
+        (Some(node), None) => Ok(node.id),
+        (None, _) => Err(format!("node {} not found", name)),
+        (Some(_), Some(_)) => Err(format!("node name {} is ambiguous", name)),
+    }
+}
+
+fn has_param(node: &Node, key: &str) -> bool {
+    if node.params.values.contains_key(key) {
+        return true;
+    }
+    if node.subnet.is_some() {
+        return promoted_param_specs(node)
+            .iter()
+            .any(|(promoted, _)| promoted.key == key);
+    }
+    node_type_for(node)
+        .is_some_and(|node_type| node_type.param_specs().iter().any(|spec| spec.key == key))
+}
+
+fn value_token(value: &ParamValue) -> String {
+    match value {
+        ParamValue::Float(value) => format!("{}", value),
+        ParamValue::Int(value) => value.to_string(),
+        ParamValue::Bool(value) => value.to_string(),
+        ParamValue::Vec2(value) => format!("{}_{}", value[0], value[1]),
+        ParamValue::Vec3(value) => format!("{}_{}_{}", value[0], value[1], value[2]),
+        ParamValue::String(value) => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{NodeDefinition, ParamValue};
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+    use crate::project::Project;
+
+    use super::{run_wedge, Wedge, WedgeParam, WedgeValues};
+
+    #[test]
+    fn range_values_include_both_ends() {
+        let values = WedgeValues::Range {
+            start: 0.01,
+            end: 0.1,
+            steps: 4,
+        }
+        .values();
+        assert_eq!(values.len(), 4);
+        assert_eq!(values[0], ParamValue::Float(0.01));
+        assert_eq!(values[3], ParamValue::Float(0.1));
+    }
+
+    #[test]
+    fn writes_one_output_per_combination() {
+        let dir = std::env::temp_dir().join(format!("lobedo_wedge_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut project = Project::default();
+        let source = project
+            .graph
+            .add_node(node_definition(BuiltinNodeKind::Box));
+        let name = project.graph.node(source).unwrap().name.clone();
+        let wedge = Wedge {
+            name: "sizes".to_string(),
+            node: name.clone(),
+            output_path: "out/box_${wedge}_${size}_${Box.center}.obj".to_string(),
+            manifest_path: String::new(),
+            params: vec![
+                WedgeParam {
+                    node: name.clone(),
+                    param: "size".to_string(),
+                    values: WedgeValues::List {
+                        values: vec![
+                            ParamValue::Vec3([1.0, 1.0, 1.0]),
+                            ParamValue::Vec3([2.0, 2.0, 2.0]),
+                        ],
+                    },
+                },
+                WedgeParam {
+                    node: name.clone(),
+                    param: "center".to_string(),
+                    values: WedgeValues::List {
+                        values: vec![
+                            ParamValue::Vec3([0.0, 0.0, 0.0]),
+                            ParamValue::Vec3([0.0, 1.0, 0.0]),
+                            ParamValue::Vec3([0.0, 2.0, 0.0]),
+                        ],
+                    },
+                },
+            ],
+        };
+
+        let manifest = run_wedge(&project, &wedge, Some(&dir), None).unwrap();
+        assert_eq!(manifest.runs.len(), 6);
+        assert_eq!(manifest.failed_runs(), 0);
+        assert!(dir.join("out/box_0_1_1_1_0_0_0.obj").exists());
+        assert!(dir.join("out/box_5_2_2_2_0_2_0.obj").exists());
+        assert_eq!(
+            manifest.runs[4].values["Box.size"],
+            ParamValue::Vec3([2.0, 2.0, 2.0])
+        );
+        let data = std::fs::read(dir.join("out/sizes.wedge.json")).unwrap();
+        let loaded: super::WedgeManifest = serde_json::from_slice(&data).unwrap();
+        assert_eq!(loaded.runs.len(), 6);
+        // The project's own graph is left alone.
+        assert!(project.graph.node(source).unwrap().params.values.is_empty());
+
+        let mut unknown = wedge.clone();
+        unknown.params[1].param = "missing".to_string();
+        assert!(run_wedge(&project, &unknown, Some(&dir), None).is_err());
+
+        let mut twice = wedge.clone();
+        twice.params[1].param = "size".to_string();
+        assert!(run_wedge(&project, &twice, Some(&dir), None).is_err());
+
+        let other = project.graph.add_node(NodeDefinition {
+            name: "Box2".to_string(),
+            ..node_definition(BuiltinNodeKind::Box)
+        });
+        project
+            .graph
+            .set_node_kind_id(other, BuiltinNodeKind::Box.id());
+        let mut shared = wedge.clone();
+        shared.params[1].node = "Box2".to_string();
+        shared.params[1].param = "size".to_string();
+        shared.params[1].values = shared.params[0].values.clone();
+        assert!(run_wedge(&project, &shared, Some(&dir), None)
+            .unwrap_err()
+            .contains("output path"));
+        shared.output_path = "out/shared_${Box.size}.obj".to_string();
+        assert!(run_wedge(&project, &shared, Some(&dir), None)
+            .unwrap_err()
+            .contains("more than once"));
+        shared.output_path = "out/shared_${Box.size}_${Box2.size}.obj".to_string();
+        assert!(run_wedge(&project, &shared, Some(&dir), None).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
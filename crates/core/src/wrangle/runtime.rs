@@ -118,10 +118,10 @@ impl<'a> MeshQueryCache<'a> {
             if normals.len() == mesh.positions.len() {
                 normals.clone()
             } else {
-                compute_point_normals(mesh)
+                compute_point_normals(mesh).into()
             }
         } else {
-            compute_point_normals(mesh)
+            compute_point_normals(mesh).into()
         };
 
         let vertex_normals = if let Some(normals) = &mesh.corner_normals {
@@ -161,7 +161,7 @@ impl<'a> MeshQueryCache<'a> {
                 vec![mesh.indices.len() as u32]
             }
         } else {
-            mesh.face_counts.clone()
+            mesh.face_counts.to_vec()
         };
         let mut cursor = 0usize;
         for &count in &face_counts {
@@ -240,7 +240,7 @@ impl<'a> MeshQueryCache<'a> {
                 vec![mesh.indices.len() as u32]
             }
         } else {
-            mesh.face_counts.clone()
+            mesh.face_counts.to_vec()
         };
         let mut cursor = 0usize;
         for (face_index, &count) in face_counts.iter().enumerate() {
@@ -267,8 +267,8 @@ impl<'a> MeshQueryCache<'a> {
 
         Self {
             mesh,
-            point_normals,
-            vertex_normals,
+            point_normals: point_normals.into_vec(),
+            vertex_normals: vertex_normals.into_vec(),
             prim_normals,
             prim_centers,
             detail_center,
@@ -1453,7 +1453,7 @@ fn build_storage(
                     _ => return Err("Cannot assign vector to float attribute".to_string()),
                 }
             }
-            Ok(AttributeStorage::Float(out))
+            Ok(AttributeStorage::Float(out.into()))
         }
         AttributeType::Int => {
             let mut out = Vec::with_capacity(values.len());
@@ -1463,7 +1463,7 @@ fn build_storage(
                     _ => return Err("Cannot assign vector to int attribute".to_string()),
                 }
             }
-            Ok(AttributeStorage::Int(out))
+            Ok(AttributeStorage::Int(out.into()))
         }
         AttributeType::Vec2 => {
             let mut out = Vec::with_capacity(values.len());
@@ -1474,7 +1474,7 @@ fn build_storage(
                     _ => return Err("Cannot assign Vec3/Vec4 to vec2 attribute".to_string()),
                 });
             }
-            Ok(AttributeStorage::Vec2(out))
+            Ok(AttributeStorage::Vec2(out.into()))
         }
         AttributeType::Vec3 => {
             let mut out = Vec::with_capacity(values.len());
@@ -1485,7 +1485,7 @@ fn build_storage(
                     _ => return Err("Cannot assign Vec2/Vec4 to vec3 attribute".to_string()),
                 });
             }
-            Ok(AttributeStorage::Vec3(out))
+            Ok(AttributeStorage::Vec3(out.into()))
         }
         AttributeType::Vec4 => {
             let mut out = Vec::with_capacity(values.len());
@@ -1496,7 +1496,7 @@ fn build_storage(
                     _ => return Err("Cannot assign Vec2/Vec3 to vec4 attribute".to_string()),
                 });
             }
-            Ok(AttributeStorage::Vec4(out))
+            Ok(AttributeStorage::Vec4(out.into()))
         }
         AttributeType::String => Err("Wrangle does not support string attributes".to_string()),
     }
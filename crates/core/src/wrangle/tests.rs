@@ -59,9 +59,9 @@ fn wrangle_point_query_secondary_mesh() {
 #[test]
 fn wrangle_point_query_secondary_splats() {
     let mut splats = SplatGeo::with_len(2);
-    splats.positions = vec![[0.0, 0.0, 0.0], [0.0, 0.0, 0.0]];
+    splats.positions = vec![[0.0, 0.0, 0.0], [0.0, 0.0, 0.0]].into();
     let mut secondary = SplatGeo::with_len(2);
-    secondary.positions = vec![[3.0, 0.0, 0.0], [6.0, 0.0, 0.0]];
+    secondary.positions = vec![[3.0, 0.0, 0.0], [6.0, 0.0, 0.0]].into();
 
     apply_wrangle_splats(
         &mut splats,
@@ -82,7 +82,7 @@ fn wrangle_splat_query_secondary_from_mesh() {
     let mut mesh =
         Mesh::with_positions_indices(vec![[0.0, 0.0, 0.0], [0.0, 0.0, 0.0]], Vec::new());
     let mut secondary = SplatGeo::with_len(2);
-    secondary.positions = vec![[5.0, 0.0, 0.0], [7.0, 0.0, 0.0]];
+    secondary.positions = vec![[5.0, 0.0, 0.0], [7.0, 0.0, 0.0]].into();
 
     apply_wrangle(
         &mut mesh,